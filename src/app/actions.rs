@@ -1,7 +1,26 @@
-use sysinfo::{Pid, System};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
-use crate::app::{AppState, InputMode, ViewMode};
-use crate::system::{docker, process};
+use crossterm::terminal;
+use sysinfo::{Pid, ProcessStatus, Signal, System};
+
+use crate::app::state::{
+    is_pm2_pending_key, pm2_pending_key, pm2_start_pending_key, ContextMenuAction,
+    ContextMenuTarget, InspectorReady, KillPlan, OpenFilesFound, OperationComplete, ThreadPane,
+    SIGNAL_MENU,
+};
+use crate::app::{
+    AppState, ColumnChooser, ContextMenu, DockerContextPicker, FormField, FormOverlay, FormPurpose,
+    InputMode, LogPane, ProcessScope, ViewMode,
+};
+use crate::system::apps::Runtime;
+use crate::system::docker::ContainerInfo;
+use crate::system::logs::{CommandOutput, LogStream};
+use crate::system::node::{self, Pm2Action};
+use crate::system::process::KillScope;
+use crate::system::threads::{self, ThreadSampler};
+use crate::system::{docker, fds, priority, process};
 
 pub(crate) fn kill_selected_process(state: &mut AppState, system: &mut System) {
     let Some(pid) = state.visible_pids.get(state.selected).cloned() else {
@@ -9,6 +28,14 @@ pub(crate) fn kill_selected_process(state: &mut AppState, system: &mut System) {
         return;
     };
 
+    // PM2 respawns killed apps, so stop them through PM2 instead
+    if state.view_mode == ViewMode::Node {
+        if let Some(Some(info)) = state.visible_node_pm2.get(state.selected).cloned() {
+            start_pm2_action(state, info.pm_id, info.name, Pm2Action::Stop);
+            return;
+        }
+    }
+
     match system.process(pid) {
         Some(process) => {
             let name = process.name().to_string();
@@ -206,13 +233,1262 @@ pub(crate) fn open_selected_container_logs(state: &mut AppState) {
     }
 }
 
+pub(crate) fn open_selected_container_top(state: &mut AppState, containers: &[ContainerInfo]) {
+    use crate::system::docker::DockerRow;
+
+    let Some(row) = state.docker_rows.get(state.docker_selected_row) else {
+        state.set_message("No container selected");
+        return;
+    };
+
+    let container_index = match row {
+        DockerRow::Item { index, .. } => *index,
+        DockerRow::Group { .. } => {
+            state.set_message("Select a container to list processes");
+            return;
+        }
+        DockerRow::Separator => {
+            state.set_message("No container selected");
+            return;
+        }
+    };
+
+    let Some(container) = containers.get(container_index) else {
+        state.set_message("No container selected");
+        return;
+    };
+    if !container.running {
+        state.set_message(format!("{} is not running", container.name));
+        return;
+    }
+
+    let id = container.id.clone();
+    let name = container.name.clone();
+    open_container_top(state, id, name);
+}
+
+/// Lists the host processes of a running container, matched through their cgroups.
+pub(crate) fn open_container_top(state: &mut AppState, id: String, name: String) {
+    state.input_mode = InputMode::Normal;
+    state.context_menu = None;
+    state.top_container_id = id;
+    state.top_container_name = name;
+    state.view_mode = ViewMode::ContainerTop;
+    state.selected = 0;
+    state.hover_row = None;
+}
+
+/// Leaves the container process list for the Process view, scoped to the same
+/// container and with the selected PID highlighted in the tree.
+pub(crate) fn jump_to_scoped_process_view(state: &mut AppState) {
+    let selected_pid = state.visible_pids.get(state.selected).copied();
+    state.process_scope = Some(ProcessScope {
+        container_id: state.top_container_id.clone(),
+        container_name: state.top_container_name.clone(),
+    });
+    state.pending_select_pid = selected_pid;
+    state.process_filter.clear();
+    state.zoom = true;
+    state.set_view(ViewMode::Process);
+    state.set_message(format!("Scope: {}", state.top_container_name));
+}
+
+pub(crate) fn open_selected_process_detail(state: &mut AppState, system: &System) {
+    let Some(pid) = state.visible_pids.get(state.selected).copied() else {
+        state.set_message("No process selected");
+        return;
+    };
+    let Some(details) =
+        process::load_process_details(system, pid, &state.container_cache, &state.user_cache)
+    else {
+        state.set_message(format!("Process PID {pid} not found"));
+        return;
+    };
+
+    let name = system
+        .process(pid)
+        .map(|process| process.name().to_string())
+        .unwrap_or_else(|| "-".to_string());
+    let container = if state.view_mode == ViewMode::ContainerTop {
+        state.top_container_name.clone()
+    } else {
+        "-".to_string()
+    };
+    let return_view = state.view_mode;
+    enter_env_view(
+        state,
+        return_view,
+        "PROCESS DETAIL",
+        format!("Process: {name}"),
+        format!("PID: {pid}"),
+        format!("Container: {container}"),
+        "Source: /proc".to_string(),
+    );
+    state.env_vars = details;
+}
+
+/// Opens the signal menu for the selected process, centered in the main area.
+pub(crate) fn open_selected_signal_menu(state: &mut AppState, system: &System) {
+    let Some(pid) = state.visible_pids.get(state.selected).copied() else {
+        state.set_message("No process selected");
+        return;
+    };
+    let name = system
+        .process(pid)
+        .map(|process| format!("{} ({pid})", process.name()))
+        .unwrap_or_else(|| format!("PID {pid}"));
+    open_signal_menu(state, pid.as_u32(), name);
+}
+
+pub(crate) fn open_signal_menu(state: &mut AppState, pid: u32, name: String) {
+    let items = SIGNAL_MENU.iter().map(|signal| ContextMenuAction::Signal(*signal)).collect();
+    open_centered_menu(state, items, ContextMenuTarget::Process { pid, name });
+}
+
+/// Opens the kill tree/group/session menu for the selected process.
+pub(crate) fn open_selected_kill_menu(state: &mut AppState, system: &System) {
+    let Some(pid) = state.visible_pids.get(state.selected).copied() else {
+        state.set_message("No process selected");
+        return;
+    };
+    let name = system
+        .process(pid)
+        .map(|process| format!("{} ({pid})", process.name()))
+        .unwrap_or_else(|| format!("PID {pid}"));
+    let items = [KillScope::Tree, KillScope::Group, KillScope::Session]
+        .into_iter()
+        .map(ContextMenuAction::KillScope)
+        .collect();
+    open_centered_menu(state, items, ContextMenuTarget::Process { pid: pid.as_u32(), name });
+}
+
+/// Offers to signal the parent that has not reaped its zombies, from either
+/// a selected zombie or a selected parent holding zombies.
+pub(crate) fn open_selected_zombie_menu(state: &mut AppState, system: &System) {
+    let Some(pid) = state.visible_pids.get(state.selected).copied() else {
+        state.set_message("No process selected");
+        return;
+    };
+    let Some(process) = system.process(pid) else {
+        state.set_message(format!("Process PID {pid} not found"));
+        return;
+    };
+    let is_zombie_of = |parent: Pid| {
+        move |child: &&sysinfo::Process| {
+            child.parent() == Some(parent) && child.status() == ProcessStatus::Zombie
+        }
+    };
+    let parent = if process.status() == ProcessStatus::Zombie {
+        let Some(parent) = process.parent() else {
+            state.set_message(format!("Zombie {} ({pid}) has no parent", process.name()));
+            return;
+        };
+        parent
+    } else if system.processes().values().any(|child| is_zombie_of(pid)(&child)) {
+        pid
+    } else {
+        state.set_message(format!("{} ({pid}) is not a zombie and holds none", process.name()));
+        return;
+    };
+    let zombies = system.processes().values().filter(is_zombie_of(parent)).count();
+    let name = system
+        .process(parent)
+        .map(|process| format!("{} ({parent})", process.name()))
+        .unwrap_or_else(|| format!("PID {parent}"));
+
+    let mut items = vec![ContextMenuAction::ZombieParent(Signal::Child)];
+    // Killing init or Spark would do more harm than the zombies
+    if parent != Pid::from_u32(1) && parent != Pid::from_u32(std::process::id()) {
+        items.push(ContextMenuAction::ZombieParent(Signal::Term));
+    }
+    open_centered_menu(state, items, ContextMenuTarget::Process { pid: parent.as_u32(), name: name.clone() });
+    if parent == Pid::from_u32(1) {
+        state.set_message(format!("{zombies} orphaned zombie(s) are held by init, which should reap them"));
+    } else {
+        state.set_message(format!(
+            "{name} holds {zombies} zombie(s); SIGCHLD asks it to reap them, killing it hands them to init"
+        ));
+    }
+}
+
+/// Lists the processes a tree, group or session kill would reach; Enter in
+/// the env pane confirms it.
+pub(crate) fn preview_kill(state: &mut AppState, pid: u32, name: &str, scope: KillScope) {
+    let mut system = System::new();
+    system.refresh_processes();
+    let targets = match process::kill_targets(&system, Pid::from_u32(pid), scope) {
+        Ok(targets) => targets,
+        Err(err) => {
+            state.set_message(format!("Cannot kill {} of {name}: {err}", scope.label()));
+            return;
+        }
+    };
+    let title = format!("KILL {}", scope.label().to_uppercase());
+    enter_env_view(
+        state,
+        state.view_mode,
+        &title,
+        format!("Target: {name}"),
+        format!("Processes: {}", targets.len()),
+        "Signal: SIGTERM".to_string(),
+        "Enter to kill, Esc to cancel".to_string(),
+    );
+    state.env_vars = targets
+        .iter()
+        .map(|target| format!("{}={} {}", target.key.pid, target.name, target.command))
+        .collect();
+    state.kill_plan = Some(KillPlan {
+        scope,
+        subject: name.to_string(),
+        targets: targets.into_iter().map(|target| target.key).collect(),
+    });
+}
+
+/// Sends SIGTERM to every previewed process, then reports in the background
+/// how many exited within a grace period.
+pub(crate) fn confirm_kill(state: &mut AppState) {
+    const GRACE: Duration = Duration::from_secs(3);
+
+    let Some(plan) = state.kill_plan.take() else {
+        return;
+    };
+    state.view_mode = state.env_return_view;
+    state.input_mode = InputMode::Normal;
+
+    let key = format!("kill:{}", plan.subject);
+    if state.pending_operations.contains_key(&key) {
+        state.set_message(format!("A kill of {} is already in progress", plan.subject));
+        return;
+    }
+    let signalled = process::signal_targets(&plan.targets, Signal::Term);
+    if signalled == 0 {
+        state.set_message(format!("No processes of {} could be signalled", plan.subject));
+        return;
+    }
+    state.set_message(format!("Sent SIGTERM to {signalled} of {} processes", plan.targets.len()));
+    state.pending_operations.insert(key.clone(), None);
+
+    let tx = state.operation_tx.clone();
+    std::thread::spawn(move || {
+        let started = Instant::now();
+        let mut running = process::count_running(&plan.targets);
+        while running > 0 && started.elapsed() < GRACE {
+            std::thread::sleep(Duration::from_millis(100));
+            running = process::count_running(&plan.targets);
+        }
+        let total = plan.targets.len();
+        let mut message = format!(
+            "Killed {} of {}: {} of {total} processes exited",
+            plan.scope.label(),
+            plan.subject,
+            total - running
+        );
+        if running > 0 {
+            message.push_str(&format!(", {running} still running"));
+        }
+        let _ = tx.send(OperationComplete {
+            container_id: key,
+            success: running == 0,
+            message,
+        });
+    });
+}
+
+/// Opens a context menu in the middle of the screen, for menus opened from the keyboard.
+fn open_centered_menu(state: &mut AppState, items: Vec<ContextMenuAction>, target: ContextMenuTarget) {
+    const MENU_WIDTH: u16 = 16;
+    let (width, height) = terminal::size().unwrap_or((80, 24));
+    let menu_height = items.len() as u16 + 2;
+    state.context_menu = Some(ContextMenu {
+        x: width.saturating_sub(MENU_WIDTH) / 2,
+        y: height.saturating_sub(menu_height) / 2,
+        items,
+        hover: Some(0),
+        target,
+        is_group: false,
+    });
+}
+
+/// PM2 app on the selected Node row, if the row is managed by PM2
+fn selected_pm2_app(state: &mut AppState) -> Option<node::Pm2Info> {
+    let info = state.visible_node_pm2.get(state.selected).cloned().flatten();
+    if info.is_none() {
+        state.set_message("Selected process is not managed by PM2");
+    }
+    info
+}
+
+pub(crate) fn open_selected_pm2_menu(state: &mut AppState) {
+    let Some(info) = selected_pm2_app(state) else {
+        return;
+    };
+    let mut items = vec![
+        ContextMenuAction::Pm2(Pm2Action::Restart),
+        ContextMenuAction::Pm2(Pm2Action::Reload),
+    ];
+    if info.status != "stopped" {
+        items.push(ContextMenuAction::Pm2(Pm2Action::Stop));
+    }
+    if info.mode == "cluster" {
+        items.push(ContextMenuAction::Pm2(Pm2Action::ScaleUp));
+        items.push(ContextMenuAction::Pm2(Pm2Action::ScaleDown));
+    }
+    items.push(ContextMenuAction::Logs);
+    items.push(ContextMenuAction::Pm2(Pm2Action::Flush));
+    items.push(ContextMenuAction::Pm2(Pm2Action::Delete));
+    items.push(ContextMenuAction::Pm2Detail);
+    open_centered_menu(
+        state,
+        items,
+        ContextMenuTarget::Pm2App {
+            pm_id: info.pm_id,
+            name: info.name,
+        },
+    );
+}
+
+/// Shows the PM2 metadata of the selected app, or its runtime details when it
+/// is not managed by PM2.
+pub(crate) fn open_selected_app_detail(state: &mut AppState) {
+    let Some(Some(app)) = state.visible_node_apps.get(state.selected).cloned() else {
+        state.set_message("No app selected");
+        return;
+    };
+    if let Some(info) = &app.pm2 {
+        open_pm2_detail(state, info);
+        if let Some(toolchain) = &app.toolchain {
+            state.env_vars.extend(toolchain.detail_lines());
+        }
+        return;
+    }
+    let return_view = state.view_mode;
+    enter_env_view(
+        state,
+        return_view,
+        "APP DETAIL",
+        format!("App: {}", app.name),
+        format!("PID: {}", app.pid),
+        format!("Runtime: {}", app.runtime_label()),
+        format!("Project: {}", app.project_name.as_deref().unwrap_or("-")),
+    );
+    state.env_vars = app.detail_lines();
+}
+
+/// Toggles the Apps view filter for Node apps running a version their project does not accept.
+pub(crate) fn toggle_wrong_version_filter(state: &mut AppState) {
+    state.node_wrong_version_only = !state.node_wrong_version_only;
+    state.selected = 0;
+    if state.node_wrong_version_only {
+        state.set_message("Showing only apps running the wrong Node version");
+    } else {
+        state.set_message("Showing all apps");
+    }
+}
+
+/// Toggles the Process view filter for D-state and zombie processes.
+pub(crate) fn toggle_stuck_filter(state: &mut AppState) {
+    state.process_stuck_only = !state.process_stuck_only;
+    state.selected = 0;
+    if state.process_stuck_only {
+        state.set_message("Showing only D-state and zombie processes with their parents");
+    } else {
+        state.set_message("Showing all processes");
+    }
+}
+
+/// Shows the PM2 metadata of an app in the env pane.
+pub(crate) fn open_pm2_detail(state: &mut AppState, info: &node::Pm2Info) {
+    let return_view = state.view_mode;
+    enter_env_view(
+        state,
+        return_view,
+        "PM2 DETAIL",
+        format!("App: {}", info.name),
+        format!("PM2 ID: {}", info.pm_id),
+        format!("Status: {} ({})", info.status, info.mode),
+        "Source: pm2 jlist".to_string(),
+    );
+    state.env_vars = info.detail_lines();
+}
+
+pub(crate) fn open_selected_pm2_logs(state: &mut AppState) {
+    if let Some(info) = selected_pm2_app(state) {
+        open_pm2_logs(state, &info);
+    }
+}
+
+/// Opens a live tail of the app's stdout and stderr log files.
+pub(crate) fn open_pm2_logs(state: &mut AppState, info: &node::Pm2Info) {
+    let files: Vec<(PathBuf, LogStream)> = [
+        (info.out_log.clone(), "out", LogStream::Stdout),
+        (info.err_log.clone(), "error", LogStream::Stderr),
+    ]
+    .into_iter()
+    .filter_map(|(path, suffix, stream)| {
+        let path = path.map(PathBuf::from).or_else(|| node::default_pm2_log_path(&info.name, suffix))?;
+        // Logging disabled with `out_file: "/dev/null"`
+        (path != Path::new("/dev/null")).then_some((path, stream))
+    })
+    .collect();
+    if files.is_empty() {
+        state.set_message(format!("{} has logging disabled", info.name));
+        return;
+    }
+
+    let return_view = state.view_mode;
+    state.log_pane = Some(LogPane::new(
+        "PM2 LOGS",
+        format!("{} (pm2 {})", info.name, info.pm_id),
+        files,
+        return_view,
+    ));
+    state.input_mode = InputMode::Normal;
+    state.view_mode = ViewMode::Logs;
+}
+
+/// Lists the package.json scripts of the selected app's project.
+pub(crate) fn open_selected_scripts(state: &mut AppState) {
+    let Some(Some(app)) = state.visible_node_apps.get(state.selected) else {
+        state.set_message("No app selected");
+        return;
+    };
+    let Some(scripts) = app.project_dir.as_deref().and_then(node::load_package_scripts) else {
+        state.set_message("No package.json found for the selected app");
+        return;
+    };
+    let project = scripts
+        .name
+        .clone()
+        .or_else(|| scripts.dir.file_name().map(|name| name.to_string_lossy().into_owned()))
+        .unwrap_or_else(|| "-".to_string());
+    if scripts.scripts.is_empty() {
+        state.set_message(format!("{project} has no scripts in package.json"));
+        return;
+    }
+
+    enter_env_view(
+        state,
+        ViewMode::Node,
+        "PACKAGE SCRIPTS",
+        format!("Project: {project}"),
+        format!("Runner: {}", scripts.manager.program()),
+        format!("Dir: {}", scripts.dir.display()),
+        format!("Scripts: {}", scripts.scripts.len()),
+    );
+    state.env_vars = scripts
+        .scripts
+        .iter()
+        .map(|(name, command)| format!("{name}={command}"))
+        .collect();
+    state.env_scripts = Some(scripts);
+    state.set_message("Enter runs the selected script");
+}
+
+/// Runs the script selected in the scripts pane and follows its output.
+pub(crate) fn run_selected_script(state: &mut AppState) {
+    let Some(scripts) = state.env_scripts.as_ref() else {
+        return;
+    };
+    let Some((name, _)) = scripts.scripts.get(state.env_selected) else {
+        return;
+    };
+    let program = scripts.manager.program();
+    let args = scripts.manager.run_args(name);
+    let subject = match &scripts.name {
+        Some(project) => format!("{project}: {name}"),
+        None => name.clone(),
+    };
+    match CommandOutput::spawn(program, &args, &scripts.dir) {
+        Ok(output) => {
+            state.set_message(format!("Started {} (PID {})", output.command_line(), output.pid()));
+            state.log_pane = Some(LogPane::for_command("SCRIPT OUTPUT", subject, output, ViewMode::Node));
+            state.env_scripts = None;
+            state.input_mode = InputMode::Normal;
+            state.view_mode = ViewMode::Logs;
+        }
+        Err(err) => state.set_message(format!("Failed to start {program}: {err}")),
+    }
+}
+
+pub(crate) fn run_selected_pm2_action(state: &mut AppState, action: Pm2Action) {
+    let Some(info) = selected_pm2_app(state) else {
+        return;
+    };
+    if matches!(action, Pm2Action::ScaleUp | Pm2Action::ScaleDown) && info.mode != "cluster" {
+        state.set_message(format!("{} runs in fork mode; only cluster apps can scale", info.name));
+        return;
+    }
+    start_pm2_action(state, info.pm_id, info.name, action);
+}
+
+/// Runs a PM2 command in the background; the row shows a spinner until it completes.
+pub(crate) fn start_pm2_action(state: &mut AppState, pm_id: u32, name: String, action: Pm2Action) {
+    let key = pm2_pending_key(pm_id);
+    if state.pending_operations.contains_key(&key) {
+        state.set_message(format!("{name} has an operation in progress"));
+        return;
+    }
+
+    state.set_message(format!("{} {}...", action.verb(), name));
+    state.pending_operations.insert(key.clone(), None);
+    let tx = state.operation_tx.clone();
+    std::thread::spawn(move || {
+        let result = node::run_pm2_action(pm_id, &name, action);
+        let message = match &result {
+            Ok(()) => format!("{} {}", action.past(), name),
+            Err(err) => format!("{name}: {err}"),
+        };
+        let _ = tx.send(OperationComplete {
+            container_id: key,
+            success: result.is_ok(),
+            message,
+        });
+    });
+}
+
+/// Starts the selected ecosystem app with `pm2 start <file> --only <name>` in the background.
+/// Returns false if the selected row is not a declared app.
+pub(crate) fn start_selected_declared_app(state: &mut AppState) -> bool {
+    let Some(Some(app)) = state.visible_node_apps.get(state.selected) else {
+        return false;
+    };
+    let Some(declared) = app.declared.clone() else {
+        return false;
+    };
+    let key = pm2_start_pending_key(&declared.name);
+    if state.pending_operations.contains_key(&key) {
+        state.set_message(format!("{} is already starting", declared.name));
+        return true;
+    }
+
+    state.set_message(format!("Starting {} from {}...", declared.name, declared.file.display()));
+    state.pending_operations.insert(key.clone(), None);
+    let tx = state.operation_tx.clone();
+    std::thread::spawn(move || {
+        let result = node::start_ecosystem_app(&declared.file, &declared.name);
+        let message = match &result {
+            Ok(()) => format!("Started {}", declared.name),
+            Err(err) => format!("{}: {err}", declared.name),
+        };
+        let _ = tx.send(OperationComplete {
+            container_id: key,
+            success: result.is_ok(),
+            message,
+        });
+    });
+    true
+}
+
+/// Activates the Node.js inspector of the selected process in the background.
+pub(crate) fn activate_selected_inspector(state: &mut AppState, system: &System) {
+    let Some(pid) = state.visible_pids.get(state.selected).copied() else {
+        state.set_message("No process selected");
+        return;
+    };
+    if pid.as_u32() == 0 {
+        state.set_message("Selected app is not running");
+        return;
+    }
+    if let Some(Some(app)) = state.visible_node_apps.get(state.selected) {
+        if app.runtime != Runtime::Node {
+            // SIGUSR1 terminates most other runtimes
+            state.set_message(format!("Inspector is only available for Node apps, not {}", app.runtime.label()));
+            return;
+        }
+    }
+    let name = system
+        .process(pid)
+        .map(|process| process.name().to_string())
+        .unwrap_or_else(|| pid.to_string());
+
+    state.set_message(format!("Activating inspector for {name} (PID {pid})..."));
+    let tx = state.inspector_tx.clone();
+    let pid = pid.as_u32();
+    std::thread::spawn(move || {
+        let result = node::activate_inspector(pid);
+        let _ = tx.send(InspectorReady { pid, name, result });
+    });
+}
+
+/// Shows finished inspector activations; returns true if anything changed.
+pub(crate) fn check_inspector_results(state: &mut AppState) -> bool {
+    let mut changed = false;
+    while let Ok(ready) = state.inspector_rx.try_recv() {
+        changed = true;
+        let target = match ready.result {
+            Ok(target) => target,
+            Err(err) => {
+                state.set_message(format!("Inspector for {}: {err}", ready.name));
+                continue;
+            }
+        };
+        if state.view_mode != ViewMode::Node {
+            state.set_message(format!("Inspector for {}: {}", ready.name, target.websocket_url));
+            continue;
+        }
+
+        enter_env_view(
+            state,
+            ViewMode::Node,
+            "NODE INSPECTOR",
+            format!("Process: {}", ready.name),
+            format!("PID: {}", ready.pid),
+            format!("Port: {}", target.port),
+            "Source: SIGUSR1".to_string(),
+        );
+        state.env_vars = vec![
+            format!("WEBSOCKET={}", target.websocket_url),
+            format!("DEVTOOLS={}", target.devtools_url),
+            "CHROME=chrome://inspect".to_string(),
+            format!("SCRIPT={}", target.title.as_deref().unwrap_or("-")),
+        ];
+        state.set_message("Inspector ready; y copies the selected URL");
+    }
+    changed
+}
+
+/// Copies the value of the selected env pane line.
+pub(crate) fn copy_selected_env_value(state: &mut AppState) {
+    let Some(line) = state.env_vars.get(state.env_selected) else {
+        return;
+    };
+    let value = line.split_once('=').map_or(line.as_str(), |(_, value)| value).to_string();
+    match crate::util::clipboard::copy_to_clipboard(&value) {
+        Ok(via) => state.set_message(format!("Copied via {via}")),
+        Err(err) => state.set_message(format!("Copy failed: {err}")),
+    }
+}
+
+/// Opens the local URL of the selected app in the browser.
+pub(crate) fn open_selected_app_url(state: &mut AppState) {
+    let Some(Some(app)) = state.visible_node_apps.get(state.selected) else {
+        state.set_message("No app selected");
+        return;
+    };
+    let Some(url) = app.url() else {
+        state.set_message(format!("{} is not listening on any port", app.name));
+        return;
+    };
+    match crate::util::browser::open_url(&url) {
+        Ok(via) => state.set_message(format!("Opened {url} via {via}")),
+        Err(err) => state.set_message(format!("Failed to open {url}: {err}")),
+    }
+}
+
+/// Folds or unfolds the children of the selected process in the tree.
+pub(crate) fn toggle_selected_fold(state: &mut AppState, system: &System) {
+    if !state.zoom {
+        state.set_message("Turn on zoom (z) to show and fold the process tree");
+        return;
+    }
+    let Some(pid) = state.visible_pids.get(state.selected).copied() else {
+        state.set_message("No process selected");
+        return;
+    };
+    let Some(process) = system.process(pid) else {
+        state.set_message(format!("Process PID {pid} not found"));
+        return;
+    };
+    let has_children = system.processes().values().any(|child| child.parent() == Some(pid));
+    let key = process::ProcessKey::of(process);
+    if state.collapsed_processes.remove(&key) {
+        state.set_message(format!("Expanded {}", process.name()));
+    } else if has_children {
+        state.collapsed_processes.insert(key);
+        state.set_message(format!("Collapsed {}", process.name()));
+    } else {
+        state.set_message(format!("{} has no child processes", process.name()));
+    }
+    state.pending_select_pid = Some(pid);
+}
+
+/// Folds every process that has children, keeping the selection on the same process.
+pub(crate) fn collapse_all_processes(state: &mut AppState, system: &System) {
+    if !state.zoom {
+        state.set_message("Turn on zoom (z) to show and fold the process tree");
+        return;
+    }
+    let parents: HashSet<Pid> = system.processes().values().filter_map(|process| process.parent()).collect();
+    for pid in parents {
+        if let Some(process) = system.process(pid) {
+            state.collapsed_processes.insert(process::ProcessKey::of(process));
+        }
+    }
+    state.pending_select_pid = state.visible_pids.get(state.selected).copied();
+    state.set_message("Collapsed all process subtrees");
+}
+
+pub(crate) fn expand_all_processes(state: &mut AppState) {
+    state.collapsed_processes.clear();
+    state.pending_select_pid = state.visible_pids.get(state.selected).copied();
+    state.set_message("Expanded all process subtrees");
+}
+
+pub(crate) fn signal_process(state: &mut AppState, pid: u32, name: &str, signal: Signal) {
+    match process::send_signal(Pid::from_u32(pid), signal) {
+        Ok(()) => state.set_message(format!("Sent {signal} to {name}")),
+        Err(err) => state.set_message(format!("Failed to signal {name}: {err}")),
+    }
+}
+
+pub(crate) fn open_selected_container_limits(state: &mut AppState, containers: &[ContainerInfo]) {
+    use crate::system::docker::DockerRow;
+
+    let Some(row) = state.docker_rows.get(state.docker_selected_row) else {
+        state.set_message("No container selected");
+        return;
+    };
+
+    let container_index = match row {
+        DockerRow::Item { index, .. } => *index,
+        DockerRow::Group { .. } => {
+            state.set_message("Select a container to edit limits");
+            return;
+        }
+        DockerRow::Separator => {
+            state.set_message("No container selected");
+            return;
+        }
+    };
+
+    let Some(container) = containers.get(container_index) else {
+        state.set_message("No container selected");
+        return;
+    };
+    let id = container.id.clone();
+    let name = container.name.clone();
+    open_container_limits(state, id, name);
+}
+
+/// Opens the resource limit form, pre-filled from the container's current `HostConfig`.
+pub(crate) fn open_container_limits(state: &mut AppState, id: String, name: String) {
+    if state.is_container_pending(&id) {
+        state.set_message(format!("{name} has an operation in progress"));
+        return;
+    }
+    let current = match docker::load_container_limits(&id) {
+        Ok(limits) => limits,
+        Err(err) => {
+            state.set_message(format!("Failed to load limits: {err}"));
+            return;
+        }
+    };
+    let max_cpus = docker::docker_host_cpus()
+        .or_else(|_| std::thread::available_parallelism().map(|count| count.get()))
+        .unwrap_or(1);
+
+    let fields = vec![
+        FormField {
+            label: "CPUs",
+            value: current.cpus_text(),
+            hint: "cores, e.g. 1.5",
+        },
+        FormField {
+            label: "CPU set",
+            value: current.cpuset_cpus.clone(),
+            hint: "e.g. 0-3,6",
+        },
+        FormField {
+            label: "Memory",
+            value: current.memory_text(),
+            hint: "e.g. 512m, 2g",
+        },
+        FormField {
+            label: "Restart",
+            value: current.restart_text(),
+            hint: "no|always|unless-stopped|on-failure[:N]",
+        },
+    ];
+    state.context_menu = None;
+    state.form = Some(FormOverlay {
+        title: format!("Limits: {name}"),
+        fields,
+        selected: 0,
+        error: None,
+        purpose: FormPurpose::ContainerLimits {
+            id,
+            name,
+            current,
+            max_cpus,
+        },
+    });
+}
+
+/// Validates the open form and starts its operation; invalid input keeps the form open.
+/// Lists the open file descriptors of the selected process in the env pane.
+pub(crate) fn open_selected_fds(state: &mut AppState, system: &System) {
+    let Some(pid) = state.visible_pids.get(state.selected).copied() else {
+        state.set_message("No process selected");
+        return;
+    };
+    let name = system
+        .process(pid)
+        .map(|process| process.name().to_string())
+        .unwrap_or_else(|| "-".to_string());
+    let entries = match fds::list_fds(pid) {
+        Ok(entries) => entries,
+        Err(err) => {
+            state.set_message(format!("Failed to read fds of {name} ({pid}): {err}"));
+            return;
+        }
+    };
+    let summary = fds::summarize_fds(pid, &entries);
+    let limit = summary
+        .limit
+        .map(|limit| format!(" of {limit}"))
+        .unwrap_or_default();
+
+    let return_view = state.view_mode;
+    enter_env_view(
+        state,
+        return_view,
+        "OPEN FILES",
+        format!("Process: {name} ({pid})"),
+        format!("Open: {}{limit}", summary.open),
+        format!("Sockets: {} | Pipes: {}", summary.sockets, summary.pipes),
+        format!("Deleted: {}", summary.deleted),
+    );
+    state.env_vars = entries
+        .iter()
+        .map(|entry| {
+            let mut value = entry.target.clone();
+            if entry.deleted {
+                value.push_str(" (deleted)");
+            }
+            if let Some(pos) = entry.pos.filter(|_| entry.kind == fds::FdKind::File) {
+                value.push_str(&format!(" | pos {pos}"));
+            }
+            if let Some(flags) = entry.flags_label() {
+                value.push_str(&format!(" | {flags}"));
+            }
+            format!("{} {}={value}", entry.fd, entry.kind.label())
+        })
+        .collect();
+}
+
+pub(crate) fn open_selected_threads(state: &mut AppState, system: &System) {
+    let Some(pid) = state.visible_pids.get(state.selected).copied() else {
+        state.set_message("No process selected");
+        return;
+    };
+    let name = system
+        .process(pid)
+        .map(|process| process.name().to_string())
+        .unwrap_or_else(|| "-".to_string());
+    let return_view = state.view_mode;
+    enter_env_view(
+        state,
+        return_view,
+        "THREADS",
+        format!("Process: {name} ({pid})"),
+        String::new(),
+        String::new(),
+        String::new(),
+    );
+    state.thread_pane = Some(ThreadPane {
+        subject: format!("{name} ({pid})"),
+        sampler: ThreadSampler::new(pid),
+    });
+    refresh_thread_pane(state);
+}
+
+/// Resamples the threads listed in the env pane, keeping the selected thread
+/// selected as the busiest-first order changes.
+pub(crate) fn refresh_thread_pane(state: &mut AppState) {
+    let Some(pane) = state.thread_pane.as_mut() else {
+        return;
+    };
+    let threads = match pane.sampler.sample() {
+        Ok(threads) => threads,
+        Err(_) => {
+            let message = format!("{} has exited", pane.subject);
+            state.thread_pane = None;
+            state.env_info_right2 = "Exited".to_string();
+            state.set_message(message);
+            return;
+        }
+    };
+    let selected_tid = state
+        .env_vars
+        .get(state.env_selected)
+        .and_then(|line| line.split_whitespace().next())
+        .and_then(|tid| tid.parse::<u32>().ok());
+
+    let running = threads.iter().filter(|thread| thread.state == 'R').count();
+    let blocked = threads.iter().filter(|thread| thread.state == 'D').count();
+    let total_cpu: f32 = threads.iter().map(|thread| thread.cpu).sum();
+    state.env_info_right1 = format!("Threads: {}", threads.len());
+    state.env_info_left2 = format!("Running: {running} | Disk sleep: {blocked}");
+    state.env_info_right2 = format!("CPU: {total_cpu:.1}%");
+    state.env_vars = threads
+        .iter()
+        .map(|thread| {
+            let last_cpu = thread
+                .last_cpu
+                .map(|cpu| cpu.to_string())
+                .unwrap_or_else(|| "-".to_string());
+            format!(
+                "{} {}={:.1}% | {} {} | cpu {last_cpu} | ctx {} vol, {} invol",
+                thread.tid,
+                thread.name,
+                thread.cpu,
+                thread.state,
+                threads::state_label(thread.state),
+                thread.voluntary_switches,
+                thread.involuntary_switches,
+            )
+        })
+        .collect();
+    state.env_selected = selected_tid
+        .and_then(|tid| threads.iter().position(|thread| thread.tid == tid))
+        .unwrap_or(state.env_selected)
+        .min(state.env_vars.len().saturating_sub(1));
+}
+
+pub(crate) fn open_find_open_file_form(state: &mut AppState) {
+    state.context_menu = None;
+    state.form = Some(FormOverlay {
+        title: "Who has this file open".to_string(),
+        fields: vec![FormField {
+            label: "Path",
+            value: String::new(),
+            hint: "full path or part",
+        }],
+        selected: 0,
+        error: None,
+        purpose: FormPurpose::FindOpenFile,
+    });
+}
+
+/// Opens the nice / I/O priority / CPU affinity form for the selected process.
+pub(crate) fn open_selected_priority_form(state: &mut AppState, system: &System) {
+    let Some(pid) = state.visible_pids.get(state.selected).copied() else {
+        state.set_message("No process selected");
+        return;
+    };
+    let name = system
+        .process(pid)
+        .map(|process| format!("{} ({pid})", process.name()))
+        .unwrap_or_else(|| format!("PID {pid}"));
+    let Some(current) = priority::read_sched_info(pid) else {
+        state.set_message(format!("Failed to read the priority of {name}"));
+        return;
+    };
+    let max_cpus = std::thread::available_parallelism()
+        .map(|count| count.get())
+        .unwrap_or(1);
+
+    let fields = vec![
+        FormField {
+            label: "Nice",
+            value: current.nice.to_string(),
+            hint: "-20 (high) to 19 (low)",
+        },
+        FormField {
+            label: "I/O class",
+            value: current.io.map(|io| io.label()).unwrap_or_default(),
+            hint: "be/0-7, rt/0-7, idle",
+        },
+        FormField {
+            label: "CPUs",
+            value: current.cpus.clone(),
+            hint: "e.g. 0-3,6",
+        },
+        FormField {
+            label: "Apply to",
+            value: "process".to_string(),
+            hint: "process or tree",
+        },
+    ];
+    state.context_menu = None;
+    state.form = Some(FormOverlay {
+        title: format!("Priority: {name}"),
+        fields,
+        selected: 0,
+        error: None,
+        purpose: FormPurpose::ProcessPriority {
+            pid,
+            name,
+            current,
+            max_cpus,
+        },
+    });
+}
+
+pub(crate) fn toggle_sched_columns(state: &mut AppState) {
+    state.show_sched_columns = !state.show_sched_columns;
+    if state.show_sched_columns {
+        state.set_message("Showing nice, I/O class and CPU affinity");
+    } else {
+        state.set_message("Hiding priority columns");
+    }
+}
+
+pub(crate) fn toggle_net_columns(state: &mut AppState) {
+    state.show_net_columns = !state.show_net_columns;
+    if state.show_net_columns {
+        state.set_message("Showing network RX/TX rates");
+    } else {
+        state.set_message("Hiding network columns");
+    }
+}
+
+pub(crate) fn open_column_chooser(state: &mut AppState) {
+    match state.column_view() {
+        Some(view) => state.column_chooser = Some(ColumnChooser::new(view, state.columns.get(view))),
+        None => state.set_message("Columns only available in list views"),
+    }
+}
+
+/// Pins the selected chooser column one step wider or narrower, starting
+/// from the width it currently renders at.
+pub(crate) fn resize_chooser_column(state: &mut AppState, delta: isize) {
+    let Some(chooser) = state.column_chooser.as_ref() else {
+        return;
+    };
+    let Some(id) = chooser.settings.get(chooser.selected).map(|setting| setting.id) else {
+        return;
+    };
+    // Same main-area width as the UI layout: 20-column sidebar plus a gap when it fits
+    let (width, _) = terminal::size().unwrap_or((80, 24));
+    let width = width as usize;
+    let main_width = if width >= 61 { width - 21 } else { width };
+    // The chooser's settings are previewed, so this is the width on screen
+    let current = state
+        .resolved_columns(chooser.view, main_width)
+        .iter()
+        .find(|column| column.id == id)
+        .map(|column| column.width)
+        .or_else(|| chooser.settings.get(chooser.selected).and_then(|setting| setting.width))
+        .unwrap_or(8);
+    if let Some(chooser) = state.column_chooser.as_mut() {
+        chooser.resize_selected(current, delta);
+    }
+}
+
+pub(crate) fn save_column_chooser(state: &mut AppState) {
+    let Some(chooser) = state.column_chooser.take() else {
+        return;
+    };
+    state.columns.set(chooser.view, chooser.settings);
+    match state.columns.save() {
+        Ok(()) => state.set_message(format!("Saved {} columns", chooser.view.label())),
+        Err(err) => state.set_message(format!("Failed to save columns: {err}")),
+    }
+}
+
+pub(crate) fn submit_form(state: &mut AppState) {
+    let Some(form) = state.form.as_mut() else {
+        return;
+    };
+    match &form.purpose {
+        FormPurpose::ContainerLimits {
+            id,
+            name,
+            current,
+            max_cpus,
+        } => {
+            let update = match docker::validate_limits(
+                current,
+                form.value(0),
+                form.value(1),
+                form.value(2),
+                form.value(3),
+                *max_cpus,
+            ) {
+                Ok(update) => update,
+                Err(err) => {
+                    form.error = Some(err);
+                    return;
+                }
+            };
+            let (id, name, current) = (id.clone(), name.clone(), current.clone());
+            state.form = None;
+            if update.is_empty() {
+                state.set_message("Limits unchanged");
+                return;
+            }
+
+            state.set_message(format!("Updating limits for {name}..."));
+            state.pending_operations.insert(id.clone(), None);
+            let tx = state.operation_tx.clone();
+            std::thread::spawn(move || {
+                let result = docker::update_container_limits(&id, &current, &update);
+                let message = match &result {
+                    Ok(()) => format!("Updated limits for {name}"),
+                    Err(err) => format!("Failed to update {name}: {err}"),
+                };
+                let _ = tx.send(OperationComplete {
+                    container_id: id,
+                    success: result.is_ok(),
+                    message,
+                });
+            });
+        }
+        FormPurpose::ProcessPriority {
+            pid,
+            name,
+            current,
+            max_cpus,
+        } => {
+            let update = match priority::validate_priority(
+                form.value(0),
+                form.value(1),
+                form.value(2),
+                *max_cpus,
+            ) {
+                Ok(update) => update,
+                Err(err) => {
+                    form.error = Some(err);
+                    return;
+                }
+            };
+            let tree = match form.value(3).trim() {
+                "" | "process" => false,
+                "tree" => true,
+                other => {
+                    form.error = Some(format!("Apply to: '{other}' is not process or tree"));
+                    return;
+                }
+            };
+            // Descendants may differ from the selected process, so a tree gets every value
+            let update = if tree { update } else { update.changed_from(current) };
+            if update.is_empty() {
+                state.form = None;
+                state.set_message("Priority unchanged");
+                return;
+            }
+            let (pid, name) = (*pid, name.clone());
+            let pids = if tree {
+                let mut system = System::new();
+                system.refresh_processes();
+                process::tree_pids(&system, pid)
+            } else {
+                vec![pid]
+            };
+            state.form = None;
+            let subject = if tree {
+                format!("{} processes in the tree of {name}", pids.len())
+            } else {
+                name
+            };
+            match priority::apply_priority(&pids, &update) {
+                Ok(()) => state.set_message(format!("Updated priority of {subject}")),
+                Err(err) => state.set_message(format!("Failed to update {subject}: {err}")),
+            }
+        }
+        FormPurpose::FindOpenFile => {
+            let query = form.value(0).trim().to_string();
+            if query.is_empty() {
+                form.error = Some("Path: enter a path or part of one".to_string());
+                return;
+            }
+            state.form = None;
+            state.set_message(format!("Searching open files for \"{query}\"..."));
+            // Scanning every fd in /proc can take a while; keep the status up until it ends
+            state.message_until = None;
+            let tx = state.open_files_tx.clone();
+            std::thread::spawn(move || {
+                let found = fds::find_open_files(&query);
+                let mut system = System::new();
+                system.refresh_processes();
+                let names = found
+                    .iter()
+                    .filter_map(|open| {
+                        let process = system.process(open.pid)?;
+                        Some((open.pid, process.name().to_string()))
+                    })
+                    .collect();
+                let _ = tx.send(OpenFilesFound { query, found, names });
+            });
+        }
+    }
+}
+
+/// Shows finished open file searches; returns true if anything changed.
+pub(crate) fn check_open_file_results(state: &mut AppState) -> bool {
+    let mut changed = false;
+    while let Ok(OpenFilesFound { query, found, names }) = state.open_files_rx.try_recv() {
+        changed = true;
+        if found.is_empty() {
+            state.set_message(format!("No process has \"{query}\" open"));
+            continue;
+        }
+        let processes = found.iter().map(|open| open.pid).collect::<HashSet<_>>().len();
+        let return_view = state.view_mode;
+        enter_env_view(
+            state,
+            return_view,
+            "OPEN FILE SEARCH",
+            format!("Query: {query}"),
+            format!("Matches: {}", found.len()),
+            format!("Processes: {processes}"),
+            "Source: /proc/*/fd".to_string(),
+        );
+        state.env_vars = found
+            .iter()
+            .map(|open| {
+                let name = names.get(&open.pid).map_or("-", String::as_str);
+                format!("{} {name}=fd {}: {}", open.pid, open.fd, open.target)
+            })
+            .collect();
+        state.message = None;
+    }
+    changed
+}
+
+pub(crate) fn open_docker_context_picker(state: &mut AppState) {
+    let contexts = match docker::list_docker_contexts() {
+        Ok(contexts) => contexts,
+        Err(err) => {
+            state.set_message(format!("Failed to list docker contexts: {err}"));
+            return;
+        }
+    };
+    if contexts.is_empty() {
+        state.set_message("No docker contexts found");
+        return;
+    }
+
+    let active = docker::active_docker_context();
+    let selected = contexts
+        .iter()
+        .position(|ctx| match &active {
+            Some(name) => &ctx.name == name,
+            None => ctx.current,
+        })
+        .unwrap_or(0);
+    state.context_menu = None;
+    state.docker_context_picker = Some(DockerContextPicker { contexts, selected });
+}
+
+pub(crate) fn switch_docker_context(state: &mut AppState, name: String) {
+    state.docker_context_picker = None;
+    if state.docker_context.as_deref() == Some(name.as_str()) {
+        return;
+    }
+
+    docker::set_docker_context(Some(name.clone()));
+    // Pending container operations belong to the previous daemon; PM2 ones are unaffected
+    state.pending_operations.retain(|key, _| is_pm2_pending_key(key));
+    state.docker_selected_row = 0;
+    state.hover_row = None;
+    // Force the container name cache to reload from the new daemon
+    state.container_last_refresh = Instant::now() - Duration::from_secs(60);
+    state.set_message(format!("Docker context: {name}"));
+    state.docker_context = Some(name);
+}
+
 pub(crate) fn open_selected_env(state: &mut AppState, system: &System) {
     match state.view_mode {
         ViewMode::Docker => open_selected_container_env(state, ViewMode::Docker),
         ViewMode::Process => open_selected_process_env(state, system, ViewMode::Process),
         ViewMode::Ports => open_selected_ports_env(state, system),
         ViewMode::Node => open_selected_process_env(state, system, ViewMode::Node),
-        ViewMode::DockerEnv => {}
+        ViewMode::ContainerTop => {
+            open_selected_process_env(state, system, ViewMode::ContainerTop)
+        }
+        ViewMode::DockerEnv | ViewMode::Logs => {}
     }
 }
 
@@ -383,6 +1659,9 @@ fn enter_env_view(
     state.env_info_left2 = info_left2;
     state.env_info_right2 = info_right2;
     state.env_selected = 0;
+    state.env_scripts = None;
+    state.kill_plan = None;
+    state.thread_pane = None;
 }
 
 fn format_ports_line(port_public: &str, port_internal: &str) -> String {
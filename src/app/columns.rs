@@ -0,0 +1,550 @@
+//! Column layouts of the list views: which columns are shown, in what order
+//! and how wide. Layouts are edited in the column chooser and saved to the
+//! "columns" section of the config file.
+
+use std::io;
+
+use serde_json::{Map, Value};
+
+use crate::config;
+
+use Align::{Left, Right};
+use ColumnWidth::{Fit, Fixed, Flex};
+
+/// A list view with a customisable column layout.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColumnView {
+    Process,
+    Docker,
+    Ports,
+    Node,
+}
+
+impl ColumnView {
+    pub const ALL: [ColumnView; 4] = [
+        ColumnView::Process,
+        ColumnView::Docker,
+        ColumnView::Ports,
+        ColumnView::Node,
+    ];
+
+    /// Name of the view in the config file
+    pub fn key(self) -> &'static str {
+        match self {
+            ColumnView::Process => "process",
+            ColumnView::Docker => "docker",
+            ColumnView::Ports => "ports",
+            ColumnView::Node => "node",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ColumnView::Process => "Process",
+            ColumnView::Docker => "Docker",
+            ColumnView::Ports => "Ports",
+            ColumnView::Node => "Apps",
+        }
+    }
+
+    /// Every column of the view, in default order.
+    pub fn defs(self) -> &'static [ColumnDef] {
+        match self {
+            ColumnView::Process => PROCESS_COLUMNS,
+            ColumnView::Docker => DOCKER_COLUMNS,
+            ColumnView::Ports => PORTS_COLUMNS,
+            ColumnView::Node => NODE_COLUMNS,
+        }
+    }
+
+    pub fn def(self, id: &str) -> Option<&'static ColumnDef> {
+        self.defs().iter().find(|def| def.id == id)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Align {
+    Left,
+    Right,
+}
+
+/// How a column is sized when its width is not pinned.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColumnWidth {
+    Fixed(usize),
+    /// As wide as its widest value, but at least `min`
+    Fit { min: usize },
+    /// Shares the space left by the other columns according to `weight`
+    Flex { min: usize, weight: usize },
+}
+
+/// Mode a column needs before it can be shown.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Requires {
+    Nothing,
+    /// Network columns toggled on with N
+    NetColumns,
+    /// Priority columns toggled on with P
+    SchedColumns,
+    /// PM2 is running
+    Pm2,
+    /// PM2 is not running
+    NoPm2,
+}
+
+impl Requires {
+    /// Shown in the column chooser next to columns that are unavailable.
+    pub fn hint(self) -> &'static str {
+        match self {
+            Requires::Nothing => "",
+            Requires::NetColumns => "needs N",
+            Requires::SchedColumns => "needs P",
+            Requires::Pm2 => "PM2 only",
+            Requires::NoPm2 => "without PM2",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ColumnDef {
+    pub id: &'static str,
+    pub title: &'static str,
+    pub align: Align,
+    pub width: ColumnWidth,
+    /// Hidden until turned on in the column chooser
+    pub hidden: bool,
+    pub requires: Requires,
+}
+
+const fn column(id: &'static str, title: &'static str, align: Align, width: ColumnWidth) -> ColumnDef {
+    ColumnDef {
+        id,
+        title,
+        align,
+        width,
+        hidden: false,
+        requires: Requires::Nothing,
+    }
+}
+
+impl ColumnDef {
+    const fn hidden(mut self) -> Self {
+        self.hidden = true;
+        self
+    }
+
+    const fn requires(mut self, requires: Requires) -> Self {
+        self.requires = requires;
+        self
+    }
+}
+
+const PROCESS_COLUMNS: &[ColumnDef] = &[
+    column("pid", "PID", Right, Fixed(7)),
+    column("cpu", "CPU%", Right, Fixed(6)),
+    column("mem", "MEM(GB)", Right, Fixed(9)),
+    column("read", "READ/s", Right, Fixed(8)),
+    column("write", "WRITE/s", Right, Fixed(8)),
+    column("rx", "RX/s", Right, Fixed(8)).requires(Requires::NetColumns),
+    column("tx", "TX/s", Right, Fixed(8)).requires(Requires::NetColumns),
+    // NI, IO ("be/4", "idle") and CPUS ("0-3,6")
+    column("nice", "NI", Right, Fixed(4)).requires(Requires::SchedColumns),
+    column("ionice", "IO", Left, Fixed(5)).requires(Requires::SchedColumns),
+    column("cpus", "CPUS", Left, Fixed(9)).requires(Requires::SchedColumns),
+    column("started", "STARTED", Left, Fixed(7)).hidden(),
+    column("user", "USER", Left, Fit { min: 4 }),
+    column("name", "NAME@CTR", Left, Flex { min: 10, weight: 2 }),
+    column("cmd", "COMMAND", Left, Flex { min: 10, weight: 2 }).hidden(),
+    column("path", "PATH", Left, Flex { min: 10, weight: 1 }),
+];
+
+const DOCKER_COLUMNS: &[ColumnDef] = &[
+    column("id", "CONTAINER", Right, Fixed(12)),
+    column("cpu", "CPU%", Right, Fixed(6)),
+    column("mem", "MEM(GB)", Right, Fixed(9)),
+    column("name", "NAME", Left, Flex { min: 10, weight: 3 }),
+    column("image", "IMAGE", Left, Flex { min: 10, weight: 3 }),
+    column("port", "PORT", Left, Flex { min: 8, weight: 2 }),
+    column("int_port", "INT PORT", Left, Flex { min: 8, weight: 2 }),
+    column("status", "STATUS", Left, Flex { min: 10, weight: 2 }),
+    column("rx", "RX/s", Right, Fixed(8)).requires(Requires::NetColumns),
+    column("tx", "TX/s", Right, Fixed(8)).requires(Requires::NetColumns),
+];
+
+const PORTS_COLUMNS: &[ColumnDef] = &[
+    column("proto", "PROTO", Left, Fixed(5)),
+    column("port", "PORT", Right, Fixed(6)),
+    column("pid", "PID", Right, Fixed(7)),
+    column("name", "NAME", Left, Flex { min: 10, weight: 2 }),
+    column("project", "PROJECT", Left, Flex { min: 8, weight: 1 }),
+    column("path", "PATH", Left, Flex { min: 10, weight: 2 }),
+];
+
+const NODE_COLUMNS: &[ColumnDef] = &[
+    column("id", "ID", Right, Fixed(3)).requires(Requires::Pm2),
+    column("pid", "PID", Right, Fixed(7)),
+    column("name", "NAME", Left, Flex { min: 8, weight: 2 }),
+    column("status", "STATUS", Left, Fixed(8)).requires(Requires::Pm2),
+    column("cpu", "CPU%", Right, Fixed(6)),
+    column("mem", "MEMORY", Right, Fixed(9)),
+    column("restarts", "RST", Right, Fixed(4)).requires(Requires::Pm2),
+    column("runtime", "RUNTIME", Left, Fixed(18)).requires(Requires::NoPm2),
+    column("uptime", "UPTIME", Left, Fixed(8)),
+    column("framework", "FRAMEWORK", Left, Fixed(13)),
+    // Fits "http://localhost:65535"
+    column("url", "URL", Left, Fixed(22)),
+    column("project", "PROJECT", Left, Flex { min: 8, weight: 2 }),
+    column("script", "SCRIPT", Left, Flex { min: 10, weight: 3 }),
+];
+
+/// A column's place in a layout.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ColumnSetting {
+    pub id: &'static str,
+    pub visible: bool,
+    /// Pinned width; None sizes the column automatically
+    pub width: Option<usize>,
+}
+
+pub fn default_settings(view: ColumnView) -> Vec<ColumnSetting> {
+    view.defs()
+        .iter()
+        .map(|def| ColumnSetting {
+            id: def.id,
+            visible: !def.hidden,
+            width: None,
+        })
+        .collect()
+}
+
+/// Column layouts of all views.
+#[derive(Clone, Debug)]
+pub struct ColumnLayouts {
+    layouts: Vec<(ColumnView, Vec<ColumnSetting>)>,
+}
+
+impl Default for ColumnLayouts {
+    fn default() -> Self {
+        Self {
+            layouts: ColumnView::ALL
+                .into_iter()
+                .map(|view| (view, default_settings(view)))
+                .collect(),
+        }
+    }
+}
+
+impl ColumnLayouts {
+    const CONFIG_SECTION: &'static str = "columns";
+
+    /// Layouts saved in the config file, or the defaults.
+    pub fn load() -> Self {
+        config::read_section(Self::CONFIG_SECTION)
+            .map(|value| Self::from_json(&value))
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        config::write_section(Self::CONFIG_SECTION, self.to_json())
+    }
+
+    pub fn get(&self, view: ColumnView) -> &[ColumnSetting] {
+        self.layouts
+            .iter()
+            .find(|(layout_view, _)| *layout_view == view)
+            .map(|(_, settings)| settings.as_slice())
+            .unwrap_or_default()
+    }
+
+    pub fn set(&mut self, view: ColumnView, settings: Vec<ColumnSetting>) {
+        match self.layouts.iter_mut().find(|(layout_view, _)| *layout_view == view) {
+            Some((_, layout)) => *layout = settings,
+            None => self.layouts.push((view, settings)),
+        }
+    }
+
+    /// Reads the "columns" config section. Unknown columns are dropped and
+    /// columns missing from the file keep their default place at the end.
+    pub fn from_json(value: &Value) -> Self {
+        let mut layouts = Self::default();
+        for view in ColumnView::ALL {
+            let Some(saved) = value.get(view.key()).and_then(Value::as_array) else {
+                continue;
+            };
+            let mut settings: Vec<ColumnSetting> = Vec::new();
+            for entry in saved {
+                let Some(def) = entry.get("id").and_then(Value::as_str).and_then(|id| view.def(id)) else {
+                    continue;
+                };
+                if settings.iter().any(|setting| setting.id == def.id) {
+                    continue;
+                }
+                settings.push(ColumnSetting {
+                    id: def.id,
+                    visible: entry.get("visible").and_then(Value::as_bool).unwrap_or(!def.hidden),
+                    width: entry
+                        .get("width")
+                        .and_then(Value::as_u64)
+                        .map(|width| width as usize)
+                        .filter(|width| *width > 0),
+                });
+            }
+            for default in default_settings(view) {
+                if !settings.iter().any(|setting| setting.id == default.id) {
+                    settings.push(default);
+                }
+            }
+            layouts.set(view, settings);
+        }
+        layouts
+    }
+
+    pub fn to_json(&self) -> Value {
+        let mut root = Map::new();
+        for (view, settings) in &self.layouts {
+            let columns = settings
+                .iter()
+                .map(|setting| {
+                    let mut entry = Map::new();
+                    entry.insert("id".to_string(), Value::from(setting.id));
+                    entry.insert("visible".to_string(), Value::from(setting.visible));
+                    if let Some(width) = setting.width {
+                        entry.insert("width".to_string(), Value::from(width));
+                    }
+                    Value::Object(entry)
+                })
+                .collect();
+            root.insert(view.key().to_string(), Value::Array(columns));
+        }
+        Value::Object(root)
+    }
+}
+
+/// A column placed on screen.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Column {
+    pub id: &'static str,
+    pub title: &'static str,
+    pub align: Align,
+    pub width: usize,
+}
+
+/// Lays out the visible, available columns of `settings` in `total_width`,
+/// borders included. `natural` holds the content width of `Fit` columns.
+pub fn resolve_columns(
+    view: ColumnView,
+    settings: &[ColumnSetting],
+    total_width: usize,
+    available: impl Fn(Requires) -> bool,
+    natural: &[(&str, usize)],
+) -> Vec<Column> {
+    let shown: Vec<(&ColumnDef, Option<usize>)> = settings
+        .iter()
+        .filter(|setting| setting.visible)
+        .filter_map(|setting| view.def(setting.id).map(|def| (def, setting.width)))
+        .filter(|(def, _)| available(def.requires))
+        .collect();
+    let content_width = total_width.saturating_sub(shown.len() + 1);
+
+    let mut widths: Vec<usize> = shown
+        .iter()
+        .map(|(def, pinned)| match (pinned, def.width) {
+            (Some(width), _) => *width,
+            (None, Fixed(width)) => width,
+            (None, Fit { min }) => natural
+                .iter()
+                .find(|(id, _)| *id == def.id)
+                .map_or(min, |(_, width)| (*width).max(min)),
+            (None, Flex { .. }) => 0,
+        })
+        .collect();
+    let flex: Vec<(usize, usize, usize)> = shown
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, (def, pinned))| match (pinned, def.width) {
+            (None, Flex { min, weight }) => Some((idx, min, weight)),
+            _ => None,
+        })
+        .collect();
+
+    // Flexible columns get their minimum while space lasts, then share the rest
+    let mut remaining = content_width.saturating_sub(widths.iter().sum());
+    for &(idx, min, _) in &flex {
+        widths[idx] = min.min(remaining);
+        remaining -= widths[idx];
+    }
+    let total_weight: usize = flex.iter().map(|(_, _, weight)| weight).sum();
+    let extra = remaining;
+    for (position, &(idx, _, weight)) in flex.iter().enumerate() {
+        let share = if position + 1 == flex.len() {
+            remaining
+        } else {
+            (extra * weight).checked_div(total_weight).unwrap_or(0)
+        };
+        widths[idx] += share;
+        remaining -= share;
+    }
+
+    shown
+        .iter()
+        .zip(widths)
+        .map(|((def, _), width)| Column {
+            id: def.id,
+            title: def.title,
+            align: def.align,
+            width,
+        })
+        .collect()
+}
+
+/// Column under `x`, counted from the table's left border.
+pub fn column_at(columns: &[Column], x: usize) -> Option<&Column> {
+    let mut start = 1;
+    for column in columns {
+        if (start..start + column.width).contains(&x) {
+            return Some(column);
+        }
+        start += column.width + 1;
+    }
+    None
+}
+
+/// Overlay editing the column layout of one view; changes preview live and
+/// are saved on Enter.
+#[derive(Clone, Debug)]
+pub struct ColumnChooser {
+    pub view: ColumnView,
+    pub settings: Vec<ColumnSetting>,
+    pub selected: usize,
+}
+
+impl ColumnChooser {
+    pub fn new(view: ColumnView, settings: &[ColumnSetting]) -> Self {
+        Self {
+            view,
+            settings: settings.to_vec(),
+            selected: 0,
+        }
+    }
+
+    /// Screen rectangle (x, y, width, height) of the chooser, centered in the main area
+    pub fn frame(&self, main_x: u16, main_width: usize, height: usize) -> (u16, u16, usize, usize) {
+        let width = 48.min(main_width.saturating_sub(4)).max(4);
+        // Columns plus two lines of key help inside the border
+        let box_height = (self.settings.len() + 4).min(height.saturating_sub(2)).max(5);
+        let x = main_x + (main_width.saturating_sub(width) / 2) as u16;
+        let y = (height.saturating_sub(box_height) / 2) as u16;
+        (x, y, width, box_height)
+    }
+
+    pub fn select(&mut self, delta: isize) {
+        let last = self.settings.len().saturating_sub(1);
+        self.selected = self.selected.saturating_add_signed(delta).min(last);
+    }
+
+    /// Shows or hides the selected column; the last visible one stays shown.
+    pub fn toggle_selected(&mut self) {
+        let visible = self.settings.iter().filter(|setting| setting.visible).count();
+        if let Some(setting) = self.settings.get_mut(self.selected) {
+            if !setting.visible || visible > 1 {
+                setting.visible = !setting.visible;
+            }
+        }
+    }
+
+    /// Moves the selected column left (up) or right (down) in the layout.
+    pub fn move_selected(&mut self, delta: isize) {
+        let Some(target) = self.selected.checked_add_signed(delta) else {
+            return;
+        };
+        if target < self.settings.len() {
+            self.settings.swap(self.selected, target);
+            self.selected = target;
+        }
+    }
+
+    /// Pins the selected column `delta` wider or narrower than `current`.
+    pub fn resize_selected(&mut self, current: usize, delta: isize) {
+        if let Some(setting) = self.settings.get_mut(self.selected) {
+            let width = setting.width.unwrap_or(current);
+            setting.width = Some(width.saturating_add_signed(delta).clamp(1, 200));
+        }
+    }
+
+    pub fn unpin_selected(&mut self) {
+        if let Some(setting) = self.settings.get_mut(self.selected) {
+            setting.width = None;
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.settings = default_settings(self.view);
+        self.selected = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn all(_: Requires) -> bool {
+        true
+    }
+
+    #[test]
+    fn test_resolve_columns() {
+        let settings = default_settings(ColumnView::Ports);
+        let columns = resolve_columns(ColumnView::Ports, &settings, 80, all, &[]);
+        let widths: Vec<usize> = columns.iter().map(|column| column.width).collect();
+        // 80 minus 7 borders minus 18 fixed leaves 55 for NAME, PROJECT and PATH
+        assert_eq!(widths, vec![5, 6, 7, 20, 13, 22]);
+
+        let mut settings = settings;
+        settings[4].visible = false;
+        settings[5].width = Some(30);
+        settings.swap(0, 3);
+        let columns = resolve_columns(ColumnView::Ports, &settings, 80, all, &[]);
+        let layout: Vec<(&str, usize)> = columns.iter().map(|column| (column.id, column.width)).collect();
+        assert_eq!(layout, vec![("name", 26), ("port", 6), ("pid", 7), ("proto", 5), ("path", 30)]);
+
+        let process = default_settings(ColumnView::Process);
+        let columns = resolve_columns(ColumnView::Process, &process, 120, |requires| requires == Requires::Nothing, &[("user", 12)]);
+        assert!(columns.iter().all(|column| column.id != "rx" && column.id != "cmd"));
+        assert_eq!(columns.iter().find(|column| column.id == "user").map(|column| column.width), Some(12));
+    }
+
+    #[test]
+    fn test_column_at() {
+        let settings = default_settings(ColumnView::Ports);
+        let columns = resolve_columns(ColumnView::Ports, &settings, 80, all, &[]);
+        // │PROTO│  PORT│    PID│NAME...
+        assert_eq!(column_at(&columns, 0), None);
+        assert_eq!(column_at(&columns, 1).map(|column| column.id), Some("proto"));
+        assert_eq!(column_at(&columns, 6), None);
+        assert_eq!(column_at(&columns, 7).map(|column| column.id), Some("port"));
+        assert_eq!(column_at(&columns, 20).map(|column| column.id), Some("pid"));
+        assert_eq!(column_at(&columns, 79), None);
+    }
+
+    #[test]
+    fn test_layouts_json_round_trip() {
+        let saved = serde_json::json!({
+            "ports": [
+                {"id": "path", "visible": true, "width": 40},
+                {"id": "bogus", "visible": true},
+                {"id": "proto", "visible": false},
+            ]
+        });
+        let layouts = ColumnLayouts::from_json(&saved);
+        let ports = layouts.get(ColumnView::Ports);
+        assert_eq!(ports[0], ColumnSetting { id: "path", visible: true, width: Some(40) });
+        assert_eq!(ports[1], ColumnSetting { id: "proto", visible: false, width: None });
+        // Columns missing from the file follow in default order
+        let ids: Vec<&str> = ports.iter().map(|setting| setting.id).collect();
+        assert_eq!(ids, vec!["path", "proto", "port", "pid", "name", "project"]);
+        assert_eq!(layouts.get(ColumnView::Docker), default_settings(ColumnView::Docker).as_slice());
+
+        let reloaded = ColumnLayouts::from_json(&layouts.to_json());
+        assert_eq!(reloaded.get(ColumnView::Ports), ports);
+    }
+}
@@ -1,16 +1,42 @@
+// Key arms keep their view/state checks inside the arm body so that every key
+// is handled (and swallowed) in one place, even when it does nothing.
+#![allow(clippy::collapsible_match)]
+
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
 use crossterm::terminal;
 use sysinfo::System;
 
 use crate::app::actions::{
-    kill_selected_in_docker, kill_selected_port_process, kill_selected_process, open_selected_container,
-    open_selected_container_logs, open_selected_env,
+    activate_selected_inspector, collapse_all_processes, confirm_kill, copy_selected_env_value,
+    expand_all_processes, jump_to_scoped_process_view, kill_selected_in_docker,
+    kill_selected_port_process, kill_selected_process, open_column_chooser, open_container_limits,
+    open_container_top, open_docker_context_picker, open_find_open_file_form, open_pm2_detail,
+    open_pm2_logs, open_selected_app_detail, open_selected_app_url, open_selected_container,
+    open_selected_container_limits, open_selected_container_logs, open_selected_container_top,
+    open_selected_env, open_selected_fds, open_selected_kill_menu, open_selected_pm2_logs,
+    open_selected_pm2_menu, open_selected_priority_form, open_selected_process_detail,
+    open_selected_scripts, open_selected_signal_menu, open_selected_threads,
+    open_selected_zombie_menu, open_signal_menu, preview_kill, resize_chooser_column,
+    run_selected_pm2_action, run_selected_script, save_column_chooser, signal_process,
+    start_pm2_action, start_selected_declared_app, submit_form, switch_docker_context,
+    toggle_net_columns, toggle_sched_columns, toggle_selected_fold, toggle_stuck_filter,
+    toggle_wrong_version_filter,
+};
+use crate::app::state::{
+    view_for_sidebar_index, ContextMenu, ContextMenuAction, ContextMenuTarget, Focus, InputMode,
+    OperationComplete, ViewMode,
 };
-use crate::app::state::{view_for_sidebar_index, ContextMenu, ContextMenuAction, ContextMenuTarget, Focus, InputMode, OperationComplete, SortBy, ViewMode};
-use crate::app::AppState;
+use crate::app::{column_at, AppState};
 use crate::system::docker::{ContainerInfo, DockerRow};
+use crate::system::node::Pm2Action;
+use crate::system::process::KillScope;
 
-pub(crate) fn handle_key_event(key: KeyEvent, state: &mut AppState, system: &mut System) -> bool {
+pub(crate) fn handle_key_event(
+    key: KeyEvent,
+    state: &mut AppState,
+    system: &mut System,
+    containers: &[ContainerInfo],
+) -> bool {
     if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
         return true;
     }
@@ -21,21 +47,54 @@ pub(crate) fn handle_key_event(key: KeyEvent, state: &mut AppState, system: &mut
         return false;
     }
 
+    if state.context_menu.is_some() {
+        handle_context_menu_mode(key, state, containers);
+        return false;
+    }
+
+    if state.form.is_some() {
+        handle_form_mode(key, state);
+        return false;
+    }
+
+    if state.docker_context_picker.is_some() {
+        handle_context_picker_mode(key, state);
+        return false;
+    }
+
+    if state.column_chooser.is_some() {
+        handle_column_chooser_mode(key, state);
+        return false;
+    }
+
     if state.view_mode == ViewMode::DockerEnv {
         return handle_env_mode(key, state);
     }
 
+    if state.view_mode == ViewMode::ContainerTop {
+        return handle_container_top_mode(key, state, system);
+    }
+
+    if state.view_mode == ViewMode::Logs {
+        return handle_logs_mode(key, state);
+    }
+
     match state.input_mode {
-        InputMode::Normal => handle_normal_mode(key, state, system),
+        InputMode::Normal => handle_normal_mode(key, state, system, containers),
         InputMode::Filter => handle_filter_mode(key, state),
     }
 }
 
-fn handle_normal_mode(key: KeyEvent, state: &mut AppState, system: &mut System) -> bool {
+fn handle_normal_mode(
+    key: KeyEvent,
+    state: &mut AppState,
+    system: &mut System,
+    containers: &[ContainerInfo],
+) -> bool {
     let list_len = match state.view_mode {
-        ViewMode::Process => state.visible_pids.len(),
+        ViewMode::Process | ViewMode::ContainerTop => state.visible_pids.len(),
         ViewMode::Docker => state.visible_containers.len(),
-        ViewMode::DockerEnv => 0,
+        ViewMode::DockerEnv | ViewMode::Logs => 0,
         ViewMode::Ports => state.visible_ports.len(),
         ViewMode::Node => state.visible_pids.len(),
     };
@@ -79,16 +138,33 @@ fn handle_normal_mode(key: KeyEvent, state: &mut AppState, system: &mut System)
             state.input_mode = InputMode::Filter;
         }
         KeyCode::Char('c') => {
-            state.toggle_sort(SortBy::Cpu);
+            sort_by_column(state, "cpu");
         }
         KeyCode::Char('m') => {
-            state.toggle_sort(SortBy::Memory);
+            sort_by_column(state, "mem");
         }
         KeyCode::Char('n') => {
-            state.toggle_sort(SortBy::Name);
+            sort_by_column(state, "name");
+        }
+        KeyCode::Char('D') => {
+            sort_by_column(state, "read");
+        }
+        KeyCode::Char('b') => {
+            if sort_by_column(state, "rx") {
+                // Rates are only sampled while the columns are shown
+                state.show_net_columns = true;
+            }
         }
         KeyCode::Char('r') => {
-            state.sort_order = state.sort_order.toggle();
+            state.reverse_sort();
+        }
+        KeyCode::Char('<') => {
+            state.cycle_sort(-1);
+            show_sort(state);
+        }
+        KeyCode::Char('>') => {
+            state.cycle_sort(1);
+            show_sort(state);
         }
         KeyCode::Char('z') => {
             if state.view_mode == ViewMode::Process {
@@ -104,15 +180,22 @@ fn handle_normal_mode(key: KeyEvent, state: &mut AppState, system: &mut System)
                 state.active_filter_mut().clear();
                 state.input_mode = InputMode::Normal;
                 state.set_message("Search cleared");
+            } else if state.view_mode == ViewMode::Process && state.process_scope.is_some() {
+                state.process_scope = None;
+                state.set_message("Container scope cleared");
+            } else if state.view_mode == ViewMode::Node && state.node_wrong_version_only {
+                toggle_wrong_version_filter(state);
+            } else if state.view_mode == ViewMode::Process && state.process_stuck_only {
+                toggle_stuck_filter(state);
             }
         }
         KeyCode::Char('d') => {
             let view = match state.view_mode {
                 ViewMode::Process => ViewMode::Docker,
                 ViewMode::Docker => ViewMode::Process,
-                ViewMode::DockerEnv => ViewMode::Docker,
+                ViewMode::DockerEnv | ViewMode::ContainerTop => ViewMode::Docker,
                 ViewMode::Ports => ViewMode::Docker,
-                ViewMode::Node => ViewMode::Docker,
+                ViewMode::Node | ViewMode::Logs => ViewMode::Docker,
             };
             state.set_view(view);
             state.focus = Focus::Main;
@@ -154,18 +237,167 @@ fn handle_normal_mode(key: KeyEvent, state: &mut AppState, system: &mut System)
         KeyCode::Enter => {
             if state.view_mode == ViewMode::Docker {
                 open_selected_container(state);
+            } else if state.view_mode == ViewMode::Node && !start_selected_declared_app(state) {
+                open_selected_pm2_menu(state);
+            }
+        }
+        KeyCode::Char(' ') if state.view_mode == ViewMode::Process => {
+            toggle_selected_fold(state, system);
+        }
+        KeyCode::Char('+') if state.view_mode == ViewMode::Process => {
+            expand_all_processes(state);
+        }
+        KeyCode::Char('-') if state.view_mode == ViewMode::Process => {
+            collapse_all_processes(state, system);
+        }
+        KeyCode::Char('+') | KeyCode::Char('-') => {
+            if state.view_mode == ViewMode::Node {
+                let action = if key.code == KeyCode::Char('+') {
+                    Pm2Action::ScaleUp
+                } else {
+                    Pm2Action::ScaleDown
+                };
+                run_selected_pm2_action(state, action);
             }
         }
         KeyCode::Char('l') => {
             if state.view_mode == ViewMode::Docker {
                 open_selected_container_logs(state);
+            } else if state.view_mode == ViewMode::Node {
+                open_selected_pm2_logs(state);
             } else {
-                state.set_message("Logs only available in Docker view");
+                state.set_message("Logs only available in Docker and Apps views");
             }
         }
         KeyCode::Char('e') => {
             open_selected_env(state, system);
         }
+        KeyCode::Char('C') => {
+            if state.view_mode == ViewMode::Docker {
+                open_docker_context_picker(state);
+            } else {
+                state.set_message("Contexts only available in Docker view");
+            }
+        }
+        KeyCode::Char('u') => {
+            if state.view_mode == ViewMode::Docker {
+                open_selected_container_limits(state, containers);
+            } else {
+                state.set_message("Limits only available in Docker view");
+            }
+        }
+        KeyCode::Char('t') => {
+            if state.view_mode == ViewMode::Docker {
+                open_selected_container_top(state, containers);
+            } else {
+                state.set_message("Container processes only available in Docker view");
+            }
+        }
+        KeyCode::Char('f') => {
+            if state.view_mode == ViewMode::Process {
+                open_selected_fds(state, system);
+            } else {
+                state.set_message("Open files only available in process view");
+            }
+        }
+        KeyCode::Char('S') => {
+            if state.view_mode == ViewMode::Process {
+                toggle_stuck_filter(state);
+            } else {
+                state.set_message("Stuck filter only available in process view");
+            }
+        }
+        KeyCode::Char('Z') => {
+            if state.view_mode == ViewMode::Process {
+                open_selected_zombie_menu(state, system);
+            } else {
+                state.set_message("Zombie actions only available in process view");
+            }
+        }
+        KeyCode::Char('T') => {
+            if state.view_mode == ViewMode::Process {
+                open_selected_threads(state, system);
+            } else {
+                state.set_message("Threads only available in process view");
+            }
+        }
+        KeyCode::Char('F') => {
+            if state.view_mode == ViewMode::Process {
+                open_find_open_file_form(state);
+            } else {
+                state.set_message("File search only available in process view");
+            }
+        }
+        KeyCode::Char('R') => {
+            if state.view_mode == ViewMode::Process {
+                open_selected_priority_form(state, system);
+            } else {
+                state.set_message("Priority only available in process view");
+            }
+        }
+        KeyCode::Char('P') => {
+            if state.view_mode == ViewMode::Process {
+                toggle_sched_columns(state);
+            } else {
+                state.set_message("Priority columns only available in process view");
+            }
+        }
+        KeyCode::Char('N') => {
+            if matches!(state.view_mode, ViewMode::Process | ViewMode::Docker) {
+                toggle_net_columns(state);
+            } else {
+                state.set_message("Network columns only available in process and docker views");
+            }
+        }
+        KeyCode::Char('K') => {
+            if state.view_mode == ViewMode::Process {
+                open_selected_kill_menu(state, system);
+            } else {
+                state.set_message("Tree kill only available in process view");
+            }
+        }
+        KeyCode::Char('s') => {
+            if state.view_mode == ViewMode::Process {
+                open_selected_signal_menu(state, system);
+            } else if state.view_mode == ViewMode::Node {
+                open_selected_scripts(state);
+            } else {
+                state.set_message("Signals only available in process view");
+            }
+        }
+        KeyCode::Char('I') => {
+            if state.view_mode == ViewMode::Node {
+                activate_selected_inspector(state, system);
+            } else {
+                state.set_message("Inspector only available in Apps view");
+            }
+        }
+        KeyCode::Char('O') => {
+            open_column_chooser(state);
+        }
+        KeyCode::Char('o') => {
+            if state.view_mode == ViewMode::Node {
+                open_selected_app_url(state);
+            } else {
+                state.set_message("Open URL only available in Apps view");
+            }
+        }
+        KeyCode::Char('w') => {
+            if state.view_mode == ViewMode::Node {
+                toggle_wrong_version_filter(state);
+            } else {
+                state.set_message("Version filter only available in Apps view");
+            }
+        }
+        KeyCode::Char('i') => {
+            if state.view_mode == ViewMode::Process {
+                open_selected_process_detail(state, system);
+            } else if state.view_mode == ViewMode::Node {
+                open_selected_app_detail(state);
+            } else {
+                state.set_message("Details only available in process and Apps views");
+            }
+        }
         KeyCode::Up => {
             if state.view_mode == ViewMode::Ports {
                 move_ports_selection(state, -1);
@@ -261,12 +493,184 @@ fn handle_filter_mode(key: KeyEvent, state: &mut AppState) -> bool {
     false
 }
 
+fn handle_context_picker_mode(key: KeyEvent, state: &mut AppState) {
+    let Some(picker) = state.docker_context_picker.as_mut() else {
+        return;
+    };
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('C') => {
+            state.docker_context_picker = None;
+        }
+        KeyCode::Up => {
+            picker.selected = picker.selected.saturating_sub(1);
+        }
+        KeyCode::Down => {
+            if picker.selected + 1 < picker.contexts.len() {
+                picker.selected += 1;
+            }
+        }
+        KeyCode::Enter => {
+            if let Some(ctx) = picker.contexts.get(picker.selected) {
+                let name = ctx.name.clone();
+                switch_docker_context(state, name);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn handle_context_menu_mode(key: KeyEvent, state: &mut AppState, containers: &[ContainerInfo]) {
+    let Some(menu) = state.context_menu.as_mut() else {
+        return;
+    };
+    let last = menu.items.len().saturating_sub(1);
+    match key.code {
+        KeyCode::Up => {
+            menu.hover = Some(menu.hover.map_or(0, |idx| idx.saturating_sub(1)));
+        }
+        KeyCode::Down => {
+            menu.hover = Some(menu.hover.map_or(0, |idx| (idx + 1).min(last)));
+        }
+        KeyCode::Enter => {
+            let action = menu.hover.and_then(|idx| menu.items.get(idx)).copied();
+            let target = menu.target.clone();
+            state.context_menu = None;
+            if let Some(action) = action {
+                execute_context_action(state, action, &target, containers);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn handle_column_chooser_mode(key: KeyEvent, state: &mut AppState) {
+    let shift = key.modifiers.contains(KeyModifiers::SHIFT);
+    match key.code {
+        KeyCode::Esc => state.column_chooser = None,
+        KeyCode::Enter => save_column_chooser(state),
+        KeyCode::Char('+') | KeyCode::Char('=') => resize_chooser_column(state, 1),
+        KeyCode::Char('-') => resize_chooser_column(state, -1),
+        _ => {
+            let Some(chooser) = state.column_chooser.as_mut() else {
+                return;
+            };
+            match key.code {
+                KeyCode::Up if shift => chooser.move_selected(-1),
+                KeyCode::Down if shift => chooser.move_selected(1),
+                KeyCode::Char('K') => chooser.move_selected(-1),
+                KeyCode::Char('J') => chooser.move_selected(1),
+                KeyCode::Up | KeyCode::Char('k') => chooser.select(-1),
+                KeyCode::Down | KeyCode::Char('j') => chooser.select(1),
+                KeyCode::Char(' ') => chooser.toggle_selected(),
+                KeyCode::Char('0') => chooser.unpin_selected(),
+                KeyCode::Char('r') => chooser.reset(),
+                _ => {}
+            }
+        }
+    }
+}
+
+fn handle_form_mode(key: KeyEvent, state: &mut AppState) {
+    let Some(form) = state.form.as_mut() else {
+        return;
+    };
+    let last = form.fields.len().saturating_sub(1);
+    match key.code {
+        KeyCode::Esc => {
+            state.form = None;
+        }
+        KeyCode::Enter => {
+            submit_form(state);
+        }
+        KeyCode::Up | KeyCode::BackTab => {
+            form.selected = form.selected.saturating_sub(1);
+        }
+        KeyCode::Down | KeyCode::Tab => {
+            form.selected = (form.selected + 1).min(last);
+        }
+        KeyCode::Backspace => {
+            if let Some(field) = form.fields.get_mut(form.selected) {
+                field.value.pop();
+            }
+            form.error = None;
+        }
+        KeyCode::Char(ch) => {
+            if key.modifiers.contains(KeyModifiers::CONTROL) && ch == 'u' {
+                if let Some(field) = form.fields.get_mut(form.selected) {
+                    field.value.clear();
+                }
+            } else if !key.modifiers.contains(KeyModifiers::CONTROL)
+                && !key.modifiers.contains(KeyModifiers::ALT)
+            {
+                if let Some(field) = form.fields.get_mut(form.selected) {
+                    field.value.push(ch);
+                }
+            }
+            form.error = None;
+        }
+        _ => {}
+    }
+}
+
+fn handle_container_top_mode(key: KeyEvent, state: &mut AppState, system: &mut System) -> bool {
+    let len = state.visible_pids.len();
+    match key.code {
+        KeyCode::Esc => {
+            state.view_mode = ViewMode::Docker;
+            state.selected = 0;
+            state.hover_row = None;
+        }
+        KeyCode::Enter => {
+            jump_to_scoped_process_view(state);
+        }
+        KeyCode::Char('i') => {
+            open_selected_process_detail(state, system);
+        }
+        KeyCode::Char('s') => {
+            open_selected_signal_menu(state, system);
+        }
+        KeyCode::Char('f') => {
+            open_selected_fds(state, system);
+        }
+        KeyCode::Char('T') => {
+            open_selected_threads(state, system);
+        }
+        KeyCode::Char('k') => {
+            kill_selected_process(state, system);
+        }
+        KeyCode::Char('e') => {
+            open_selected_env(state, system);
+        }
+        KeyCode::Up => {
+            state.selected = state.selected.saturating_sub(1);
+        }
+        KeyCode::Down => {
+            if state.selected + 1 < len {
+                state.selected += 1;
+            }
+        }
+        KeyCode::PageUp => {
+            state.selected = state.selected.saturating_sub(10);
+        }
+        KeyCode::PageDown => {
+            state.selected = (state.selected + 10).min(len.saturating_sub(1));
+        }
+        _ => {}
+    }
+    false
+}
+
 fn handle_env_mode(key: KeyEvent, state: &mut AppState) -> bool {
     match key.code {
         KeyCode::Esc => {
             state.view_mode = state.env_return_view;
             state.input_mode = InputMode::Normal;
+            state.env_scripts = None;
+            state.kill_plan = None;
+            state.thread_pane = None;
         }
+        KeyCode::Enter if state.env_scripts.is_some() => run_selected_script(state),
+        KeyCode::Enter if state.kill_plan.is_some() => confirm_kill(state),
         KeyCode::Up => {
             if state.env_selected > 0 {
                 state.env_selected -= 1;
@@ -280,6 +684,7 @@ fn handle_env_mode(key: KeyEvent, state: &mut AppState) -> bool {
         KeyCode::PageUp => {
             state.env_selected = state.env_selected.saturating_sub(10);
         }
+        KeyCode::Char('y') => copy_selected_env_value(state),
         KeyCode::PageDown => {
             if !state.env_vars.is_empty() {
                 state.env_selected = (state.env_selected + 10).min(state.env_vars.len() - 1);
@@ -290,6 +695,82 @@ fn handle_env_mode(key: KeyEvent, state: &mut AppState) -> bool {
     false
 }
 
+fn handle_logs_mode(key: KeyEvent, state: &mut AppState) -> bool {
+    let Some(pane) = state.log_pane.as_mut() else {
+        state.view_mode = ViewMode::Node;
+        return false;
+    };
+
+    if pane.search_editing {
+        match key.code {
+            KeyCode::Esc => {
+                pane.search_editing = false;
+                pane.search.clear();
+                pane.current_match = None;
+            }
+            KeyCode::Enter => {
+                pane.search_editing = false;
+                pane.current_match = None;
+                if !pane.search.is_empty() && !pane.jump_to_match(true) {
+                    let message = format!("No match for \"{}\"", pane.search);
+                    state.set_message(message);
+                }
+            }
+            KeyCode::Backspace => {
+                pane.search.pop();
+            }
+            KeyCode::Char(ch) => {
+                pane.search.push(ch);
+            }
+            _ => {}
+        }
+        return false;
+    }
+
+    match key.code {
+        KeyCode::Esc => {
+            let return_view = pane.return_view;
+            state.log_pane = None;
+            state.view_mode = return_view;
+            state.input_mode = InputMode::Normal;
+        }
+        KeyCode::Char('q') => return true,
+        KeyCode::Char(' ') => {
+            pane.paused = !pane.paused;
+            let label = if pane.paused { "paused" } else { "resumed" };
+            state.set_message(format!("Log tail {label}"));
+        }
+        KeyCode::Char('/') => {
+            pane.search_editing = true;
+            pane.search.clear();
+            pane.current_match = None;
+        }
+        KeyCode::Char('n') | KeyCode::Char('N') => {
+            if pane.search.is_empty() {
+                state.set_message("Press / to search");
+            } else {
+                let older = key.code == KeyCode::Char('n');
+                if !pane.jump_to_match(older) {
+                    let edge = if older { "oldest" } else { "newest" };
+                    state.set_message(format!("No {edge} match beyond this one"));
+                }
+            }
+        }
+        KeyCode::Char('x') => {
+            pane.search.clear();
+            pane.current_match = None;
+        }
+        KeyCode::Up => pane.scroll_by(1),
+        KeyCode::Down => pane.scroll_by(-1),
+        KeyCode::PageUp => pane.scroll_by(10),
+        KeyCode::PageDown => pane.scroll_by(-10),
+        KeyCode::Home | KeyCode::Char('g') => pane.scroll = pane.lines.len().saturating_sub(1),
+        KeyCode::End | KeyCode::Char('G') => pane.scroll = 0,
+        _ => {}
+    }
+    false
+}
+
 fn move_ports_selection(state: &mut AppState, direction: isize) -> bool {
     if direction == 0 {
         return false;
@@ -356,18 +837,37 @@ fn move_docker_selection(state: &mut AppState, direction: isize) -> bool {
     }
 }
 
+/// Sorts the current view by the key of column `id`, saying so when it has none.
+fn sort_by_column(state: &mut AppState, id: &str) -> bool {
+    let sorted = state.sort_by_column(id);
+    if !sorted {
+        state.set_message(format!("Can't sort {} by {id}", view_label(state.view_mode)));
+    }
+    sorted
+}
+
+fn show_sort(state: &mut AppState) {
+    if let Some(label) = state.sort_label() {
+        state.set_message(format!("Sort: {label}"));
+    }
+}
+
 fn view_label(mode: ViewMode) -> &'static str {
     match mode {
         ViewMode::Process => "Processes",
         ViewMode::Docker => "Docker",
         ViewMode::DockerEnv => "Env",
+        ViewMode::ContainerTop => "Container processes",
+        ViewMode::Logs => "Logs",
         ViewMode::Ports => "Ports",
-        ViewMode::Node => "Node.js",
+        ViewMode::Node => "Apps",
     }
 }
 
 const SIDEBAR_WIDTH: u16 = 20;
 const SIDEBAR_MENU_START_ROW: u16 = 10; // After logo, title, separator
+/// Row of the table header in the Process, Docker, Ports and Apps views
+const HEADER_ROW: u16 = 11;
 
 /// Returns true if a re-render is needed
 pub(crate) fn handle_mouse_event(mouse: MouseEvent, state: &mut AppState, containers: &[crate::system::docker::ContainerInfo]) -> bool {
@@ -378,6 +878,29 @@ pub(crate) fn handle_mouse_event(mouse: MouseEvent, state: &mut AppState, contai
     // Check if sidebar is visible
     let show_sidebar = width >= SIDEBAR_WIDTH + 1 + 40; // sidebar + gap + min main
 
+    // The form is keyboard driven; only let a right-click dismiss it
+    if state.form.is_some() {
+        if mouse.kind == MouseEventKind::Down(MouseButton::Right) {
+            state.form = None;
+            return true;
+        }
+        return false;
+    }
+
+    // Like the form, the column chooser is keyboard driven
+    if state.column_chooser.is_some() {
+        if mouse.kind == MouseEventKind::Down(MouseButton::Right) {
+            state.column_chooser = None;
+            return true;
+        }
+        return false;
+    }
+
+    if state.docker_context_picker.is_some() {
+        let main_x = if show_sidebar { SIDEBAR_WIDTH + 1 } else { 0 };
+        return handle_context_picker_mouse(mouse, state, main_x, width, height);
+    }
+
     // If context menu is open, handle it first
     if let Some(ref menu) = state.context_menu {
         match mouse.kind {
@@ -423,7 +946,7 @@ pub(crate) fn handle_mouse_event(mouse: MouseEvent, state: &mut AppState, contai
                 handle_sidebar_click(state, y);
             } else {
                 let main_x = if show_sidebar { SIDEBAR_WIDTH + 1 } else { 0 };
-                handle_main_click(state, x.saturating_sub(main_x), y, height);
+                handle_main_click(state, x.saturating_sub(main_x), y, width - main_x, height);
             }
             true
         }
@@ -476,6 +999,56 @@ pub(crate) fn handle_mouse_event(mouse: MouseEvent, state: &mut AppState, contai
     }
 }
 
+fn handle_context_picker_mouse(
+    mouse: MouseEvent,
+    state: &mut AppState,
+    main_x: u16,
+    width: u16,
+    height: u16,
+) -> bool {
+    let Some(picker) = state.docker_context_picker.as_mut() else {
+        return false;
+    };
+    let main_width = width.saturating_sub(main_x) as usize;
+    let (frame_x, frame_y, frame_width, frame_height) =
+        picker.frame(main_x, main_width, height as usize);
+    let inside = mouse.column >= frame_x
+        && (mouse.column as usize) < frame_x as usize + frame_width
+        && mouse.row > frame_y
+        && (mouse.row as usize) < frame_y as usize + frame_height - 1;
+    let item = if inside {
+        let offset = picker.scroll_offset(frame_height - 2);
+        Some(offset + (mouse.row - frame_y - 1) as usize).filter(|idx| *idx < picker.contexts.len())
+    } else {
+        None
+    };
+
+    match mouse.kind {
+        MouseEventKind::Down(MouseButton::Left) => {
+            match item.and_then(|idx| picker.contexts.get(idx)) {
+                Some(ctx) => {
+                    let name = ctx.name.clone();
+                    switch_docker_context(state, name);
+                }
+                None => state.docker_context_picker = None,
+            }
+            true
+        }
+        MouseEventKind::Down(MouseButton::Right) => {
+            state.docker_context_picker = None;
+            true
+        }
+        MouseEventKind::Moved => match item {
+            Some(idx) if idx != picker.selected => {
+                picker.selected = idx;
+                true
+            }
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
 fn handle_sidebar_click(state: &mut AppState, y: u16) {
     // Menu items start after logo (7 lines), title (1), separator (1) = row 9
     // But there's also top border at row 0, so items are at rows 9, 10, 11, 12
@@ -514,7 +1087,7 @@ fn handle_scroll(state: &mut AppState, direction: isize) {
     }
 
     match state.view_mode {
-        ViewMode::Process => {
+        ViewMode::Process | ViewMode::ContainerTop => {
             let len = state.visible_pids.len();
             if direction < 0 && state.selected > 0 {
                 state.selected -= 1;
@@ -538,23 +1111,45 @@ fn handle_scroll(state: &mut AppState, direction: isize) {
                 state.env_selected += 1;
             }
         }
+        ViewMode::Logs => {
+            // Wheel up reveals older lines
+            if let Some(pane) = state.log_pane.as_mut() {
+                pane.scroll_by(-3 * direction);
+            }
+        }
     }
 }
 
-fn handle_main_click(state: &mut AppState, _x: u16, y: u16, height: u16) {
+fn handle_main_click(state: &mut AppState, x: u16, y: u16, width: u16, height: u16) {
     // Skip if in filter mode or DockerEnv view
     if state.input_mode == InputMode::Filter {
         return;
     }
 
+    // The table header sits above its separator, two rows over the list
+    if y == HEADER_ROW {
+        if let Some(view) = state.column_view() {
+            let columns = state.resolved_columns(view, width as usize);
+            if let Some(column) = column_at(&columns, x as usize) {
+                let id = column.id;
+                if state.sort_by_column(id) {
+                    show_sort(state);
+                }
+            }
+            return;
+        }
+    }
+
     // The list starts at different rows depending on the view
     // Generally: title area + header + system bars + table header
     // For most views, list content starts around row 13-15
     let list_start: u16 = match state.view_mode {
         ViewMode::Process => 13,  // After title, header, cpu/mem/swap bars, table header
+        ViewMode::ContainerTop => 11, // After title, info box, table header
         ViewMode::Docker => 13,
         ViewMode::Ports => 13,
         ViewMode::Node => 13,
+        ViewMode::Logs => return,
         ViewMode::DockerEnv => {
             // Env view has its own scroll
             if y >= 6 {
@@ -581,7 +1176,7 @@ fn handle_main_click(state: &mut AppState, _x: u16, y: u16, height: u16) {
     }
 
     match state.view_mode {
-        ViewMode::Process => {
+        ViewMode::Process | ViewMode::ContainerTop => {
             // Calculate centered scroll offset
             let total = state.visible_pids.len();
             let half = max_rows / 2;
@@ -643,7 +1238,7 @@ fn handle_main_click(state: &mut AppState, _x: u16, y: u16, height: u16) {
                 state.selected = target_row;
             }
         }
-        ViewMode::DockerEnv => {}
+        ViewMode::DockerEnv | ViewMode::Logs => {}
     }
 }
 
@@ -656,9 +1251,14 @@ fn handle_main_hover(state: &mut AppState, _x: u16, y: u16, height: u16) {
 
     let list_start: u16 = match state.view_mode {
         ViewMode::Process => 13,
+        ViewMode::ContainerTop => 11,
         ViewMode::Docker => 13,
         ViewMode::Ports => 13,
         ViewMode::Node => 13,
+        ViewMode::Logs => {
+            state.hover_row = None;
+            return;
+        }
         ViewMode::DockerEnv => {
             if y >= 6 {
                 let hover = (y - 6) as usize;
@@ -688,7 +1288,7 @@ fn handle_main_hover(state: &mut AppState, _x: u16, y: u16, height: u16) {
     }
 
     match state.view_mode {
-        ViewMode::Process => {
+        ViewMode::Process | ViewMode::ContainerTop => {
             let total = state.visible_pids.len();
             let half = max_rows / 2;
             let scroll = if state.selected <= half {
@@ -756,7 +1356,7 @@ fn handle_main_hover(state: &mut AppState, _x: u16, y: u16, height: u16) {
                 state.hover_row = None;
             }
         }
-        ViewMode::DockerEnv => {}
+        ViewMode::DockerEnv | ViewMode::Logs => {}
     }
 }
 
@@ -828,6 +1428,8 @@ fn handle_docker_right_click(
                     ContextMenuAction::Shell,
                     ContextMenuAction::Logs,
                     ContextMenuAction::Env,
+                    ContextMenuAction::Top,
+                    ContextMenuAction::Limits,
                     ContextMenuAction::Stop,
                     ContextMenuAction::Restart,
                 ]
@@ -914,6 +1516,8 @@ fn handle_process_right_click(
 
     let items = vec![
         ContextMenuAction::Kill,
+        ContextMenuAction::KillScope(KillScope::Tree),
+        ContextMenuAction::Signals,
         ContextMenuAction::Env,
     ];
 
@@ -1004,6 +1608,7 @@ fn handle_ports_right_click(
 
     let items = vec![
         ContextMenuAction::Kill,
+        ContextMenuAction::Signals,
         ContextMenuAction::Env,
     ];
 
@@ -1052,6 +1657,29 @@ fn execute_context_action(
     target: &ContextMenuTarget,
     containers: &[ContainerInfo],
 ) {
+    if let ContextMenuTarget::Pm2App { pm_id, name } = target {
+        match action {
+            ContextMenuAction::Pm2(pm2_action) => {
+                start_pm2_action(state, *pm_id, name.clone(), pm2_action);
+            }
+            ContextMenuAction::Pm2Detail | ContextMenuAction::Logs => {
+                let info = state
+                    .visible_node_pm2
+                    .iter()
+                    .flatten()
+                    .find(|info| info.pm_id == *pm_id)
+                    .cloned();
+                match info {
+                    Some(info) if action == ContextMenuAction::Logs => open_pm2_logs(state, &info),
+                    Some(info) => open_pm2_detail(state, &info),
+                    None => state.set_message(format!("{name} is no longer listed by PM2")),
+                }
+            }
+            _ => {}
+        }
+        return;
+    }
+
     // Handle process-specific actions
     if let ContextMenuTarget::Process { pid, name } = target {
         match action {
@@ -1095,6 +1723,18 @@ fn execute_context_action(
                     }
                 }
             }
+            ContextMenuAction::Signals => {
+                open_signal_menu(state, *pid, name.clone());
+            }
+            ContextMenuAction::Signal(signal) => {
+                signal_process(state, *pid, name, signal);
+            }
+            ContextMenuAction::KillScope(scope) => {
+                preview_kill(state, *pid, name, scope);
+            }
+            ContextMenuAction::ZombieParent(signal) => {
+                signal_process(state, *pid, name, signal);
+            }
             _ => {}
         }
         return;
@@ -1112,6 +1752,12 @@ fn execute_context_action(
                     state.set_message(format!("Opening shell in {}...", name));
                     let _ = crate::system::docker::open_container_shell(id);
                 }
+                ContextMenuAction::Top => {
+                    open_container_top(state, id.clone(), name.clone());
+                }
+                ContextMenuAction::Limits => {
+                    open_container_limits(state, id.clone(), name.clone());
+                }
                 ContextMenuAction::Env => {
                     match crate::system::docker::load_container_env(id) {
                         Ok(env_vars) => {
@@ -1148,7 +1794,7 @@ fn execute_context_action(
             state.set_message(format!("{} {}...", action_name, name));
             // Track expected state: Start/Restart -> running, Stop -> stopped
             let expected_running = !matches!(action, ContextMenuAction::Stop);
-            state.pending_operations.insert(id.clone(), expected_running);
+            state.pending_operations.insert(id.clone(), Some(expected_running));
 
             let id = id.clone();
             let tx = state.operation_tx.clone();
@@ -1187,7 +1833,7 @@ fn execute_context_action(
 
             // Mark all containers as pending with expected state
             for (id, _) in &group_containers {
-                state.pending_operations.insert(id.clone(), expected_running);
+                state.pending_operations.insert(id.clone(), Some(expected_running));
             }
 
             // Start operations for each container
@@ -1209,7 +1855,7 @@ fn execute_context_action(
                 });
             }
         }
-        // Process targets are handled at the start of the function
-        ContextMenuTarget::Process { .. } => {}
+        // Process and PM2 targets are handled at the start of the function
+        ContextMenuTarget::Process { .. } | ContextMenuTarget::Pm2App { .. } => {}
     }
 }
@@ -1,7 +1,11 @@
 mod actions;
+mod columns;
 mod input;
 mod runtime;
+mod sort;
 mod state;
 
+pub use columns::{column_at, Align, Column, ColumnChooser, ColumnView};
 pub use runtime::run;
-pub use state::{AppState, ContextMenu, Focus, InputMode, SortBy, SortOrder, ViewMode};
+pub use sort::{DockerSortBy, NodeSortBy, PortSortBy, SortBy, SortOrder, ViewSort};
+pub use state::{AppState, ContextMenu, DockerContextPicker, Focus, FormField, FormOverlay, FormPurpose, InputMode, LogPane, ProcessScope, ViewMode};
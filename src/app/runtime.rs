@@ -1,14 +1,17 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::io;
 use std::time::{Duration, Instant};
 
 use crossterm::event::{self, Event};
 use sysinfo::{Pid, System, Users};
 
+use crate::app::actions::{check_inspector_results, check_open_file_results, refresh_thread_pane};
+use crate::app::columns::ColumnLayouts;
 use crate::app::input::{handle_key_event, handle_mouse_event};
 use crate::app::{AppState, ViewMode};
-use crate::system::{docker, node, ports, process};
+use crate::system::{docker, net, node, ports, priority, process};
 use crate::ui;
+use crate::util::{Filterable, Query};
 
 pub fn run(stdout: &mut io::Stdout) -> io::Result<()> {
     let mut system = System::new_all();
@@ -17,11 +20,20 @@ pub fn run(stdout: &mut io::Stdout) -> io::Result<()> {
     let docker_worker = docker::start_docker_stats_worker(Duration::from_secs(2));
 
     let mut state = AppState::new();
+    state.columns = ColumnLayouts::load();
+    state.docker_context = docker::active_docker_context();
+    let mut docker_context_resolved = state.docker_context.is_some();
     update_system_snapshot(&mut state, &system);
     maybe_refresh_user_cache(&mut state);
     let tick_rate = Duration::from_millis(1000);
     let input_poll = Duration::from_millis(60);
     let mut last_tick = Instant::now();
+    // Time covered by the per-refresh disk I/O counters; zero until the first tick
+    let mut io_interval = Duration::ZERO;
+    let mut net_sampler = net::NetSampler::default();
+    let mut net_sample = net::NetSample::default();
+    // Namespace rates of containers, by container ID
+    let mut container_net: Vec<(String, net::NetRates)> = Vec::new();
     let mut needs_render = true;
 
     let mut process_cache: HashMap<Pid, process::ProcInfo> = HashMap::new();
@@ -40,7 +52,7 @@ pub fn run(stdout: &mut io::Stdout) -> io::Result<()> {
     let mut node_view: Vec<node::NodeProcessInfo> = Vec::new();
     let mut node_rows: Vec<node::NodeRow> = Vec::new();
     let mut node_dirty = true;
-    let mut pm2_available = false;
+    let mut last_log_poll = Instant::now();
 
     loop {
         if event::poll(input_poll)? {
@@ -48,36 +60,69 @@ pub fn run(stdout: &mut io::Stdout) -> io::Result<()> {
 
             if let Event::Key(key) = ev {
                 let prev_filter = state.active_filter().to_string();
-                let prev_sort_by = state.sort_by;
-                let prev_sort_order = state.sort_order;
+                let prev_process_sort = state.process_sort;
+                let prev_docker_sort = state.docker_sort;
+                let prev_ports_sort = state.ports_sort;
+                let prev_node_sort = state.node_sort;
                 let prev_zoom = state.zoom;
                 let prev_view = state.view_mode;
-
-                if handle_key_event(key, &mut state, &mut system) {
+                let prev_scope = state.process_scope.clone();
+                let prev_wrong_version = state.node_wrong_version_only;
+                let prev_stuck_only = state.process_stuck_only;
+                let prev_collapsed = state.collapsed_processes.len();
+                let prev_sched_columns = state.show_sched_columns;
+                let prev_net_columns = state.show_net_columns;
+
+                if handle_key_event(key, &mut state, &mut system, &docker_view) {
                     break;
                 }
 
                 let filter_changed = state.active_filter() != prev_filter;
-                let sort_changed =
-                    state.sort_by != prev_sort_by || state.sort_order != prev_sort_order;
                 let zoom_changed = state.zoom != prev_zoom;
                 let view_changed = state.view_mode != prev_view;
+                let scope_changed = state.process_scope != prev_scope;
 
                 if filter_changed {
                     match state.view_mode {
                         ViewMode::Process => process_dirty = true,
                         ViewMode::Docker => docker_dirty = true,
-                        ViewMode::DockerEnv => {}
+                        ViewMode::DockerEnv | ViewMode::ContainerTop | ViewMode::Logs => {}
                         ViewMode::Ports => ports_dirty = true,
                         ViewMode::Node => node_dirty = true,
                     }
                 }
-                if sort_changed {
+                if state.process_sort != prev_process_sort {
                     process_dirty = true;
+                }
+                if state.docker_sort != prev_docker_sort {
                     docker_dirty = true;
                 }
-                if zoom_changed {
+                if state.ports_sort != prev_ports_sort {
+                    ports_dirty = true;
+                }
+                if state.node_sort != prev_node_sort {
+                    node_dirty = true;
+                }
+                if zoom_changed
+                    || scope_changed
+                    || state.collapsed_processes.len() != prev_collapsed
+                    || state.show_sched_columns != prev_sched_columns
+                    || state.process_stuck_only != prev_stuck_only
+                {
+                    process_dirty = true;
+                }
+                if state.show_net_columns != prev_net_columns {
+                    if state.show_net_columns {
+                        // Start from fresh counters; rates appear after the next tick
+                        net_sampler = net::NetSampler::default();
+                        net_sample = net_sampler.sample(&system);
+                        container_net.clear();
+                    }
                     process_dirty = true;
+                    docker_dirty = true;
+                }
+                if state.node_wrong_version_only != prev_wrong_version {
+                    node_dirty = true;
                 }
                 if view_changed {
                     process_dirty = true;
@@ -109,17 +154,31 @@ pub fn run(stdout: &mut io::Stdout) -> io::Result<()> {
         }
 
         if last_tick.elapsed() >= tick_rate {
+            io_interval = last_tick.elapsed();
             refresh_system(&mut system);
             update_system_snapshot(&mut state, &system);
             last_tick = Instant::now();
+            if state.show_net_columns && matches!(state.view_mode, ViewMode::Process | ViewMode::Docker) {
+                net_sample = net_sampler.sample(&system);
+                container_net = net_sample
+                    .namespace_roots
+                    .iter()
+                    .filter_map(|(pid, rates)| Some((docker::container_id_for(*pid)?, *rates)))
+                    .collect();
+                docker_dirty = true;
+            }
             // Only mark dirty for the active view to avoid unnecessary data collection
             match state.view_mode {
-                ViewMode::Process => process_dirty = true,
-                ViewMode::Docker | ViewMode::DockerEnv => {
+                ViewMode::Process | ViewMode::ContainerTop => process_dirty = true,
+                ViewMode::Docker => {
                     // Docker data is refreshed via worker, no need to mark dirty here
                 }
+                ViewMode::DockerEnv => {
+                    refresh_thread_pane(&mut state);
+                }
                 ViewMode::Ports => ports_dirty = true,
                 ViewMode::Node => node_dirty = true,
+                ViewMode::Logs => {}
             }
             needs_render = true;
         }
@@ -133,6 +192,14 @@ pub fn run(stdout: &mut io::Stdout) -> io::Result<()> {
             needs_render = true;
         }
 
+        if check_inspector_results(&mut state) {
+            needs_render = true;
+        }
+
+        if check_open_file_results(&mut state) {
+            needs_render = true;
+        }
+
         // Animate spinner if there are pending operations
         if state.tick_spinner() {
             needs_render = true;
@@ -145,19 +212,50 @@ pub fn run(stdout: &mut io::Stdout) -> io::Result<()> {
                     maybe_refresh_user_cache(&mut state);
                     process_cache = process::collect_processes(
                         &system,
-                        &state.process_filter,
+                        &parse_filter::<process::ProcInfo>(&mut state, |state| &state.process_filter),
                         &state.container_cache,
                         &state.user_cache,
                         state.zoom,
+                        state.process_scope.as_ref().map(|scope| scope.container_id.as_str()),
+                        io_interval,
                     );
+                    if state.process_stuck_only {
+                        process::retain_stuck(&mut process_cache, &system);
+                    }
+                    if state.show_net_columns {
+                        for (pid, proc_info) in process_cache.iter_mut() {
+                            proc_info.net = Some(net_sample.by_pid.get(pid).copied().unwrap_or_default());
+                        }
+                    }
+                    // Forget folds of processes that have exited
+                    state.collapsed_processes.retain(|key| {
+                        system
+                            .process(key.pid)
+                            .is_some_and(|process| process.start_time() == key.start_time)
+                    });
                     rows_cache = process::build_tree_rows(
                         &process_cache,
-                        state.sort_by,
-                        state.sort_order,
-                        state.zoom,
+                        state.process_sort.by,
+                        state.process_sort.order,
+                        // Stuck processes are listed under their parents
+                        state.zoom || state.process_stuck_only,
+                        &state.collapsed_processes,
                     );
-                    clamp_selection(&mut state, rows_cache.len());
+                    if state.show_sched_columns {
+                        for row in &rows_cache {
+                            if let Some(proc_info) = process_cache.get_mut(&row.pid) {
+                                proc_info.sched = priority::read_sched_info(row.pid);
+                            }
+                        }
+                    }
                     state.visible_pids = rows_cache.iter().map(|row| row.pid).collect();
+                    state.process_user_width = rows_cache
+                        .iter()
+                        .filter_map(|row| process_cache.get(&row.pid))
+                        .map(|proc_info| proc_info.user.chars().count())
+                        .fold(4, usize::max);
+                    select_pending_pid(&mut state);
+                    clamp_selection(&mut state, rows_cache.len());
                     state.visible_containers.clear();
                     state.visible_container_names.clear();
                     state.visible_container_ports_public.clear();
@@ -176,6 +274,12 @@ pub fn run(stdout: &mut io::Stdout) -> io::Result<()> {
                 }
             }
             ViewMode::Docker => {
+                if !docker_context_resolved {
+                    // Without --context the CLI default applies; look it up once for the header
+                    state.docker_context = docker::current_docker_context();
+                    docker_context_resolved = true;
+                }
+
                 if last_docker_pull.elapsed() >= Duration::from_millis(500) {
                     docker_raw = docker_worker.snapshot();
                     docker_dirty = true;
@@ -189,9 +293,19 @@ pub fn run(stdout: &mut io::Stdout) -> io::Result<()> {
 
                 if docker_dirty {
                     docker_view = docker_raw.clone();
-                    docker::apply_container_filter(&mut docker_view, &state.docker_filter);
+                    let query = parse_filter::<docker::ContainerInfo>(&mut state, |state| &state.docker_filter);
+                    docker::apply_container_filter(&mut docker_view, &query);
+                    // Rates go in before grouping so the NET sort can use them
+                    if state.show_net_columns {
+                        for container in &mut docker_view {
+                            container.net = container_net
+                                .iter()
+                                .find(|(id, _)| docker::container_ids_match(id, &container.id))
+                                .map(|(_, rates)| *rates);
+                        }
+                    }
                     let (grouped, rows) =
-                        docker::group_containers(docker_view);
+                        docker::group_containers(docker_view, state.docker_sort);
                     docker_view = grouped;
                     docker_rows = rows.clone();
                     state.docker_rows = rows;
@@ -244,10 +358,59 @@ pub fn run(stdout: &mut io::Stdout) -> io::Result<()> {
                     needs_render = false;
                 }
             }
+            ViewMode::Logs => {
+                if let Some(pane) = state.log_pane.as_mut() {
+                    let poll_due = last_log_poll.elapsed() >= Duration::from_millis(250);
+                    if !pane.paused && poll_due {
+                        last_log_poll = Instant::now();
+                        if pane.poll() {
+                            needs_render = true;
+                        }
+                    }
+                }
+
+                if needs_render {
+                    ui::render_logs(stdout, &state)?;
+                    needs_render = false;
+                }
+            }
+            ViewMode::ContainerTop => {
+                if process_dirty {
+                    maybe_refresh_container_cache(&mut state);
+                    maybe_refresh_user_cache(&mut state);
+                    process_cache = process::collect_processes(
+                        &system,
+                        &Query::default(),
+                        &state.container_cache,
+                        &state.user_cache,
+                        true,
+                        Some(&state.top_container_id),
+                        io_interval,
+                    );
+                    rows_cache = process::build_tree_rows(
+                        &process_cache,
+                        state.process_sort.by,
+                        state.process_sort.order,
+                        true,
+                        &HashSet::new(),
+                    );
+                    state.visible_pids = rows_cache.iter().map(|row| row.pid).collect();
+                    clamp_selection(&mut state, rows_cache.len());
+                    process_dirty = false;
+                    needs_render = true;
+                }
+
+                if needs_render {
+                    ui::render_container_top(stdout, &state, &process_cache, &rows_cache)?;
+                    needs_render = false;
+                }
+            }
             ViewMode::Ports => {
                 if ports_dirty {
                     ports_cache = ports::collect_ports(&system);
-                    crate::util::apply_filter(&mut ports_cache, &state.ports_filter);
+                    let query = parse_filter::<ports::PortInfo>(&mut state, |state| &state.ports_filter);
+                    crate::util::apply_filter(&mut ports_cache, &query);
+                    ports::sort_ports(&mut ports_cache, state.ports_sort);
                     ports_rows = ports::group_ports(&ports_cache);
                     clamp_selection(&mut state, ports_rows.len());
                     state.visible_ports.clear();
@@ -281,9 +444,14 @@ pub fn run(stdout: &mut io::Stdout) -> io::Result<()> {
             }
             ViewMode::Node => {
                 if node_dirty {
-                    pm2_available = node::is_pm2_running();
+                    state.pm2_available = node::is_pm2_running();
 
-                    let node_cache = node::collect_node_processes(&system, &state.node_filter);
+                    let query = parse_filter::<node::NodeProcessInfo>(&mut state, |state| &state.node_filter);
+                    let mut node_cache = node::collect_node_processes(&system, &query);
+                    if state.node_wrong_version_only {
+                        node_cache.retain(node::NodeProcessInfo::is_version_mismatch);
+                    }
+                    node::sort_node_processes(&mut node_cache, state.node_sort);
                     let mut node_main = Vec::new();
                     let mut node_utils = Vec::new();
                     for proc in node_cache {
@@ -326,18 +494,25 @@ pub fn run(stdout: &mut io::Stdout) -> io::Result<()> {
                     clamp_selection(&mut state, node_rows.len());
                     state.visible_pids.clear();
                     state.visible_node_selectable.clear();
+                    state.visible_node_pm2.clear();
+                    state.visible_node_apps.clear();
                     state.visible_pids.reserve(node_rows.len());
                     state.visible_node_selectable.reserve(node_rows.len());
+                    state.visible_node_pm2.reserve(node_rows.len());
                     for row in &node_rows {
                         match row {
                             node::NodeRow::Item { index } => {
                                 let proc = &node_view[*index];
                                 state.visible_pids.push(proc.pid);
                                 state.visible_node_selectable.push(true);
+                                state.visible_node_pm2.push(proc.pm2.clone());
+                                state.visible_node_apps.push(Some(proc.clone()));
                             }
                             _ => {
                                 state.visible_pids.push(Pid::from_u32(0));
                                 state.visible_node_selectable.push(false);
+                                state.visible_node_pm2.push(None);
+                                state.visible_node_apps.push(None);
                             }
                         }
                     }
@@ -350,7 +525,7 @@ pub fn run(stdout: &mut io::Stdout) -> io::Result<()> {
                 }
 
                 if needs_render {
-                    ui::render_node_processes(stdout, &state, &node_view, &node_rows, pm2_available)?;
+                    ui::render_node_processes(stdout, &state, &node_view, &node_rows)?;
                     needs_render = false;
                 }
             }
@@ -372,6 +547,7 @@ fn update_system_snapshot(state: &mut AppState, system: &System) {
     state.mem_available = system.available_memory();
     state.swap_total = system.total_swap();
     state.swap_used = system.used_swap();
+    (state.zombie_count, state.zombie_parents) = process::count_zombies(system);
 }
 
 fn maybe_refresh_container_cache(state: &mut AppState) {
@@ -401,6 +577,28 @@ fn maybe_refresh_user_cache(state: &mut AppState) {
     state.user_cache = cache;
 }
 
+/// Parses a view's filter, reporting syntax errors in the status line.
+/// An invalid filter matches everything until it is fixed.
+fn parse_filter<T: Filterable>(state: &mut AppState, filter: fn(&AppState) -> &str) -> Query {
+    match Query::parse::<T>(filter(state)) {
+        Ok(query) => query,
+        Err(err) => {
+            state.set_message(err);
+            Query::default()
+        }
+    }
+}
+
+/// Moves the selection onto the PID requested by a jump from another view, if it is listed.
+fn select_pending_pid(state: &mut AppState) {
+    let Some(pid) = state.pending_select_pid.take() else {
+        return;
+    };
+    if let Some(index) = state.visible_pids.iter().position(|visible| *visible == pid) {
+        state.selected = index;
+    }
+}
+
 fn clamp_selection(state: &mut AppState, list_len: usize) {
     if list_len == 0 {
         state.selected = 0;
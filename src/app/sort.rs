@@ -0,0 +1,457 @@
+//! Sort state of the list views. Every view sorts by its own keys; a key is
+//! picked with the sort letters, cycled with `<`/`>` or chosen by clicking the
+//! header of its column.
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SortOrder {
+    Asc,
+    Desc,
+}
+
+impl SortOrder {
+    pub fn toggle(self) -> Self {
+        match self {
+            SortOrder::Asc => SortOrder::Desc,
+            SortOrder::Desc => SortOrder::Asc,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SortOrder::Asc => "asc",
+            SortOrder::Desc => "desc",
+        }
+    }
+
+    /// Marker drawn after the title of the sorted column
+    pub fn arrow(self) -> char {
+        match self {
+            SortOrder::Asc => '▲',
+            SortOrder::Desc => '▼',
+        }
+    }
+}
+
+pub trait SortKey: Copy + PartialEq + 'static {
+    /// Keys in the order `<` and `>` step through them
+    const ALL: &'static [Self];
+
+    fn label(self) -> &'static str;
+
+    /// Column showing the key, where the sort arrow is drawn
+    fn column(self) -> Option<&'static str>;
+
+    /// Key picked by clicking the header of column `id`
+    fn for_column(id: &str) -> Option<Self>;
+
+    /// Order a key starts in when it is picked
+    fn default_order(self) -> SortOrder {
+        SortOrder::Desc
+    }
+}
+
+/// Sort key and direction of one view.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ViewSort<K> {
+    pub by: K,
+    pub order: SortOrder,
+}
+
+impl<K: SortKey> ViewSort<K> {
+    pub fn new(by: K) -> Self {
+        Self {
+            by,
+            order: by.default_order(),
+        }
+    }
+
+    /// Sorts by `by`, or reverses the order when already sorting by it.
+    pub fn toggle(&mut self, by: K) {
+        if self.by == by {
+            self.order = self.order.toggle();
+        } else {
+            *self = Self::new(by);
+        }
+    }
+
+    /// Switches to the next (`delta` > 0) or previous key, wrapping around.
+    pub fn cycle(&mut self, delta: isize) {
+        let len = K::ALL.len() as isize;
+        let current = K::ALL.iter().position(|key| *key == self.by).unwrap_or(0) as isize;
+        let next = (current + delta).rem_euclid(len) as usize;
+        *self = Self::new(K::ALL[next]);
+    }
+
+    pub fn label(&self) -> String {
+        format!("{} {}", self.by.label(), self.order.label())
+    }
+
+    /// Sorted column and direction, for the header arrow
+    pub fn column(&self) -> Option<(&'static str, SortOrder)> {
+        self.by.column().map(|id| (id, self.order))
+    }
+}
+
+/// Sort keys of the Process and container process views.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SortBy {
+    Cpu,
+    Memory,
+    Name,
+    /// Disk read plus write throughput
+    Io,
+    /// Network receive plus transmit throughput
+    Net,
+    Pid,
+    User,
+    Started,
+}
+
+impl SortKey for SortBy {
+    const ALL: &'static [Self] = &[
+        SortBy::Cpu,
+        SortBy::Memory,
+        SortBy::Io,
+        SortBy::Net,
+        SortBy::Pid,
+        SortBy::User,
+        SortBy::Name,
+        SortBy::Started,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            SortBy::Cpu => "CPU",
+            SortBy::Memory => "MEM",
+            SortBy::Name => "NAME",
+            SortBy::Io => "IO",
+            SortBy::Net => "NET",
+            SortBy::Pid => "PID",
+            SortBy::User => "USER",
+            SortBy::Started => "STARTED",
+        }
+    }
+
+    fn column(self) -> Option<&'static str> {
+        Some(match self {
+            SortBy::Cpu => "cpu",
+            SortBy::Memory => "mem",
+            SortBy::Name => "name",
+            SortBy::Io => "read",
+            SortBy::Net => "rx",
+            SortBy::Pid => "pid",
+            SortBy::User => "user",
+            SortBy::Started => "started",
+        })
+    }
+
+    fn for_column(id: &str) -> Option<Self> {
+        Some(match id {
+            "cpu" => SortBy::Cpu,
+            "mem" => SortBy::Memory,
+            "name" => SortBy::Name,
+            "read" | "write" => SortBy::Io,
+            "rx" | "tx" => SortBy::Net,
+            "pid" => SortBy::Pid,
+            "user" => SortBy::User,
+            "started" => SortBy::Started,
+            _ => return None,
+        })
+    }
+
+    fn default_order(self) -> SortOrder {
+        match self {
+            SortBy::Pid | SortBy::User => SortOrder::Asc,
+            _ => SortOrder::Desc,
+        }
+    }
+}
+
+/// Sort keys of the Docker view. Groups are ordered by their first container,
+/// except with `Group`, which orders them by name.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DockerSortBy {
+    /// Most recently active first when descending
+    Activity,
+    /// Unhealthy first when descending
+    Health,
+    Group,
+    Cpu,
+    Memory,
+    Net,
+    Name,
+    Image,
+    Status,
+}
+
+impl SortKey for DockerSortBy {
+    const ALL: &'static [Self] = &[
+        DockerSortBy::Activity,
+        DockerSortBy::Health,
+        DockerSortBy::Group,
+        DockerSortBy::Cpu,
+        DockerSortBy::Memory,
+        DockerSortBy::Net,
+        DockerSortBy::Name,
+        DockerSortBy::Image,
+        DockerSortBy::Status,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            DockerSortBy::Activity => "ACTIVITY",
+            DockerSortBy::Health => "HEALTH",
+            DockerSortBy::Group => "GROUP",
+            DockerSortBy::Cpu => "CPU",
+            DockerSortBy::Memory => "MEM",
+            DockerSortBy::Net => "NET",
+            DockerSortBy::Name => "NAME",
+            DockerSortBy::Image => "IMAGE",
+            DockerSortBy::Status => "STATUS",
+        }
+    }
+
+    fn column(self) -> Option<&'static str> {
+        match self {
+            DockerSortBy::Activity | DockerSortBy::Group => None,
+            DockerSortBy::Health | DockerSortBy::Name => Some("name"),
+            DockerSortBy::Cpu => Some("cpu"),
+            DockerSortBy::Memory => Some("mem"),
+            DockerSortBy::Net => Some("rx"),
+            DockerSortBy::Image => Some("image"),
+            DockerSortBy::Status => Some("status"),
+        }
+    }
+
+    fn for_column(id: &str) -> Option<Self> {
+        Some(match id {
+            "cpu" => DockerSortBy::Cpu,
+            "mem" => DockerSortBy::Memory,
+            "rx" | "tx" => DockerSortBy::Net,
+            "name" => DockerSortBy::Name,
+            "image" => DockerSortBy::Image,
+            "status" => DockerSortBy::Status,
+            _ => return None,
+        })
+    }
+
+    fn default_order(self) -> SortOrder {
+        match self {
+            DockerSortBy::Group | DockerSortBy::Name | DockerSortBy::Image | DockerSortBy::Status => {
+                SortOrder::Asc
+            }
+            _ => SortOrder::Desc,
+        }
+    }
+}
+
+/// Sort keys of the Ports view. Groups follow their first port.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PortSortBy {
+    Port,
+    Proto,
+    Pid,
+    Name,
+    Project,
+}
+
+impl SortKey for PortSortBy {
+    const ALL: &'static [Self] = &[
+        PortSortBy::Port,
+        PortSortBy::Proto,
+        PortSortBy::Pid,
+        PortSortBy::Name,
+        PortSortBy::Project,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            PortSortBy::Port => "PORT",
+            PortSortBy::Proto => "PROTO",
+            PortSortBy::Pid => "PID",
+            PortSortBy::Name => "NAME",
+            PortSortBy::Project => "PROJECT",
+        }
+    }
+
+    fn column(self) -> Option<&'static str> {
+        Some(match self {
+            PortSortBy::Port => "port",
+            PortSortBy::Proto => "proto",
+            PortSortBy::Pid => "pid",
+            PortSortBy::Name => "name",
+            PortSortBy::Project => "project",
+        })
+    }
+
+    fn for_column(id: &str) -> Option<Self> {
+        Some(match id {
+            "port" => PortSortBy::Port,
+            "proto" => PortSortBy::Proto,
+            "pid" => PortSortBy::Pid,
+            "name" => PortSortBy::Name,
+            "project" => PortSortBy::Project,
+            _ => return None,
+        })
+    }
+
+    fn default_order(self) -> SortOrder {
+        SortOrder::Asc
+    }
+}
+
+/// Sort keys of the Apps view. Groups follow their first app.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum NodeSortBy {
+    /// PM2 id, then declared apps by name, then PID
+    Id,
+    Status,
+    Restarts,
+    Uptime,
+    Cpu,
+    Memory,
+    Pid,
+    Name,
+}
+
+impl SortKey for NodeSortBy {
+    const ALL: &'static [Self] = &[
+        NodeSortBy::Id,
+        NodeSortBy::Status,
+        NodeSortBy::Restarts,
+        NodeSortBy::Uptime,
+        NodeSortBy::Cpu,
+        NodeSortBy::Memory,
+        NodeSortBy::Pid,
+        NodeSortBy::Name,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            NodeSortBy::Id => "ID",
+            NodeSortBy::Status => "STATUS",
+            NodeSortBy::Restarts => "RESTARTS",
+            NodeSortBy::Uptime => "UPTIME",
+            NodeSortBy::Cpu => "CPU",
+            NodeSortBy::Memory => "MEM",
+            NodeSortBy::Pid => "PID",
+            NodeSortBy::Name => "NAME",
+        }
+    }
+
+    fn column(self) -> Option<&'static str> {
+        Some(match self {
+            NodeSortBy::Id => "id",
+            NodeSortBy::Status => "status",
+            NodeSortBy::Restarts => "restarts",
+            NodeSortBy::Uptime => "uptime",
+            NodeSortBy::Cpu => "cpu",
+            NodeSortBy::Memory => "mem",
+            NodeSortBy::Pid => "pid",
+            NodeSortBy::Name => "name",
+        })
+    }
+
+    fn for_column(id: &str) -> Option<Self> {
+        Some(match id {
+            "id" => NodeSortBy::Id,
+            "status" => NodeSortBy::Status,
+            "restarts" => NodeSortBy::Restarts,
+            "uptime" => NodeSortBy::Uptime,
+            "cpu" => NodeSortBy::Cpu,
+            "mem" => NodeSortBy::Memory,
+            "pid" => NodeSortBy::Pid,
+            "name" => NodeSortBy::Name,
+            _ => return None,
+        })
+    }
+
+    fn default_order(self) -> SortOrder {
+        match self {
+            NodeSortBy::Id | NodeSortBy::Status | NodeSortBy::Pid | NodeSortBy::Name => SortOrder::Asc,
+            _ => SortOrder::Desc,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_view_sort_default_orders() {
+        assert_eq!(ViewSort::new(SortBy::Cpu).order, SortOrder::Desc);
+        assert_eq!(ViewSort::new(SortBy::Pid).order, SortOrder::Asc);
+        assert_eq!(ViewSort::new(DockerSortBy::Name).order, SortOrder::Asc);
+        assert_eq!(ViewSort::new(DockerSortBy::Health).order, SortOrder::Desc);
+        assert_eq!(ViewSort::new(PortSortBy::Project).order, SortOrder::Asc);
+        assert_eq!(ViewSort::new(NodeSortBy::Restarts).order, SortOrder::Desc);
+    }
+
+    #[test]
+    fn test_view_sort_toggle() {
+        let mut sort = ViewSort::new(SortBy::Cpu);
+        sort.toggle(SortBy::Cpu);
+        assert_eq!(
+            sort,
+            ViewSort {
+                by: SortBy::Cpu,
+                order: SortOrder::Asc
+            }
+        );
+        sort.toggle(SortBy::Cpu);
+        assert_eq!(sort.order, SortOrder::Desc);
+
+        // A new key starts in its own default order
+        sort.toggle(SortBy::Cpu);
+        sort.toggle(SortBy::Name);
+        assert_eq!(
+            sort,
+            ViewSort {
+                by: SortBy::Name,
+                order: SortOrder::Desc
+            }
+        );
+        sort.toggle(SortBy::User);
+        assert_eq!(
+            sort,
+            ViewSort {
+                by: SortBy::User,
+                order: SortOrder::Asc
+            }
+        );
+        assert_eq!(sort.label(), "USER asc");
+        assert_eq!(sort.column(), Some(("user", SortOrder::Asc)));
+    }
+
+    #[test]
+    fn test_view_sort_cycle_wraps_around() {
+        let mut sort = ViewSort::new(PortSortBy::Port);
+        sort.cycle(-1);
+        assert_eq!(sort.by, PortSortBy::Project);
+        sort.cycle(1);
+        assert_eq!(sort.by, PortSortBy::Port);
+        sort.cycle(7);
+        assert_eq!(sort.by, PortSortBy::Pid);
+
+        // Cycling resets the order to the new key's default
+        let mut sort = ViewSort::new(DockerSortBy::Status);
+        sort.toggle(DockerSortBy::Status);
+        assert_eq!(sort.order, SortOrder::Desc);
+        sort.cycle(1);
+        assert_eq!(sort, ViewSort::new(DockerSortBy::Activity));
+        assert_eq!(sort.column(), None);
+    }
+
+    #[test]
+    fn test_for_column_round_trips() {
+        for key in SortBy::ALL {
+            assert_eq!(key.column().and_then(SortBy::for_column), Some(*key));
+        }
+        for key in NodeSortBy::ALL {
+            assert_eq!(key.column().and_then(NodeSortBy::for_column), Some(*key));
+        }
+        assert_eq!(DockerSortBy::for_column("tx"), Some(DockerSortBy::Net));
+        assert_eq!(DockerSortBy::for_column("ports"), None);
+    }
+}
@@ -1,10 +1,21 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
 use std::sync::mpsc::{self, Receiver, Sender};
 use std::time::{Duration, Instant};
 
-use sysinfo::{Pid, Uid};
+use sysinfo::{Pid, Signal, Uid};
 
-use crate::system::docker::DockerRow;
+use super::columns::{
+    resolve_columns, Column, ColumnChooser, ColumnLayouts, ColumnSetting, ColumnView, Requires,
+};
+use super::sort::{DockerSortBy, NodeSortBy, PortSortBy, SortBy, SortKey, SortOrder, ViewSort};
+use crate::system::docker::{ContainerLimits, DockerContext, DockerRow};
+use crate::system::fds::OpenFile;
+use crate::system::logs::{CommandOutput, LogBuffer, LogSource, LogStream, LogTail};
+use crate::system::node::{InspectorTarget, NodeProcessInfo, PackageScripts, Pm2Action, Pm2Info};
+use crate::system::priority::SchedInfo;
+use crate::system::process::{KillScope, ProcessKey};
+use crate::system::threads::ThreadSampler;
 
 /// Message sent when a container operation completes
 #[derive(Debug)]
@@ -14,6 +25,194 @@ pub struct OperationComplete {
     pub message: String,
 }
 
+/// Message sent when a background inspector activation finishes
+#[derive(Debug)]
+pub struct InspectorReady {
+    pub pid: u32,
+    pub name: String,
+    pub result: Result<InspectorTarget, String>,
+}
+
+/// Message sent when a background open file search finishes
+pub struct OpenFilesFound {
+    pub query: String,
+    pub found: Vec<OpenFile>,
+    /// Names of the processes holding the matches
+    pub names: HashMap<Pid, String>,
+}
+
+/// A tree, group or session kill previewed in the env pane, waiting for Enter
+#[derive(Clone, Debug)]
+pub struct KillPlan {
+    pub scope: KillScope,
+    /// "name (pid)" of the selected process
+    pub subject: String,
+    pub targets: Vec<ProcessKey>,
+}
+
+/// Threads of a process listed in the env pane, resampled every tick
+pub struct ThreadPane {
+    /// "name (pid)" of the process
+    pub subject: String,
+    pub sampler: ThreadSampler,
+}
+
+/// One editable line of a [`FormOverlay`]
+#[derive(Clone, Debug)]
+pub struct FormField {
+    pub label: &'static str,
+    pub value: String,
+    /// Shown next to the input, e.g. accepted formats
+    pub hint: &'static str,
+}
+
+/// What a form submits to
+#[derive(Clone, Debug)]
+pub enum FormPurpose {
+    ContainerLimits {
+        id: String,
+        name: String,
+        current: ContainerLimits,
+        /// CPUs on the docker host, bounding the CPU fields
+        max_cpus: usize,
+    },
+    ProcessPriority {
+        pid: Pid,
+        name: String,
+        current: SchedInfo,
+        max_cpus: usize,
+    },
+    FindOpenFile,
+}
+
+/// Modal form drawn over the main area, edited with the keyboard
+#[derive(Clone, Debug)]
+pub struct FormOverlay {
+    pub title: String,
+    pub fields: Vec<FormField>,
+    pub selected: usize,
+    /// Validation error from the last submit attempt
+    pub error: Option<String>,
+    pub purpose: FormPurpose,
+}
+
+impl FormOverlay {
+    /// Screen rectangle (x, y, width, height) of the form, centered in the main area
+    pub fn frame(&self, main_x: u16, main_width: usize, height: usize) -> (u16, u16, usize, usize) {
+        let width = 60.min(main_width.saturating_sub(4)).max(4);
+        // Fields, a blank line and the status line inside the border
+        let box_height = (self.fields.len() + 4).min(height.saturating_sub(2)).max(3);
+        let x = main_x + (main_width.saturating_sub(width) / 2) as u16;
+        let y = (height.saturating_sub(box_height) / 2) as u16;
+        (x, y, width, box_height)
+    }
+
+    pub fn value(&self, index: usize) -> &str {
+        self.fields.get(index).map(|field| field.value.as_str()).unwrap_or("")
+    }
+}
+
+/// Lines kept in memory per log pane
+const LOG_PANE_CAPACITY: usize = 5_000;
+/// How much of each file is shown when a log pane opens
+const LOG_PANE_BACKLOG_BYTES: u64 = 64 * 1024;
+
+/// Live tail of one or more log files, shown in `ViewMode::Logs`
+pub struct LogPane {
+    pub title: String,
+    /// What the logs belong to, e.g. "api (pm2 0)"
+    pub subject: String,
+    pub source: LogSource,
+    pub lines: LogBuffer,
+    pub paused: bool,
+    /// Lines hidden below the bottom of the pane; 0 follows new output
+    pub scroll: usize,
+    pub search: String,
+    /// True while the search term is being typed
+    pub search_editing: bool,
+    /// Line index of the search hit last jumped to
+    pub current_match: Option<usize>,
+    pub return_view: ViewMode,
+}
+
+impl LogPane {
+    pub fn new(
+        title: &str,
+        subject: String,
+        files: Vec<(PathBuf, LogStream)>,
+        return_view: ViewMode,
+    ) -> Self {
+        let (tail, backlog) = LogTail::open(files, LOG_PANE_BACKLOG_BYTES);
+        let mut pane = Self::with_source(title, subject, LogSource::Files(tail), return_view);
+        pane.lines.extend(backlog);
+        pane
+    }
+
+    /// Pane that shows the output of a command as it runs.
+    pub fn for_command(
+        title: &str,
+        subject: String,
+        output: CommandOutput,
+        return_view: ViewMode,
+    ) -> Self {
+        Self::with_source(title, subject, LogSource::Command(output), return_view)
+    }
+
+    fn with_source(title: &str, subject: String, source: LogSource, return_view: ViewMode) -> Self {
+        Self {
+            title: title.to_string(),
+            subject,
+            source,
+            lines: LogBuffer::new(LOG_PANE_CAPACITY),
+            paused: false,
+            scroll: 0,
+            search: String::new(),
+            search_editing: false,
+            current_match: None,
+            return_view,
+        }
+    }
+
+    /// Pulls newly written lines; returns true if any arrived.
+    pub fn poll(&mut self) -> bool {
+        let new_lines = self.source.poll();
+        if new_lines.is_empty() {
+            return false;
+        }
+        let added = new_lines.len();
+        let dropped = self.lines.extend(new_lines);
+        if self.scroll > 0 {
+            // Keep the lines in view still while scrolled back
+            self.scroll = (self.scroll + added).min(self.lines.len().saturating_sub(1));
+        }
+        self.current_match = self.current_match.and_then(|index| index.checked_sub(dropped));
+        true
+    }
+
+    pub fn scroll_by(&mut self, delta: isize) {
+        let max = self.lines.len().saturating_sub(1);
+        self.scroll = self.scroll.saturating_add_signed(delta).min(max);
+    }
+
+    /// Moves to the next search hit, older (up) or newer (down) than the current
+    /// one, and scrolls it to the bottom line. Returns false if there is none.
+    pub fn jump_to_match(&mut self, older: bool) -> bool {
+        let matches = self.lines.matches(&self.search);
+        let from = self.current_match.unwrap_or(self.lines.len());
+        let target = if older {
+            matches.iter().rev().find(|&&index| index < from)
+        } else {
+            matches.iter().find(|&&index| index > from)
+        };
+        let Some(&index) = target else {
+            return false;
+        };
+        self.current_match = Some(index);
+        self.scroll = self.lines.len() - 1 - index;
+        true
+    }
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum InputMode {
     Normal,
@@ -29,8 +228,30 @@ pub enum ContextMenuAction {
     Shell,
     Env,
     Kill,
+    Top,
+    Limits,
+    Signals,
+    Signal(Signal),
+    KillScope(KillScope),
+    /// Signals the parent of a zombie; the menu target is that parent
+    ZombieParent(Signal),
+    Pm2(Pm2Action),
+    Pm2Detail,
 }
 
+/// Signals offered by the signal menu, in display order
+pub const SIGNAL_MENU: [Signal; 9] = [
+    Signal::Term,
+    Signal::Kill,
+    Signal::Hangup,
+    Signal::Interrupt,
+    Signal::Quit,
+    Signal::User1,
+    Signal::User2,
+    Signal::Stop,
+    Signal::Continue,
+];
+
 impl ContextMenuAction {
     pub fn label(&self, is_group: bool) -> &'static str {
         match self {
@@ -41,12 +262,64 @@ impl ContextMenuAction {
             ContextMenuAction::Shell => "$ Shell",
             ContextMenuAction::Env => "# Env",
             ContextMenuAction::Kill => "x Kill",
+            ContextMenuAction::Top => "= Processes",
+            ContextMenuAction::Limits => "% Limits",
+            ContextMenuAction::Signals => "! Signal...",
+            ContextMenuAction::Signal(signal) => signal_label(*signal),
+            ContextMenuAction::KillScope(scope) => kill_scope_label(*scope),
+            ContextMenuAction::ZombieParent(signal) => {
+                if *signal == Signal::Child { "~ Reap (CHLD)" } else { "x Kill parent" }
+            }
+            ContextMenuAction::Pm2(action) => pm2_label(*action),
+            ContextMenuAction::Pm2Detail => "? Details",
         }
     }
 
     /// Returns true if this action is only available for single containers (not groups)
     pub fn is_container_only(&self) -> bool {
-        matches!(self, ContextMenuAction::Logs | ContextMenuAction::Shell | ContextMenuAction::Env)
+        matches!(
+            self,
+            ContextMenuAction::Logs
+                | ContextMenuAction::Shell
+                | ContextMenuAction::Env
+                | ContextMenuAction::Top
+                | ContextMenuAction::Limits
+        )
+    }
+}
+
+fn pm2_label(action: Pm2Action) -> &'static str {
+    match action {
+        Pm2Action::Restart => "~ Restart",
+        Pm2Action::Reload => "~ Reload",
+        Pm2Action::Stop => "x Stop",
+        Pm2Action::Delete => "x Delete",
+        Pm2Action::ScaleUp => "+ Scale up",
+        Pm2Action::ScaleDown => "- Scale down",
+        Pm2Action::Flush => "] Flush logs",
+    }
+}
+
+fn kill_scope_label(scope: KillScope) -> &'static str {
+    match scope {
+        KillScope::Tree => "x Kill tree",
+        KillScope::Group => "x Kill group",
+        KillScope::Session => "x Kill session",
+    }
+}
+
+fn signal_label(signal: Signal) -> &'static str {
+    match signal {
+        Signal::Term => "  SIGTERM  15",
+        Signal::Kill => "  SIGKILL   9",
+        Signal::Hangup => "  SIGHUP    1",
+        Signal::Interrupt => "  SIGINT    2",
+        Signal::Quit => "  SIGQUIT   3",
+        Signal::User1 => "  SIGUSR1  10",
+        Signal::User2 => "  SIGUSR2  12",
+        Signal::Stop => "  SIGSTOP  19",
+        Signal::Continue => "  SIGCONT  18",
+        _ => "  signal",
     }
 }
 
@@ -56,6 +329,7 @@ pub enum ContextMenuTarget {
     Container { id: String, name: String, running: bool },
     Group { name: String, path: Option<String> },
     Process { pid: u32, name: String },
+    Pm2App { pm_id: u32, name: String },
 }
 
 #[derive(Clone, Debug)]
@@ -68,6 +342,39 @@ pub struct ContextMenu {
     pub is_group: bool,
 }
 
+/// Overlay listing docker contexts, opened from the Docker view
+#[derive(Clone, Debug)]
+pub struct DockerContextPicker {
+    pub contexts: Vec<DockerContext>,
+    pub selected: usize,
+}
+
+impl DockerContextPicker {
+    /// Screen rectangle (x, y, width, height) of the picker, centered in the main area
+    pub fn frame(&self, main_x: u16, main_width: usize, height: usize) -> (u16, u16, usize, usize) {
+        let longest = self
+            .contexts
+            .iter()
+            .map(|ctx| ctx.name.chars().count() + ctx.endpoint.chars().count() + 6)
+            .max()
+            .unwrap_or(0);
+        let width = longest.max(36).min(main_width.saturating_sub(4)).max(4);
+        let box_height = (self.contexts.len() + 2).min(height.saturating_sub(2)).max(3);
+        let x = main_x + (main_width.saturating_sub(width) / 2) as u16;
+        let y = (height.saturating_sub(box_height) / 2) as u16;
+        (x, y, width, box_height)
+    }
+
+    /// Index of the first context shown when only `visible` rows fit
+    pub fn scroll_offset(&self, visible: usize) -> usize {
+        if visible > 0 && self.selected >= visible {
+            self.selected + 1 - visible
+        } else {
+            0
+        }
+    }
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum Focus {
     Sidebar,
@@ -79,41 +386,47 @@ pub enum ViewMode {
     Process,
     Docker,
     DockerEnv,
+    ContainerTop,
+    Logs,
     Ports,
     Node,
 }
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
-pub enum SortBy {
-    Cpu,
-    Memory,
-    Name,
-}
-
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
-pub enum SortOrder {
-    Asc,
-    Desc,
-}
-
-impl SortOrder {
-    pub fn toggle(self) -> Self {
-        match self {
-            SortOrder::Asc => SortOrder::Desc,
-            SortOrder::Desc => SortOrder::Asc,
-        }
-    }
+/// Restricts the Process view to the processes of one container
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProcessScope {
+    pub container_id: String,
+    pub container_name: String,
 }
 
 pub struct AppState {
     pub input_mode: InputMode,
     pub process_filter: String,
+    pub process_scope: Option<ProcessScope>,
+    /// PID to select once the next process list has been built
+    pub pending_select_pid: Option<Pid>,
+    /// Process tree nodes whose children are folded away
+    pub collapsed_processes: HashSet<ProcessKey>,
     pub docker_filter: String,
     pub ports_filter: String,
     pub node_filter: String,
-    pub sort_by: SortBy,
-    pub sort_order: SortOrder,
+    /// Sort of the Process view, shared with the container process view
+    pub process_sort: ViewSort<SortBy>,
+    pub docker_sort: ViewSort<DockerSortBy>,
+    pub ports_sort: ViewSort<PortSortBy>,
+    pub node_sort: ViewSort<NodeSortBy>,
     pub zoom: bool,
+    /// Shows the nice, I/O priority and CPU affinity columns in the Process view
+    pub show_sched_columns: bool,
+    /// Shows the network RX/TX columns in the Process and Docker views
+    pub show_net_columns: bool,
+    /// Column layouts of the list views, loaded from the config file
+    pub columns: ColumnLayouts,
+    pub column_chooser: Option<ColumnChooser>,
+    /// PM2 is running; the Apps view then shows its ID, status and restart columns
+    pub pm2_available: bool,
+    /// Widest user name in the process list, which sizes the USER column
+    pub process_user_width: usize,
     pub view_mode: ViewMode,
     pub focus: Focus,
     pub sidebar_index: usize,
@@ -132,9 +445,22 @@ pub struct AppState {
     pub hover_row: Option<usize>,
     pub sidebar_hover: Option<usize>,
     pub context_menu: Option<ContextMenu>,
+    pub docker_context_picker: Option<DockerContextPicker>,
+    pub form: Option<FormOverlay>,
+    pub log_pane: Option<LogPane>,
+    /// Docker context shown in the Docker view header (None until resolved)
+    pub docker_context: Option<String>,
     pub visible_ports: Vec<Pid>,
     pub visible_ports_container_ids: Vec<Option<String>>,
     pub visible_node_selectable: Vec<bool>,
+    /// PM2 data for each Node row (None for unmanaged processes and headers)
+    pub visible_node_pm2: Vec<Option<Pm2Info>>,
+    /// App behind each Node row (None for headers)
+    pub visible_node_apps: Vec<Option<NodeProcessInfo>>,
+    /// Only list Node apps whose version does not satisfy their project's requirement
+    pub node_wrong_version_only: bool,
+    /// Process view lists only D-state and zombie processes with their parents
+    pub process_stuck_only: bool,
     pub container_cache: HashMap<String, String>,
     pub container_last_refresh: Instant,
     pub user_cache: HashMap<Uid, String>,
@@ -149,15 +475,32 @@ pub struct AppState {
     pub env_info_right2: String,
     pub env_selected: usize,
     pub env_return_view: ViewMode,
+    /// Set while the env pane lists package.json scripts; Enter runs the selected one
+    pub env_scripts: Option<PackageScripts>,
+    /// Set while the env pane previews a tree, group or session kill; Enter confirms it
+    pub kill_plan: Option<KillPlan>,
+    /// Set while the env pane lists the threads of a process
+    pub thread_pane: Option<ThreadPane>,
+    pub top_container_id: String,
+    pub top_container_name: String,
     pub cpu_usage: f32,
     pub mem_total: u64,
     pub mem_available: u64,
     pub swap_total: u64,
     pub swap_used: u64,
-    /// Maps container ID -> expected running state (true = should be running, false = should be stopped)
-    pub pending_operations: HashMap<String, bool>,
+    pub zombie_count: usize,
+    /// Distinct parents that have not reaped their zombie children
+    pub zombie_parents: usize,
+    /// Maps container ID -> expected running state (true = should be running, false = should be stopped).
+    /// `None` marks operations that don't change the running state; they finish when
+    /// their `OperationComplete` arrives.
+    pub pending_operations: HashMap<String, Option<bool>>,
     pub operation_tx: Sender<OperationComplete>,
     pub operation_rx: Receiver<OperationComplete>,
+    pub inspector_tx: Sender<InspectorReady>,
+    pub inspector_rx: Receiver<InspectorReady>,
+    pub open_files_tx: Sender<OpenFilesFound>,
+    pub open_files_rx: Receiver<OpenFilesFound>,
     /// Frame counter for animated spinner
     pub spinner_frame: usize,
 }
@@ -165,15 +508,28 @@ pub struct AppState {
 impl AppState {
     pub fn new() -> Self {
         let (operation_tx, operation_rx) = mpsc::channel();
+        let (inspector_tx, inspector_rx) = mpsc::channel();
+        let (open_files_tx, open_files_rx) = mpsc::channel();
         Self {
             input_mode: InputMode::Normal,
             process_filter: String::new(),
+            process_scope: None,
+            pending_select_pid: None,
+            collapsed_processes: HashSet::new(),
             docker_filter: String::new(),
             ports_filter: String::new(),
             node_filter: String::new(),
-            sort_by: SortBy::Memory,
-            sort_order: SortOrder::Desc,
+            process_sort: ViewSort::new(SortBy::Memory),
+            docker_sort: ViewSort::new(DockerSortBy::Activity),
+            ports_sort: ViewSort::new(PortSortBy::Port),
+            node_sort: ViewSort::new(NodeSortBy::Id),
             zoom: false,
+            show_sched_columns: false,
+            show_net_columns: false,
+            columns: ColumnLayouts::default(),
+            column_chooser: None,
+            pm2_available: false,
+            process_user_width: 4,
             view_mode: ViewMode::Process,
             focus: Focus::Main,
             sidebar_index: 0,
@@ -192,9 +548,17 @@ impl AppState {
             hover_row: None,
             sidebar_hover: None,
             context_menu: None,
+            docker_context_picker: None,
+            form: None,
+            log_pane: None,
+            docker_context: None,
             visible_ports: Vec::new(),
             visible_ports_container_ids: Vec::new(),
             visible_node_selectable: Vec::new(),
+            visible_node_pm2: Vec::new(),
+            visible_node_apps: Vec::new(),
+            node_wrong_version_only: false,
+            process_stuck_only: false,
             container_cache: HashMap::new(),
             container_last_refresh: Instant::now() - Duration::from_secs(60),
             user_cache: HashMap::new(),
@@ -209,14 +573,25 @@ impl AppState {
             env_info_right2: "-".to_string(),
             env_selected: 0,
             env_return_view: ViewMode::Process,
+            env_scripts: None,
+            kill_plan: None,
+            thread_pane: None,
+            top_container_id: String::new(),
+            top_container_name: String::new(),
             cpu_usage: 0.0,
             mem_total: 0,
             mem_available: 0,
             swap_total: 0,
             swap_used: 0,
+            zombie_count: 0,
+            zombie_parents: 0,
             pending_operations: HashMap::new(),
             operation_tx,
             operation_rx,
+            inspector_tx,
+            inspector_rx,
+            open_files_tx,
+            open_files_rx,
             spinner_frame: 0,
         }
     }
@@ -240,10 +615,13 @@ impl AppState {
     pub fn check_completed_operations(&mut self) -> bool {
         let mut any_completed = false;
         while let Ok(msg) = self.operation_rx.try_recv() {
-            // Only remove from pending on failure - success keeps it pending until state matches
-            if !msg.success {
+            // Start/stop success keeps it pending until the container state matches
+            let awaits_state = matches!(self.pending_operations.get(&msg.container_id), Some(Some(_)));
+            if !msg.success || !awaits_state {
                 self.pending_operations.remove(&msg.container_id);
-                self.set_message(msg.message);
+                if !msg.message.is_empty() {
+                    self.set_message(msg.message);
+                }
             }
             any_completed = true;
         }
@@ -258,6 +636,9 @@ impl AppState {
 
         let mut to_remove = Vec::new();
         for (container_id, expected_running) in &self.pending_operations {
+            let Some(expected_running) = expected_running else {
+                continue;
+            };
             // Find this container in the list
             if let Some(container) = containers.iter().find(|c| &c.id == container_id) {
                 // If actual state matches expected state, operation is complete
@@ -278,6 +659,14 @@ impl AppState {
         self.pending_operations.contains_key(container_id)
     }
 
+    pub fn is_pm2_pending(&self, pm_id: u32) -> bool {
+        self.pending_operations.contains_key(&pm2_pending_key(pm_id))
+    }
+
+    pub fn is_pm2_start_pending(&self, name: &str) -> bool {
+        self.pending_operations.contains_key(&pm2_start_pending_key(name))
+    }
+
     pub(crate) fn set_message(&mut self, message: impl Into<String>) {
         self.message = Some(message.into());
         self.message_until = Some(Instant::now() + Duration::from_secs(3));
@@ -294,36 +683,136 @@ impl AppState {
         false
     }
 
-    pub(crate) fn toggle_sort(&mut self, sort_by: SortBy) {
-        if self.sort_by == sort_by {
-            self.sort_order = self.sort_order.toggle();
-        } else {
-            self.sort_by = sort_by;
-            self.sort_order = SortOrder::Desc;
+    /// Sorts the current view by the key shown in column `id`, or reverses
+    /// the order when already sorted by it. False when the column has no key.
+    pub(crate) fn sort_by_column(&mut self, id: &str) -> bool {
+        fn toggle<K: SortKey>(sort: &mut ViewSort<K>, id: &str) -> bool {
+            K::for_column(id).map(|key| sort.toggle(key)).is_some()
+        }
+        match self.view_mode {
+            ViewMode::Process | ViewMode::ContainerTop => toggle(&mut self.process_sort, id),
+            ViewMode::Docker => toggle(&mut self.docker_sort, id),
+            ViewMode::Ports => toggle(&mut self.ports_sort, id),
+            ViewMode::Node => toggle(&mut self.node_sort, id),
+            ViewMode::DockerEnv | ViewMode::Logs => false,
+        }
+    }
+
+    pub(crate) fn reverse_sort(&mut self) {
+        match self.view_mode {
+            ViewMode::Process | ViewMode::ContainerTop => {
+                self.process_sort.order = self.process_sort.order.toggle()
+            }
+            ViewMode::Docker => self.docker_sort.order = self.docker_sort.order.toggle(),
+            ViewMode::Ports => self.ports_sort.order = self.ports_sort.order.toggle(),
+            ViewMode::Node => self.node_sort.order = self.node_sort.order.toggle(),
+            ViewMode::DockerEnv | ViewMode::Logs => {}
+        }
+    }
+
+    /// Steps the current view to its next or previous sort key.
+    pub(crate) fn cycle_sort(&mut self, delta: isize) {
+        match self.view_mode {
+            ViewMode::Process | ViewMode::ContainerTop => self.process_sort.cycle(delta),
+            ViewMode::Docker => self.docker_sort.cycle(delta),
+            ViewMode::Ports => self.ports_sort.cycle(delta),
+            ViewMode::Node => self.node_sort.cycle(delta),
+            ViewMode::DockerEnv | ViewMode::Logs => {}
+        }
+    }
+
+    /// Sort key and order of the current view, e.g. "CPU desc"
+    pub(crate) fn sort_label(&self) -> Option<String> {
+        match self.view_mode {
+            ViewMode::Process | ViewMode::ContainerTop => Some(self.process_sort.label()),
+            ViewMode::Docker => Some(self.docker_sort.label()),
+            ViewMode::Ports => Some(self.ports_sort.label()),
+            ViewMode::Node => Some(self.node_sort.label()),
+            ViewMode::DockerEnv | ViewMode::Logs => None,
+        }
+    }
+
+    /// Column of `view` carrying the sort arrow, and its direction.
+    pub(crate) fn sorted_column(&self, view: ColumnView) -> Option<(&'static str, SortOrder)> {
+        match view {
+            ColumnView::Process => self.process_sort.column(),
+            ColumnView::Docker => self.docker_sort.column(),
+            ColumnView::Ports => self.ports_sort.column(),
+            ColumnView::Node => self.node_sort.column(),
         }
     }
 
     pub(crate) fn set_view(&mut self, view: ViewMode) {
+        if view != ViewMode::Logs {
+            // Stop tailing once the pane is left through the sidebar or view keys
+            self.log_pane = None;
+        }
         self.view_mode = view;
         self.selected = 0;
         self.sidebar_index = sidebar_index_for_view(view);
     }
 
+    /// List view whose columns can be customised, if the current view is one.
+    pub(crate) fn column_view(&self) -> Option<ColumnView> {
+        match self.view_mode {
+            ViewMode::Process => Some(ColumnView::Process),
+            ViewMode::Docker => Some(ColumnView::Docker),
+            ViewMode::Ports => Some(ColumnView::Ports),
+            ViewMode::Node => Some(ColumnView::Node),
+            ViewMode::DockerEnv | ViewMode::ContainerTop | ViewMode::Logs => None,
+        }
+    }
+
+    /// Column layout of `view`, previewing the chooser's edits while it is open.
+    pub(crate) fn column_settings(&self, view: ColumnView) -> &[ColumnSetting] {
+        match &self.column_chooser {
+            Some(chooser) if chooser.view == view => &chooser.settings,
+            _ => self.columns.get(view),
+        }
+    }
+
+    /// Columns of `view` laid out across `width`, as the list renders them.
+    pub(crate) fn resolved_columns(&self, view: ColumnView, width: usize) -> Vec<Column> {
+        let natural: &[(&str, usize)] = match view {
+            ColumnView::Process => &[("user", self.process_user_width)],
+            ColumnView::Docker | ColumnView::Ports | ColumnView::Node => &[],
+        };
+        resolve_columns(
+            view,
+            self.column_settings(view),
+            width,
+            |requires| self.column_available(requires),
+            natural,
+        )
+    }
+
+    pub(crate) fn column_available(&self, requires: Requires) -> bool {
+        match requires {
+            Requires::Nothing => true,
+            Requires::NetColumns => self.show_net_columns,
+            Requires::SchedColumns => self.show_sched_columns,
+            Requires::Pm2 => self.pm2_available,
+            Requires::NoPm2 => !self.pm2_available,
+        }
+    }
+
     pub(crate) fn active_filter(&self) -> &str {
         match self.view_mode {
             ViewMode::Process => &self.process_filter,
-            ViewMode::Docker | ViewMode::DockerEnv => &self.docker_filter,
+            ViewMode::Docker | ViewMode::DockerEnv | ViewMode::ContainerTop => &self.docker_filter,
             ViewMode::Ports => &self.ports_filter,
-            ViewMode::Node => &self.node_filter,
+            ViewMode::Node | ViewMode::Logs => &self.node_filter,
         }
     }
 
     pub(crate) fn active_filter_mut(&mut self) -> &mut String {
         match self.view_mode {
             ViewMode::Process => &mut self.process_filter,
-            ViewMode::Docker | ViewMode::DockerEnv => &mut self.docker_filter,
+            ViewMode::Docker | ViewMode::DockerEnv | ViewMode::ContainerTop => {
+                &mut self.docker_filter
+            }
             ViewMode::Ports => &mut self.ports_filter,
-            ViewMode::Node => &mut self.node_filter,
+            ViewMode::Node | ViewMode::Logs => &mut self.node_filter,
         }
     }
 
@@ -354,12 +843,28 @@ impl AppState {
     }
 }
 
+/// PM2 operations share `pending_operations` with containers under a prefixed key
+const PM2_PENDING_PREFIX: &str = "pm2:";
+
+pub(crate) fn pm2_pending_key(pm_id: u32) -> String {
+    format!("{PM2_PENDING_PREFIX}{pm_id}")
+}
+
+/// Key for `pm2 start` of an ecosystem app, which has no `pm_id` yet
+pub(crate) fn pm2_start_pending_key(name: &str) -> String {
+    format!("{PM2_PENDING_PREFIX}start:{name}")
+}
+
+pub(crate) fn is_pm2_pending_key(key: &str) -> bool {
+    key.starts_with(PM2_PENDING_PREFIX)
+}
+
 pub(crate) fn sidebar_index_for_view(view: ViewMode) -> usize {
     match view {
         ViewMode::Process => 0,
         ViewMode::Ports => 1,
-        ViewMode::Docker | ViewMode::DockerEnv => 2,
-        ViewMode::Node => 3,
+        ViewMode::Docker | ViewMode::DockerEnv | ViewMode::ContainerTop => 2,
+        ViewMode::Node | ViewMode::Logs => 3,
     }
 }
 
@@ -0,0 +1,134 @@
+use std::env;
+use std::path::PathBuf;
+
+const USAGE: &str = "\
+Usage: spark [OPTIONS]
+
+Options:
+  --context <NAME>     Docker context to use for the Docker and Ports views
+  --ecosystem <FILE>   PM2 ecosystem file whose apps the Apps view lists (repeatable)
+  --theme <NAME>       Colour theme: dark, light, high-contrast, mono or one from the config file
+  -h, --help           Print this help
+  -V, --version        Print version";
+
+/// Options parsed from the command line.
+#[derive(Debug, Default)]
+pub struct CliOptions {
+    pub docker_context: Option<String>,
+    pub ecosystem_files: Vec<PathBuf>,
+    pub theme: Option<String>,
+}
+
+pub enum CliCommand {
+    Run(CliOptions),
+    /// Print the text to stdout and exit without starting the UI
+    Print(String),
+}
+
+pub fn parse_args() -> Result<CliCommand, String> {
+    parse_from(env::args().skip(1))
+}
+
+fn parse_from(args: impl IntoIterator<Item = String>) -> Result<CliCommand, String> {
+    let mut options = CliOptions::default();
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        let (flag, inline_value) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => (flag.to_string(), Some(value.to_string())),
+            _ => (arg.clone(), None),
+        };
+
+        match flag.as_str() {
+            "-h" | "--help" => return Ok(CliCommand::Print(USAGE.to_string())),
+            "-V" | "--version" => {
+                return Ok(CliCommand::Print(format!("spark {}", env!("CARGO_PKG_VERSION"))));
+            }
+            "--context" => {
+                let value = inline_value
+                    .or_else(|| args.next())
+                    .filter(|value| !value.is_empty())
+                    .ok_or_else(|| "--context requires a context name".to_string())?;
+                options.docker_context = Some(value);
+            }
+            "--ecosystem" => {
+                let value = inline_value
+                    .or_else(|| args.next())
+                    .filter(|value| !value.is_empty())
+                    .ok_or_else(|| "--ecosystem requires a file path".to_string())?;
+                options.ecosystem_files.push(PathBuf::from(value));
+            }
+            "--theme" => {
+                let value = inline_value
+                    .or_else(|| args.next())
+                    .filter(|value| !value.is_empty())
+                    .ok_or_else(|| "--theme requires a theme name".to_string())?;
+                options.theme = Some(value);
+            }
+            _ => return Err(format!("unknown argument '{arg}'\n\n{USAGE}")),
+        }
+    }
+
+    Ok(CliCommand::Run(options))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<CliCommand, String> {
+        parse_from(args.iter().map(|arg| arg.to_string()))
+    }
+
+    fn options(args: &[&str]) -> CliOptions {
+        match parse(args) {
+            Ok(CliCommand::Run(options)) => options,
+            Ok(CliCommand::Print(text)) => panic!("expected options, got {text:?}"),
+            Err(err) => panic!("expected options, got error {err:?}"),
+        }
+    }
+
+    #[test]
+    fn test_parse_option_values() {
+        let parsed = options(&[
+            "--context",
+            "staging",
+            "--ecosystem=a.config.js",
+            "--ecosystem",
+            "b.json",
+        ]);
+        assert_eq!(parsed.docker_context.as_deref(), Some("staging"));
+        assert_eq!(
+            parsed.ecosystem_files,
+            vec![PathBuf::from("a.config.js"), PathBuf::from("b.json")]
+        );
+        assert_eq!(parsed.theme, None);
+
+        let parsed = options(&["--theme=high-contrast"]);
+        assert_eq!(parsed.theme.as_deref(), Some("high-contrast"));
+        assert!(options(&[]).docker_context.is_none());
+    }
+
+    #[test]
+    fn test_parse_help_and_version() {
+        assert!(matches!(
+            parse(&["--help"]),
+            Ok(CliCommand::Print(text)) if text.starts_with("Usage:")
+        ));
+        assert!(matches!(parse(&["--context", "x", "-h"]), Ok(CliCommand::Print(_))));
+        assert!(matches!(
+            parse(&["-V"]),
+            Ok(CliCommand::Print(text)) if text.starts_with("spark ")
+        ));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse(&["--context"]).is_err());
+        assert!(parse(&["--context="]).is_err());
+        assert!(parse(&["--theme", ""]).is_err());
+        assert!(parse(&["--verbose"]).is_err());
+        // Only long flags take inline values
+        assert!(parse(&["-h=1"]).is_err());
+    }
+}
@@ -0,0 +1,74 @@
+//! Settings persisted between runs in `$XDG_CONFIG_HOME/spark/config.json`
+//! (`~/.config/spark/config.json` by default). Each feature owns one
+//! top-level section of the file and leaves the others untouched.
+
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use serde_json::{Map, Value};
+
+pub fn config_path() -> Option<PathBuf> {
+    let base = std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(base.join("spark").join("config.json"))
+}
+
+/// The named section, or None when the file or section is missing or unreadable.
+pub fn read_section(name: &str) -> Option<Value> {
+    let text = fs::read_to_string(config_path()?).ok()?;
+    let mut root: Value = serde_json::from_str(&text).ok()?;
+    root.get_mut(name).map(Value::take)
+}
+
+/// Replaces the named section, keeping the rest of the file. Refuses to write
+/// when the existing file is not a JSON object, so a typo in a hand-edited
+/// file does not wipe the other sections.
+pub fn write_section(name: &str, value: Value) -> io::Result<()> {
+    let path = config_path()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no HOME or XDG_CONFIG_HOME set"))?;
+    let mut root = match fs::read_to_string(&path) {
+        Ok(text) => parse_root(&text).map_err(|err| {
+            io::Error::new(io::ErrorKind::InvalidData, format!("{}: {err}", path.display()))
+        })?,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Map::new(),
+        Err(err) => return Err(err),
+    };
+    root.insert(name.to_string(), value);
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let text = serde_json::to_string_pretty(&Value::Object(root))
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+    fs::write(path, text + "\n")
+}
+
+/// Top-level object of the config file; an empty file counts as empty.
+fn parse_root(text: &str) -> Result<Map<String, Value>, String> {
+    if text.trim().is_empty() {
+        return Ok(Map::new());
+    }
+    match serde_json::from_str(text) {
+        Ok(Value::Object(map)) => Ok(map),
+        Ok(_) => Err("not a JSON object, fix or remove it".to_string()),
+        Err(err) => Err(format!("invalid JSON ({err}), fix or remove it")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_root() {
+        let root = parse_root(r#"{"theme": "light", "columns": {}}"#).unwrap();
+        assert_eq!(root.get("theme"), Some(&Value::from("light")));
+        assert!(parse_root("  \n").unwrap().is_empty());
+        // A hand-edited file with a typo must not be replaced by an empty one
+        assert!(parse_root(r#"{"theme": "light",}"#).is_err());
+        assert!(parse_root("[]").is_err());
+    }
+}
@@ -1,9 +1,12 @@
 mod app;
+mod cli;
+mod config;
 mod system;
 mod ui;
 mod util;
 
 use std::io;
+use std::process::ExitCode;
 
 use crossterm::cursor::{Hide, Show};
 use crossterm::event::{DisableMouseCapture, EnableMouseCapture};
@@ -11,7 +14,26 @@ use crossterm::style::ResetColor;
 use crossterm::terminal::{self, EnterAlternateScreen, LeaveAlternateScreen};
 use crossterm::execute;
 
-fn main() -> io::Result<()> {
+use crate::cli::{CliCommand, CliOptions};
+use crate::system::{docker, node};
+
+fn main() -> io::Result<ExitCode> {
+    let options = match cli::parse_args() {
+        Ok(CliCommand::Run(options)) => options,
+        Ok(CliCommand::Print(text)) => {
+            println!("{text}");
+            return Ok(ExitCode::SUCCESS);
+        }
+        Err(err) => {
+            eprintln!("Error: {err}");
+            return Ok(ExitCode::from(2));
+        }
+    };
+    if let Err(err) = apply_options(&options) {
+        eprintln!("Error: {err}");
+        return Ok(ExitCode::from(2));
+    }
+
     let mut stdout = io::stdout();
     terminal::enable_raw_mode()?;
     execute!(stdout, EnterAlternateScreen, Hide, EnableMouseCapture)?;
@@ -25,5 +47,27 @@ fn main() -> io::Result<()> {
         eprintln!("Error: {err}");
     }
 
-    Ok(())
+    Ok(ExitCode::SUCCESS)
+}
+
+fn apply_options(options: &CliOptions) -> Result<(), String> {
+    if let Some(name) = &options.docker_context {
+        // Only reject names when the context list could be read; without docker
+        // the Docker view reports the failure itself.
+        if let Ok(contexts) = docker::list_docker_contexts() {
+            if !contexts.iter().any(|ctx| &ctx.name == name) {
+                let available: Vec<&str> = contexts.iter().map(|ctx| ctx.name.as_str()).collect();
+                return Err(format!(
+                    "unknown docker context '{name}' (available: {})",
+                    available.join(", ")
+                ));
+            }
+        }
+        docker::set_docker_context(Some(name.clone()));
+    }
+    if let Some(missing) = options.ecosystem_files.iter().find(|file| !file.is_file()) {
+        return Err(format!("ecosystem file '{}' not found", missing.display()));
+    }
+    node::set_ecosystem_files(options.ecosystem_files.clone());
+    ui::init_theme(options.theme.as_deref())
 }
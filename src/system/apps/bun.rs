@@ -0,0 +1,57 @@
+use std::path::PathBuf;
+
+use sysinfo::Process;
+
+use super::{
+    command_version, dir_name, display_path, exe_name, file_stem, first_version, json_name,
+    positional_args, project_root_for, DetectedApp, Runtime, RuntimeDetector,
+};
+
+const MARKERS: &[&str] = &["package.json", "bunfig.toml"];
+
+/// Package manager and tooling subcommands that are not apps
+const TOOLING: &[&str] = &[
+    "install", "i", "add", "remove", "rm", "update", "link", "unlink", "pm", "x", "build",
+    "upgrade", "create", "init", "repl", "outdated", "publish",
+];
+
+pub struct BunDetector;
+
+impl RuntimeDetector for BunDetector {
+    fn detect(&self, process: &Process) -> Option<DetectedApp> {
+        if exe_name(process) != "bun" {
+            return None;
+        }
+        let positional = positional_args(process.cmd(), &["--cwd", "--config", "-c", "--env-file"]);
+        let (name, entry, script) = match positional.as_slice() {
+            [command, ..] if TOOLING.contains(command) => return None,
+            ["run", target, ..] if is_file(target) => (file_stem(target), display_path(target), Some(*target)),
+            // `bun run dev` runs a package.json script
+            ["run", script, ..] => (script.to_string(), format!("run {script}"), None),
+            [target, ..] => (file_stem(target), display_path(target), Some(*target)),
+            [] => return None,
+        };
+
+        let project_dir = project_root_for(process, script.map(PathBuf::from).as_deref(), MARKERS);
+        let project_name = project_dir
+            .as_deref()
+            .and_then(|dir| json_name(&dir.join("package.json")).or_else(|| dir_name(dir)));
+        let version = process
+            .exe()
+            .and_then(|exe| command_version(exe, &["--version"], first_version));
+
+        Some(DetectedApp {
+            runtime: Runtime::Bun,
+            name,
+            entry,
+            project_dir,
+            project_name,
+            version,
+            global_tool: false,
+        })
+    }
+}
+
+fn is_file(target: &str) -> bool {
+    target.contains('/') || target.contains('.')
+}
@@ -0,0 +1,52 @@
+use std::path::PathBuf;
+
+use sysinfo::Process;
+
+use super::{
+    command_version, dir_name, display_path, exe_name, file_stem, first_version, json_name,
+    positional_args, project_root_for, DetectedApp, Runtime, RuntimeDetector,
+};
+
+const MARKERS: &[&str] = &["deno.json", "deno.jsonc", "package.json"];
+
+/// Options of `deno run` and `deno serve` whose value is the next argument
+const FLAGS_WITH_VALUE: &[&str] = &["-c", "--config", "--import-map", "--env-file", "--port", "--host"];
+
+pub struct DenoDetector;
+
+impl RuntimeDetector for DenoDetector {
+    fn detect(&self, process: &Process) -> Option<DetectedApp> {
+        if exe_name(process) != "deno" {
+            return None;
+        }
+        let positional = positional_args(process.cmd(), FLAGS_WITH_VALUE);
+        let (name, entry, script) = match positional.as_slice() {
+            ["run" | "serve", script, ..] => (file_stem(script), display_path(script), Some(*script)),
+            ["task", task, ..] => (task.to_string(), format!("task {task}"), None),
+            // lsp, fmt, lint, repl, test and friends are tooling
+            [command, ..] if !command.contains('.') && !command.contains('/') => return None,
+            [script, ..] => (file_stem(script), display_path(script), Some(*script)),
+            [] => return None,
+        };
+
+        let project_dir = project_root_for(process, script.map(PathBuf::from).as_deref(), MARKERS);
+        let project_name = project_dir.as_deref().and_then(|dir| {
+            json_name(&dir.join("deno.json"))
+                .or_else(|| json_name(&dir.join("package.json")))
+                .or_else(|| dir_name(dir))
+        });
+        let version = process
+            .exe()
+            .and_then(|exe| command_version(exe, &["--version"], first_version));
+
+        Some(DetectedApp {
+            runtime: Runtime::Deno,
+            name,
+            entry,
+            project_dir,
+            project_name,
+            version,
+            global_tool: false,
+        })
+    }
+}
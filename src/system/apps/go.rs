@@ -0,0 +1,180 @@
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+use sysinfo::Process;
+
+use super::{
+    cached, dir_name, display_path, exe_name, find_project_root, read_process_cwd, DetectedApp,
+    Runtime, RuntimeDetector,
+};
+
+const MARKERS: &[&str] = &["go.mod"];
+
+/// Go toolchain and editor binaries that are not apps
+const TOOLING: &[&str] = &["go", "gopls", "dlv", "air", "golangci-lint", "staticcheck", "gofmt"];
+
+/// Start of the `.go.buildinfo` section written by the Go linker
+const BUILDINFO_MAGIC: &[u8] = b"\xff Go buildinf:";
+/// How far into the binary to look for the build info
+const BUILDINFO_SCAN_LIMIT: u64 = 64 * 1024 * 1024;
+
+pub struct GoDetector;
+
+impl RuntimeDetector for GoDetector {
+    fn detect(&self, process: &Process) -> Option<DetectedApp> {
+        let exe = process.exe()?;
+        if TOOLING.contains(&exe_name(process).as_str()) {
+            return None;
+        }
+        let project_dir = module_root(exe, read_process_cwd(process).as_deref())?;
+        let version = go_build_version(exe)?;
+
+        let entry = process
+            .cmd()
+            .first()
+            .map(|arg0| display_path(arg0))
+            .unwrap_or_else(|| display_path(&exe.to_string_lossy()));
+        Some(DetectedApp {
+            runtime: Runtime::Go,
+            name: process.name().to_string(),
+            entry,
+            project_name: module_name(&project_dir).or_else(|| dir_name(&project_dir)),
+            project_dir: Some(project_dir),
+            version: Some(version).filter(|version| !version.is_empty()),
+            global_tool: false,
+        })
+    }
+}
+
+/// Module a binary was built from. Go CLIs such as gh or kubectl are often
+/// started inside some other module, so the working directory only counts for
+/// `go run`, which builds into a `go-build` temp dir; otherwise the binary itself
+/// must lie in the module (e.g. `go build` output or its `bin/`).
+fn module_root(exe: &Path, cwd: Option<&Path>) -> Option<PathBuf> {
+    if let Some(root) = exe.parent().and_then(|dir| find_project_root(dir, MARKERS)) {
+        return Some(root);
+    }
+    let in_build_cache = exe
+        .components()
+        .any(|part| part.as_os_str().to_string_lossy().starts_with("go-build"));
+    if in_build_cache {
+        return find_project_root(cwd?, MARKERS);
+    }
+    None
+}
+
+/// Last segment of the `module` path in go.mod.
+fn module_name(dir: &Path) -> Option<String> {
+    let contents = fs::read_to_string(dir.join("go.mod")).ok()?;
+    let module = contents
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("module "))?
+        .trim()
+        .trim_matches('"');
+    module.rsplit('/').next().map(str::to_string)
+}
+
+/// Go version the binary was built with, or an empty string for builds older
+/// than Go 1.18. None if the file is not a Go binary. Cached per path.
+fn go_build_version(exe: &Path) -> Option<String> {
+    static CACHE: OnceLock<Mutex<HashMap<PathBuf, Option<String>>>> = OnceLock::new();
+    cached(&CACHE, exe.to_path_buf(), || {
+        let mut file = File::open(exe).ok()?;
+        let offset = find_magic(&mut file)?;
+        let mut header = [0u8; 64];
+        file.seek(SeekFrom::Start(offset)).ok()?;
+        let read = file.read(&mut header).ok()?;
+        Some(parse_buildinfo_version(&header[..read]).unwrap_or_default())
+    })
+}
+
+/// Offset of [`BUILDINFO_MAGIC`], scanning in chunks that overlap by the magic's length.
+fn find_magic(file: &mut File) -> Option<u64> {
+    let mut chunk = vec![0u8; 1024 * 1024];
+    let mut base = 0u64;
+    let mut carry = 0usize;
+    while base < BUILDINFO_SCAN_LIMIT {
+        let read = file.read(&mut chunk[carry..]).ok()?;
+        if read == 0 {
+            return None;
+        }
+        let filled = carry + read;
+        if let Some(pos) = chunk[..filled]
+            .windows(BUILDINFO_MAGIC.len())
+            .position(|window| window == BUILDINFO_MAGIC)
+        {
+            return Some(base + pos as u64);
+        }
+        carry = (BUILDINFO_MAGIC.len() - 1).min(filled);
+        chunk.copy_within(filled - carry..filled, 0);
+        base += (filled - carry) as u64;
+    }
+    None
+}
+
+/// Reads the inline version string that follows the 32-byte header when flag
+/// bit 2 is set (Go 1.18+): a uvarint length, then e.g. "go1.22.1".
+fn parse_buildinfo_version(header: &[u8]) -> Option<String> {
+    let flags = *header.get(BUILDINFO_MAGIC.len() + 1)?;
+    if flags & 0x2 == 0 {
+        return None;
+    }
+    let data = header.get(32..)?;
+    let mut len = 0usize;
+    let mut shift = 0;
+    let mut pos = 0;
+    loop {
+        let byte = *data.get(pos)?;
+        pos += 1;
+        len |= usize::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            break;
+        }
+        shift += 7;
+        if shift > 28 {
+            return None;
+        }
+    }
+    let version = std::str::from_utf8(data.get(pos..pos + len)?).ok()?;
+    Some(version.trim_start_matches("go").to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::TempDir;
+
+    #[test]
+    fn test_parse_buildinfo_version() {
+        let mut header = BUILDINFO_MAGIC.to_vec();
+        header.extend([8u8, 0x2]);
+        header.resize(32, 0);
+        header.push(8);
+        header.extend(b"go1.22.1");
+        assert_eq!(parse_buildinfo_version(&header).as_deref(), Some("1.22.1"));
+
+        // Pre-1.18 binaries store pointers instead of inline strings
+        header[BUILDINFO_MAGIC.len() + 1] = 0;
+        assert_eq!(parse_buildinfo_version(&header), None);
+    }
+
+    #[test]
+    fn test_module_root_requires_binary_in_module_or_build_cache() {
+        let module = TempDir::new("go");
+        fs::create_dir_all(module.join("bin")).unwrap();
+        fs::write(module.join("go.mod"), "module example.com/acme/api\n").unwrap();
+
+        assert_eq!(module_root(&module.join("bin").join("api"), None), Some(module.to_path_buf()));
+        let go_run = Path::new("/tmp/go-build1234567/b001/exe/api");
+        assert_eq!(module_root(go_run, Some(&module)), Some(module.to_path_buf()));
+        assert_eq!(module_root(go_run, None), None);
+
+        // A system Go CLI started from inside the module is not the module's app
+        assert_eq!(module_root(Path::new("/usr/bin/gh"), Some(&module)), None);
+        let kubectl = Path::new("/usr/local/bin/kubectl");
+        assert_eq!(module_root(kubectl, Some(&module.join("bin"))), None);
+    }
+}
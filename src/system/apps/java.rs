@@ -0,0 +1,191 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use sysinfo::Process;
+
+use super::{
+    command_version, dir_name, display_path, exe_name, file_stem, first_version, project_root_for,
+    toml_string, DetectedApp, Runtime, RuntimeDetector,
+};
+
+const MARKERS: &[&str] = &[
+    "pom.xml",
+    "build.gradle",
+    "build.gradle.kts",
+    "settings.gradle",
+    "settings.gradle.kts",
+];
+
+/// JVM options whose value is the next argument
+const FLAGS_WITH_VALUE: &[&str] = &[
+    "-cp",
+    "-classpath",
+    "--class-path",
+    "-p",
+    "--module-path",
+    "--add-opens",
+    "--add-exports",
+    "--add-modules",
+    "--add-reads",
+    "--upgrade-module-path",
+];
+
+/// Build tools, daemons and IDE helpers that also run on the JVM
+const TOOLING: &[&str] = &[
+    "org.gradle.",
+    "GradleDaemon",
+    "GradleWrapperMain",
+    "KotlinCompileDaemon",
+    "org.jetbrains.",
+    "com.intellij.",
+    "org.eclipse.equinox.launcher",
+    "org.apache.maven.",
+    "org.codehaus.plexus.classworlds",
+    "sonarlint",
+];
+
+pub struct JavaDetector;
+
+impl RuntimeDetector for JavaDetector {
+    fn detect(&self, process: &Process) -> Option<DetectedApp> {
+        if exe_name(process) != "java" {
+            return None;
+        }
+        let entry = parse_entry(process.cmd())?;
+        if TOOLING.iter().any(|tool| entry.display.contains(tool)) {
+            return None;
+        }
+
+        let project_dir = project_root_for(process, entry.jar.as_deref(), MARKERS);
+        // A bare JAR deployed outside any source tree is still an app
+        if project_dir.is_none() && entry.jar.is_none() {
+            return None;
+        }
+        let project_name = project_dir.as_deref().and_then(project_name);
+        let version = process
+            .exe()
+            .and_then(|exe| command_version(exe, &["-version"], first_version));
+
+        Some(DetectedApp {
+            runtime: Runtime::Java,
+            name: entry.name,
+            entry: entry.display,
+            project_dir,
+            project_name,
+            version,
+            global_tool: false,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct JavaEntry {
+    name: String,
+    display: String,
+    jar: Option<PathBuf>,
+}
+
+/// Finds the `-jar` archive, `-m` module or main class of a `java` command line.
+fn parse_entry(cmd: &[String]) -> Option<JavaEntry> {
+    let mut args = cmd.iter().skip(1).map(String::as_str);
+    while let Some(arg) = args.next() {
+        match arg {
+            "-jar" => {
+                let jar = args.next()?;
+                return Some(JavaEntry {
+                    name: file_stem(jar),
+                    display: display_path(jar),
+                    jar: Some(PathBuf::from(jar)),
+                });
+            }
+            "-m" | "--module" => {
+                let module = args.next()?;
+                return Some(class_entry(module));
+            }
+            flag if FLAGS_WITH_VALUE.contains(&flag) => {
+                args.next();
+            }
+            flag if flag.starts_with('-') => {}
+            main_class => return Some(class_entry(main_class)),
+        }
+    }
+    None
+}
+
+/// Entry for a main class, named after its simple class name.
+fn class_entry(main_class: &str) -> JavaEntry {
+    let class = main_class.rsplit('/').next().unwrap_or(main_class);
+    JavaEntry {
+        name: class.rsplit('.').next().unwrap_or(class).to_string(),
+        display: main_class.to_string(),
+        jar: None,
+    }
+}
+
+/// Maven artifactId or Gradle root project name, else the directory name.
+fn project_name(dir: &Path) -> Option<String> {
+    let from_pom = || {
+        let pom = fs::read_to_string(dir.join("pom.xml")).ok()?;
+        pom_artifact_id(&pom)
+    };
+    let from_gradle = || {
+        ["settings.gradle", "settings.gradle.kts"].iter().find_map(|file| {
+            let settings = fs::read_to_string(dir.join(file)).ok()?;
+            toml_string(&settings, None, "rootProject.name")
+        })
+    };
+    from_pom().or_else(from_gradle).or_else(|| dir_name(dir))
+}
+
+/// The project's own `<artifactId>`, skipping the one inside `<parent>`.
+fn pom_artifact_id(pom: &str) -> Option<String> {
+    let mut rest = pom;
+    if let (Some(start), Some(end)) = (rest.find("<parent>"), rest.find("</parent>")) {
+        if start < end {
+            rest = &rest[end..];
+        }
+    }
+    let start = rest.find("<artifactId>")? + "<artifactId>".len();
+    let end = rest[start..].find("</artifactId>")? + start;
+    Some(rest[start..end].trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::system::apps::cmd;
+
+    #[test]
+    fn test_parse_entry() {
+        let entry = parse_entry(&cmd(&[
+            "java",
+            "-Xmx512m",
+            "-Dspring.profiles.active=dev",
+            "-jar",
+            "target/orders-0.4.1.jar",
+        ]))
+        .unwrap();
+        assert_eq!(entry.name, "orders-0.4.1");
+        assert_eq!(entry.jar, Some(PathBuf::from("target/orders-0.4.1.jar")));
+
+        let entry = parse_entry(&cmd(&[
+            "/usr/lib/jvm/java-21-openjdk/bin/java",
+            "-cp",
+            "build/classes:lib/*",
+            "--add-opens",
+            "java.base/java.lang=ALL-UNNAMED",
+            "com.acme.orders.OrdersApplication",
+            "--server.port=8081",
+        ]))
+        .unwrap();
+        assert_eq!(entry.name, "OrdersApplication");
+        assert_eq!(entry.jar, None);
+    }
+
+    #[test]
+    fn test_pom_artifact_id() {
+        let pom = "<project><parent><artifactId>spring-boot-starter-parent</artifactId></parent>\
+                   <groupId>com.acme</groupId><artifactId>orders</artifactId></project>";
+        assert_eq!(pom_artifact_id(pom).as_deref(), Some("orders"));
+    }
+}
@@ -0,0 +1,410 @@
+//! Runtime detection for the Apps view. Each language implements
+//! [`RuntimeDetector`]; the first detector that recognises a process wins.
+
+mod bun;
+mod deno;
+mod go;
+mod java;
+mod python;
+mod ruby;
+
+use std::collections::HashMap;
+use std::fs;
+use std::hash::Hash;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::{Mutex, OnceLock};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use sysinfo::Process;
+
+use crate::system::node::NodeDetector;
+
+pub use bun::BunDetector;
+pub use deno::DenoDetector;
+pub use go::GoDetector;
+pub use java::JavaDetector;
+pub use python::PythonDetector;
+pub use ruby::RubyDetector;
+
+/// Language runtime an app process runs on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Runtime {
+    Node,
+    Bun,
+    Deno,
+    Python,
+    Ruby,
+    Java,
+    Go,
+}
+
+impl Runtime {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Runtime::Node => "Node",
+            Runtime::Bun => "Bun",
+            Runtime::Deno => "Deno",
+            Runtime::Python => "Python",
+            Runtime::Ruby => "Ruby",
+            Runtime::Java => "Java",
+            Runtime::Go => "Go",
+        }
+    }
+}
+
+/// What a detector found out about one process.
+#[derive(Clone, Debug)]
+pub struct DetectedApp {
+    pub runtime: Runtime,
+    pub name: String,
+    /// Entry point: script path, module, JAR or binary
+    pub entry: String,
+    pub project_dir: Option<PathBuf>,
+    pub project_name: Option<String>,
+    pub version: Option<String>,
+    pub global_tool: bool,
+}
+
+pub trait RuntimeDetector: Sync {
+    /// Returns the app if `process` runs on this runtime and looks like a
+    /// project process rather than an editor, daemon or system service.
+    fn detect(&self, process: &Process) -> Option<DetectedApp>;
+}
+
+/// All detectors, in the order they are tried.
+pub fn detectors() -> &'static [&'static dyn RuntimeDetector] {
+    &[
+        &NodeDetector,
+        &BunDetector,
+        &DenoDetector,
+        &PythonDetector,
+        &RubyDetector,
+        &JavaDetector,
+        &GoDetector,
+    ]
+}
+
+pub fn detect_app(process: &Process) -> Option<DetectedApp> {
+    detectors().iter().find_map(|detector| detector.detect(process))
+}
+
+/// Lowercased executable file name, falling back to the process name.
+pub(crate) fn exe_name(process: &Process) -> String {
+    process
+        .exe()
+        .and_then(Path::file_name)
+        .map(|name| name.to_string_lossy().to_lowercase())
+        .unwrap_or_else(|| process.name().to_lowercase())
+}
+
+pub(crate) fn read_process_cwd(process: &Process) -> Option<PathBuf> {
+    fs::read_link(format!("/proc/{}/cwd", process.pid().as_u32())).ok()
+}
+
+/// Nearest directory at or above `start` that contains one of `markers`,
+/// remembered per directory so refreshes do not walk the tree again.
+pub(crate) fn find_project_root(start: &Path, markers: &'static [&'static str]) -> Option<PathBuf> {
+    type Key = (PathBuf, &'static [&'static str]);
+    static CACHE: OnceLock<Mutex<HashMap<Key, Option<PathBuf>>>> = OnceLock::new();
+    cached(&CACHE, (start.to_path_buf(), markers), || {
+        start
+            .ancestors()
+            .take(16)
+            .find(|dir| markers.iter().any(|marker| dir.join(marker).exists()))
+            .filter(|dir| !is_system_dir(dir))
+            .map(Path::to_path_buf)
+    })
+}
+
+/// Project root from the entry point's directory, then from the working directory.
+pub(crate) fn project_root_for(
+    process: &Process,
+    entry: Option<&Path>,
+    markers: &'static [&'static str],
+) -> Option<PathBuf> {
+    let cwd = read_process_cwd(process);
+    let entry_dir = entry.and_then(|entry| {
+        let path = if entry.is_absolute() {
+            entry.to_path_buf()
+        } else {
+            cwd.as_ref()?.join(entry)
+        };
+        Some(if path.is_dir() { path } else { path.parent()?.to_path_buf() })
+    });
+    entry_dir
+        .into_iter()
+        .chain(cwd)
+        .find_map(|dir| find_project_root(&dir, markers))
+}
+
+fn is_system_dir(dir: &Path) -> bool {
+    dir == Path::new("/")
+        || ["/usr", "/lib", "/opt/homebrew", "/snap", "/proc"]
+            .iter()
+            .any(|prefix| dir.starts_with(prefix))
+}
+
+/// Last path component, used when a project declares no name.
+pub(crate) fn dir_name(dir: &Path) -> Option<String> {
+    dir.file_name().map(|name| name.to_string_lossy().into_owned())
+}
+
+/// Shortens a path under `$HOME` to `~/...` for display.
+pub(crate) fn display_path(path: &str) -> String {
+    match std::env::var("HOME") {
+        Ok(home) if !home.is_empty() && path.starts_with(&home) => path.replacen(&home, "~", 1),
+        _ => path.to_string(),
+    }
+}
+
+/// Arguments after the executable that are neither flags nor values of the
+/// flags listed in `flags_with_value`.
+pub(crate) fn positional_args<'a>(cmd: &'a [String], flags_with_value: &[&str]) -> Vec<&'a str> {
+    let mut positional = Vec::new();
+    let mut skip_value = false;
+    for arg in cmd.iter().skip(1) {
+        if skip_value {
+            skip_value = false;
+        } else if arg.starts_with('-') {
+            skip_value = flags_with_value.contains(&arg.as_str());
+        } else {
+            positional.push(arg.as_str());
+        }
+    }
+    positional
+}
+
+/// File name without extension, e.g. "server" for "dist/server.js".
+pub(crate) fn file_stem(path: &str) -> String {
+    Path::new(path)
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.to_string())
+}
+
+/// The "name" field of a JSON manifest such as package.json or deno.json.
+pub(crate) fn json_name(path: &Path) -> Option<String> {
+    let contents = fs::read_to_string(path).ok()?;
+    let value: serde_json::Value = serde_json::from_str(&contents).ok()?;
+    value.get("name")?.as_str().map(str::to_string)
+}
+
+/// How long a version probe may run before it is killed.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Install prefixes whose root-owned binaries may be run to ask for a version.
+const TRUSTED_PREFIXES: &[&str] = &["/usr/", "/bin/", "/opt/", "/nix/store/"];
+
+/// State of the version probe for one executable.
+#[derive(Clone)]
+enum Probe {
+    Running,
+    Done(Option<String>),
+}
+
+/// Runtime version from the install path when it carries one, e.g.
+/// `~/.rbenv/versions/3.3.0/bin/ruby`, else from `<exe> <args>` with `parse`
+/// applied to the combined output; `java -version` prints to stderr. The probe
+/// runs once per executable on a background thread and the version is None
+/// until it finishes. Only binaries owned by the current user, or by root
+/// under a system prefix, are run.
+pub(crate) fn command_version(
+    exe: &Path,
+    args: &'static [&'static str],
+    parse: fn(&str) -> Option<String>,
+) -> Option<String> {
+    static CACHE: OnceLock<Mutex<HashMap<PathBuf, Probe>>> = OnceLock::new();
+
+    if let Some(version) = version_from_path(exe) {
+        return Some(version);
+    }
+    let cache = CACHE.get_or_init(|| Mutex::new(HashMap::new()));
+    let mut guard = cache.lock().unwrap_or_else(|err| err.into_inner());
+    if let Some(probe) = guard.get(exe) {
+        return match probe {
+            Probe::Running => None,
+            Probe::Done(version) => version.clone(),
+        };
+    }
+    if !may_probe(exe) {
+        guard.insert(exe.to_path_buf(), Probe::Done(None));
+        return None;
+    }
+    guard.insert(exe.to_path_buf(), Probe::Running);
+    let exe = exe.to_path_buf();
+    thread::spawn(move || {
+        let version = run_with_timeout(&exe, args, PROBE_TIMEOUT).and_then(|text| parse(&text));
+        let mut guard = cache.lock().unwrap_or_else(|err| err.into_inner());
+        guard.insert(exe, Probe::Done(version));
+    });
+    None
+}
+
+/// Version named by a directory the executable is installed in, such as
+/// `3.3.0`, `ruby-3.3.0` or `jdk-17.0.9`.
+fn version_from_path(exe: &Path) -> Option<String> {
+    exe.parent()?.ancestors().take(4).find_map(|dir| {
+        let name = dir.file_name()?.to_str()?;
+        let rest = name.trim_start_matches(|ch: char| ch.is_ascii_alphabetic() || ch == '-');
+        let version = first_version(rest)?;
+        rest.starts_with(&version).then_some(version)
+    })
+}
+
+fn may_probe(exe: &Path) -> bool {
+    owned_by_current_user(exe)
+        || (owned_by_root(exe) && TRUSTED_PREFIXES.iter().any(|prefix| exe.starts_with(prefix)))
+}
+
+#[cfg(unix)]
+pub(crate) fn owned_by_current_user(path: &Path) -> bool {
+    // SAFETY: geteuid has no preconditions and cannot fail
+    let uid = unsafe { libc::geteuid() };
+    owner(path) == Some(uid)
+}
+
+#[cfg(not(unix))]
+pub(crate) fn owned_by_current_user(_path: &Path) -> bool {
+    false
+}
+
+#[cfg(unix)]
+fn owned_by_root(path: &Path) -> bool {
+    owner(path) == Some(0)
+}
+
+#[cfg(not(unix))]
+fn owned_by_root(_path: &Path) -> bool {
+    false
+}
+
+#[cfg(unix)]
+fn owner(path: &Path) -> Option<u32> {
+    use std::os::unix::fs::MetadataExt;
+
+    fs::metadata(path).ok().map(|meta| meta.uid())
+}
+
+/// Combined stdout and stderr of `<exe> <args>`, or None if it does not exit
+/// within `timeout`.
+fn run_with_timeout(exe: &Path, args: &[&str], timeout: Duration) -> Option<String> {
+    let mut child = Command::new(exe)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .ok()?;
+    let started = Instant::now();
+    while child.try_wait().ok()?.is_none() {
+        if started.elapsed() >= timeout {
+            let _ = child.kill();
+            let _ = child.wait();
+            return None;
+        }
+        thread::sleep(Duration::from_millis(20));
+    }
+    let output = child.wait_with_output().ok()?;
+    let mut text = String::from_utf8_lossy(&output.stdout).into_owned();
+    text.push_str(&String::from_utf8_lossy(&output.stderr));
+    Some(text)
+}
+
+/// Memoises `compute` per key in a process-wide cache.
+pub(crate) fn cached<K: Eq + Hash, V: Clone>(
+    cache: &'static OnceLock<Mutex<HashMap<K, V>>>,
+    key: K,
+    compute: impl FnOnce() -> V,
+) -> V {
+    let cache = cache.get_or_init(|| Mutex::new(HashMap::new()));
+    if let Some(value) = cache.lock().ok().and_then(|guard| guard.get(&key).cloned()) {
+        return value;
+    }
+    let value = compute();
+    if let Ok(mut guard) = cache.lock() {
+        guard.insert(key, value.clone());
+    }
+    value
+}
+
+/// First dotted version number in `text`, e.g. "3.12.1" from "Python 3.12.1".
+pub(crate) fn first_version(text: &str) -> Option<String> {
+    text.split(|ch: char| !(ch.is_ascii_digit() || ch == '.'))
+        .map(|part| part.trim_matches('.'))
+        .find(|part| part.contains('.') && part.starts_with(|ch: char| ch.is_ascii_digit()))
+        .map(str::to_string)
+}
+
+/// Owned command line from string literals, for detector tests.
+#[cfg(test)]
+pub(crate) fn cmd(args: &[&str]) -> Vec<String> {
+    args.iter().map(|arg| arg.to_string()).collect()
+}
+
+/// Value of the first `key = "value"` line in a TOML-like file, optionally
+/// limited to one `[section]`.
+pub(crate) fn toml_string(contents: &str, section: Option<&str>, key: &str) -> Option<String> {
+    let mut current: Option<&str> = None;
+    for line in contents.lines() {
+        let line = line.trim();
+        if let Some(name) = line.strip_prefix('[').and_then(|rest| rest.strip_suffix(']')) {
+            current = Some(name.trim());
+            continue;
+        }
+        if section.is_some() && current != section {
+            continue;
+        }
+        let Some((name, value)) = line.split_once('=') else {
+            continue;
+        };
+        if name.trim() == key {
+            let value = value.trim().trim_matches(|ch| ch == '"' || ch == '\'');
+            return Some(value.to_string()).filter(|value| !value.is_empty());
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_version() {
+        assert_eq!(first_version("Python 3.12.1\n").as_deref(), Some("3.12.1"));
+        assert_eq!(
+            first_version("openjdk version \"17.0.9\" 2023-10-17").as_deref(),
+            Some("17.0.9")
+        );
+        assert_eq!(first_version("deno 1.40.2 (release, x86_64)").as_deref(), Some("1.40.2"));
+        assert_eq!(first_version("no version here"), None);
+    }
+
+    #[test]
+    fn test_toml_string() {
+        let contents = "[build-system]\nrequires = [\"hatchling\"]\nname = \"wrong\"\n\n[project]\nname = \"billing-api\"\nversion = \"0.3.0\"\n";
+        assert_eq!(toml_string(contents, Some("project"), "name").as_deref(), Some("billing-api"));
+        assert_eq!(toml_string(contents, Some("tool.poetry"), "name"), None);
+        assert_eq!(toml_string("module = 'x'", None, "module").as_deref(), Some("x"));
+    }
+
+    #[test]
+    fn test_version_from_path() {
+        let version = |exe: &str| version_from_path(Path::new(exe));
+        assert_eq!(version("/home/dev/.rbenv/versions/3.3.0/bin/ruby").as_deref(), Some("3.3.0"));
+        assert_eq!(version("/home/dev/.rvm/rubies/ruby-3.2.2/bin/ruby").as_deref(), Some("3.2.2"));
+        assert_eq!(version("/usr/lib/jvm/jdk-17.0.9/bin/java").as_deref(), Some("17.0.9"));
+        assert_eq!(version("/usr/lib/jvm/java-17-openjdk/bin/java"), None);
+        assert_eq!(version("/usr/bin/python3.12"), None);
+    }
+
+    #[test]
+    fn test_run_with_timeout() {
+        let sh = Path::new("/bin/sh");
+        let text = run_with_timeout(sh, &["-c", "echo out; echo err >&2"], PROBE_TIMEOUT);
+        assert_eq!(text.as_deref(), Some("out\nerr\n"));
+        assert_eq!(run_with_timeout(sh, &["-c", "sleep 5"], Duration::from_millis(100)), None);
+    }
+}
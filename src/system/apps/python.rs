@@ -0,0 +1,227 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use sysinfo::Process;
+
+use super::{
+    command_version, dir_name, display_path, exe_name, file_stem, first_version, project_root_for,
+    toml_string, DetectedApp, Runtime, RuntimeDetector,
+};
+
+const MARKERS: &[&str] = &[
+    "pyproject.toml",
+    "setup.py",
+    "setup.cfg",
+    "requirements.txt",
+    "Pipfile",
+    "manage.py",
+];
+
+/// Servers and workers usually started through their console script or `-m`
+const SERVERS: &[&str] = &[
+    "uvicorn",
+    "gunicorn",
+    "hypercorn",
+    "daphne",
+    "granian",
+    "celery",
+    "flask",
+    "fastapi",
+    "streamlit",
+    "dramatiq",
+    "rq",
+    "waitress-serve",
+];
+
+/// Editor and tooling processes that are not apps
+const TOOLING: &[&str] = &[
+    "pylsp",
+    "pyls",
+    "jedi-language-server",
+    "pyright",
+    "debugpy",
+    "ipykernel_launcher",
+    "pip",
+    "poetry",
+    "pipenv",
+    "virtualenv",
+    "pre-commit",
+    "mypy",
+    "black",
+];
+
+pub struct PythonDetector;
+
+impl RuntimeDetector for PythonDetector {
+    fn detect(&self, process: &Process) -> Option<DetectedApp> {
+        let exe = exe_name(process);
+        if !is_python(&exe) {
+            return None;
+        }
+        let cmd = process.cmd();
+        if cmd.iter().any(|arg| arg.contains(".vscode") || arg.contains("jetbrains")) {
+            return None;
+        }
+        let entry = parse_entry(cmd)?;
+        if TOOLING.contains(&entry.name.as_str()) {
+            return None;
+        }
+
+        let project_dir = project_root_for(process, entry.script.as_deref(), MARKERS);
+        // Without a project, only well-known servers count as apps
+        if project_dir.is_none() && !entry.server {
+            return None;
+        }
+        let project_name = project_dir.as_deref().and_then(project_name);
+        let version = python_version(&exe, process.exe());
+
+        Some(DetectedApp {
+            runtime: Runtime::Python,
+            name: entry.name,
+            entry: entry.display,
+            project_dir,
+            project_name,
+            version,
+            global_tool: false,
+        })
+    }
+}
+
+/// `python`, `python3`, `python3.12` or `pypy3`
+fn is_python(exe: &str) -> bool {
+    ["python", "pypy"].iter().any(|prefix| {
+        exe.strip_prefix(prefix)
+            .is_some_and(|rest| rest.chars().all(|ch| ch.is_ascii_digit() || ch == '.'))
+    })
+}
+
+#[derive(Debug, PartialEq)]
+struct PythonEntry {
+    name: String,
+    display: String,
+    script: Option<PathBuf>,
+    server: bool,
+}
+
+/// Finds what the interpreter runs: `-m module`, a console script such as
+/// `.venv/bin/uvicorn`, or a script file.
+fn parse_entry(cmd: &[String]) -> Option<PythonEntry> {
+    let mut args = cmd.iter().skip(1).map(String::as_str);
+    while let Some(arg) = args.next() {
+        match arg {
+            "-m" => {
+                let module = args.next()?;
+                return Some(server_entry(module, app_target(args), None));
+            }
+            // Inline code has no entry point worth showing
+            "-c" => return None,
+            "-W" | "-X" => {
+                args.next();
+            }
+            flag if flag.starts_with('-') => {}
+            script => {
+                let name = file_stem(script);
+                if SERVERS.contains(&name.as_str()) {
+                    return Some(server_entry(&name, app_target(args), Some(PathBuf::from(script))));
+                }
+                return Some(PythonEntry {
+                    name,
+                    display: display_path(script),
+                    script: Some(PathBuf::from(script)),
+                    server: false,
+                });
+            }
+        }
+    }
+    None
+}
+
+/// The `module:attr` app a server is started with, else its first positional argument.
+fn app_target<'a>(args: impl Iterator<Item = &'a str>) -> Option<&'a str> {
+    let positional: Vec<&str> = args.filter(|arg| !arg.starts_with('-')).collect();
+    let is_app_spec = |arg: &&str| {
+        arg.split_once(':').is_some_and(|(module, attr)| {
+            !attr.is_empty()
+                && module.starts_with(|ch: char| ch.is_ascii_alphabetic() || ch == '_')
+                && module.chars().all(|ch| ch.is_ascii_alphanumeric() || ch == '_' || ch == '.')
+        })
+    };
+    positional
+        .iter()
+        .copied()
+        .find(is_app_spec)
+        .or(positional.first().copied())
+}
+
+/// Entry for a module or console script, shown with its app target, e.g. "uvicorn main:app".
+fn server_entry(name: &str, target: Option<&str>, script: Option<PathBuf>) -> PythonEntry {
+    let display = match target {
+        Some(target) => format!("{name} {target}"),
+        None => name.to_string(),
+    };
+    PythonEntry {
+        name: name.to_string(),
+        display,
+        script,
+        server: SERVERS.contains(&name),
+    }
+}
+
+fn project_name(dir: &Path) -> Option<String> {
+    fs::read_to_string(dir.join("pyproject.toml"))
+        .ok()
+        .and_then(|contents| {
+            toml_string(&contents, Some("project"), "name")
+                .or_else(|| toml_string(&contents, Some("tool.poetry"), "name"))
+        })
+        .or_else(|| dir_name(dir))
+}
+
+/// Version from the interpreter name when it carries one, else `python --version`.
+fn python_version(exe_name: &str, exe: Option<&Path>) -> Option<String> {
+    let suffix = exe_name.trim_start_matches(|ch: char| ch.is_ascii_alphabetic());
+    if suffix.contains('.') {
+        return Some(suffix.to_string());
+    }
+    command_version(exe?, &["--version"], first_version)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::system::apps::cmd;
+
+    #[test]
+    fn test_parse_entry() {
+        let entry = parse_entry(&cmd(&["python3", "-u", "-m", "uvicorn", "main:app", "--reload"])).unwrap();
+        assert_eq!(entry.display, "uvicorn main:app");
+        assert!(entry.server);
+
+        let entry = parse_entry(&cmd(&[
+            "/srv/billing/.venv/bin/python3",
+            "/srv/billing/.venv/bin/gunicorn",
+            "-w",
+            "4",
+            "billing.wsgi:application",
+        ]))
+        .unwrap();
+        assert_eq!(entry.display, "gunicorn billing.wsgi:application");
+        assert_eq!(entry.script, Some(PathBuf::from("/srv/billing/.venv/bin/gunicorn")));
+
+        let entry = parse_entry(&cmd(&["python", "-X", "dev", "manage.py", "runserver"])).unwrap();
+        assert_eq!(entry.name, "manage");
+        assert_eq!(entry.display, "manage.py");
+        assert!(!entry.server);
+
+        assert_eq!(parse_entry(&cmd(&["python3", "-c", "print(1)"])), None);
+        assert_eq!(parse_entry(&cmd(&["python3"])), None);
+    }
+
+    #[test]
+    fn test_is_python() {
+        assert!(is_python("python3.12"));
+        assert!(is_python("pypy3"));
+        assert!(!is_python("python-config"));
+        assert!(!is_python("ruby"));
+    }
+}
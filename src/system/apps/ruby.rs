@@ -0,0 +1,132 @@
+use std::path::PathBuf;
+
+use sysinfo::Process;
+
+use super::{
+    command_version, dir_name, display_path, exe_name, file_stem, first_version, positional_args,
+    project_root_for, DetectedApp, Runtime, RuntimeDetector,
+};
+
+const MARKERS: &[&str] = &["Gemfile", "config.ru", ".ruby-version"];
+
+/// Servers and job runners, matched on the executable or the rewritten process title
+const SERVERS: &[&str] = &[
+    "puma",
+    "rails",
+    "unicorn",
+    "sidekiq",
+    "rackup",
+    "thin",
+    "falcon",
+    "good_job",
+    "solid_queue",
+    "resque",
+];
+
+/// Language servers and linters that are not apps
+const TOOLING: &[&str] = &["solargraph", "ruby-lsp", "rubocop", "steep", "srb"];
+
+pub struct RubyDetector;
+
+impl RuntimeDetector for RubyDetector {
+    fn detect(&self, process: &Process) -> Option<DetectedApp> {
+        let exe = exe_name(process);
+        if !exe.starts_with("ruby") {
+            return None;
+        }
+        let entry = parse_entry(process.cmd())?;
+        if TOOLING.contains(&entry.name.as_str()) {
+            return None;
+        }
+
+        let project_dir = project_root_for(process, entry.script.as_deref(), MARKERS);
+        if project_dir.is_none() && !SERVERS.contains(&entry.name.as_str()) {
+            return None;
+        }
+        let version = process
+            .exe()
+            .and_then(|exe| command_version(exe, &["--version"], first_version));
+
+        Some(DetectedApp {
+            runtime: Runtime::Ruby,
+            name: entry.name,
+            entry: entry.display,
+            project_name: project_dir.as_deref().and_then(dir_name),
+            project_dir,
+            version,
+            global_tool: false,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct RubyEntry {
+    name: String,
+    display: String,
+    script: Option<PathBuf>,
+}
+
+fn parse_entry(cmd: &[String]) -> Option<RubyEntry> {
+    // Puma, Unicorn and Sidekiq rewrite argv into a single title,
+    // e.g. "puma 6.4.2 (tcp://0.0.0.0:3000) [shop]"
+    if let [title] = cmd {
+        let tool = title.split_whitespace().next()?;
+        if SERVERS.contains(&tool) {
+            return Some(RubyEntry {
+                name: tool.to_string(),
+                display: title.clone(),
+                script: None,
+            });
+        }
+    }
+
+    let positional = positional_args(cmd, &["-I", "-r", "-C", "-E"]);
+    let mut rest = positional.iter().copied();
+    let mut script = rest.next()?;
+    let mut name = file_stem(script);
+    // `bundle exec puma` runs the tool named after `exec`
+    if name == "bundle" && rest.next() == Some("exec") {
+        let tool = rest.next()?;
+        script = tool;
+        name = file_stem(tool);
+    }
+    let display = match (name.as_str(), rest.next()) {
+        ("rails" | "rake", Some(command)) => format!("{name} {command}"),
+        _ if SERVERS.contains(&name.as_str()) => name.clone(),
+        _ => display_path(script),
+    };
+    Some(RubyEntry {
+        name,
+        display,
+        script: script.contains('/').then(|| PathBuf::from(script)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::system::apps::cmd;
+
+    #[test]
+    fn test_parse_entry() {
+        let entry = parse_entry(&cmd(&["puma 6.4.2 (tcp://0.0.0.0:3000) [shop]"])).unwrap();
+        assert_eq!(entry.name, "puma");
+        assert_eq!(entry.display, "puma 6.4.2 (tcp://0.0.0.0:3000) [shop]");
+
+        let entry = parse_entry(&cmd(&["ruby", "bin/rails", "server", "-p", "3000"])).unwrap();
+        assert_eq!(entry.display, "rails server");
+        assert_eq!(entry.script, Some(PathBuf::from("bin/rails")));
+
+        let entry = parse_entry(&cmd(&[
+            "/home/dev/.rbenv/versions/3.3.0/bin/ruby",
+            "/home/dev/.rbenv/versions/3.3.0/bin/bundle",
+            "exec",
+            "sidekiq",
+            "-C",
+            "config/sidekiq.yml",
+        ]))
+        .unwrap();
+        assert_eq!(entry.name, "sidekiq");
+        assert_eq!(entry.script, None);
+    }
+}
@@ -1,11 +1,12 @@
 use std::collections::HashMap;
 use std::io;
-use std::process::Command;
 
 use sysinfo::Pid;
 
+use super::context::docker_command;
+
 pub fn load_container_env(container_id: &str) -> io::Result<Vec<String>> {
-    let output = Command::new("docker")
+    let output = docker_command()
         .args([
             "inspect",
             "--format",
@@ -15,10 +16,7 @@ pub fn load_container_env(container_id: &str) -> io::Result<Vec<String>> {
         .output()?;
 
     if !output.status.success() {
-        return Err(io::Error::new(
-            io::ErrorKind::Other,
-            "docker inspect failed",
-        ));
+        return Err(io::Error::other("docker inspect failed"));
     }
 
     let stdout = String::from_utf8_lossy(&output.stdout);
@@ -33,17 +31,14 @@ pub fn load_container_env(container_id: &str) -> io::Result<Vec<String>> {
 }
 
 pub fn kill_container(container_id: &str) -> io::Result<()> {
-    let output = Command::new("docker")
+    let output = docker_command()
         .args(["kill", container_id])
         .output()?;
 
     if output.status.success() {
         Ok(())
     } else {
-        Err(io::Error::new(
-            io::ErrorKind::Other,
-            "docker kill failed",
-        ))
+        Err(io::Error::other("docker kill failed"))
     }
 }
 
@@ -60,52 +55,43 @@ pub fn kill_containers(container_ids: &[String]) -> (usize, usize) {
 }
 
 pub fn start_container(container_id: &str) -> io::Result<()> {
-    let output = Command::new("docker")
+    let output = docker_command()
         .args(["start", container_id])
         .output()?;
 
     if output.status.success() {
         Ok(())
     } else {
-        Err(io::Error::new(
-            io::ErrorKind::Other,
-            "docker start failed",
-        ))
+        Err(io::Error::other("docker start failed"))
     }
 }
 
 pub fn stop_container(container_id: &str) -> io::Result<()> {
-    let output = Command::new("docker")
+    let output = docker_command()
         .args(["stop", container_id])
         .output()?;
 
     if output.status.success() {
         Ok(())
     } else {
-        Err(io::Error::new(
-            io::ErrorKind::Other,
-            "docker stop failed",
-        ))
+        Err(io::Error::other("docker stop failed"))
     }
 }
 
 pub fn restart_container(container_id: &str) -> io::Result<()> {
-    let output = Command::new("docker")
+    let output = docker_command()
         .args(["restart", container_id])
         .output()?;
 
     if output.status.success() {
         Ok(())
     } else {
-        Err(io::Error::new(
-            io::ErrorKind::Other,
-            "docker restart failed",
-        ))
+        Err(io::Error::other("docker restart failed"))
     }
 }
 
 pub fn load_docker_container_cache() -> Option<HashMap<String, String>> {
-    let output = Command::new("docker")
+    let output = docker_command()
         .args(["ps", "--no-trunc", "--format", "{{.ID}} {{.Names}}"])
         .output()
         .ok()?;
@@ -132,14 +118,19 @@ pub fn load_docker_container_cache() -> Option<HashMap<String, String>> {
     Some(cache)
 }
 
-pub fn container_label_for(pid: Pid, container_cache: &HashMap<String, String>) -> Option<String> {
-    let id = get_container_id(pid)?;
-    if let Some(name) = resolve_container_name(&id, container_cache) {
-        return Some(name);
+pub fn container_label_for_id(id: &str, container_cache: &HashMap<String, String>) -> String {
+    if let Some(name) = resolve_container_name(id, container_cache) {
+        return name;
     }
 
-    let short = if id.len() > 12 { &id[..12] } else { &id };
-    Some(format!("ctr:{short}"))
+    let short = if id.len() > 12 { &id[..12] } else { id };
+    format!("ctr:{short}")
+}
+
+/// Compares a cgroup-derived container ID with a docker ID, either of which may be truncated.
+pub fn container_ids_match(a: &str, b: &str) -> bool {
+    let len = a.len().min(b.len());
+    len >= 12 && a[..len] == b[..len]
 }
 
 fn resolve_container_name(id: &str, container_cache: &HashMap<String, String>) -> Option<String> {
@@ -154,15 +145,16 @@ fn resolve_container_name(id: &str, container_cache: &HashMap<String, String>) -
     None
 }
 
+/// Container ID of a host process, read from its cgroup path.
 #[cfg(target_os = "linux")]
-fn get_container_id(pid: Pid) -> Option<String> {
+pub fn container_id_for(pid: Pid) -> Option<String> {
     let path = format!("/proc/{}/cgroup", pid.as_u32());
     let contents = std::fs::read_to_string(path).ok()?;
     extract_container_id(&contents)
 }
 
 #[cfg(not(target_os = "linux"))]
-fn get_container_id(_pid: Pid) -> Option<String> {
+pub fn container_id_for(_pid: Pid) -> Option<String> {
     None
 }
 
@@ -0,0 +1,132 @@
+use std::io;
+use std::process::Command;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::RwLock;
+
+/// Context explicitly selected by the user (CLI flag or context picker).
+/// `None` means the docker CLI default applies (`docker context use`, `DOCKER_CONTEXT`).
+static ACTIVE_CONTEXT: RwLock<Option<String>> = RwLock::new(None);
+
+/// Bumped on every context switch so in-flight stats loads can be discarded.
+static CONTEXT_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Clone, Debug)]
+pub struct DockerContext {
+    pub name: String,
+    pub endpoint: String,
+    /// True for the context the docker CLI would use without `--context`
+    pub current: bool,
+}
+
+pub fn list_docker_contexts() -> io::Result<Vec<DockerContext>> {
+    let output = Command::new("docker")
+        .args([
+            "context",
+            "ls",
+            "--format",
+            "{{.Name}}|{{.Current}}|{{.DockerEndpoint}}",
+        ])
+        .output()?;
+
+    if !output.status.success() {
+        return Err(io::Error::other("docker context ls failed"));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Ok(parse_context_list(&stdout))
+}
+
+/// Name of the context the docker CLI resolves to when no `--context` is passed.
+pub fn current_docker_context() -> Option<String> {
+    list_docker_contexts()
+        .ok()?
+        .into_iter()
+        .find(|ctx| ctx.current)
+        .map(|ctx| ctx.name)
+}
+
+pub fn active_docker_context() -> Option<String> {
+    let guard = ACTIVE_CONTEXT.read().unwrap_or_else(|err| err.into_inner());
+    guard.clone()
+}
+
+pub fn set_docker_context(name: Option<String>) {
+    let mut guard = ACTIVE_CONTEXT.write().unwrap_or_else(|err| err.into_inner());
+    *guard = name;
+    CONTEXT_GENERATION.fetch_add(1, Ordering::SeqCst);
+}
+
+pub(crate) fn context_generation() -> u64 {
+    CONTEXT_GENERATION.load(Ordering::SeqCst)
+}
+
+/// `docker` command bound to the active context. Every docker call goes through here.
+pub(crate) fn docker_command() -> Command {
+    let mut command = Command::new("docker");
+    if let Some(name) = active_docker_context() {
+        command.args(["--context", &name]);
+    }
+    command
+}
+
+/// Shell prefix equivalent of `docker_command`, for commands run in a spawned terminal.
+pub(crate) fn docker_shell_prefix() -> String {
+    match active_docker_context() {
+        Some(name) => format!("docker --context '{}'", name.replace('\'', "'\\''")),
+        None => "docker".to_string(),
+    }
+}
+
+fn parse_context_list(stdout: &str) -> Vec<DockerContext> {
+    let mut contexts = Vec::new();
+    for raw_line in stdout.lines() {
+        let line = raw_line.trim_end_matches('\r');
+        if line.trim().is_empty() {
+            continue;
+        }
+        let mut parts = line.splitn(3, '|');
+        let name = parts.next().unwrap_or("").trim();
+        let current = parts.next().unwrap_or("").trim();
+        let endpoint = parts.next().unwrap_or("").trim();
+        if name.is_empty() {
+            continue;
+        }
+        contexts.push(DockerContext {
+            // Older CLIs mark the current context with a trailing " *"
+            name: name.trim_end_matches(" *").to_string(),
+            endpoint: endpoint.to_string(),
+            current: current == "true" || name.ends_with(" *"),
+        });
+    }
+    contexts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_context_list() {
+        let output = "default|true|unix:///var/run/docker.sock\r\n\
+                      staging|false|ssh://deploy@staging\n\
+                      \n\
+                      |false|tcp://nameless:2376\n";
+        let contexts = parse_context_list(output);
+        assert_eq!(contexts.len(), 2);
+        assert_eq!(contexts[0].name, "default");
+        assert_eq!(contexts[0].endpoint, "unix:///var/run/docker.sock");
+        assert!(contexts[0].current);
+        assert_eq!(contexts[1].name, "staging");
+        assert_eq!(contexts[1].endpoint, "ssh://deploy@staging");
+        assert!(!contexts[1].current);
+    }
+
+    #[test]
+    fn test_parse_context_list_legacy_current_marker() {
+        let output = "remote *||tcp://10.0.0.2:2376\nlocal||unix:///var/run/docker.sock\n";
+        let contexts = parse_context_list(output);
+        assert_eq!(contexts[0].name, "remote");
+        assert!(contexts[0].current);
+        assert!(!contexts[1].current);
+    }
+}
@@ -0,0 +1,388 @@
+use std::io;
+
+use super::context::docker_command;
+
+const NANOS_PER_CPU: f64 = 1_000_000_000.0;
+const DEFAULT_CPU_PERIOD: i64 = 100_000;
+/// Smallest memory limit the docker daemon accepts
+const MIN_MEMORY_BYTES: u64 = 6 * 1024 * 1024;
+
+/// Resource limits from a container's `HostConfig`. Zero means unset.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ContainerLimits {
+    pub nano_cpus: i64,
+    pub cpu_quota: i64,
+    pub cpu_period: i64,
+    pub cpuset_cpus: String,
+    pub memory: i64,
+    pub memory_swap: i64,
+    pub restart_policy: String,
+    pub restart_retries: i64,
+}
+
+impl ContainerLimits {
+    /// CPU limit in cores, from either `--cpus` or `--cpu-quota`/`--cpu-period`
+    pub fn cpus(&self) -> Option<f64> {
+        if self.nano_cpus > 0 {
+            Some(self.nano_cpus as f64 / NANOS_PER_CPU)
+        } else if self.cpu_quota > 0 {
+            Some(self.cpu_quota as f64 / self.effective_cpu_period() as f64)
+        } else {
+            None
+        }
+    }
+
+    pub fn cpus_text(&self) -> String {
+        self.cpus().map(format_cpus).unwrap_or_default()
+    }
+
+    pub fn memory_text(&self) -> String {
+        if self.memory > 0 {
+            format_memory(self.memory as u64)
+        } else {
+            String::new()
+        }
+    }
+
+    pub fn restart_text(&self) -> String {
+        match self.restart_policy.as_str() {
+            "" => "no".to_string(),
+            "on-failure" if self.restart_retries > 0 => {
+                format!("on-failure:{}", self.restart_retries)
+            }
+            policy => policy.to_string(),
+        }
+    }
+
+    fn effective_cpu_period(&self) -> i64 {
+        if self.cpu_period > 0 {
+            self.cpu_period
+        } else {
+            DEFAULT_CPU_PERIOD
+        }
+    }
+}
+
+/// Changes to apply with `docker update`. `None` fields are left untouched.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LimitsUpdate {
+    pub cpus: Option<f64>,
+    pub cpuset_cpus: Option<String>,
+    pub memory: Option<u64>,
+    pub restart: Option<String>,
+}
+
+impl LimitsUpdate {
+    pub fn is_empty(&self) -> bool {
+        self.cpus.is_none()
+            && self.cpuset_cpus.is_none()
+            && self.memory.is_none()
+            && self.restart.is_none()
+    }
+}
+
+pub fn load_container_limits(container_id: &str) -> io::Result<ContainerLimits> {
+    let output = docker_command()
+        .args([
+            "inspect",
+            "--format",
+            "{{.HostConfig.NanoCpus}}|{{.HostConfig.CpuQuota}}|{{.HostConfig.CpuPeriod}}|\
+             {{.HostConfig.CpusetCpus}}|{{.HostConfig.Memory}}|{{.HostConfig.MemorySwap}}|\
+             {{.HostConfig.RestartPolicy.Name}}|{{.HostConfig.RestartPolicy.MaximumRetryCount}}",
+            container_id,
+        ])
+        .output()?;
+
+    if !output.status.success() {
+        return Err(io::Error::other("docker inspect failed"));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    parse_limits(stdout.trim())
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "unexpected docker inspect output"))
+}
+
+/// CPU count of the daemon's host, which may differ from ours with a remote context.
+pub fn docker_host_cpus() -> io::Result<usize> {
+    let output = docker_command()
+        .args(["info", "--format", "{{.NCPU}}"])
+        .output()?;
+
+    if !output.status.success() {
+        return Err(io::Error::other("docker info failed"));
+    }
+
+    String::from_utf8_lossy(&output.stdout)
+        .trim()
+        .parse()
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "unexpected docker info output"))
+}
+
+pub fn update_container_limits(
+    container_id: &str,
+    current: &ContainerLimits,
+    update: &LimitsUpdate,
+) -> io::Result<()> {
+    let mut command = docker_command();
+    command.arg("update");
+    if let Some(cpus) = update.cpus {
+        // Containers limited through quota/period reject --cpus, so keep their style
+        if current.nano_cpus == 0 && current.cpu_quota > 0 {
+            let quota = (cpus * current.effective_cpu_period() as f64).round() as i64;
+            command.args(["--cpu-quota", &quota.to_string()]);
+        } else {
+            command.args(["--cpus", &format_cpus(cpus)]);
+        }
+    }
+    if let Some(cpuset) = &update.cpuset_cpus {
+        command.args(["--cpuset-cpus", cpuset]);
+    }
+    if let Some(memory) = update.memory {
+        command.args(["--memory", &memory.to_string()]);
+        // The daemon rejects a memory limit above an existing swap limit; keep the same headroom
+        if current.memory_swap > 0 {
+            let headroom = (current.memory_swap - current.memory.max(0)).max(0) as u64;
+            command.args(["--memory-swap", &(memory + headroom).to_string()]);
+        }
+    }
+    if let Some(restart) = &update.restart {
+        command.args(["--restart", restart]);
+    }
+    command.arg(container_id);
+
+    let output = command.output()?;
+    if output.status.success() {
+        return Ok(());
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let reason = stderr
+        .lines()
+        .map(|line| line.trim().trim_start_matches("Error response from daemon: "))
+        .find(|line| !line.is_empty())
+        .unwrap_or("docker update failed");
+    Err(io::Error::other(reason.to_string()))
+}
+
+/// Validates form input against the current limits and returns only the changed values.
+/// `max_cpus` bounds both the CPU count and the cpuset indexes.
+pub fn validate_limits(
+    current: &ContainerLimits,
+    cpus: &str,
+    cpuset_cpus: &str,
+    memory: &str,
+    restart: &str,
+    max_cpus: usize,
+) -> Result<LimitsUpdate, String> {
+    let mut update = LimitsUpdate::default();
+
+    let cpus = cpus.trim();
+    if cpus.is_empty() || cpus == "0" {
+        if current.cpus().is_some() {
+            return Err("CPUs: docker update cannot remove a CPU limit, enter a new value".to_string());
+        }
+    } else {
+        let value: f64 = cpus
+            .parse()
+            .map_err(|_| format!("CPUs: '{cpus}' is not a number"))?;
+        if !value.is_finite() || value < 0.01 {
+            return Err("CPUs: must be at least 0.01".to_string());
+        }
+        if value > max_cpus as f64 {
+            return Err(format!("CPUs: only {max_cpus} available"));
+        }
+        let changed = current
+            .cpus()
+            .is_none_or(|old| (old - value).abs() > f64::EPSILON);
+        if changed {
+            update.cpus = Some(value);
+        }
+    }
+
+    let cpuset_cpus = cpuset_cpus.trim();
+    if cpuset_cpus.is_empty() {
+        if !current.cpuset_cpus.is_empty() {
+            return Err("CPU set: docker update cannot clear a CPU set, enter a new value".to_string());
+        }
+    } else {
+        validate_cpuset(cpuset_cpus, max_cpus).map_err(|err| format!("CPU set: {err}"))?;
+        if cpuset_cpus != current.cpuset_cpus {
+            update.cpuset_cpus = Some(cpuset_cpus.to_string());
+        }
+    }
+
+    let memory = memory.trim();
+    if memory.is_empty() || memory == "0" {
+        if current.memory > 0 {
+            return Err("Memory: docker update cannot remove a memory limit, enter a new value".to_string());
+        }
+    } else {
+        let bytes = parse_memory(memory).map_err(|err| format!("Memory: {err}"))?;
+        if bytes < MIN_MEMORY_BYTES {
+            return Err("Memory: must be at least 6m".to_string());
+        }
+        if bytes != current.memory.max(0) as u64 {
+            update.memory = Some(bytes);
+        }
+    }
+
+    let restart = restart.trim();
+    let restart = if restart.is_empty() { "no" } else { restart };
+    validate_restart_policy(restart).map_err(|err| format!("Restart: {err}"))?;
+    if restart != current.restart_text() {
+        update.restart = Some(restart.to_string());
+    }
+
+    Ok(update)
+}
+
+fn parse_limits(line: &str) -> Option<ContainerLimits> {
+    let parts: Vec<&str> = line.split('|').map(str::trim).collect();
+    if parts.len() != 8 {
+        return None;
+    }
+    let number = |text: &str| -> Option<i64> {
+        if text.is_empty() || text == "<no value>" {
+            Some(0)
+        } else {
+            text.parse().ok()
+        }
+    };
+    Some(ContainerLimits {
+        nano_cpus: number(parts[0])?,
+        cpu_quota: number(parts[1])?,
+        cpu_period: number(parts[2])?,
+        cpuset_cpus: parts[3].to_string(),
+        memory: number(parts[4])?,
+        memory_swap: number(parts[5])?,
+        restart_policy: parts[6].to_string(),
+        restart_retries: number(parts[7])?,
+    })
+}
+
+fn validate_cpuset(text: &str, max_cpus: usize) -> Result<(), String> {
+    for part in text.split(',') {
+        let (start, end) = match part.split_once('-') {
+            Some((start, end)) => (start, end),
+            None => (part, part),
+        };
+        let start: usize = start
+            .parse()
+            .map_err(|_| format!("'{part}' is not a CPU index or range"))?;
+        let end: usize = end
+            .parse()
+            .map_err(|_| format!("'{part}' is not a CPU index or range"))?;
+        if start > end {
+            return Err(format!("range '{part}' is reversed"));
+        }
+        if end >= max_cpus {
+            return Err(format!("CPU {end} does not exist (0-{})", max_cpus.saturating_sub(1)));
+        }
+    }
+    Ok(())
+}
+
+fn validate_restart_policy(text: &str) -> Result<(), String> {
+    match text {
+        "no" | "always" | "unless-stopped" | "on-failure" => Ok(()),
+        _ => match text.strip_prefix("on-failure:") {
+            Some(retries) if retries.parse::<u32>().is_ok() => Ok(()),
+            _ => Err(format!(
+                "'{text}' is not one of no, always, unless-stopped, on-failure[:N]"
+            )),
+        },
+    }
+}
+
+/// Parses docker-style sizes: `512m`, `1.5g`, `2048k`, `1073741824`.
+fn parse_memory(text: &str) -> Result<u64, String> {
+    let lower = text.to_ascii_lowercase();
+    let trimmed = lower.strip_suffix('b').unwrap_or(&lower);
+    let (number, multiplier) = match trimmed.chars().last() {
+        Some('k') => (&trimmed[..trimmed.len() - 1], 1024f64),
+        Some('m') => (&trimmed[..trimmed.len() - 1], 1024f64 * 1024.0),
+        Some('g') => (&trimmed[..trimmed.len() - 1], 1024f64 * 1024.0 * 1024.0),
+        _ => (trimmed, 1f64),
+    };
+    let value: f64 = number
+        .trim()
+        .parse()
+        .map_err(|_| format!("'{text}' is not a size like 512m or 2g"))?;
+    if !value.is_finite() || value <= 0.0 {
+        return Err(format!("'{text}' must be positive"));
+    }
+    Ok((value * multiplier).round() as u64)
+}
+
+fn format_memory(bytes: u64) -> String {
+    const KIB: u64 = 1024;
+    const MIB: u64 = KIB * 1024;
+    const GIB: u64 = MIB * 1024;
+    if bytes.is_multiple_of(GIB) {
+        format!("{}g", bytes / GIB)
+    } else if bytes.is_multiple_of(MIB) {
+        format!("{}m", bytes / MIB)
+    } else if bytes.is_multiple_of(KIB) {
+        format!("{}k", bytes / KIB)
+    } else {
+        bytes.to_string()
+    }
+}
+
+fn format_cpus(cpus: f64) -> String {
+    let text = format!("{cpus:.2}");
+    text.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_limits() {
+        let limits = parse_limits("1500000000|0|0|0-1|536870912|1073741824|on-failure|3").unwrap();
+        assert_eq!(limits.cpus_text(), "1.5");
+        assert_eq!(limits.cpuset_cpus, "0-1");
+        assert_eq!(limits.memory_text(), "512m");
+        assert_eq!(limits.restart_text(), "on-failure:3");
+
+        let unset = parse_limits("0|0|0||0|0||0").unwrap();
+        assert_eq!(unset.cpus_text(), "");
+        assert_eq!(unset.memory_text(), "");
+        assert_eq!(unset.restart_text(), "no");
+    }
+
+    #[test]
+    fn test_parse_limits_cpu_quota() {
+        let limits = parse_limits("0|50000|100000||0|0|always|0").unwrap();
+        assert_eq!(limits.cpus_text(), "0.5");
+    }
+
+    #[test]
+    fn test_validate_limits_changes_only() {
+        let current = parse_limits("1000000000|0|0||268435456|0|no|0").unwrap();
+        let update = validate_limits(&current, "1", "", "256m", "no", 4).unwrap();
+        assert!(update.is_empty());
+
+        let update = validate_limits(&current, "0.5", "0,2-3", "1g", "unless-stopped", 4).unwrap();
+        assert_eq!(update.cpus, Some(0.5));
+        assert_eq!(update.cpuset_cpus.as_deref(), Some("0,2-3"));
+        assert_eq!(update.memory, Some(1024 * 1024 * 1024));
+        assert_eq!(update.restart.as_deref(), Some("unless-stopped"));
+    }
+
+    #[test]
+    fn test_validate_limits_errors() {
+        let current = ContainerLimits::default();
+        assert!(validate_limits(&current, "abc", "", "", "no", 4).is_err());
+        assert!(validate_limits(&current, "8", "", "", "no", 4).is_err());
+        assert!(validate_limits(&current, "", "3-1", "", "no", 4).is_err());
+        assert!(validate_limits(&current, "", "4", "", "no", 4).is_err());
+        assert!(validate_limits(&current, "", "", "1m", "no", 4).is_err());
+        assert!(validate_limits(&current, "", "", "12x", "no", 4).is_err());
+        assert!(validate_limits(&current, "", "", "", "sometimes", 4).is_err());
+        assert!(validate_limits(&current, "", "", "", "on-failure:5", 4).is_ok());
+
+        let limited = parse_limits("0|0|0||268435456|0|no|0").unwrap();
+        assert!(validate_limits(&limited, "", "", "", "no", 4).is_err());
+    }
+}
@@ -1,4 +1,6 @@
 mod container;
+mod context;
+mod limits;
 mod stats;
 mod terminal;
 
@@ -7,7 +9,8 @@ use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::Duration;
 
-use crate::util::{contains_lower, Filterable};
+use crate::system::net::NetRates;
+use crate::util::{FieldValue, Filterable};
 
 /// Container health status from Docker healthcheck
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -23,8 +26,18 @@ pub enum HealthStatus {
 }
 
 pub use container::{
-    container_label_for, kill_container, kill_containers, load_container_env,
-    load_docker_container_cache, restart_container, start_container, stop_container,
+    container_id_for, container_ids_match, container_label_for_id, kill_container,
+    kill_containers, load_container_env, load_docker_container_cache, restart_container,
+    start_container, stop_container,
+};
+pub(crate) use context::{context_generation, docker_command};
+pub use limits::{
+    docker_host_cpus, load_container_limits, update_container_limits, validate_limits,
+    ContainerLimits,
+};
+pub use context::{
+    active_docker_context, current_docker_context, list_docker_contexts, set_docker_context,
+    DockerContext,
 };
 pub use stats::{apply_container_filter, group_containers, load_docker_stats};
 pub use terminal::{open_container_logs, open_container_shell};
@@ -48,18 +61,53 @@ pub struct ContainerInfo {
     pub activity_secs: u64,
     /// Container health status from healthcheck
     pub health: HealthStatus,
+    /// Network throughput of the container's namespace, while its columns are shown
+    pub net: Option<NetRates>,
 }
 
 impl Filterable for ContainerInfo {
-    fn matches_filter(&self, filter_lower: &str) -> bool {
-        contains_lower(&self.id, filter_lower)
-            || contains_lower(&self.name, filter_lower)
-            || contains_lower(&self.image, filter_lower)
-            || contains_lower(&self.port_public, filter_lower)
-            || contains_lower(&self.port_internal, filter_lower)
-            || contains_lower(&self.status, filter_lower)
-            || contains_lower(&self.group_name, filter_lower)
-            || self.group_path.as_deref().map_or(false, |p| contains_lower(p, filter_lower))
+    const FIELDS: &'static [&'static str] = &[
+        "id", "name", "image", "port", "status", "group", "path", "cpu", "mem", "running",
+    ];
+
+    fn search_text(&self) -> Vec<Cow<'_, str>> {
+        let mut text = vec![
+            Cow::Borrowed(self.id.as_str()),
+            Cow::Borrowed(self.name.as_str()),
+            Cow::Borrowed(self.image.as_ref()),
+            Cow::Borrowed(self.port_public.as_ref()),
+            Cow::Borrowed(self.port_internal.as_ref()),
+            Cow::Borrowed(self.status.as_ref()),
+            Cow::Borrowed(self.group_name.as_ref()),
+        ];
+        text.extend(self.group_path.as_deref().map(Cow::Borrowed));
+        text
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn field(&self, name: &str) -> Option<FieldValue<'_>> {
+        Some(match name {
+            "id" => FieldValue::Text(Cow::Borrowed(&self.id)),
+            "name" => FieldValue::Text(Cow::Borrowed(&self.name)),
+            "image" => FieldValue::Text(Cow::Borrowed(&self.image)),
+            "port" => FieldValue::Numbers(
+                [&self.port_public, &self.port_internal]
+                    .into_iter()
+                    .flat_map(|ports| ports.split(|ch: char| !ch.is_ascii_digit()))
+                    .filter_map(|port| port.parse().ok())
+                    .collect(),
+            ),
+            "status" => FieldValue::Text(Cow::Borrowed(&self.status)),
+            "group" => FieldValue::Text(Cow::Borrowed(&self.group_name)),
+            "path" => FieldValue::Text(Cow::Borrowed(self.group_path.as_deref()?)),
+            "cpu" => FieldValue::Number(f64::from(self.cpu)),
+            "mem" => FieldValue::Number(self.memory_bytes as f64),
+            "running" => FieldValue::Text(Cow::Borrowed(if self.running { "yes" } else { "no" })),
+            _ => return None,
+        })
     }
 }
 
@@ -75,27 +123,54 @@ pub enum DockerRow {
     Separator,
 }
 
+/// Stats tagged with the context generation they were loaded for.
+struct StatsSnapshot {
+    generation: u64,
+    containers: Vec<ContainerInfo>,
+}
+
 pub struct DockerStatsWorker {
-    data: Arc<Mutex<Vec<ContainerInfo>>>,
+    data: Arc<Mutex<StatsSnapshot>>,
 }
 
 impl DockerStatsWorker {
+    /// Latest stats for the active context. Empty right after a context switch,
+    /// until the worker has loaded data from the new daemon.
     pub fn snapshot(&self) -> Vec<ContainerInfo> {
         let guard = self.data.lock().unwrap_or_else(|err| err.into_inner());
-        guard.clone()
+        if guard.generation != context_generation() {
+            return Vec::new();
+        }
+        guard.containers.clone()
     }
 }
 
 pub fn start_docker_stats_worker(interval: Duration) -> DockerStatsWorker {
-    let data = Arc::new(Mutex::new(Vec::new()));
+    const WAKE_STEP: Duration = Duration::from_millis(100);
+
+    let data = Arc::new(Mutex::new(StatsSnapshot {
+        generation: context_generation(),
+        containers: Vec::new(),
+    }));
     let thread_data = Arc::clone(&data);
 
     thread::spawn(move || loop {
+        let generation = context_generation();
         if let Some(stats) = load_docker_stats() {
-            let mut guard = thread_data.lock().unwrap_or_else(|err| err.into_inner());
-            *guard = stats;
+            // Drop results that were loaded while the context was being switched
+            if generation == context_generation() {
+                let mut guard = thread_data.lock().unwrap_or_else(|err| err.into_inner());
+                guard.generation = generation;
+                guard.containers = stats;
+            }
+        }
+
+        // Sleep in short steps so a context switch is picked up right away
+        let mut slept = Duration::ZERO;
+        while slept < interval && generation == context_generation() {
+            thread::sleep(WAKE_STEP);
+            slept += WAKE_STEP;
         }
-        thread::sleep(interval);
     });
 
     DockerStatsWorker { data }
@@ -1,9 +1,12 @@
 use std::borrow::Cow;
+use std::cmp::Ordering;
 use std::collections::BTreeMap;
 use std::path::Path;
-use std::process::Command;
 
+use super::context::docker_command;
 use super::{ContainerInfo, DockerRow, HealthStatus};
+use crate::app::{DockerSortBy, SortOrder, ViewSort};
+use crate::util::Query;
 
 /// Static string constants to avoid repeated allocations
 const DASH: &str = "-";
@@ -16,7 +19,7 @@ pub fn load_docker_stats() -> Option<Vec<ContainerInfo>> {
     // Combined format: stats data + metadata in single command
     // Format: ID|Name|CPU|MemUsage|Image|Ports|Status|Labels
     // Use -a to include stopped containers
-    let output = Command::new("docker")
+    let output = docker_command()
         .args([
             "ps",
             "-a",
@@ -50,7 +53,7 @@ pub fn load_docker_stats() -> Option<Vec<ContainerInfo>> {
     }
 
     // Get stats for all containers in a single call
-    let stats_output = Command::new("docker")
+    let stats_output = docker_command()
         .args([
             "stats",
             "--no-stream",
@@ -153,6 +156,7 @@ pub fn load_docker_stats() -> Option<Vec<ContainerInfo>> {
             running,
             activity_secs,
             health,
+            net: None,
         });
     }
 
@@ -167,12 +171,12 @@ struct ComposeGroup {
 
 pub fn group_containers(
     containers: Vec<ContainerInfo>,
+    sort: ViewSort<DockerSortBy>,
 ) -> (Vec<ContainerInfo>, Vec<DockerRow>) {
     struct GroupBucket {
         name: Cow<'static, str>,
         path: Option<String>,
         containers: Vec<ContainerInfo>,
-        min_activity: u64, // Most recent activity in group
     }
 
     let mut grouped: BTreeMap<String, GroupBucket> = BTreeMap::new();
@@ -186,55 +190,38 @@ pub fn group_containers(
             name: container.group_name.clone(),
             path: container.group_path.clone(),
             containers: Vec::new(),
-            min_activity: u64::MAX,
         });
-        // Track the most recent activity in the group
-        bucket.min_activity = bucket.min_activity.min(container.activity_secs);
         bucket.containers.push(container);
     }
 
     let other = grouped.remove("Other");
 
-    // Convert to vec and sort by activity (most recent first)
     let mut buckets: Vec<_> = grouped.into_values().collect();
-    buckets.sort_by_key(|b| b.min_activity);
+    for bucket in &mut buckets {
+        sort_containers(&mut bucket.containers, sort);
+    }
+    // Groups follow their first container, or go by name when sorting by group
+    buckets.sort_by(|a, b| {
+        let ordering = match sort.by {
+            DockerSortBy::Group => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+            _ => compare_containers(&a.containers[0], &b.containers[0], sort.by),
+        };
+        match sort.order {
+            SortOrder::Asc => ordering,
+            SortOrder::Desc => ordering.reverse(),
+        }
+    });
 
     let mut flat = Vec::new();
     let mut rows = Vec::new();
-    let mut first_group = true;
-
-    for mut bucket in buckets {
-        // Sort containers within group by activity (most recent first)
-        bucket.containers.sort_by_key(|c| c.activity_secs);
-
-        if !first_group {
-            rows.push(DockerRow::Separator);
-        }
-        first_group = false;
-        let running_count = bucket.containers.iter().filter(|c| c.running).count();
-        rows.push(DockerRow::Group {
-            name: bucket.name.to_string(),
-            path: bucket.path.clone(),
-            count: bucket.containers.len(),
-            running_count,
-        });
-        let total = bucket.containers.len();
-        for (idx, container) in bucket.containers.into_iter().enumerate() {
-            let is_last = idx + 1 == total;
-            let prefix = if is_last { "  └─ " } else { "  ├─ " };
-            let idx = flat.len();
-            flat.push(container);
-            rows.push(DockerRow::Item {
-                index: idx,
-                prefix: prefix.to_string(),
-            });
-        }
-    }
 
     // "Other" group always goes last
     if let Some(mut bucket) = other {
-        bucket.containers.sort_by_key(|c| c.activity_secs);
+        sort_containers(&mut bucket.containers, sort);
+        buckets.push(bucket);
+    }
 
+    for bucket in buckets {
         if !rows.is_empty() {
             rows.push(DockerRow::Separator);
         }
@@ -261,8 +248,52 @@ pub fn group_containers(
     (flat, rows)
 }
 
-pub fn apply_container_filter(containers: &mut Vec<ContainerInfo>, filter: &str) {
-    crate::util::apply_filter(containers, filter);
+/// Sorts the containers of one group; sorting by group keeps them by activity.
+fn sort_containers(containers: &mut [ContainerInfo], sort: ViewSort<DockerSortBy>) {
+    let (by, order) = match sort.by {
+        DockerSortBy::Group => (DockerSortBy::Activity, SortOrder::Desc),
+        by => (by, sort.order),
+    };
+    containers.sort_by(|a, b| {
+        let ordering = compare_containers(a, b, by);
+        let ordering = match order {
+            SortOrder::Asc => ordering,
+            SortOrder::Desc => ordering.reverse(),
+        };
+        ordering.then_with(|| a.name.cmp(&b.name))
+    });
+}
+
+fn compare_containers(a: &ContainerInfo, b: &ContainerInfo, by: DockerSortBy) -> Ordering {
+    match by {
+        // Fewer seconds since the last activity ranks higher
+        DockerSortBy::Activity | DockerSortBy::Group => b.activity_secs.cmp(&a.activity_secs),
+        DockerSortBy::Health => health_rank(a.health).cmp(&health_rank(b.health)),
+        DockerSortBy::Cpu => a.cpu.total_cmp(&b.cpu),
+        DockerSortBy::Memory => a.memory_bytes.cmp(&b.memory_bytes),
+        DockerSortBy::Net => net_total(a).cmp(&net_total(b)),
+        DockerSortBy::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+        DockerSortBy::Image => a.image.cmp(&b.image),
+        DockerSortBy::Status => b.running.cmp(&a.running).then_with(|| a.status.cmp(&b.status)),
+    }
+}
+
+/// Higher for worse health, so descending lists failing containers first
+fn health_rank(health: HealthStatus) -> u8 {
+    match health {
+        HealthStatus::Healthy => 0,
+        HealthStatus::None => 1,
+        HealthStatus::Starting => 2,
+        HealthStatus::Unhealthy => 3,
+    }
+}
+
+fn net_total(container: &ContainerInfo) -> u64 {
+    container.net.map_or(0, |net| net.total())
+}
+
+pub fn apply_container_filter(containers: &mut Vec<ContainerInfo>, query: &Query) {
+    crate::util::apply_filter(containers, query);
 }
 
 fn compose_group_from_labels(labels: &str) -> Option<ComposeGroup> {
@@ -522,3 +553,141 @@ fn parse_health_status(status: &str) -> HealthStatus {
         HealthStatus::None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn container(
+        name: &str,
+        group: &'static str,
+        activity_secs: u64,
+        health: HealthStatus,
+    ) -> ContainerInfo {
+        ContainerInfo {
+            id: format!("{name}-id"),
+            name: name.to_string(),
+            image: Cow::Borrowed(DASH),
+            port_public: Cow::Borrowed(DASH),
+            port_internal: Cow::Borrowed(DASH),
+            status: Cow::Borrowed("Up"),
+            cpu: 0.0,
+            memory_bytes: 0,
+            group_name: Cow::Borrowed(group),
+            group_path: None,
+            running: true,
+            activity_secs,
+            health,
+            net: None,
+        }
+    }
+
+    fn layout(rows: &[DockerRow], containers: &[ContainerInfo]) -> Vec<String> {
+        rows.iter()
+            .filter_map(|row| match row {
+                DockerRow::Group { name, .. } => Some(format!("[{name}]")),
+                DockerRow::Item { index, .. } => Some(containers[*index].name.clone()),
+                DockerRow::Separator => None,
+            })
+            .collect()
+    }
+
+    fn sample() -> Vec<ContainerInfo> {
+        vec![
+            container("zeta-db", "zeta", 5, HealthStatus::Healthy),
+            container("zeta-web", "zeta", 60, HealthStatus::Unhealthy),
+            container("loner", OTHER, 1, HealthStatus::None),
+            container("alpha-api", "Alpha", 300, HealthStatus::Starting),
+            container("alpha-cache", "Alpha", 2, HealthStatus::None),
+        ]
+    }
+
+    #[test]
+    fn test_group_sort_orders_groups_by_name_and_containers_by_activity() {
+        let (flat, rows) = group_containers(sample(), ViewSort::new(DockerSortBy::Group));
+        assert_eq!(
+            layout(&rows, &flat),
+            [
+                "[Alpha]",
+                "alpha-cache",
+                "alpha-api",
+                "[zeta]",
+                "zeta-db",
+                "zeta-web",
+                "[Other]",
+                "loner"
+            ]
+        );
+
+        // Reversing flips the groups only; "Other" stays last
+        let mut sort = ViewSort::new(DockerSortBy::Group);
+        sort.toggle(DockerSortBy::Group);
+        let (flat, rows) = group_containers(sample(), sort);
+        assert_eq!(
+            layout(&rows, &flat),
+            [
+                "[zeta]",
+                "zeta-db",
+                "zeta-web",
+                "[Alpha]",
+                "alpha-cache",
+                "alpha-api",
+                "[Other]",
+                "loner"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_activity_sort_orders_groups_by_first_container() {
+        let (flat, rows) = group_containers(sample(), ViewSort::new(DockerSortBy::Activity));
+        assert_eq!(
+            layout(&rows, &flat),
+            [
+                "[Alpha]",
+                "alpha-cache",
+                "alpha-api",
+                "[zeta]",
+                "zeta-db",
+                "zeta-web",
+                "[Other]",
+                "loner"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_health_sort_lists_failing_containers_first() {
+        let (flat, rows) = group_containers(sample(), ViewSort::new(DockerSortBy::Health));
+        assert_eq!(
+            layout(&rows, &flat),
+            [
+                "[zeta]",
+                "zeta-web",
+                "zeta-db",
+                "[Alpha]",
+                "alpha-api",
+                "alpha-cache",
+                "[Other]",
+                "loner"
+            ]
+        );
+
+        let mut ranked = [
+            HealthStatus::Unhealthy,
+            HealthStatus::Healthy,
+            HealthStatus::Starting,
+            HealthStatus::None,
+        ];
+        ranked.sort_by_key(|health| health_rank(*health));
+        assert_eq!(
+            ranked,
+            [
+                HealthStatus::Healthy,
+                HealthStatus::None,
+                HealthStatus::Starting,
+                HealthStatus::Unhealthy,
+            ]
+        );
+    }
+}
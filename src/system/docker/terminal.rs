@@ -2,9 +2,12 @@ use std::env;
 use std::io;
 use std::process::Command;
 
+use super::context::docker_shell_prefix;
+
 pub fn open_container_shell(container_id: &str) -> io::Result<()> {
     let cmd = format!(
-        "docker exec -it {id} bash 2>/dev/null || docker exec -it {id} sh; exec bash",
+        "{docker} exec -it {id} bash 2>/dev/null || {docker} exec -it {id} sh; exec bash",
+        docker = docker_shell_prefix(),
         id = container_id
     );
     if let Ok(term) = env::var("TERMINAL") {
@@ -37,7 +40,11 @@ pub fn open_container_shell(container_id: &str) -> io::Result<()> {
 }
 
 pub fn open_container_logs(container_id: &str) -> io::Result<()> {
-    let cmd = format!("docker logs -f --tail 200 {id}; exec bash", id = container_id);
+    let cmd = format!(
+        "{docker} logs -f --tail 200 {id}; exec bash",
+        docker = docker_shell_prefix(),
+        id = container_id
+    );
     if let Ok(term) = env::var("TERMINAL") {
         if try_spawn_terminal(&term, TerminalMode::DashE, &cmd).is_ok() {
             return Ok(());
@@ -0,0 +1,254 @@
+//! Open file descriptors of processes, read from `/proc/<pid>/fd` and `fdinfo`.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use sysinfo::Pid;
+
+use super::ports;
+
+const DELETED_SUFFIX: &str = " (deleted)";
+
+/// What a file descriptor points to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FdKind {
+    File,
+    Socket,
+    Pipe,
+    EventFd,
+    /// Other anonymous inodes: epoll, inotify, timerfd, signalfd, ...
+    AnonInode,
+}
+
+impl FdKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            FdKind::File => "file",
+            FdKind::Socket => "socket",
+            FdKind::Pipe => "pipe",
+            FdKind::EventFd => "eventfd",
+            FdKind::AnonInode => "anon",
+        }
+    }
+}
+
+/// One entry of `/proc/<pid>/fd`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FdEntry {
+    pub fd: u32,
+    pub kind: FdKind,
+    /// Path, or the socket's addresses when found in the socket tables
+    pub target: String,
+    /// The file was deleted while still open
+    pub deleted: bool,
+    pub pos: Option<u64>,
+    /// `open(2)` flags from fdinfo
+    pub flags: Option<u32>,
+}
+
+impl FdEntry {
+    /// e.g. "rw append cloexec"
+    pub fn flags_label(&self) -> Option<String> {
+        let flags = self.flags?;
+        let mut parts = vec![match flags & 0o3 {
+            0 => "r",
+            1 => "w",
+            _ => "rw",
+        }];
+        for (bit, name) in [(0o2000, "append"), (0o4000, "nonblock"), (0o2000000, "cloexec")] {
+            if flags & bit != 0 {
+                parts.push(name);
+            }
+        }
+        Some(parts.join(" "))
+    }
+}
+
+/// Summary of a process's fd table.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FdSummary {
+    pub open: usize,
+    /// Soft "Max open files" limit
+    pub limit: Option<u64>,
+    pub deleted: usize,
+    pub sockets: usize,
+    pub pipes: usize,
+}
+
+/// A file descriptor found by [`find_open_files`].
+pub struct OpenFile {
+    pub pid: Pid,
+    pub fd: u32,
+    pub target: String,
+}
+
+/// Every open file descriptor of `pid`, sorted by number. Sockets are
+/// described from the socket tables of the process's network namespace.
+pub fn list_fds(pid: Pid) -> io::Result<Vec<FdEntry>> {
+    let proc_dir = Path::new("/proc").join(pid.as_u32().to_string());
+    let sockets = ports::socket_descriptions(pid);
+    let mut entries = Vec::new();
+    for entry in fs::read_dir(proc_dir.join("fd"))? {
+        let entry = entry?;
+        let Some(fd) = entry.file_name().to_str().and_then(|name| name.parse::<u32>().ok()) else {
+            continue;
+        };
+        // The fd may close between listing and reading it
+        let Ok(link) = fs::read_link(entry.path()) else {
+            continue;
+        };
+        let mut fd_entry = parse_fd_link(fd, &link.to_string_lossy());
+        if fd_entry.kind == FdKind::Socket {
+            let inode = socket_inode(&fd_entry.target);
+            if let Some(description) = inode.and_then(|inode| sockets.get(&inode)) {
+                fd_entry.target = description.clone();
+            }
+        }
+        if let Ok(info) = fs::read_to_string(proc_dir.join("fdinfo").join(fd.to_string())) {
+            let (pos, flags) = parse_fdinfo(&info);
+            fd_entry.pos = pos;
+            fd_entry.flags = flags;
+        }
+        entries.push(fd_entry);
+    }
+    entries.sort_by_key(|entry| entry.fd);
+    Ok(entries)
+}
+
+pub fn summarize_fds(pid: Pid, entries: &[FdEntry]) -> FdSummary {
+    let limits = fs::read_to_string(format!("/proc/{}/limits", pid.as_u32())).unwrap_or_default();
+    FdSummary {
+        open: entries.len(),
+        limit: parse_open_files_limit(&limits),
+        deleted: entries.iter().filter(|entry| entry.deleted).count(),
+        sockets: entries.iter().filter(|entry| entry.kind == FdKind::Socket).count(),
+        pipes: entries.iter().filter(|entry| entry.kind == FdKind::Pipe).count(),
+    }
+}
+
+/// File descriptors of all readable processes whose target contains `query`.
+pub fn find_open_files(query: &str) -> Vec<OpenFile> {
+    let mut found = Vec::new();
+    let Ok(entries) = fs::read_dir("/proc") else {
+        return found;
+    };
+    for entry in entries.flatten() {
+        let Some(pid) = entry.file_name().to_str().and_then(|name| name.parse::<u32>().ok()) else {
+            continue;
+        };
+        let Ok(fd_entries) = fs::read_dir(entry.path().join("fd")) else {
+            continue;
+        };
+        for fd_entry in fd_entries.flatten() {
+            let Some(fd) = fd_entry.file_name().to_str().and_then(|name| name.parse::<u32>().ok())
+            else {
+                continue;
+            };
+            let Ok(link) = fs::read_link(fd_entry.path()) else {
+                continue;
+            };
+            let target = link.to_string_lossy();
+            if target.contains(query) {
+                found.push(OpenFile {
+                    pid: Pid::from_u32(pid),
+                    fd,
+                    target: target.into_owned(),
+                });
+            }
+        }
+    }
+    found.sort_by_key(|open| (open.pid, open.fd));
+    found
+}
+
+fn parse_fd_link(fd: u32, link: &str) -> FdEntry {
+    let (kind, target) = if link.starts_with("socket:[") {
+        (FdKind::Socket, link.to_string())
+    } else if let Some(inode) = link.strip_prefix("pipe:") {
+        (FdKind::Pipe, format!("pipe {inode}"))
+    } else if let Some(name) = link.strip_prefix("anon_inode:") {
+        // Older kernels write "anon_inode:[eventfd]", newer ones drop the brackets
+        let name = name.trim_start_matches('[').trim_end_matches(']');
+        let kind = if name == "eventfd" { FdKind::EventFd } else { FdKind::AnonInode };
+        (kind, name.to_string())
+    } else {
+        (FdKind::File, link.to_string())
+    };
+    let deleted = kind == FdKind::File && target.ends_with(DELETED_SUFFIX);
+    let target = if deleted {
+        target.trim_end_matches(DELETED_SUFFIX).to_string()
+    } else {
+        target
+    };
+    FdEntry {
+        fd,
+        kind,
+        target,
+        deleted,
+        pos: None,
+        flags: None,
+    }
+}
+
+fn socket_inode(link: &str) -> Option<u64> {
+    link.strip_prefix("socket:[")?.strip_suffix(']')?.parse().ok()
+}
+
+/// `pos` and octal `flags` from an fdinfo file.
+fn parse_fdinfo(info: &str) -> (Option<u64>, Option<u32>) {
+    let field = |name: &str| {
+        info.lines()
+            .find_map(|line| line.strip_prefix(name))
+            .map(str::trim)
+    };
+    let pos = field("pos:").and_then(|pos| pos.parse().ok());
+    let flags = field("flags:").and_then(|flags| u32::from_str_radix(flags, 8).ok());
+    (pos, flags)
+}
+
+fn parse_open_files_limit(limits: &str) -> Option<u64> {
+    let line = limits.lines().find(|line| line.starts_with("Max open files"))?;
+    line.trim_start_matches("Max open files")
+        .split_whitespace()
+        .next()?
+        .parse()
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_fd_link() {
+        let entry = parse_fd_link(4, "/var/log/app/out.log (deleted)");
+        assert_eq!(entry.kind, FdKind::File);
+        assert_eq!(entry.target, "/var/log/app/out.log");
+        assert!(entry.deleted);
+
+        assert_eq!(parse_fd_link(5, "anon_inode:[eventfd]").kind, FdKind::EventFd);
+        assert_eq!(parse_fd_link(6, "anon_inode:inotify").target, "inotify");
+        assert_eq!(parse_fd_link(7, "pipe:[88120]").target, "pipe [88120]");
+        assert_eq!(socket_inode(&parse_fd_link(8, "socket:[91542]").target), Some(91542));
+    }
+
+    #[test]
+    fn test_parse_fdinfo_and_limits() {
+        let (pos, flags) = parse_fdinfo("pos:\t1024\nflags:\t02102001\nmnt_id:\t29\nino:\t131\n");
+        assert_eq!(pos, Some(1024));
+        let entry = FdEntry {
+            fd: 1,
+            kind: FdKind::File,
+            target: String::new(),
+            deleted: false,
+            pos,
+            flags,
+        };
+        assert_eq!(entry.flags_label().as_deref(), Some("w append cloexec"));
+
+        let limits = "Limit                     Soft Limit           Hard Limit           Units\n\
+                      Max open files            1024                 524288               files\n";
+        assert_eq!(parse_open_files_limit(limits), Some(1024));
+    }
+}
@@ -0,0 +1,515 @@
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitStatus, Stdio};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+
+/// Which output stream a log line was written to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LogStream {
+    Stdout,
+    Stderr,
+}
+
+#[derive(Clone, Debug)]
+pub struct LogLine {
+    pub stream: LogStream,
+    pub text: String,
+}
+
+/// Bounded line buffer; the oldest lines are dropped once it is full.
+pub struct LogBuffer {
+    lines: VecDeque<LogLine>,
+    capacity: usize,
+}
+
+impl LogBuffer {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            lines: VecDeque::new(),
+            capacity: capacity.max(1),
+        }
+    }
+
+    /// Appends lines and returns how many old lines were dropped to make room.
+    pub fn extend(&mut self, lines: impl IntoIterator<Item = LogLine>) -> usize {
+        let mut dropped = 0;
+        for line in lines {
+            if self.lines.len() == self.capacity {
+                self.lines.pop_front();
+                dropped += 1;
+            }
+            self.lines.push_back(line);
+        }
+        dropped
+    }
+
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    pub fn get(&self, index: usize) -> Option<&LogLine> {
+        self.lines.get(index)
+    }
+
+    /// Indices of the lines containing `needle`, ignoring ASCII case.
+    pub fn matches(&self, needle: &str) -> Vec<usize> {
+        if needle.is_empty() {
+            return Vec::new();
+        }
+        let needle = needle.to_ascii_lowercase();
+        self.lines
+            .iter()
+            .enumerate()
+            .filter(|(_, line)| line.text.to_ascii_lowercase().contains(&needle))
+            .map(|(index, _)| index)
+            .collect()
+    }
+}
+
+struct TailFile {
+    path: PathBuf,
+    stream: LogStream,
+    offset: u64,
+    /// Bytes after the last newline, completed by the next read
+    partial: Vec<u8>,
+    /// Timestamp key of the last line that carried one
+    last_key: u64,
+}
+
+/// Follows a set of log files, like `tail -F` on each of them.
+pub struct LogTail {
+    files: Vec<TailFile>,
+}
+
+impl LogTail {
+    /// Starts following `files` and returns the last `backlog_bytes` of each,
+    /// interleaved by time.
+    pub fn open(files: Vec<(PathBuf, LogStream)>, backlog_bytes: u64) -> (Self, Vec<LogLine>) {
+        let mut tail = Self {
+            files: files
+                .into_iter()
+                .map(|(path, stream)| TailFile {
+                    path,
+                    stream,
+                    offset: 0,
+                    partial: Vec::new(),
+                    last_key: 0,
+                })
+                .collect(),
+        };
+        let batches = tail
+            .files
+            .iter_mut()
+            .map(|file| read_new_lines(file, Some(backlog_bytes)))
+            .collect();
+        (tail, merge_by_time(batches))
+    }
+
+    /// Reads whatever was appended since the last call.
+    pub fn poll(&mut self) -> Vec<LogLine> {
+        let batches = self
+            .files
+            .iter_mut()
+            .map(|file| read_new_lines(file, None))
+            .collect();
+        merge_by_time(batches)
+    }
+
+    pub fn paths(&self) -> impl Iterator<Item = (&Path, LogStream)> {
+        self.files.iter().map(|file| (file.path.as_path(), file.stream))
+    }
+}
+
+enum CommandEvent {
+    Line(LogLine),
+    Exited(io::Result<ExitStatus>),
+}
+
+/// Captures the output of a command started from the TUI. The command keeps
+/// running if the output is dropped; its pipes are drained until it exits.
+pub struct CommandOutput {
+    pid: u32,
+    command_line: String,
+    dir: PathBuf,
+    events: Receiver<CommandEvent>,
+    exit: Option<ExitStatus>,
+}
+
+impl CommandOutput {
+    pub fn spawn(program: &str, args: &[String], dir: &Path) -> io::Result<Self> {
+        let mut child = Command::new(program)
+            .args(args)
+            .current_dir(dir)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+
+        let (tx, events) = mpsc::channel();
+        let mut readers = Vec::new();
+        if let Some(stdout) = child.stdout.take() {
+            readers.push(forward_lines(stdout, LogStream::Stdout, tx.clone()));
+        }
+        if let Some(stderr) = child.stderr.take() {
+            readers.push(forward_lines(stderr, LogStream::Stderr, tx.clone()));
+        }
+        let pid = child.id();
+        thread::spawn(move || {
+            // Report the exit only after the last line was forwarded
+            for reader in readers {
+                let _ = reader.join();
+            }
+            let _ = tx.send(CommandEvent::Exited(child.wait()));
+        });
+
+        Ok(Self {
+            pid,
+            command_line: std::iter::once(program)
+                .chain(args.iter().map(String::as_str))
+                .collect::<Vec<_>>()
+                .join(" "),
+            dir: dir.to_path_buf(),
+            events,
+            exit: None,
+        })
+    }
+
+    /// Lines written since the last call; a final line notes the exit status.
+    pub fn poll(&mut self) -> Vec<LogLine> {
+        let mut lines = Vec::new();
+        while let Ok(event) = self.events.try_recv() {
+            match event {
+                CommandEvent::Line(line) => lines.push(line),
+                CommandEvent::Exited(result) => {
+                    let text = match result {
+                        Ok(status) => {
+                            self.exit = Some(status);
+                            format!("[{status}]")
+                        }
+                        Err(err) => format!("[wait failed: {err}]"),
+                    };
+                    lines.push(LogLine {
+                        stream: LogStream::Stderr,
+                        text,
+                    });
+                }
+            }
+        }
+        lines
+    }
+
+    pub fn pid(&self) -> u32 {
+        self.pid
+    }
+
+    pub fn command_line(&self) -> &str {
+        &self.command_line
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn exit_status(&self) -> Option<ExitStatus> {
+        self.exit
+    }
+}
+
+fn forward_lines(
+    pipe: impl Read + Send + 'static,
+    stream: LogStream,
+    tx: Sender<CommandEvent>,
+) -> thread::JoinHandle<()> {
+    thread::spawn(move || {
+        let mut reader = BufReader::new(pipe);
+        let mut buf = Vec::new();
+        loop {
+            buf.clear();
+            match reader.read_until(b'\n', &mut buf) {
+                Ok(0) | Err(_) => break,
+                Ok(_) => {
+                    let raw = String::from_utf8_lossy(&buf);
+                    let text = clean_line(raw.trim_end_matches('\n'));
+                    // Keep draining after the pane is closed so the command never blocks
+                    let _ = tx.send(CommandEvent::Line(LogLine { stream, text }));
+                }
+            }
+        }
+    })
+}
+
+/// Where a log pane reads its lines from.
+pub enum LogSource {
+    Files(LogTail),
+    Command(CommandOutput),
+}
+
+impl LogSource {
+    pub fn poll(&mut self) -> Vec<LogLine> {
+        match self {
+            LogSource::Files(tail) => tail.poll(),
+            LogSource::Command(output) => output.poll(),
+        }
+    }
+}
+
+fn read_new_lines(file: &mut TailFile, backlog: Option<u64>) -> Vec<(u64, LogLine)> {
+    let Ok(mut handle) = File::open(&file.path) else {
+        return Vec::new();
+    };
+    let len = handle.metadata().map(|meta| meta.len()).unwrap_or(0);
+    if len < file.offset {
+        // Truncated by `pm2 flush` or replaced by log rotation
+        file.offset = 0;
+        file.partial.clear();
+    }
+
+    let mut skip_first = false;
+    if let Some(backlog) = backlog {
+        // Start one byte early so a cut exactly at a line start drops only the newline
+        file.offset = len.saturating_sub(backlog.saturating_add(1));
+        skip_first = file.offset > 0;
+    }
+    if len == file.offset || handle.seek(SeekFrom::Start(file.offset)).is_err() {
+        return Vec::new();
+    }
+
+    let mut bytes = Vec::new();
+    if handle.read_to_end(&mut bytes).is_err() {
+        return Vec::new();
+    }
+    file.offset += bytes.len() as u64;
+
+    // Keep bytes until a line is complete so a character split across reads
+    // is decoded whole
+    let mut buffer = std::mem::take(&mut file.partial);
+    buffer.extend_from_slice(&bytes);
+    let mut pieces: Vec<&[u8]> = buffer.split(|&byte| byte == b'\n').collect();
+    // The last piece is empty after a trailing newline, or an incomplete line
+    let rest = pieces.pop().unwrap_or_default().to_vec();
+    if skip_first && !pieces.is_empty() {
+        pieces.remove(0);
+    }
+
+    let lines = pieces
+        .into_iter()
+        .map(|piece| {
+            let clean = clean_line(&String::from_utf8_lossy(piece));
+            if let Some(key) = timestamp_key(&clean) {
+                file.last_key = key;
+            }
+            (
+                file.last_key,
+                LogLine {
+                    stream: file.stream,
+                    text: clean,
+                },
+            )
+        })
+        .collect();
+    file.partial = rest;
+    lines
+}
+
+/// Interleaves per-file batches by timestamp key. Each batch stays in file order,
+/// and ties go to the earlier batch.
+fn merge_by_time(batches: Vec<Vec<(u64, LogLine)>>) -> Vec<LogLine> {
+    let total = batches.iter().map(Vec::len).sum();
+    let mut merged = Vec::with_capacity(total);
+    let mut iters: Vec<_> = batches
+        .into_iter()
+        .map(|batch| batch.into_iter().peekable())
+        .collect();
+    loop {
+        let next = iters
+            .iter_mut()
+            .enumerate()
+            .filter_map(|(index, iter)| iter.peek().map(|(key, _)| (*key, index)))
+            .min();
+        let Some((_, index)) = next else {
+            break;
+        };
+        if let Some((_, line)) = iters[index].next() {
+            merged.push(line);
+        }
+    }
+    merged
+}
+
+/// Sortable key for a leading `YYYY-MM-DD[T ]HH:MM[:SS[.mmm]]` timestamp, the
+/// form PM2 writes with `--time` or a `log_date_format`.
+fn timestamp_key(text: &str) -> Option<u64> {
+    let bytes = text.trim_start().as_bytes();
+    let number = |range: std::ops::Range<usize>| -> Option<u64> {
+        let digits = bytes.get(range)?;
+        if !digits.iter().all(u8::is_ascii_digit) {
+            return None;
+        }
+        std::str::from_utf8(digits).ok()?.parse().ok()
+    };
+    let separator = |index: usize, allowed: &[u8]| {
+        bytes.get(index).is_some_and(|byte| allowed.contains(byte))
+    };
+
+    if !separator(4, b"-") || !separator(7, b"-") || !separator(10, b"T ") || !separator(13, b":") {
+        return None;
+    }
+    let date = number(0..4)? * 10_000 + number(5..7)? * 100 + number(8..10)?;
+    let minutes = number(11..13)? * 100 + number(14..16)?;
+    let seconds = if separator(16, b":") {
+        number(17..19).unwrap_or(0)
+    } else {
+        0
+    };
+    let millis = if separator(19, b".") {
+        number(20..23).unwrap_or(0)
+    } else {
+        0
+    };
+    Some(((date * 10_000 + minutes) * 100 + seconds) * 1_000 + millis)
+}
+
+/// Drops ANSI escape sequences and control characters, and expands tabs.
+fn clean_line(raw: &str) -> String {
+    let mut out = String::with_capacity(raw.len());
+    let mut chars = raw.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\u{1b}' => {
+                if chars.peek() == Some(&'[') {
+                    chars.next();
+                    // CSI sequences end with a byte in '@'..='~'
+                    for next in chars.by_ref() {
+                        if ('@'..='~').contains(&next) {
+                            break;
+                        }
+                    }
+                }
+            }
+            '\t' => out.push_str("    "),
+            ch if ch.is_control() => {}
+            ch => out.push(ch),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(key: u64, stream: LogStream, text: &str) -> (u64, LogLine) {
+        (
+            key,
+            LogLine {
+                stream,
+                text: text.to_string(),
+            },
+        )
+    }
+
+    #[test]
+    fn test_timestamp_key() {
+        assert!(timestamp_key("2024-06-04T10:12:44: listening").is_some());
+        assert!(
+            timestamp_key("2024-06-04 10:12:44.120 +02:00: a")
+                > timestamp_key("2024-06-04 10:12:44.020 +02:00: b")
+        );
+        assert!(timestamp_key("2024-06-04 10:13 +02:00: a") > timestamp_key("2024-06-04T10:12:59: b"));
+        assert_eq!(timestamp_key("GET /health 200"), None);
+        assert_eq!(timestamp_key("2024-06-04"), None);
+    }
+
+    #[test]
+    fn test_merge_by_time_interleaves_streams() {
+        let out = vec![
+            line(1, LogStream::Stdout, "out 1"),
+            line(3, LogStream::Stdout, "out 3"),
+            line(3, LogStream::Stdout, "out 3b"),
+        ];
+        let err = vec![line(2, LogStream::Stderr, "err 2"), line(3, LogStream::Stderr, "err 3")];
+        let merged: Vec<String> = merge_by_time(vec![out, err])
+            .into_iter()
+            .map(|line| line.text)
+            .collect();
+        assert_eq!(merged, vec!["out 1", "err 2", "out 3", "out 3b", "err 3"]);
+    }
+
+    #[test]
+    fn test_clean_line() {
+        assert_eq!(clean_line("\u{1b}[32minfo\u{1b}[39m:\tready\r"), "info:    ready");
+    }
+
+    #[test]
+    fn test_tail_follows_appends_and_truncation() {
+        use std::io::Write;
+
+        let path = std::env::temp_dir().join(format!("spark-logs-test-{}.log", std::process::id()));
+        std::fs::write(&path, "skipped partial\nold 1\nold 2\n").unwrap();
+
+        // A small backlog starts mid-file and drops the cut-off first line
+        let (mut tail, backlog) = LogTail::open(vec![(path.clone(), LogStream::Stdout)], 8);
+        let texts: Vec<_> = backlog.iter().map(|line| line.text.as_str()).collect();
+        assert_eq!(texts, vec!["old 2"]);
+
+        let mut file = std::fs::OpenOptions::new().append(true).open(&path).unwrap();
+        write!(file, "new 1\nnew ").unwrap();
+        let texts: Vec<_> = tail.poll().into_iter().map(|line| line.text).collect();
+        assert_eq!(texts, vec!["new 1"]);
+        writeln!(file, "2").unwrap();
+        let texts: Vec<_> = tail.poll().into_iter().map(|line| line.text).collect();
+        assert_eq!(texts, vec!["new 2"]);
+
+        std::fs::write(&path, "after flush\n").unwrap();
+        let texts: Vec<_> = tail.poll().into_iter().map(|line| line.text).collect();
+        assert_eq!(texts, vec!["after flush"]);
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_tail_keeps_split_characters_whole() {
+        use std::io::Write;
+
+        let path = std::env::temp_dir()
+            .join(format!("spark-logs-utf8-test-{}.log", std::process::id()));
+        std::fs::write(&path, "").unwrap();
+        let (mut tail, _) = LogTail::open(vec![(path.clone(), LogStream::Stdout)], 0);
+
+        let mut file = std::fs::OpenOptions::new().append(true).open(&path).unwrap();
+        let bytes = "café\n".as_bytes();
+        file.write_all(&bytes[..4]).unwrap();
+        assert!(tail.poll().is_empty());
+        file.write_all(&bytes[4..]).unwrap();
+        let texts: Vec<_> = tail.poll().into_iter().map(|line| line.text).collect();
+        assert_eq!(texts, vec!["café"]);
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_command_output_captures_both_streams() {
+        let args = vec!["-c".to_string(), "echo out; echo err >&2; exit 3".to_string()];
+        let mut output = CommandOutput::spawn("sh", &args, &std::env::temp_dir()).unwrap();
+        let mut lines = Vec::new();
+        for _ in 0..200 {
+            lines.extend(output.poll());
+            if output.exit_status().is_some() {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        assert_eq!(output.exit_status().and_then(|status| status.code()), Some(3));
+        assert!(lines.iter().any(|line| line.stream == LogStream::Stdout && line.text == "out"));
+        assert!(lines.iter().any(|line| line.stream == LogStream::Stderr && line.text == "err"));
+        // The exit note comes last
+        assert!(lines.last().is_some_and(|line| line.text.contains('3')));
+    }
+}
@@ -1,4 +1,12 @@
+pub mod apps;
 pub mod docker;
+pub mod fds;
+pub mod logs;
+pub mod net;
 pub mod node;
 pub mod ports;
+pub mod priority;
 pub mod process;
+mod stat;
+pub mod threads;
+pub mod wait;
@@ -0,0 +1,375 @@
+//! Network throughput attributed to processes.
+//!
+//! Processes in Spark's own network namespace are measured per TCP socket,
+//! using the byte counters the kernel reports through `sock_diag`. Other
+//! namespaces (usually containers) are measured as a whole from their
+//! `/proc/<pid>/net/dev` and credited to the namespace's root process.
+//! UDP traffic in Spark's namespace has no per-socket counters and is not counted.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::time::Instant;
+
+use sysinfo::{Pid, System};
+
+use super::ports;
+
+/// Receive and transmit throughput in bytes per second.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct NetRates {
+    pub rx: u64,
+    pub tx: u64,
+}
+
+impl NetRates {
+    pub fn total(&self) -> u64 {
+        self.rx + self.tx
+    }
+}
+
+/// Rates from one [`NetSampler::sample`] call.
+#[derive(Default)]
+pub struct NetSample {
+    pub by_pid: HashMap<Pid, NetRates>,
+    /// Root process of every foreign network namespace with the namespace's rates
+    pub namespace_roots: Vec<(Pid, NetRates)>,
+}
+
+/// Keeps the byte counters of the previous sample to turn them into rates.
+#[derive(Default)]
+pub struct NetSampler {
+    /// Received and sent bytes per TCP socket inode
+    sockets: HashMap<u64, (u64, u64)>,
+    /// Received and sent bytes per network namespace inode
+    namespaces: HashMap<u64, (u64, u64)>,
+    last: Option<Instant>,
+}
+
+impl NetSampler {
+    /// Reads the current counters. The first sample only records them and
+    /// reports no traffic.
+    pub fn sample(&mut self, system: &System) -> NetSample {
+        let elapsed_ms = self.last.map(|last| last.elapsed().as_millis() as u64);
+        self.last = Some(Instant::now());
+        let rate = |bytes: u64| match elapsed_ms {
+            Some(ms) if ms > 0 => bytes * 1000 / ms,
+            _ => 0,
+        };
+        let mut sample = NetSample::default();
+
+        let sockets = tcp_socket_bytes().unwrap_or_default();
+        if !sockets.is_empty() {
+            let inode_map = ports::build_inode_pid_map();
+            for (inode, &(rx, tx)) in &sockets {
+                let Some(pid) = inode_map.get(inode) else {
+                    continue;
+                };
+                // Sockets opened since the last sample count from zero
+                let (prev_rx, prev_tx) = self.sockets.get(inode).copied().unwrap_or((0, 0));
+                let entry = sample.by_pid.entry(*pid).or_default();
+                entry.rx += rate(rx.saturating_sub(prev_rx));
+                entry.tx += rate(tx.saturating_sub(prev_tx));
+            }
+        }
+        self.sockets = sockets;
+
+        let mut namespaces = HashMap::new();
+        for (pid, root_ns) in namespace_roots(system) {
+            let dev = fs::read_to_string(format!("/proc/{}/net/dev", pid.as_u32()));
+            let Some((rx, tx)) = dev.ok().map(|dev| parse_net_dev(&dev)) else {
+                continue;
+            };
+            namespaces.insert(root_ns, (rx, tx));
+            let Some(&(prev_rx, prev_tx)) = self.namespaces.get(&root_ns) else {
+                continue;
+            };
+            let rates = NetRates {
+                rx: rate(rx.saturating_sub(prev_rx)),
+                tx: rate(tx.saturating_sub(prev_tx)),
+            };
+            let entry = sample.by_pid.entry(pid).or_default();
+            entry.rx += rates.rx;
+            entry.tx += rates.tx;
+            sample.namespace_roots.push((pid, rates));
+        }
+        self.namespaces = namespaces;
+
+        sample
+    }
+}
+
+fn net_namespace(pid: &str) -> Option<u64> {
+    let link = fs::read_link(format!("/proc/{pid}/ns/net")).ok()?;
+    link.to_str()?.strip_prefix("net:[")?.strip_suffix(']')?.parse().ok()
+}
+
+/// The root process of every network namespace other than Spark's own: the
+/// process whose parent lives in another namespace, lowest PID first.
+fn namespace_roots(system: &System) -> Vec<(Pid, u64)> {
+    let Some(own) = net_namespace("self") else {
+        return Vec::new();
+    };
+    let namespaces: HashMap<Pid, u64> = system
+        .processes()
+        .iter()
+        .filter(|(_, process)| process.thread_kind().is_none())
+        .filter_map(|(pid, _)| Some((*pid, net_namespace(&pid.as_u32().to_string())?)))
+        .collect();
+    let mut roots: HashMap<u64, Pid> = HashMap::new();
+    for (pid, ns) in &namespaces {
+        if *ns == own {
+            continue;
+        }
+        let parent_ns = system
+            .process(*pid)
+            .and_then(|process| process.parent())
+            .and_then(|parent| namespaces.get(&parent));
+        if parent_ns == Some(ns) {
+            continue;
+        }
+        let root = roots.entry(*ns).or_insert(*pid);
+        if *pid < *root {
+            *root = *pid;
+        }
+    }
+    let mut roots: Vec<(Pid, u64)> = roots.into_iter().map(|(ns, pid)| (pid, ns)).collect();
+    roots.sort();
+    roots
+}
+
+/// Received and transmitted bytes summed over all interfaces except loopback.
+fn parse_net_dev(dev: &str) -> (u64, u64) {
+    let mut totals = (0, 0);
+    // Two header lines precede the interfaces
+    for line in dev.lines().skip(2) {
+        let Some((name, counters)) = line.split_once(':') else {
+            continue;
+        };
+        if name.trim() == "lo" {
+            continue;
+        }
+        let fields: Vec<u64> = counters
+            .split_whitespace()
+            .map(|field| field.parse().unwrap_or(0))
+            .collect();
+        if fields.len() >= 9 {
+            totals.0 += fields[0];
+            totals.1 += fields[8];
+        }
+    }
+    totals
+}
+
+const NLMSG_HEADER_LEN: usize = 16;
+const NLMSG_ERROR: u16 = 2;
+const NLMSG_DONE: u16 = 3;
+const SOCK_DIAG_BY_FAMILY: u16 = 20;
+const INET_DIAG_INFO: u16 = 2;
+/// Size of `struct inet_diag_msg`, after which its attributes start
+const INET_DIAG_MSG_LEN: usize = 72;
+const INET_DIAG_MSG_INODE: usize = 68;
+/// Offsets of `tcpi_bytes_acked` and `tcpi_bytes_received` in `struct tcp_info`
+const TCP_INFO_BYTES_ACKED: usize = 120;
+const TCP_INFO_BYTES_RECEIVED: usize = 128;
+
+fn align4(len: usize) -> usize {
+    (len + 3) & !3
+}
+
+fn read_u16(buf: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_ne_bytes(buf.get(offset..offset + 2)?.try_into().ok()?))
+}
+
+fn read_u32(buf: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_ne_bytes(buf.get(offset..offset + 4)?.try_into().ok()?))
+}
+
+fn read_u64(buf: &[u8], offset: usize) -> Option<u64> {
+    Some(u64::from_ne_bytes(buf.get(offset..offset + 8)?.try_into().ok()?))
+}
+
+/// Adds the received and sent bytes of each socket in a `sock_diag` reply to
+/// `sockets`. Returns whether the reply ends the dump.
+fn parse_diag_reply(buf: &[u8], sockets: &mut HashMap<u64, (u64, u64)>) -> io::Result<bool> {
+    let mut offset = 0;
+    while offset + NLMSG_HEADER_LEN <= buf.len() {
+        let len = read_u32(buf, offset).unwrap_or(0) as usize;
+        let kind = read_u16(buf, offset + 4).unwrap_or(0);
+        if len < NLMSG_HEADER_LEN || offset + len > buf.len() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "truncated netlink message"));
+        }
+        match kind {
+            NLMSG_DONE => return Ok(true),
+            NLMSG_ERROR => {
+                let errno = read_u32(buf, offset + NLMSG_HEADER_LEN).unwrap_or(0) as i32;
+                return Err(io::Error::from_raw_os_error(-errno));
+            }
+            _ => {}
+        }
+        let message = &buf[offset + NLMSG_HEADER_LEN..offset + len];
+        if let Some(inode) = read_u32(message, INET_DIAG_MSG_INODE).filter(|inode| *inode != 0) {
+            if let Some(counters) = tcp_info_bytes(message.get(INET_DIAG_MSG_LEN..).unwrap_or(&[])) {
+                sockets.insert(inode as u64, counters);
+            }
+        }
+        offset += align4(len);
+    }
+    Ok(false)
+}
+
+/// Finds the `INET_DIAG_INFO` attribute and reads its byte counters.
+fn tcp_info_bytes(mut attributes: &[u8]) -> Option<(u64, u64)> {
+    while attributes.len() >= 4 {
+        let len = read_u16(attributes, 0)? as usize;
+        let kind = read_u16(attributes, 2)?;
+        if len < 4 || len > attributes.len() {
+            return None;
+        }
+        if kind == INET_DIAG_INFO {
+            // Kernels before 4.1 report a shorter tcp_info without the counters
+            let info = &attributes[4..len];
+            return Some((read_u64(info, TCP_INFO_BYTES_RECEIVED)?, read_u64(info, TCP_INFO_BYTES_ACKED)?));
+        }
+        attributes = attributes.get(align4(len)..)?;
+    }
+    None
+}
+
+#[cfg(target_os = "linux")]
+fn tcp_socket_bytes() -> io::Result<HashMap<u64, (u64, u64)>> {
+    sys::tcp_socket_bytes()
+}
+
+#[cfg(not(target_os = "linux"))]
+fn tcp_socket_bytes() -> io::Result<HashMap<u64, (u64, u64)>> {
+    Ok(HashMap::new())
+}
+
+#[cfg(target_os = "linux")]
+mod sys {
+    use std::collections::HashMap;
+    use std::io;
+    use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+
+    use super::{parse_diag_reply, INET_DIAG_INFO, SOCK_DIAG_BY_FAMILY};
+
+    /// `struct inet_diag_req_v2` with a zeroed socket id, which matches every socket
+    #[repr(C)]
+    struct InetDiagRequest {
+        header: libc::nlmsghdr,
+        family: u8,
+        protocol: u8,
+        ext: u8,
+        pad: u8,
+        states: u32,
+        id: [u8; 48],
+    }
+
+    pub(super) fn tcp_socket_bytes() -> io::Result<HashMap<u64, (u64, u64)>> {
+        // SAFETY: socket() takes integers and returns a new descriptor or -1
+        let fd = unsafe {
+            libc::socket(libc::AF_NETLINK, libc::SOCK_DGRAM | libc::SOCK_CLOEXEC, libc::NETLINK_SOCK_DIAG)
+        };
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        // SAFETY: `fd` was just opened and is owned by nothing else
+        let socket = unsafe { OwnedFd::from_raw_fd(fd) };
+        let mut sockets = HashMap::new();
+        for family in [libc::AF_INET, libc::AF_INET6] {
+            request_dump(&socket, family as u8)?;
+            let mut buf = vec![0u8; 32 * 1024];
+            loop {
+                // SAFETY: `buf` is valid for writes of its length
+                let received = unsafe {
+                    libc::recv(socket.as_raw_fd(), buf.as_mut_ptr().cast(), buf.len(), 0)
+                };
+                if received < 0 {
+                    return Err(io::Error::last_os_error());
+                }
+                if parse_diag_reply(&buf[..received as usize], &mut sockets)? || received == 0 {
+                    break;
+                }
+            }
+        }
+        Ok(sockets)
+    }
+
+    fn request_dump(socket: &OwnedFd, family: u8) -> io::Result<()> {
+        let request = InetDiagRequest {
+            header: libc::nlmsghdr {
+                nlmsg_len: std::mem::size_of::<InetDiagRequest>() as u32,
+                nlmsg_type: SOCK_DIAG_BY_FAMILY,
+                nlmsg_flags: (libc::NLM_F_REQUEST | libc::NLM_F_DUMP) as u16,
+                nlmsg_seq: 1,
+                nlmsg_pid: 0,
+            },
+            family,
+            protocol: libc::IPPROTO_TCP as u8,
+            ext: 1 << (INET_DIAG_INFO - 1),
+            pad: 0,
+            states: u32::MAX,
+            id: [0; 48],
+        };
+        // SAFETY: sockaddr_nl is plain data, valid when zeroed; a zero nl_pid addresses the kernel
+        let mut kernel: libc::sockaddr_nl = unsafe { std::mem::zeroed() };
+        kernel.nl_family = libc::AF_NETLINK as libc::sa_family_t;
+        // SAFETY: both pointers refer to live values whose sizes are passed along
+        let sent = unsafe {
+            libc::sendto(
+                socket.as_raw_fd(),
+                (&request as *const InetDiagRequest).cast(),
+                std::mem::size_of::<InetDiagRequest>(),
+                0,
+                (&kernel as *const libc::sockaddr_nl).cast(),
+                std::mem::size_of::<libc::sockaddr_nl>() as libc::socklen_t,
+            )
+        };
+        if sent < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_net_dev() {
+        let dev = "Inter-|   Receive                                                |  Transmit\n \
+                   face |bytes    packets errs drop fifo frame compressed multicast|bytes    packets errs drop fifo colls carrier compressed\n    \
+                   lo:  5000      50    0    0    0     0          0         0     5000      50    0    0    0     0       0          0\n  \
+                   eth0: 12000     90    0    0    0     0          0         0     3400      40    0    0    0     0       0          0\n  \
+                   eth1:   800      9    0    0    0     0          0         0      100       2    0    0    0     0       0          0\n";
+        assert_eq!(parse_net_dev(dev), (12800, 3500));
+    }
+
+    #[test]
+    fn test_parse_diag_reply() {
+        let mut info = vec![0u8; 136];
+        info[TCP_INFO_BYTES_ACKED..TCP_INFO_BYTES_ACKED + 8].copy_from_slice(&700u64.to_ne_bytes());
+        info[TCP_INFO_BYTES_RECEIVED..TCP_INFO_BYTES_RECEIVED + 8]
+            .copy_from_slice(&1500u64.to_ne_bytes());
+        let mut message = vec![0u8; INET_DIAG_MSG_LEN];
+        message[INET_DIAG_MSG_INODE..INET_DIAG_MSG_INODE + 4].copy_from_slice(&4242u32.to_ne_bytes());
+        message.extend_from_slice(&((info.len() + 4) as u16).to_ne_bytes());
+        message.extend_from_slice(&INET_DIAG_INFO.to_ne_bytes());
+        message.extend_from_slice(&info);
+
+        let mut reply = Vec::new();
+        reply.extend_from_slice(&((message.len() + NLMSG_HEADER_LEN) as u32).to_ne_bytes());
+        reply.extend_from_slice(&SOCK_DIAG_BY_FAMILY.to_ne_bytes());
+        reply.extend_from_slice(&[0; 10]);
+        reply.extend_from_slice(&message);
+        reply.extend_from_slice(&(NLMSG_HEADER_LEN as u32).to_ne_bytes());
+        reply.extend_from_slice(&NLMSG_DONE.to_ne_bytes());
+        reply.extend_from_slice(&[0; 10]);
+
+        let mut sockets = HashMap::new();
+        assert!(parse_diag_reply(&reply, &mut sockets).unwrap());
+        assert_eq!(sockets.get(&4242), Some(&(1500, 700)));
+    }
+}
@@ -6,44 +6,73 @@ use std::sync::{Mutex, OnceLock};
 
 use sysinfo::{Pid, Process, System};
 
+use super::framework;
+use super::toolchain::{self, NodeToolchain, VersionManager};
 use super::NodeProcessInfo;
-
-/// Detect all Node.js processes running on the system.
-/// Filters out IDE, extension, and system processes.
-pub fn detect_node_processes(system: &System) -> Vec<NodeProcessInfo> {
-    let mut node_procs = Vec::new();
-
-    for (pid, process) in system.processes() {
-        if is_node_process(process) {
-            let script = extract_script_path(process);
-
-            // Filter out IDE/system processes - only keep project processes
-            if !is_project_process(process, &script) {
-                continue;
-            }
-
-            let node_version = detect_node_version(*pid);
-            let project_name = project_name_from_process_with_script(process, &script);
-
-            let uses_nvm = process_uses_nvm(process, &script);
-
-            node_procs.push(NodeProcessInfo {
+use crate::system::apps::{self, DetectedApp, Runtime, RuntimeDetector};
+
+/// Detect app processes of every supported runtime.
+/// Each detector filters out IDE, extension, and system processes.
+pub fn detect_app_processes(system: &System) -> Vec<NodeProcessInfo> {
+    system
+        .processes()
+        .iter()
+        .filter_map(|(pid, process)| {
+            let app = apps::detect_app(process)?;
+            let toolchain = (app.runtime == Runtime::Node).then(|| {
+                node_toolchain(process, app.project_dir.as_deref(), app.version.as_deref())
+            });
+            let framework = matches!(app.runtime, Runtime::Node | Runtime::Bun | Runtime::Deno)
+                .then(|| framework::detect_framework(process.cmd(), app.project_dir.as_deref()))
+                .flatten();
+            Some(NodeProcessInfo {
                 pid: *pid,
-                name: extract_process_name(process, &script),
-                script,
-                project_name,
-                uses_nvm,
-                node_version,
+                runtime: app.runtime,
+                name: app.name,
+                script: app.entry,
+                project_dir: app.project_dir,
+                project_name: app.project_name,
+                global_tool: app.global_tool,
+                version: app.version,
+                toolchain,
+                framework,
+                ports: Vec::new(),
                 cpu: process.cpu_usage(),
                 memory_bytes: process.memory(),
                 uptime_secs: Some(process.run_time()),
                 pm2: None,
                 worker_count: 1,
-            });
+                declared: None,
+            })
+        })
+        .collect()
+}
+
+/// Detects Node.js processes with the heuristics below.
+pub struct NodeDetector;
+
+impl RuntimeDetector for NodeDetector {
+    fn detect(&self, process: &Process) -> Option<DetectedApp> {
+        if !is_node_process(process) {
+            return None;
         }
-    }
+        let script = extract_script_path(process);
 
-    node_procs
+        // Filter out IDE/system processes - only keep project processes
+        if !is_project_process(process, &script) {
+            return None;
+        }
+
+        Some(DetectedApp {
+            runtime: Runtime::Node,
+            name: extract_process_name(process, &script),
+            project_dir: project_dir_from_process(process),
+            project_name: project_name_from_process_with_script(process, &script),
+            version: detect_node_version(process.pid()),
+            global_tool: process_runs_global_tool(process, &script),
+            entry: script,
+        })
+    }
 }
 
 /// Check if a Node process is a project process (not IDE/system).
@@ -213,8 +242,8 @@ fn is_project_process(process: &Process, script: &str) -> bool {
 fn is_node_process(process: &Process) -> bool {
     let name = process.name().to_lowercase();
 
-    // Direct node/bun/deno executables
-    if name == "node" || name == "nodejs" || name == "bun" || name == "deno" {
+    // Direct node executables; Bun and Deno have their own detectors
+    if name == "node" || name == "nodejs" {
         return true;
     }
 
@@ -230,23 +259,36 @@ fn is_node_process(process: &Process) -> bool {
             return true;
         }
         // Check for nvm/fnm/volta managed node
-        if exe_str.contains(".nvm/") || exe_str.contains(".fnm/") || exe_str.contains(".volta/") {
-            if exe_str.ends_with("/node") || exe_str.contains("/node/") {
-                return true;
-            }
+        if (exe_str.contains(".nvm/") || exe_str.contains(".fnm/") || exe_str.contains(".volta/"))
+            && (exe_str.ends_with("/node") || exe_str.contains("/node/"))
+        {
+            return true;
         }
     }
 
     false
 }
 
-fn process_uses_nvm(process: &Process, script: &str) -> bool {
+/// Directories where version managers and npm keep globally installed CLIs
+const GLOBAL_TOOL_DIRS: &[&str] = &[
+    "/.nvm/",
+    "/nvm/",
+    "/.volta/",
+    "/fnm/node-versions/",
+    "/.asdf/installs/nodejs/",
+    "/.nodenv/versions/",
+    "/n/versions/node/",
+    "/lib/node_modules/",
+];
+
+/// True for version manager processes and globally installed CLIs rather than project apps.
+fn process_runs_global_tool(process: &Process, script: &str) -> bool {
     let name = process.name().to_lowercase();
     if name == "nvm" {
         return true;
     }
     let script_lower = script.to_lowercase();
-    if script_lower.contains("/.nvm/") || script_lower.contains("/nvm/") {
+    if GLOBAL_TOOL_DIRS.iter().any(|dir| script_lower.contains(dir)) {
         return true;
     }
     for arg in process.cmd() {
@@ -376,6 +418,16 @@ fn project_name_from_process_with_script(process: &Process, script: &str) -> Opt
     project_name_from_candidates(&candidates)
 }
 
+/// Directory of the package.json that owns a process, from its script or cwd.
+fn project_dir_from_process(process: &Process) -> Option<PathBuf> {
+    let script = extract_script_path(process);
+    script_dir_from_path(&script, Some(process))
+        .into_iter()
+        .chain(read_process_cwd(process))
+        .find_map(|dir| super::scripts::find_package_json(&dir))
+        .and_then(|manifest| manifest.parent().map(Path::to_path_buf))
+}
+
 pub(crate) fn project_name_from_script(script: &str) -> Option<String> {
     let candidates = script_dir_from_path(script, None).into_iter().collect::<Vec<_>>();
     project_name_from_candidates(&candidates)
@@ -460,9 +512,7 @@ fn extract_package_name(contents: &str) -> Option<String> {
         match ch {
             '{' => depth += 1,
             '}' => {
-                if depth > 0 {
-                    depth -= 1;
-                }
+                depth = depth.saturating_sub(1);
             }
             '"' => {
                 if depth == 1 {
@@ -493,7 +543,7 @@ fn extract_package_name(contents: &str) -> Option<String> {
 fn read_json_string(iter: &mut std::iter::Peekable<std::str::Chars<'_>>) -> String {
     let mut out = String::new();
     let mut escape = false;
-    while let Some(ch) = iter.next() {
+    for ch in iter.by_ref() {
         if escape {
             out.push(ch);
             escape = false;
@@ -579,6 +629,28 @@ fn is_script_path(script: &str) -> bool {
         || lower.ends_with(".jsx")
 }
 
+/// Version manager and project version requirement of a Node process.
+fn node_toolchain(
+    process: &Process,
+    project_dir: Option<&Path>,
+    version: Option<&str>,
+) -> NodeToolchain {
+    let manager = process
+        .exe()
+        .map(|exe| toolchain::manager_for_exe(&exe.to_string_lossy()))
+        .unwrap_or(VersionManager::Other);
+    let required = project_dir.and_then(toolchain::find_requirement);
+    let satisfied = match (version, required.as_ref()) {
+        (Some(version), Some(required)) => toolchain::satisfies(version, &required.spec),
+        _ => None,
+    };
+    NodeToolchain {
+        manager,
+        required,
+        satisfied,
+    }
+}
+
 /// Try to detect the Node.js version for a process.
 fn detect_node_version(pid: Pid) -> Option<String> {
     // Try to read from /proc/[pid]/exe symlink to find the node binary
@@ -587,9 +659,9 @@ fn detect_node_version(pid: Pid) -> Option<String> {
     if let Ok(exe) = fs::read_link(&exe_path) {
         let exe_str = exe.to_string_lossy();
 
-        // Try to extract version from path (common with nvm/fnm/volta)
+        // Try to extract version from a version manager's install path
         // e.g., /home/user/.nvm/versions/node/v20.10.0/bin/node
-        if let Some(version) = extract_version_from_path(&exe_str) {
+        if let Some(version) = toolchain::version_from_exe(&exe_str) {
             return Some(version);
         }
 
@@ -602,24 +674,6 @@ fn detect_node_version(pid: Pid) -> Option<String> {
     None
 }
 
-/// Extract version from node binary path (nvm/fnm/volta style).
-fn extract_version_from_path(path: &str) -> Option<String> {
-    // Look for version patterns like v20.10.0 or 20.10.0
-    let parts: Vec<&str> = path.split('/').collect();
-
-    for part in parts {
-        if part.starts_with('v') && part.len() > 1 {
-            let rest = &part[1..];
-            if rest.chars().next().map_or(false, |c| c.is_ascii_digit()) {
-                // Looks like a version
-                return Some(part.to_string());
-            }
-        }
-    }
-
-    None
-}
-
 /// Get node version by running node --version (with simple caching).
 fn get_node_version_cached(node_path: &str) -> Option<String> {
     use std::sync::Mutex;
@@ -0,0 +1,206 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::{Mutex, OnceLock, RwLock};
+use std::thread;
+use std::time::SystemTime;
+
+use serde_json::Value;
+
+use crate::system::apps::owned_by_current_user;
+
+/// Ecosystem files picked up from project directories. Only JSON ones: loading
+/// a JavaScript file runs it, and anyone who can write to the directory of some
+/// Node process could then run code as the user running spark.
+const DISCOVERED_FILES: &[&str] = &["ecosystem.config.json", "ecosystem.json"];
+
+/// Prints the exported config as JSON; functions and other non-JSON values are dropped.
+const NODE_LOADER: &str = "const config = require(process.argv[1]); \
+     process.stdout.write(JSON.stringify(config && config.default ? config.default : config));";
+
+/// Ecosystem files passed on the command line, searched in addition to
+/// those found in project directories.
+static CONFIGURED_FILES: RwLock<Vec<PathBuf>> = RwLock::new(Vec::new());
+
+/// An app declared in a PM2 ecosystem file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EcosystemApp {
+    pub name: String,
+    pub script: Option<String>,
+    /// Working directory, resolved against the file's directory
+    pub cwd: Option<PathBuf>,
+    /// "fork" or "cluster"
+    pub exec_mode: Option<String>,
+    /// Ecosystem file declaring the app
+    pub file: PathBuf,
+}
+
+pub fn set_ecosystem_files(files: Vec<PathBuf>) {
+    let mut guard = CONFIGURED_FILES.write().unwrap_or_else(|err| err.into_inner());
+    *guard = files;
+}
+
+pub(crate) fn configured_ecosystem_files() -> Vec<PathBuf> {
+    CONFIGURED_FILES.read().unwrap_or_else(|err| err.into_inner()).clone()
+}
+
+/// JSON ecosystem file in `dir`, if any.
+pub(crate) fn find_ecosystem_file(dir: &Path) -> Option<PathBuf> {
+    DISCOVERED_FILES
+        .iter()
+        .map(|name| dir.join(name))
+        .find(|path| path.is_file())
+}
+
+type LoadResult = Result<Vec<EcosystemApp>, String>;
+
+/// Load state of one ecosystem file.
+#[derive(Default)]
+struct CacheEntry {
+    /// Last result and the modification time of the file it was read from
+    loaded: Option<(Option<SystemTime>, LoadResult)>,
+    loading: bool,
+}
+
+type Cache = HashMap<PathBuf, CacheEntry>;
+
+/// Apps declared in an ecosystem file. Files are loaded on a background thread
+/// (JavaScript ones are evaluated with `node`) and the result is kept until the
+/// file changes; until the first load finishes this returns None, and while a
+/// changed file is reloaded it returns the previous result.
+pub fn load_ecosystem_apps(file: &Path) -> Option<LoadResult> {
+    static CACHE: OnceLock<Mutex<Cache>> = OnceLock::new();
+
+    let cache = CACHE.get_or_init(|| Mutex::new(HashMap::new()));
+    let modified = fs::metadata(file).and_then(|meta| meta.modified()).ok();
+    let mut guard = cache.lock().unwrap_or_else(|err| err.into_inner());
+    let entry = guard.entry(file.to_path_buf()).or_default();
+    let current = entry
+        .loaded
+        .as_ref()
+        .is_some_and(|(loaded_modified, _)| *loaded_modified == modified);
+    if !current && !entry.loading {
+        entry.loading = true;
+        let file = file.to_path_buf();
+        thread::spawn(move || {
+            let apps = read_ecosystem_json(&file).and_then(|json| parse_ecosystem(&json, &file));
+            let mut guard = cache.lock().unwrap_or_else(|err| err.into_inner());
+            let entry = guard.entry(file).or_default();
+            entry.loaded = Some((modified, apps));
+            entry.loading = false;
+        });
+    }
+    entry.loaded.as_ref().map(|(_, apps)| apps.clone())
+}
+
+fn read_ecosystem_json(file: &Path) -> Result<String, String> {
+    if file.extension().is_some_and(|ext| ext == "json") {
+        return fs::read_to_string(file).map_err(|err| format!("{}: {err}", file.display()));
+    }
+    let absolute = fs::canonicalize(file).map_err(|err| format!("{}: {err}", file.display()))?;
+    if !may_evaluate(file, &absolute) {
+        return Err(format!(
+            "{}: not evaluated, pass it with --ecosystem or own the file",
+            file.display()
+        ));
+    }
+    let output = Command::new("node")
+        .args(["-e", NODE_LOADER])
+        .arg(&absolute)
+        .current_dir(absolute.parent().unwrap_or(Path::new("/")))
+        .output()
+        .map_err(|err| format!("failed to run node: {err}"))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let reason = stderr
+            .lines()
+            .map(str::trim)
+            .find(|line| line.contains("Error"))
+            .unwrap_or("node exited with an error");
+        return Err(format!("{}: {reason}", file.display()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// JavaScript files are only run when passed with `--ecosystem` or owned by
+/// the user running spark.
+fn may_evaluate(file: &Path, absolute: &Path) -> bool {
+    let configured = configured_ecosystem_files().iter().any(|configured| {
+        configured == file || fs::canonicalize(configured).is_ok_and(|path| path == absolute)
+    });
+    configured || owned_by_current_user(absolute)
+}
+
+/// Parses `{ "apps": [...] }` or a bare array of app declarations.
+fn parse_ecosystem(json: &str, file: &Path) -> Result<Vec<EcosystemApp>, String> {
+    let value: Value =
+        serde_json::from_str(json).map_err(|err| format!("{}: {err}", file.display()))?;
+    let apps = match &value {
+        Value::Array(apps) => apps,
+        Value::Object(config) => match config.get("apps") {
+            Some(Value::Array(apps)) => apps,
+            Some(app @ Value::Object(_)) => std::slice::from_ref(app),
+            _ => return Ok(Vec::new()),
+        },
+        _ => return Err(format!("{}: expected an object or array", file.display())),
+    };
+
+    let base = file.parent().unwrap_or(Path::new(""));
+    let text = |app: &Value, key: &str| app.get(key).and_then(Value::as_str).map(str::to_string);
+    Ok(apps
+        .iter()
+        .filter_map(|app| {
+            let script = text(app, "script");
+            // PM2 names unnamed apps after their script
+            let name = text(app, "name").or_else(|| {
+                let script = script.as_deref()?;
+                let stem = Path::new(script).file_stem()?;
+                Some(stem.to_string_lossy().into_owned())
+            })?;
+            Some(EcosystemApp {
+                name,
+                script,
+                cwd: text(app, "cwd").map(|cwd| base.join(cwd)),
+                exec_mode: text(app, "exec_mode").map(|mode| mode.trim_end_matches("_mode").to_string()),
+                file: file.to_path_buf(),
+            })
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::TempDir;
+
+    #[test]
+    fn test_parse_ecosystem() {
+        let json = r#"{"apps": [
+            {"name": "api", "script": "dist/server.js", "cwd": "services/api", "exec_mode": "cluster_mode"},
+            {"script": "./worker.js"},
+            {"args": "--no-name-or-script"}
+        ]}"#;
+        let apps = parse_ecosystem(json, Path::new("/srv/shop/ecosystem.config.js")).unwrap();
+        assert_eq!(apps.len(), 2);
+        assert_eq!(apps[0].name, "api");
+        assert_eq!(apps[0].cwd, Some(PathBuf::from("/srv/shop/services/api")));
+        assert_eq!(apps[0].exec_mode.as_deref(), Some("cluster"));
+        assert_eq!(apps[1].name, "worker");
+        assert_eq!(apps[1].file, PathBuf::from("/srv/shop/ecosystem.config.js"));
+
+        let apps = parse_ecosystem(r#"[{"name": "cron"}]"#, Path::new("/srv/ecosystem.json")).unwrap();
+        assert_eq!(apps[0].name, "cron");
+        assert!(parse_ecosystem("42", Path::new("/srv/ecosystem.json")).is_err());
+    }
+
+    #[test]
+    fn test_discovery_skips_javascript_files() {
+        let dir = TempDir::new("ecosystem");
+        fs::write(dir.join("ecosystem.config.js"), "module.exports = { apps: [] };").unwrap();
+        assert_eq!(find_ecosystem_file(&dir), None);
+
+        fs::write(dir.join("ecosystem.json"), r#"{"apps": []}"#).unwrap();
+        assert_eq!(find_ecosystem_file(&dir), Some(dir.join("ecosystem.json")));
+    }
+}
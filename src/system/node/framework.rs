@@ -0,0 +1,278 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::SystemTime;
+
+use serde_json::Value;
+
+/// Web framework or dev server an app is built on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Framework {
+    NextJs,
+    Nuxt,
+    Remix,
+    Astro,
+    SvelteKit,
+    Gatsby,
+    Angular,
+    Vite,
+    Webpack,
+    Nest,
+    Fastify,
+    Koa,
+    Hapi,
+    Express,
+}
+
+impl Framework {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Framework::NextJs => "Next.js",
+            Framework::Nuxt => "Nuxt",
+            Framework::Remix => "Remix",
+            Framework::Astro => "Astro",
+            Framework::SvelteKit => "SvelteKit",
+            Framework::Gatsby => "Gatsby",
+            Framework::Angular => "Angular",
+            Framework::Vite => "Vite",
+            Framework::Webpack => "webpack",
+            Framework::Nest => "NestJS",
+            Framework::Fastify => "Fastify",
+            Framework::Koa => "Koa",
+            Framework::Hapi => "hapi",
+            Framework::Express => "Express",
+        }
+    }
+
+    /// Port the framework listens on unless configured otherwise.
+    fn default_port(&self) -> u16 {
+        match self {
+            Framework::Astro => 4321,
+            Framework::SvelteKit | Framework::Vite => 5173,
+            Framework::Gatsby => 8000,
+            Framework::Angular => 4200,
+            Framework::Webpack => 8080,
+            _ => 3000,
+        }
+    }
+}
+
+/// Framework of one process and, when started through the framework's CLI,
+/// the command it runs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FrameworkInfo {
+    pub framework: Framework,
+    /// "dev", "start", "preview" or "build"
+    pub mode: Option<&'static str>,
+}
+
+impl FrameworkInfo {
+    /// e.g. "Next.js dev"
+    pub fn label(&self) -> String {
+        match self.mode {
+            Some(mode) => format!("{} {mode}", self.framework.label()),
+            None => self.framework.label().to_string(),
+        }
+    }
+}
+
+/// npm packages whose CLI starts a framework's server.
+const CLI_PACKAGES: &[(&str, Framework)] = &[
+    ("next", Framework::NextJs),
+    ("nuxt", Framework::Nuxt),
+    ("nuxi", Framework::Nuxt),
+    ("@remix-run/dev", Framework::Remix),
+    ("remix", Framework::Remix),
+    ("astro", Framework::Astro),
+    ("@sveltejs/kit", Framework::SvelteKit),
+    ("svelte-kit", Framework::SvelteKit),
+    ("gatsby", Framework::Gatsby),
+    ("@angular/cli", Framework::Angular),
+    ("ng", Framework::Angular),
+    ("vite", Framework::Vite),
+    ("webpack-dev-server", Framework::Webpack),
+    ("webpack-cli", Framework::Webpack),
+    ("webpack", Framework::Webpack),
+    ("@nestjs/cli", Framework::Nest),
+    ("nest", Framework::Nest),
+];
+
+/// Runtime dependencies that identify a framework, most specific first.
+/// Bundlers are left out since they are also used to build plain servers.
+const DEPENDENCIES: &[(&str, Framework)] = &[
+    ("next", Framework::NextJs),
+    ("nuxt", Framework::Nuxt),
+    ("@remix-run/node", Framework::Remix),
+    ("@remix-run/serve", Framework::Remix),
+    ("astro", Framework::Astro),
+    ("@sveltejs/kit", Framework::SvelteKit),
+    ("gatsby", Framework::Gatsby),
+    ("@angular/core", Framework::Angular),
+    ("@nestjs/core", Framework::Nest),
+    ("fastify", Framework::Fastify),
+    ("koa", Framework::Koa),
+    ("@hapi/hapi", Framework::Hapi),
+    ("express", Framework::Express),
+];
+
+/// Listeners that are never the app's own URL: the Node inspector and Vite's HMR socket.
+const AUXILIARY_PORTS: &[u16] = &[9229, 9230, 24678];
+
+/// Recognises the framework from the command line, falling back to the
+/// dependencies in the project's package.json.
+pub fn detect_framework(cmd: &[String], project_dir: Option<&Path>) -> Option<FrameworkInfo> {
+    framework_from_cmd(cmd).or_else(|| {
+        let framework = project_dir.and_then(framework_from_dependencies)?;
+        Some(FrameworkInfo { framework, mode: None })
+    })
+}
+
+/// The app's own port: the framework default when it is listening there,
+/// else the lowest port that is not an inspector or HMR socket.
+pub fn app_port(framework: Option<Framework>, ports: &[u16]) -> Option<u16> {
+    framework
+        .map(|framework| framework.default_port())
+        .filter(|port| ports.contains(port))
+        .or_else(|| ports.iter().copied().find(|port| !AUXILIARY_PORTS.contains(port)))
+}
+
+fn framework_from_cmd(cmd: &[String]) -> Option<FrameworkInfo> {
+    // Next.js renames its server process, e.g. "next-server (v14.2.3)"
+    if let Some(title) = cmd.first().filter(|title| title.starts_with("next-server")) {
+        let mode = title.contains("dev").then_some("dev");
+        return Some(FrameworkInfo { framework: Framework::NextJs, mode });
+    }
+
+    let mut args = cmd.iter().skip(1).map(String::as_str);
+    while let Some(arg) = args.next() {
+        if arg.starts_with('-') {
+            continue;
+        }
+        let Some(framework) = cli_framework(arg) else {
+            // Look past `deno run`/`bun x`, but nothing else precedes the CLI
+            if matches!(arg, "run" | "x" | "exec") {
+                continue;
+            }
+            return None;
+        };
+        let rest: Vec<&str> = args.collect();
+        let subcommand = rest.iter().find(|arg| !arg.starts_with('-')).copied();
+        let watching = rest.iter().any(|arg| matches!(*arg, "--watch" | "-w"));
+        let mode = match subcommand {
+            Some("dev" | "develop" | "serve") => Some("dev"),
+            Some("start") if watching => Some("dev"),
+            Some("start") => Some("start"),
+            Some("preview") => Some("preview"),
+            Some("build") => Some("build"),
+            // Vite and webpack-dev-server serve without a subcommand
+            _ if matches!(framework, Framework::Vite) || arg.contains("webpack-dev-server") => Some("dev"),
+            _ => None,
+        };
+        return Some(FrameworkInfo { framework, mode });
+    }
+    None
+}
+
+/// Framework whose CLI `arg` points to: `node_modules/.bin/next`,
+/// `node_modules/next/dist/bin/next`, a bare `vite` or Deno's `npm:vite`.
+fn cli_framework(arg: &str) -> Option<Framework> {
+    let package = match arg.rsplit_once("node_modules/") {
+        Some((_, rest)) => match rest.strip_prefix(".bin/") {
+            Some(bin) => bin.to_string(),
+            None => {
+                let mut parts = rest.split('/');
+                let first = parts.next()?;
+                if first.starts_with('@') {
+                    format!("{first}/{}", parts.next()?)
+                } else {
+                    first.to_string()
+                }
+            }
+        },
+        None => {
+            let name = arg.strip_prefix("npm:").unwrap_or(arg);
+            // Keep scoped names, drop version suffixes such as "vite@5"
+            let name = match name.rfind('@') {
+                Some(pos) if pos > 0 => &name[..pos],
+                _ => name,
+            };
+            if name.contains('/') && !name.starts_with('@') {
+                return None;
+            }
+            name.to_string()
+        }
+    };
+    let package = package.trim_end_matches(".js").trim_end_matches(".mjs");
+    CLI_PACKAGES
+        .iter()
+        .find(|(name, _)| *name == package)
+        .map(|(_, framework)| *framework)
+}
+
+/// Cached per project until package.json changes.
+fn framework_from_dependencies(dir: &Path) -> Option<Framework> {
+    type Cache = HashMap<PathBuf, (Option<SystemTime>, Option<Framework>)>;
+    static CACHE: OnceLock<Mutex<Cache>> = OnceLock::new();
+
+    let path = dir.join("package.json");
+    let modified = fs::metadata(&path).and_then(|meta| meta.modified()).ok();
+    let mut cache = CACHE
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .unwrap_or_else(|err| err.into_inner());
+    if let Some((cached_modified, framework)) = cache.get(&path) {
+        if *cached_modified == modified {
+            return *framework;
+        }
+    }
+    let framework = fs::read_to_string(&path)
+        .ok()
+        .and_then(|contents| parse_dependencies(&contents));
+    cache.insert(path, (modified, framework));
+    framework
+}
+
+fn parse_dependencies(package_json: &str) -> Option<Framework> {
+    let package: Value = serde_json::from_str(package_json).ok()?;
+    let dependencies = package.get("dependencies")?.as_object()?;
+    DEPENDENCIES
+        .iter()
+        .find(|(name, _)| dependencies.contains_key(*name))
+        .map(|(_, framework)| *framework)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::system::apps::cmd;
+
+    #[test]
+    fn test_framework_from_cmd() {
+        let info = framework_from_cmd(&cmd(&["node", "/srv/shop/node_modules/.bin/next", "dev", "-p", "3001"]));
+        assert_eq!(info.map(|info| info.label()).as_deref(), Some("Next.js dev"));
+
+        let info = framework_from_cmd(&cmd(&["node", "/srv/ui/node_modules/vite/bin/vite.js", "--host"]));
+        assert_eq!(info.map(|info| info.label()).as_deref(), Some("Vite dev"));
+
+        let info = framework_from_cmd(&cmd(&["node", "node_modules/@nestjs/cli/bin/nest.js", "start", "--watch"]));
+        assert_eq!(info, Some(FrameworkInfo { framework: Framework::Nest, mode: Some("dev") }));
+
+        let info = framework_from_cmd(&cmd(&["deno", "run", "-A", "npm:vite@5", "preview"]));
+        assert_eq!(info.map(|info| info.label()).as_deref(), Some("Vite preview"));
+
+        assert_eq!(framework_from_cmd(&cmd(&["node", "dist/server.js"])), None);
+    }
+
+    #[test]
+    fn test_parse_dependencies_and_port() {
+        let package = r#"{"dependencies": {"express": "^4.19.0", "@nestjs/core": "^10.0.0"},
+                          "devDependencies": {"vite": "^5.0.0"}}"#;
+        assert_eq!(parse_dependencies(package), Some(Framework::Nest));
+        assert_eq!(parse_dependencies(r#"{"devDependencies": {"next": "14"}}"#), None);
+
+        assert_eq!(app_port(Some(Framework::Vite), &[5173, 24678]), Some(5173));
+        assert_eq!(app_port(Some(Framework::NextJs), &[9229, 4000]), Some(4000));
+        assert_eq!(app_port(None, &[9229]), None);
+    }
+}
@@ -0,0 +1,148 @@
+use std::collections::HashSet;
+use std::io::{Read, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpStream};
+use std::time::{Duration, Instant};
+
+use serde_json::Value;
+use sysinfo::{Pid, Signal};
+
+use crate::system::{ports, process};
+
+/// Ports Node tries for `--inspect` by default; 9229 unless overridden.
+const INSPECTOR_PORTS: std::ops::RangeInclusive<u16> = 9229..=9329;
+const ACTIVATION_TIMEOUT: Duration = Duration::from_secs(3);
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// A running Node.js inspector session.
+#[derive(Clone, Debug)]
+pub struct InspectorTarget {
+    pub port: u16,
+    /// `ws://` URL for debuggers such as VS Code
+    pub websocket_url: String,
+    /// `devtools://` URL that opens Chrome DevTools directly
+    pub devtools_url: String,
+    /// Script the inspector reports, usually the entry point
+    pub title: Option<String>,
+}
+
+/// Activates the inspector of a running Node.js process with SIGUSR1 and waits
+/// for it to start listening. Returns the existing session if one is already open.
+pub fn activate_inspector(pid: u32) -> Result<InspectorTarget, String> {
+    let pid = Pid::from_u32(pid);
+    let before = ports::listening_ports_for_pid(pid);
+    if let Some(target) = before
+        .iter()
+        .filter(|port| INSPECTOR_PORTS.contains(port))
+        .find_map(|port| query_inspector(*port))
+    {
+        return Ok(target);
+    }
+
+    process::send_signal(pid, Signal::User1).map_err(|err| err.to_string())?;
+
+    let before: HashSet<u16> = before.into_iter().collect();
+    let started = Instant::now();
+    let mut unreachable = None;
+    while started.elapsed() < ACTIVATION_TIMEOUT {
+        std::thread::sleep(POLL_INTERVAL);
+        let mut new_ports: Vec<u16> = ports::listening_ports_for_pid(pid)
+            .into_iter()
+            .filter(|port| !before.contains(port))
+            .collect();
+        // Check the usual inspector ports first
+        new_ports.sort_by_key(|port| !INSPECTOR_PORTS.contains(port));
+        for port in new_ports {
+            match query_inspector(port) {
+                Some(target) => return Ok(target),
+                None => unreachable = Some(port),
+            }
+        }
+    }
+
+    Err(match unreachable {
+        Some(port) => format!("inspector on port {port} did not answer on 127.0.0.1"),
+        // Node prints "Starting inspector ... failed: address already in use" to stderr
+        None => "no inspector port opened; the port may be in use or SIGUSR1 is handled by the app"
+            .to_string(),
+    })
+}
+
+/// Asks the inspector HTTP endpoint for its debug targets.
+fn query_inspector(port: u16) -> Option<InspectorTarget> {
+    let addr = SocketAddr::from((Ipv4Addr::LOCALHOST, port));
+    let mut stream = TcpStream::connect_timeout(&addr, Duration::from_millis(300)).ok()?;
+    stream.set_read_timeout(Some(Duration::from_millis(500))).ok()?;
+    let request =
+        format!("GET /json/list HTTP/1.1\r\nHost: 127.0.0.1:{port}\r\nConnection: close\r\n\r\n");
+    stream.write_all(request.as_bytes()).ok()?;
+
+    let mut response = Vec::new();
+    // A read timeout after the body arrived still leaves a usable response
+    let _ = stream.read_to_end(&mut response);
+    parse_json_list(port, &String::from_utf8_lossy(&response))
+}
+
+/// Parses the HTTP response of `/json/list` into the first debug target.
+fn parse_json_list(port: u16, response: &str) -> Option<InspectorTarget> {
+    let body = response
+        .split_once("\r\n\r\n")
+        .map_or(response, |(_, body)| body);
+    let targets: Value = serde_json::from_str(body.trim()).ok()?;
+    let target = targets.as_array()?.first()?;
+
+    let websocket_url = target.get("webSocketDebuggerUrl")?.as_str()?.to_string();
+    let devtools_url = target
+        .get("devtoolsFrontendUrl")
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .unwrap_or_else(|| {
+            let ws = websocket_url.trim_start_matches("ws://");
+            format!("devtools://devtools/bundled/js_app.html?experimental=true&v8only=true&ws={ws}")
+        });
+    let title = target
+        .get("title")
+        .and_then(Value::as_str)
+        .filter(|title| !title.is_empty())
+        .map(str::to_string);
+
+    Some(InspectorTarget {
+        port,
+        websocket_url,
+        devtools_url,
+        title,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_json_list_response() {
+        let response = "HTTP/1.0 200 OK\r\nContent-Type: application/json; charset=UTF-8\r\n\r\n\
+            [ {\n  \"description\": \"node.js instance\",\n  \
+            \"devtoolsFrontendUrl\": \"devtools://devtools/bundled/js_app.html?experimental=true&v8only=true&ws=127.0.0.1:9229/0f2c9ba8-5d7b-4c1e-9d3a-6a4f2b1c8e77\",\n  \
+            \"id\": \"0f2c9ba8-5d7b-4c1e-9d3a-6a4f2b1c8e77\",\n  \
+            \"title\": \"/srv/api/dist/server.js\",\n  \"type\": \"node\",\n  \
+            \"webSocketDebuggerUrl\": \"ws://127.0.0.1:9229/0f2c9ba8-5d7b-4c1e-9d3a-6a4f2b1c8e77\"\n} ]\n";
+        let target = parse_json_list(9229, response).unwrap();
+        assert_eq!(target.port, 9229);
+        assert_eq!(
+            target.websocket_url,
+            "ws://127.0.0.1:9229/0f2c9ba8-5d7b-4c1e-9d3a-6a4f2b1c8e77"
+        );
+        assert!(target.devtools_url.starts_with("devtools://devtools/bundled/js_app.html"));
+        assert_eq!(target.title.as_deref(), Some("/srv/api/dist/server.js"));
+    }
+
+    #[test]
+    fn test_parse_json_list_builds_devtools_url() {
+        let response = "HTTP/1.1 200 OK\r\n\r\n[{\"webSocketDebuggerUrl\":\"ws://127.0.0.1:9230/abc\"}]";
+        let target = parse_json_list(9230, response).unwrap();
+        assert_eq!(
+            target.devtools_url,
+            "devtools://devtools/bundled/js_app.html?experimental=true&v8only=true&ws=127.0.0.1:9230/abc"
+        );
+        assert!(parse_json_list(9230, "HTTP/1.1 404 Not Found\r\n\r\n").is_none());
+    }
+}
@@ -1,23 +1,55 @@
 mod detect;
+mod ecosystem;
+mod framework;
+mod inspector;
 mod pm2;
+mod scripts;
+mod toolchain;
 
-use std::collections::HashMap;
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
 
 use sysinfo::Pid;
 
-pub use detect::detect_node_processes;
-pub(crate) use detect::project_name_from_process;
-pub use pm2::{is_pm2_running, load_pm2_processes, Pm2Process};
+use crate::app::{NodeSortBy, SortOrder, ViewSort};
+use crate::system::apps::{self, Runtime};
+use crate::system::ports;
+use crate::util::{apply_filter, FieldValue, Filterable, Query};
 
-/// Information about a Node.js process, optionally enriched with PM2 data.
+pub use detect::{detect_app_processes, NodeDetector};
+pub(crate) use detect::project_name_from_process;
+pub use ecosystem::{load_ecosystem_apps, set_ecosystem_files, EcosystemApp};
+pub use framework::FrameworkInfo;
+pub use inspector::{activate_inspector, InspectorTarget};
+pub use pm2::{
+    default_pm2_log_path, is_pm2_running, load_pm2_processes, run_pm2_action, start_ecosystem_app, Pm2Action,
+    Pm2Process, Pm2Versioning,
+};
+pub use scripts::{load_package_scripts, PackageScripts};
+pub use toolchain::NodeToolchain;
+
+/// Information about an app process of any runtime, optionally enriched with PM2 data.
 #[derive(Clone, Debug)]
 pub struct NodeProcessInfo {
     pub pid: Pid,
+    pub runtime: Runtime,
     pub name: String,
+    /// Entry point: script, module, JAR or binary
     pub script: String,
+    pub project_dir: Option<PathBuf>,
     pub project_name: Option<String>,
-    pub uses_nvm: bool,
-    pub node_version: Option<String>,
+    /// Version manager process or globally installed CLI, listed under UTILS
+    pub global_tool: bool,
+    /// Runtime version, e.g. "v20.11.1" for Node or "3.12" for Python
+    pub version: Option<String>,
+    /// Version manager and required version, for Node processes
+    pub toolchain: Option<NodeToolchain>,
+    /// Web framework or dev server, for JavaScript runtimes
+    pub framework: Option<FrameworkInfo>,
+    /// TCP ports the process or its children listen on
+    pub ports: Vec<u16>,
     pub cpu: f32,
     pub memory_bytes: u64,
     pub uptime_secs: Option<u64>,
@@ -25,6 +57,114 @@ pub struct NodeProcessInfo {
     pub pm2: Option<Pm2Info>,
     /// Number of worker processes (for cluster mode, 1 = single process)
     pub worker_count: usize,
+    /// Ecosystem file entry of an app PM2 does not know about yet (not running)
+    pub declared: Option<EcosystemApp>,
+}
+
+impl NodeProcessInfo {
+    /// Runtime, version and Node version manager, e.g. "Node v20.11.1 nvm".
+    pub fn runtime_label(&self) -> String {
+        let mut label = self.runtime.label().to_string();
+        if let Some(version) = &self.version {
+            label.push(' ');
+            label.push_str(version);
+        }
+        if let Some(toolchain) = &self.toolchain {
+            label.push(' ');
+            label.push_str(toolchain.manager.label());
+        }
+        label
+    }
+
+    /// Local URL of the app's own listening port, e.g. "http://localhost:3000".
+    pub fn url(&self) -> Option<String> {
+        let framework = self.framework.map(|info| info.framework);
+        framework::app_port(framework, &self.ports).map(|port| format!("http://localhost:{port}"))
+    }
+
+    /// Whether the running Node version does not satisfy the project's requirement.
+    pub fn is_version_mismatch(&self) -> bool {
+        self.toolchain.as_ref().is_some_and(NodeToolchain::is_mismatch)
+    }
+
+    /// KEY=VALUE lines for the app detail pane.
+    pub fn detail_lines(&self) -> Vec<String> {
+        let text = |value: Option<&str>| value.unwrap_or("-").to_string();
+        let mut lines = vec![
+            format!("NAME={}", self.name),
+            format!("PID={}", self.pid),
+            format!("RUNTIME={}", self.runtime.label()),
+            format!("VERSION={}", text(self.version.as_deref())),
+            format!("ENTRY={}", self.script),
+            format!("PROJECT={}", text(self.project_name.as_deref())),
+            format!(
+                "PROJECT_DIR={}",
+                self.project_dir
+                    .as_ref()
+                    .map_or_else(|| "-".to_string(), |dir| dir.display().to_string())
+            ),
+            format!("UPTIME={}", format_uptime(self.uptime_secs)),
+            format!(
+                "FRAMEWORK={}",
+                self.framework.map_or_else(|| "-".to_string(), |info| info.label())
+            ),
+            format!("URL={}", self.url().unwrap_or_else(|| "-".to_string())),
+            format!("PORTS={}", join_ports(&self.ports)),
+        ];
+        if let Some(toolchain) = &self.toolchain {
+            lines.extend(toolchain.detail_lines());
+        }
+        if let Some(declared) = &self.declared {
+            lines.push(format!("ECOSYSTEM_FILE={}", declared.file.display()));
+            lines.push(format!("EXEC_MODE={}", declared.exec_mode.as_deref().unwrap_or("fork")));
+        }
+        lines
+    }
+}
+
+impl Filterable for NodeProcessInfo {
+    const FIELDS: &'static [&'static str] = &[
+        "name", "script", "pid", "runtime", "framework", "port", "project", "status", "mode", "cpu",
+        "mem",
+    ];
+
+    fn search_text(&self) -> Vec<Cow<'_, str>> {
+        let mut text = vec![
+            Cow::Borrowed(self.name.as_str()),
+            Cow::Borrowed(self.script.as_str()),
+            Cow::Owned(self.pid.to_string()),
+            Cow::Borrowed(self.runtime.label()),
+        ];
+        text.extend(self.framework.map(|info| Cow::Owned(info.label())));
+        text.extend(self.ports.iter().map(|port| Cow::Owned(port.to_string())));
+        text.extend(self.project_name.as_deref().map(Cow::Borrowed));
+        if let Some(pm2) = &self.pm2 {
+            text.push(Cow::Borrowed(pm2.status.as_str()));
+            text.push(Cow::Borrowed(pm2.mode.as_str()));
+        }
+        text
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn field(&self, name: &str) -> Option<FieldValue<'_>> {
+        Some(match name {
+            "name" => FieldValue::Text(Cow::Borrowed(&self.name)),
+            "script" => FieldValue::Text(Cow::Borrowed(&self.script)),
+            "pid" => FieldValue::Number(f64::from(self.pid.as_u32())),
+            "runtime" => FieldValue::Text(Cow::Owned(self.runtime_label())),
+            "framework" => FieldValue::Text(Cow::Owned(self.framework?.label())),
+            "port" => FieldValue::Numbers(self.ports.iter().map(|port| f64::from(*port)).collect()),
+            "project" => FieldValue::Text(Cow::Borrowed(self.project_name.as_deref()?)),
+            "status" => FieldValue::Text(Cow::Borrowed(&self.pm2.as_ref()?.status)),
+            "mode" => FieldValue::Text(Cow::Borrowed(&self.pm2.as_ref()?.mode)),
+            "cpu" => FieldValue::Number(f64::from(self.cpu)),
+            "mem" => FieldValue::Number(self.memory_bytes as f64),
+            _ => return None,
+        })
+    }
 }
 
 /// PM2-specific information for a process.
@@ -36,6 +176,90 @@ pub struct Pm2Info {
     pub status: String,     // "online", "stopped", "errored"
     pub restarts: u32,
     pub pm2_uptime: Option<u64>,
+    pub script: Option<String>,
+    pub cwd: Option<String>,
+    pub node_args: Vec<String>,
+    pub watch: bool,
+    pub unstable_restarts: u32,
+    pub created_at: Option<u64>,
+    pub versioning: Option<Pm2Versioning>,
+    pub max_memory_restart: Option<u64>,
+    pub instance_var: Option<String>,
+    pub out_log: Option<String>,
+    pub err_log: Option<String>,
+}
+
+impl From<&Pm2Process> for Pm2Info {
+    fn from(proc: &Pm2Process) -> Self {
+        Pm2Info {
+            pm_id: proc.pm_id,
+            name: proc.name.clone(),
+            mode: proc.mode.clone(),
+            status: proc.status.clone(),
+            restarts: proc.restarts,
+            pm2_uptime: proc.uptime_ms.map(|ms| ms / 1000),
+            script: proc.script.clone(),
+            cwd: proc.cwd.clone(),
+            node_args: proc.node_args.clone(),
+            watch: proc.watch,
+            unstable_restarts: proc.unstable_restarts,
+            created_at: proc.created_at,
+            versioning: proc.versioning.clone(),
+            max_memory_restart: proc.max_memory_restart,
+            instance_var: proc.instance_var.clone(),
+            out_log: proc.out_log.clone(),
+            err_log: proc.err_log.clone(),
+        }
+    }
+}
+
+impl Pm2Info {
+    /// KEY=VALUE lines for the PM2 detail pane.
+    pub fn detail_lines(&self) -> Vec<String> {
+        let text = |value: &Option<String>| value.clone().unwrap_or_else(|| "-".to_string());
+        let now_secs = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let created = self
+            .created_at
+            .map(|ms| format!("{} ago", format_uptime(Some(now_secs.saturating_sub(ms / 1000)))))
+            .unwrap_or_else(|| "-".to_string());
+        let max_memory = self
+            .max_memory_restart
+            .map(|bytes| format!("{:.1} MB", bytes as f64 / 1024.0 / 1024.0))
+            .unwrap_or_else(|| "-".to_string());
+        let node_args = if self.node_args.is_empty() {
+            "-".to_string()
+        } else {
+            self.node_args.join(" ")
+        };
+
+        let mut lines = vec![
+            format!("NAME={}", self.name),
+            format!("PM_ID={}", self.pm_id),
+            format!("STATUS={}", self.status),
+            format!("MODE={}", self.mode),
+            format!("SCRIPT={}", text(&self.script)),
+            format!("CWD={}", text(&self.cwd)),
+            format!("NODE_ARGS={node_args}"),
+            format!("WATCH={}", if self.watch { "on" } else { "off" }),
+            format!("RESTARTS={}", self.restarts),
+            format!("UNSTABLE_RESTARTS={}", self.unstable_restarts),
+            format!("UPTIME={}", format_uptime(self.pm2_uptime)),
+            format!("CREATED={created}"),
+            format!("MAX_MEMORY_RESTART={max_memory}"),
+            format!("INSTANCE_VAR={}", text(&self.instance_var)),
+            format!("OUT_LOG={}", text(&self.out_log)),
+            format!("ERR_LOG={}", text(&self.err_log)),
+        ];
+        if let Some(versioning) = &self.versioning {
+            lines.push(format!("GIT_BRANCH={}", text(&versioning.branch)));
+            lines.push(format!("GIT_REVISION={}", text(&versioning.revision)));
+            lines.push(format!("GIT_REMOTE={}", text(&versioning.url)));
+        }
+        lines
+    }
 }
 
 pub enum NodeRow {
@@ -48,14 +272,14 @@ pub enum NodeRow {
     UtilsSeparator,
 }
 
-/// Collect all Node.js processes, merging with PM2 data if available.
+/// Collect all app processes, merging with PM2 data if available.
 /// Groups cluster workers together to avoid duplicates.
 pub fn collect_node_processes(
     system: &sysinfo::System,
-    filter: &str,
+    query: &Query,
 ) -> Vec<NodeProcessInfo> {
-    // First, detect all Node.js processes from the system
-    let mut node_procs = detect_node_processes(system);
+    // First, detect all app processes from the system
+    let mut node_procs = detect_app_processes(system);
 
     // Try to load PM2 process list (gracefully handle errors)
     let pm2_procs = load_pm2_processes().unwrap_or_default();
@@ -70,14 +294,7 @@ pub fn collect_node_processes(
     for node_proc in &mut node_procs {
         if let Some(pm2_proc) = pm2_by_pid.get(&node_proc.pid) {
             node_proc.name = pm2_proc.name.clone();
-            node_proc.pm2 = Some(Pm2Info {
-                pm_id: pm2_proc.pm_id,
-                name: pm2_proc.name.clone(),
-                mode: pm2_proc.mode.clone(),
-                status: pm2_proc.status.clone(),
-                restarts: pm2_proc.restarts,
-                pm2_uptime: pm2_proc.uptime_ms.map(|ms| ms / 1000),
-            });
+            node_proc.pm2 = Some(Pm2Info::from(*pm2_proc));
             if let Some(uptime_ms) = pm2_proc.uptime_ms {
                 node_proc.uptime_secs = Some(uptime_ms / 1000);
             }
@@ -100,83 +317,206 @@ pub fn collect_node_processes(
             let project_name = detect::project_name_from_script(&script);
             node_procs.push(NodeProcessInfo {
                 pid: Pid::from_u32(pm2_proc.pid.unwrap_or(0)),
+                runtime: Runtime::Node,
                 name: pm2_proc.name.clone(),
                 script,
+                project_dir: pm2_proc.cwd.as_ref().map(PathBuf::from),
                 project_name,
-                uses_nvm: false,
-                node_version: None,
+                global_tool: false,
+                version: None,
+                toolchain: None,
+                framework: None,
+                ports: Vec::new(),
                 cpu: pm2_proc.cpu.unwrap_or(0.0),
                 memory_bytes: pm2_proc.memory_bytes.unwrap_or(0),
                 uptime_secs: pm2_proc.uptime_ms.map(|ms| ms / 1000),
-                pm2: Some(Pm2Info {
-                    pm_id: pm2_proc.pm_id,
-                    name: pm2_proc.name.clone(),
-                    mode: pm2_proc.mode.clone(),
-                    status: pm2_proc.status.clone(),
-                    restarts: pm2_proc.restarts,
-                    pm2_uptime: pm2_proc.uptime_ms.map(|ms| ms / 1000),
-                }),
+                pm2: Some(Pm2Info::from(pm2_proc)),
                 worker_count: 1,
+                declared: None,
             });
         }
     }
 
+    // Add apps declared in ecosystem files that PM2 has never been told about
+    let known_names: HashSet<&str> = pm2_procs.iter().map(|p| p.name.as_str()).collect();
+    let mut declared = Vec::new();
+    for app in declared_ecosystem_apps(&node_procs) {
+        if known_names.contains(app.name.as_str()) {
+            continue;
+        }
+        let project_dir = app
+            .cwd
+            .clone()
+            .or_else(|| app.file.parent().map(PathBuf::from));
+        declared.push(NodeProcessInfo {
+            pid: Pid::from_u32(0),
+            runtime: Runtime::Node,
+            name: app.name.clone(),
+            script: app.script.clone().unwrap_or_else(|| "-".to_string()),
+            project_name: project_dir.as_deref().and_then(apps::dir_name),
+            project_dir,
+            global_tool: false,
+            version: None,
+            toolchain: None,
+            framework: None,
+            ports: Vec::new(),
+            cpu: 0.0,
+            memory_bytes: 0,
+            uptime_secs: None,
+            pm2: None,
+            worker_count: 1,
+            declared: Some(app),
+        });
+    }
+    node_procs.extend(declared);
+    attach_listening_ports(system, &mut node_procs);
+
     // Group cluster workers by script path (for non-PM2 processes)
     // PM2 processes are already unique per pm_id
     node_procs = group_cluster_workers(node_procs);
 
-    // Apply filter if provided
-    if !filter.is_empty() {
-        let filter_lower = filter.to_lowercase();
-        node_procs.retain(|p| {
-            p.name.to_lowercase().contains(&filter_lower)
-                || p.script.to_lowercase().contains(&filter_lower)
-                || p.pid.to_string().contains(&filter_lower)
-                || p.project_name
-                    .as_deref()
-                    .map_or(false, |name| name.to_lowercase().contains(&filter_lower))
-                || p.pm2.as_ref().map_or(false, |pm2| {
-                    pm2.status.to_lowercase().contains(&filter_lower)
-                        || pm2.mode.to_lowercase().contains(&filter_lower)
-                })
-        });
-    }
+    apply_filter(&mut node_procs, query);
 
-    // Sort by PM2 ID if available, then by PID
-    node_procs.sort_by(|a, b| {
-        match (&a.pm2, &b.pm2) {
-            (Some(a_pm2), Some(b_pm2)) => a_pm2.pm_id.cmp(&b_pm2.pm_id),
-            (Some(_), None) => std::cmp::Ordering::Less,
-            (None, Some(_)) => std::cmp::Ordering::Greater,
-            (None, None) => a.pid.cmp(&b.pid),
+    node_procs
+}
+
+/// Orders apps before grouping; groups then follow their first app.
+pub fn sort_node_processes(procs: &mut [NodeProcessInfo], sort: ViewSort<NodeSortBy>) {
+    procs.sort_by(|a, b| {
+        let ordering = match sort.by {
+            NodeSortBy::Id => Ordering::Equal,
+            NodeSortBy::Status => status_label(a).cmp(status_label(b)),
+            NodeSortBy::Restarts => restarts(a).cmp(&restarts(b)),
+            NodeSortBy::Uptime => uptime_secs(a).cmp(&uptime_secs(b)),
+            NodeSortBy::Cpu => a.cpu.total_cmp(&b.cpu),
+            NodeSortBy::Memory => a.memory_bytes.cmp(&b.memory_bytes),
+            NodeSortBy::Pid => a.pid.cmp(&b.pid),
+            NodeSortBy::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+        }
+        .then_with(|| compare_by_id(a, b));
+        match sort.order {
+            SortOrder::Asc => ordering,
+            SortOrder::Desc => ordering.reverse(),
         }
     });
+}
 
-    node_procs
+/// PM2 apps by ID, then declared apps by name, then the rest by PID
+fn compare_by_id(a: &NodeProcessInfo, b: &NodeProcessInfo) -> Ordering {
+    match (&a.pm2, &b.pm2) {
+        (Some(a_pm2), Some(b_pm2)) => a_pm2.pm_id.cmp(&b_pm2.pm_id),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => match (&a.declared, &b.declared) {
+            (Some(_), Some(_)) => a.name.cmp(&b.name),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => a.pid.cmp(&b.pid),
+        },
+    }
+}
+
+fn status_label(proc: &NodeProcessInfo) -> &str {
+    match (&proc.pm2, &proc.declared) {
+        (Some(pm2), _) => &pm2.status,
+        (None, Some(_)) => "declared",
+        (None, None) => "-",
+    }
+}
+
+fn restarts(proc: &NodeProcessInfo) -> u32 {
+    proc.pm2.as_ref().map_or(0, |pm2| pm2.restarts)
+}
+
+fn uptime_secs(proc: &NodeProcessInfo) -> Option<u64> {
+    proc.pm2.as_ref().and_then(|pm2| pm2.pm2_uptime).or(proc.uptime_secs)
+}
+
+/// Fills in the ports each app listens on, including those of child processes
+/// since dev servers such as `next dev` serve from a forked worker.
+fn attach_listening_ports(system: &sysinfo::System, procs: &mut [NodeProcessInfo]) {
+    let by_pid = ports::listening_ports_by_pid();
+    if by_pid.is_empty() {
+        return;
+    }
+    let mut children: HashMap<Pid, Vec<Pid>> = HashMap::new();
+    for (pid, process) in system.processes() {
+        if let Some(parent) = process.parent() {
+            children.entry(parent).or_default().push(*pid);
+        }
+    }
+    // PID 0 marks apps that are not running
+    for proc in procs.iter_mut().filter(|proc| proc.pid.as_u32() != 0) {
+        let mut stack = vec![proc.pid];
+        let mut ports = Vec::new();
+        while let Some(pid) = stack.pop() {
+            ports.extend(by_pid.get(&pid).into_iter().flatten().copied());
+            stack.extend(children.get(&pid).into_iter().flatten().copied());
+        }
+        ports.sort_unstable();
+        ports.dedup();
+        proc.ports = ports;
+    }
+}
+
+/// Apps from the configured ecosystem files and from the JSON ones found in the
+/// project directories of running Node apps, first declaration of a name wins.
+fn declared_ecosystem_apps(procs: &[NodeProcessInfo]) -> Vec<EcosystemApp> {
+    let mut files = ecosystem::configured_ecosystem_files();
+    for proc in procs {
+        if proc.runtime != Runtime::Node {
+            continue;
+        }
+        let dir = proc
+            .pm2
+            .as_ref()
+            .and_then(|pm2| pm2.cwd.as_deref().map(PathBuf::from))
+            .or_else(|| proc.project_dir.clone());
+        if let Some(file) = dir.as_deref().and_then(ecosystem::find_ecosystem_file) {
+            if !files.contains(&file) {
+                files.push(file);
+            }
+        }
+    }
+
+    let mut seen = HashSet::new();
+    files
+        .iter()
+        .filter_map(|file| load_ecosystem_apps(file)?.ok())
+        .flatten()
+        .filter(|app| seen.insert(app.name.clone()))
+        .collect()
+}
+
+fn join_ports(ports: &[u16]) -> String {
+    if ports.is_empty() {
+        return "-".to_string();
+    }
+    ports.iter().map(u16::to_string).collect::<Vec<_>>().join(",")
 }
 
 pub fn is_node_util(proc: &NodeProcessInfo) -> bool {
-    proc.uses_nvm
+    proc.global_tool
         || contains_token(&proc.name, "nvm")
         || contains_token(&proc.script, "nvm")
         || proc
             .project_name
             .as_deref()
-            .map_or(false, |name| contains_token(name, "nvm"))
+            .is_some_and(|name| contains_token(name, "nvm"))
         || contains_cli(&proc.name)
         || contains_cli(&proc.script)
         || proc
             .project_name
             .as_deref()
-            .map_or(false, |name| contains_cli(name))
+            .is_some_and(contains_cli)
 }
 
 /// Group cluster workers running the same script into a single entry.
 /// Aggregates CPU and memory, keeps the lowest PID as representative.
 fn group_cluster_workers(procs: Vec<NodeProcessInfo>) -> Vec<NodeProcessInfo> {
-    // PM2 processes have their own grouping, skip them
+    // PM2 processes have their own grouping and declared apps are not running, skip them
     let (pm2_procs, non_pm2_procs): (Vec<_>, Vec<_>) =
-        procs.into_iter().partition(|p| p.pm2.is_some());
+        procs.into_iter().partition(|p| p.pm2.is_some() || p.declared.is_some());
 
     // Group non-PM2 processes by script path
     let mut groups: HashMap<String, Vec<NodeProcessInfo>> = HashMap::new();
@@ -198,14 +538,18 @@ fn group_cluster_workers(procs: Vec<NodeProcessInfo>) -> Vec<NodeProcessInfo> {
             let total_cpu: f32 = group.iter().map(|p| p.cpu).sum();
             let total_memory: u64 = group.iter().map(|p| p.memory_bytes).sum();
             let worker_count = group.len();
-            let uses_nvm = group.iter().any(|p| p.uses_nvm);
+            let global_tool = group.iter().any(|p| p.global_tool);
+            let mut ports: Vec<u16> = group.iter().flat_map(|p| p.ports.iter().copied()).collect();
+            ports.sort_unstable();
+            ports.dedup();
 
             // Use first (lowest PID) as the primary
             let mut primary = group.remove(0);
             primary.cpu = total_cpu;
             primary.memory_bytes = total_memory;
             primary.worker_count = worker_count;
-            primary.uses_nvm = uses_nvm;
+            primary.global_tool = global_tool;
+            primary.ports = ports;
 
             merged.push(primary);
         }
@@ -375,3 +719,98 @@ pub fn format_uptime(secs: Option<u64>) -> String {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn app(pid: u32, name: &str, cpu: f32) -> NodeProcessInfo {
+        NodeProcessInfo {
+            pid: Pid::from_u32(pid),
+            runtime: Runtime::Node,
+            name: name.to_string(),
+            script: String::new(),
+            project_dir: None,
+            project_name: None,
+            global_tool: false,
+            version: None,
+            toolchain: None,
+            framework: None,
+            ports: Vec::new(),
+            cpu,
+            memory_bytes: 0,
+            uptime_secs: None,
+            pm2: None,
+            worker_count: 1,
+            declared: None,
+        }
+    }
+
+    fn pm2_app(pid: u32, name: &str, pm_id: u32, restarts: u32) -> NodeProcessInfo {
+        NodeProcessInfo {
+            pm2: Some(Pm2Info {
+                pm_id,
+                name: name.to_string(),
+                mode: "fork".to_string(),
+                status: "online".to_string(),
+                restarts,
+                pm2_uptime: None,
+                script: None,
+                cwd: None,
+                node_args: Vec::new(),
+                watch: false,
+                unstable_restarts: 0,
+                created_at: None,
+                versioning: None,
+                max_memory_restart: None,
+                instance_var: None,
+                out_log: None,
+                err_log: None,
+            }),
+            ..app(pid, name, 0.0)
+        }
+    }
+
+    fn declared_app(name: &str) -> NodeProcessInfo {
+        NodeProcessInfo {
+            declared: Some(EcosystemApp {
+                name: name.to_string(),
+                script: None,
+                cwd: None,
+                exec_mode: None,
+                file: PathBuf::from("/srv/ecosystem.json"),
+            }),
+            ..app(0, name, 0.0)
+        }
+    }
+
+    fn names(procs: &[NodeProcessInfo]) -> Vec<&str> {
+        procs.iter().map(|proc| proc.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_sort_node_processes() {
+        let mut procs = vec![
+            app(900, "vite", 12.0),
+            declared_app("worker"),
+            pm2_app(400, "api", 1, 3),
+            app(300, "next", 12.0),
+            declared_app("cron"),
+            pm2_app(500, "web", 0, 0),
+        ];
+
+        // PM2 apps by id, then declared apps by name, then the rest by PID
+        sort_node_processes(&mut procs, ViewSort::new(NodeSortBy::Id));
+        assert_eq!(
+            names(&procs),
+            ["web", "api", "cron", "worker", "next", "vite"]
+        );
+
+        // Equal CPU falls back to the id order, reversed along with the key
+        sort_node_processes(&mut procs, ViewSort::new(NodeSortBy::Cpu));
+        assert_eq!(names(&procs)[..2], ["vite", "next"]);
+
+        sort_node_processes(&mut procs, ViewSort::new(NodeSortBy::Restarts));
+        assert_eq!(names(&procs)[0], "api");
+    }
+}
@@ -1,5 +1,8 @@
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
+use serde_json::Value;
+
 /// PM2 process information.
 #[derive(Clone, Debug)]
 pub struct Pm2Process {
@@ -13,6 +16,30 @@ pub struct Pm2Process {
     pub restarts: u32,
     pub uptime_ms: Option<u64>,
     pub script: Option<String>,
+    /// Working directory the app was started in (`pm_cwd`)
+    pub cwd: Option<String>,
+    pub node_args: Vec<String>,
+    pub watch: bool,
+    /// Restarts that happened before `min_uptime` was reached
+    pub unstable_restarts: u32,
+    /// Epoch milliseconds when the app was first added to PM2
+    pub created_at: Option<u64>,
+    pub versioning: Option<Pm2Versioning>,
+    /// Memory threshold in bytes that triggers an automatic restart
+    pub max_memory_restart: Option<u64>,
+    /// Env var carrying the cluster instance number, e.g. "NODE_APP_INSTANCE"
+    pub instance_var: Option<String>,
+    /// Log files as resolved by PM2, honouring `out_file`/`error_file`
+    pub out_log: Option<String>,
+    pub err_log: Option<String>,
+}
+
+/// Source control metadata PM2 records for apps started from a git checkout.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Pm2Versioning {
+    pub branch: Option<String>,
+    pub revision: Option<String>,
+    pub url: Option<String>,
 }
 
 /// Check if PM2 daemon is running.
@@ -51,6 +78,102 @@ pub fn load_pm2_processes() -> Result<Vec<Pm2Process>, Pm2Error> {
     parse_pm2_json(&stdout)
 }
 
+/// Log file PM2 uses when none is configured: `$PM2_HOME/logs/<name>-<suffix>.log`.
+pub fn default_pm2_log_path(name: &str, suffix: &str) -> Option<PathBuf> {
+    let home = std::env::var_os("PM2_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".pm2")))?;
+    Some(home.join("logs").join(format!("{name}-{suffix}.log")))
+}
+
+/// Process control commands offered for PM2-managed apps.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Pm2Action {
+    Restart,
+    /// Zero-downtime reload (falls back to restart for fork mode)
+    Reload,
+    Stop,
+    Delete,
+    ScaleUp,
+    ScaleDown,
+    Flush,
+}
+
+impl Pm2Action {
+    /// Progress text, e.g. "Restarting"
+    pub fn verb(self) -> &'static str {
+        match self {
+            Pm2Action::Restart => "Restarting",
+            Pm2Action::Reload => "Reloading",
+            Pm2Action::Stop => "Stopping",
+            Pm2Action::Delete => "Deleting",
+            Pm2Action::ScaleUp => "Scaling up",
+            Pm2Action::ScaleDown => "Scaling down",
+            Pm2Action::Flush => "Flushing logs of",
+        }
+    }
+
+    /// Completion text, e.g. "Restarted"
+    pub fn past(self) -> &'static str {
+        match self {
+            Pm2Action::Restart => "Restarted",
+            Pm2Action::Reload => "Reloaded",
+            Pm2Action::Stop => "Stopped",
+            Pm2Action::Delete => "Deleted",
+            Pm2Action::ScaleUp => "Scaled up",
+            Pm2Action::ScaleDown => "Scaled down",
+            Pm2Action::Flush => "Flushed logs of",
+        }
+    }
+}
+
+/// Run a PM2 command against one app. Scaling addresses the app by name
+/// (PM2 scales all instances of an app); everything else uses `pm_id`.
+pub fn run_pm2_action(pm_id: u32, name: &str, action: Pm2Action) -> Result<(), Pm2Error> {
+    let id = pm_id.to_string();
+    let args: Vec<&str> = match action {
+        Pm2Action::Restart => vec!["restart", &id],
+        Pm2Action::Reload => vec!["reload", &id],
+        Pm2Action::Stop => vec!["stop", &id],
+        Pm2Action::Delete => vec!["delete", &id],
+        Pm2Action::ScaleUp => vec!["scale", name, "+1"],
+        Pm2Action::ScaleDown => vec!["scale", name, "-1"],
+        Pm2Action::Flush => vec!["flush", &id],
+    };
+    run_pm2(&args)
+}
+
+/// Start one app declared in an ecosystem file, as `pm2 start <file> --only <name>`.
+pub fn start_ecosystem_app(file: &Path, name: &str) -> Result<(), Pm2Error> {
+    let file = file.to_string_lossy();
+    run_pm2(&["start", &file, "--only", name])
+}
+
+fn run_pm2(args: &[&str]) -> Result<(), Pm2Error> {
+    let output = Command::new("pm2")
+        .args(args)
+        .output()
+        .map_err(|e| Pm2Error::CommandFailed(e.to_string()))?;
+
+    if output.status.success() {
+        return Ok(());
+    }
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    // PM2 prints most errors as "[PM2][ERROR] ..." on stdout
+    let reason = stderr
+        .lines()
+        .chain(stdout.lines())
+        .map(str::trim)
+        .find(|line| line.to_ascii_lowercase().contains("error"))
+        .or_else(|| stderr.lines().map(str::trim).find(|line| !line.is_empty()))
+        .unwrap_or("pm2 exited with an error");
+    Err(Pm2Error::CommandFailed(
+        reason.trim_start_matches("[PM2][ERROR]").trim().to_string(),
+    ))
+}
+
 /// Errors that can occur when interacting with PM2.
 #[derive(Debug)]
 pub enum Pm2Error {
@@ -71,139 +194,94 @@ impl std::fmt::Display for Pm2Error {
     }
 }
 
-/// Parse PM2 JSON output manually (avoiding external JSON crate dependency).
+/// Parse `pm2 jlist` output.
 fn parse_pm2_json(json_str: &str) -> Result<Vec<Pm2Process>, Pm2Error> {
-    let trimmed = json_str.trim();
-
-    // Handle empty array
-    if trimmed == "[]" {
-        return Ok(Vec::new());
-    }
-
-    // Very basic JSON array parsing
-    // PM2 jlist output format:
-    // [{"pm_id":0,"name":"app","pid":1234,"monit":{"memory":123456,"cpu":5.5},...},...]
-
-    let mut processes = Vec::new();
-
-    // Remove outer brackets
-    let inner = trimmed
-        .strip_prefix('[')
-        .and_then(|s| s.strip_suffix(']'))
-        .ok_or_else(|| Pm2Error::ParseError("Invalid JSON array".to_string()))?;
-
-    if inner.trim().is_empty() {
-        return Ok(processes);
-    }
-
-    // Split by },{ pattern (accounting for nested objects)
-    let objects = split_json_objects(inner);
-
-    for obj_str in objects {
-        if let Some(proc) = parse_pm2_object(&obj_str) {
-            processes.push(proc);
-        }
-    }
-
-    Ok(processes)
-}
-
-/// Split JSON array into individual object strings.
-fn split_json_objects(json: &str) -> Vec<String> {
-    let mut objects = Vec::new();
-    let mut current = String::new();
-    let mut brace_depth = 0;
-    let mut in_string = false;
-    let mut escape_next = false;
-
-    for ch in json.chars() {
-        if escape_next {
-            current.push(ch);
-            escape_next = false;
-            continue;
-        }
-
-        if ch == '\\' && in_string {
-            current.push(ch);
-            escape_next = true;
+    // PM2 can print notices before the JSON array, and those start with `[` too
+    // ("[PM2] Spawning PM2 daemon"), so try each line that starts with `[` in turn.
+    let mut error = Pm2Error::ParseError("Invalid JSON array".to_string());
+    let mut offset = 0;
+    for line in json_str.split_inclusive('\n') {
+        let start = offset;
+        offset += line.len();
+        if !line.trim_start().starts_with('[') {
             continue;
         }
-
-        if ch == '"' {
-            in_string = !in_string;
-        }
-
-        if !in_string {
-            match ch {
-                '{' => brace_depth += 1,
-                '}' => brace_depth -= 1,
-                ',' if brace_depth == 0 => {
-                    if !current.trim().is_empty() {
-                        objects.push(current.trim().to_string());
-                    }
-                    current = String::new();
-                    continue;
-                }
-                _ => {}
+        match serde_json::from_str::<Value>(&json_str[start..]) {
+            Ok(Value::Array(entries)) => {
+                return Ok(entries.iter().filter_map(parse_pm2_object).collect());
             }
+            Ok(_) => {}
+            Err(err) => error = Pm2Error::ParseError(err.to_string()),
         }
-
-        current.push(ch);
-    }
-
-    if !current.trim().is_empty() {
-        objects.push(current.trim().to_string());
     }
-
-    objects
+    Err(error)
 }
 
-/// Parse a single PM2 process object.
-fn parse_pm2_object(json: &str) -> Option<Pm2Process> {
-    // Extract fields using simple string matching
+/// Parse a single entry of the `jlist` array.
+fn parse_pm2_object(entry: &Value) -> Option<Pm2Process> {
+    let pm_id = as_u32(entry.get("pm_id")?)?;
+    let env = entry.get("pm2_env").unwrap_or(&Value::Null);
+    let monit = entry.get("monit").unwrap_or(&Value::Null);
 
-    let pm_id = extract_u32(json, "pm_id")?;
-    let name = extract_string(json, "name").unwrap_or_else(|| "unknown".to_string());
-    let pid = extract_u32(json, "pid");
-
-    // Status is in pm2_env.status
-    let status = extract_nested_string(json, "pm2_env", "status")
-        .or_else(|| extract_string(json, "status"))
+    let name = get_string(entry, "name")
+        .or_else(|| get_string(env, "name"))
         .unwrap_or_else(|| "unknown".to_string());
+    // Stopped apps report pid 0
+    let pid = entry.get("pid").and_then(as_u32).filter(|&pid| pid != 0);
 
-    // Mode (fork/cluster) is in pm2_env.exec_mode
-    let mode = extract_nested_string(json, "pm2_env", "exec_mode")
-        .map(|m| {
-            if m.contains("cluster") {
-                "cluster".to_string()
-            } else {
-                "fork".to_string()
-            }
-        })
-        .unwrap_or_else(|| "fork".to_string());
-
-    // Restarts count
-    let restarts = extract_nested_u32(json, "pm2_env", "restart_time").unwrap_or(0);
-
-    // Uptime in pm2_env.pm_uptime (timestamp when started)
-    let uptime_ms = extract_nested_u64(json, "pm2_env", "pm_uptime").and_then(|start_time| {
-        let now = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .ok()?
-            .as_millis() as u64;
-        if now > start_time {
-            Some(now - start_time)
-        } else {
-            None
-        }
-    });
-
-    // Memory and CPU from monit object
-    let memory_bytes = extract_nested_u64(json, "monit", "memory");
-    let cpu = extract_nested_f32(json, "monit", "cpu");
+    let status = get_string(env, "status").unwrap_or_else(|| "unknown".to_string());
 
-    // Script path
-    let script = extract_nested_string(json, "pm2_env", "pm_exec_path");
+    // exec_mode is "fork_mode" or "cluster_mode"
+    let mode = match get_string(env, "exec_mode") {
+        Some(m) if m.contains("cluster") => "cluster",
+        _ => "fork",
+    }
+    .to_string();
+
+    let restarts = env.get("restart_time").and_then(as_u32).unwrap_or(0);
+    let unstable_restarts = env.get("unstable_restarts").and_then(as_u32).unwrap_or(0);
+
+    // pm_uptime is the timestamp (ms) of the last start; only meaningful while online
+    let uptime_ms = env
+        .get("pm_uptime")
+        .and_then(Value::as_u64)
+        .filter(|_| status == "online")
+        .and_then(|start_time| now_ms()?.checked_sub(start_time));
+    let created_at = env.get("created_at").and_then(Value::as_u64);
+
+    let memory_bytes = monit.get("memory").and_then(Value::as_u64);
+    let cpu = monit.get("cpu").and_then(Value::as_f64).map(|cpu| cpu as f32);
+
+    let node_args = match env.get("node_args") {
+        Some(Value::Array(args)) => args
+            .iter()
+            .filter_map(|arg| arg.as_str().map(str::to_string))
+            .collect(),
+        Some(Value::String(args)) => args.split_whitespace().map(str::to_string).collect(),
+        _ => Vec::new(),
+    };
+
+    // `watch` is either a flag or the list of watched paths
+    let watch = match env.get("watch") {
+        Some(Value::Bool(flag)) => *flag,
+        Some(Value::Array(paths)) => !paths.is_empty(),
+        Some(Value::String(path)) => !path.is_empty(),
+        _ => false,
+    };
+
+    let versioning = env
+        .get("versioning")
+        .filter(|v| v.is_object())
+        .map(|v| Pm2Versioning {
+            branch: get_string(v, "branch"),
+            revision: get_string(v, "revision"),
+            url: get_string(v, "url"),
+        });
+
+    let max_memory_restart = env.get("max_memory_restart").and_then(|value| match value {
+        Value::String(text) => parse_memory_size(text),
+        other => other.as_u64(),
+    });
 
     Some(Pm2Process {
         pm_id,
@@ -215,240 +293,173 @@ fn parse_pm2_object(json: &str) -> Option<Pm2Process> {
         memory_bytes,
         restarts,
         uptime_ms,
-        script,
+        script: get_string(env, "pm_exec_path"),
+        cwd: get_string(env, "pm_cwd"),
+        node_args,
+        watch,
+        unstable_restarts,
+        created_at,
+        versioning,
+        max_memory_restart,
+        instance_var: get_string(env, "instance_var"),
+        out_log: get_string(env, "pm_out_log_path"),
+        err_log: get_string(env, "pm_err_log_path"),
     })
 }
 
-/// Extract a string value from JSON.
-fn extract_string(json: &str, key: &str) -> Option<String> {
-    let pattern = format!("\"{}\":\"", key);
-    let start = json.find(&pattern)? + pattern.len();
-    let rest = &json[start..];
-
-    // Find the closing quote (handling escapes)
-    let mut end = 0;
-    let mut escape_next = false;
-    for (i, ch) in rest.chars().enumerate() {
-        if escape_next {
-            escape_next = false;
-            continue;
-        }
-        if ch == '\\' {
-            escape_next = true;
-            continue;
-        }
-        if ch == '"' {
-            end = i;
-            break;
-        }
-    }
-
-    Some(rest[..end].to_string())
+/// Non-empty string field of a JSON object.
+fn get_string(object: &Value, key: &str) -> Option<String> {
+    object
+        .get(key)
+        .and_then(Value::as_str)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
 }
 
-/// Extract a u32 value from JSON.
-fn extract_u32(json: &str, key: &str) -> Option<u32> {
-    let pattern = format!("\"{}\":", key);
-    let start = json.find(&pattern)? + pattern.len();
-    let rest = json[start..].trim_start();
-
-    let end = rest
-        .find(|c: char| !c.is_ascii_digit())
-        .unwrap_or(rest.len());
-
-    if end == 0 {
-        return None;
-    }
-
-    rest[..end].parse().ok()
+fn as_u32(value: &Value) -> Option<u32> {
+    value.as_u64().and_then(|n| u32::try_from(n).ok())
 }
 
-/// Extract a u64 value from JSON.
-fn extract_u64(json: &str, key: &str) -> Option<u64> {
-    let pattern = format!("\"{}\":", key);
-    let start = json.find(&pattern)? + pattern.len();
-    let rest = json[start..].trim_start();
-
-    let end = rest
-        .find(|c: char| !c.is_ascii_digit())
-        .unwrap_or(rest.len());
-
-    if end == 0 {
-        return None;
-    }
+fn now_ms() -> Option<u64> {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .ok()
+        .map(|d| d.as_millis() as u64)
+}
 
-    rest[..end].parse().ok()
+/// Parse PM2 memory sizes such as "300M", "1G" or "512K" into bytes.
+fn parse_memory_size(text: &str) -> Option<u64> {
+    let text = text.trim();
+    let (digits, multiplier) = match text.chars().last()?.to_ascii_uppercase() {
+        'K' => (&text[..text.len() - 1], 1024),
+        'M' => (&text[..text.len() - 1], 1024 * 1024),
+        'G' => (&text[..text.len() - 1], 1024 * 1024 * 1024),
+        _ => (text, 1),
+    };
+    digits.trim().parse::<u64>().ok().map(|n| n * multiplier)
 }
 
-/// Extract a f32 value from JSON.
-fn extract_f32(json: &str, key: &str) -> Option<f32> {
-    let pattern = format!("\"{}\":", key);
-    let start = json.find(&pattern)? + pattern.len();
-    let rest = json[start..].trim_start();
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    let end = rest
-        .find(|c: char| !c.is_ascii_digit() && c != '.' && c != '-')
-        .unwrap_or(rest.len());
+    const JLIST_FIXTURE: &str = include_str!("fixtures/pm2_jlist.json");
 
-    if end == 0 {
-        return None;
+    fn fixture() -> Vec<Pm2Process> {
+        parse_pm2_json(JLIST_FIXTURE).unwrap()
     }
 
-    rest[..end].parse().ok()
-}
-
-/// Extract a nested string value from JSON (e.g., "pm2_env": {"status": "online"}).
-fn extract_nested_string(json: &str, parent: &str, key: &str) -> Option<String> {
-    let parent_pattern = format!("\"{}\":{{", parent);
-
-    // Also try with space: "parent": {
-    let alt_pattern = format!("\"{}\" : {{", parent);
-
-    let parent_start = json
-        .find(&parent_pattern)
-        .or_else(|| json.find(&alt_pattern))?;
-
-    let parent_content = &json[parent_start..];
-
-    // Find matching brace
-    let mut brace_depth = 0;
-    let mut started = false;
-    let mut end = parent_content.len();
-
-    for (i, ch) in parent_content.chars().enumerate() {
-        if ch == '{' {
-            brace_depth += 1;
-            started = true;
-        } else if ch == '}' {
-            brace_depth -= 1;
-            if started && brace_depth == 0 {
-                end = i + 1;
-                break;
-            }
-        }
+    #[test]
+    fn test_parse_empty_array() {
+        let result = parse_pm2_json("[]").unwrap();
+        assert!(result.is_empty());
     }
 
-    let nested = &parent_content[..end];
-    extract_string(nested, key)
-}
-
-/// Extract a nested u32 value from JSON.
-fn extract_nested_u32(json: &str, parent: &str, key: &str) -> Option<u32> {
-    let parent_pattern = format!("\"{}\":{{", parent);
-    let alt_pattern = format!("\"{}\" : {{", parent);
-
-    let parent_start = json
-        .find(&parent_pattern)
-        .or_else(|| json.find(&alt_pattern))?;
-
-    let parent_content = &json[parent_start..];
-
-    let mut brace_depth = 0;
-    let mut started = false;
-    let mut end = parent_content.len();
-
-    for (i, ch) in parent_content.chars().enumerate() {
-        if ch == '{' {
-            brace_depth += 1;
-            started = true;
-        } else if ch == '}' {
-            brace_depth -= 1;
-            if started && brace_depth == 0 {
-                end = i + 1;
-                break;
-            }
-        }
+    #[test]
+    fn test_parse_minimal_entry() {
+        let json = r#"[{"pm_id":5,"pid":1234,"name":"my-app","pm2_env":{"version":"1.0"}}]"#;
+        let procs = parse_pm2_json(json).unwrap();
+        assert_eq!(procs.len(), 1);
+        assert_eq!(procs[0].pm_id, 5);
+        assert_eq!(procs[0].pid, Some(1234));
+        assert_eq!(procs[0].name, "my-app");
+        assert_eq!(procs[0].status, "unknown");
+        assert_eq!(procs[0].mode, "fork");
     }
 
-    let nested = &parent_content[..end];
-    extract_u32(nested, key)
-}
-
-/// Extract a nested u64 value from JSON.
-fn extract_nested_u64(json: &str, parent: &str, key: &str) -> Option<u64> {
-    let parent_pattern = format!("\"{}\":{{", parent);
-    let alt_pattern = format!("\"{}\" : {{", parent);
-
-    let parent_start = json
-        .find(&parent_pattern)
-        .or_else(|| json.find(&alt_pattern))?;
-
-    let parent_content = &json[parent_start..];
-
-    let mut brace_depth = 0;
-    let mut started = false;
-    let mut end = parent_content.len();
-
-    for (i, ch) in parent_content.chars().enumerate() {
-        if ch == '{' {
-            brace_depth += 1;
-            started = true;
-        } else if ch == '}' {
-            brace_depth -= 1;
-            if started && brace_depth == 0 {
-                end = i + 1;
-                break;
-            }
-        }
+    #[test]
+    fn test_parse_escaped_strings() {
+        let json = r#"[{"pm_id":0,"name":"say \"hi\"","pm2_env":{"pm_cwd":"C:\\apps\\api"}}]"#;
+        let procs = parse_pm2_json(json).unwrap();
+        assert_eq!(procs[0].name, "say \"hi\"");
+        assert_eq!(procs[0].cwd.as_deref(), Some("C:\\apps\\api"));
     }
 
-    let nested = &parent_content[..end];
-    extract_u64(nested, key)
-}
-
-/// Extract a nested f32 value from JSON.
-fn extract_nested_f32(json: &str, parent: &str, key: &str) -> Option<f32> {
-    let parent_pattern = format!("\"{}\":{{", parent);
-    let alt_pattern = format!("\"{}\" : {{", parent);
-
-    let parent_start = json
-        .find(&parent_pattern)
-        .or_else(|| json.find(&alt_pattern))?;
-
-    let parent_content = &json[parent_start..];
-
-    let mut brace_depth = 0;
-    let mut started = false;
-    let mut end = parent_content.len();
-
-    for (i, ch) in parent_content.chars().enumerate() {
-        if ch == '{' {
-            brace_depth += 1;
-            started = true;
-        } else if ch == '}' {
-            brace_depth -= 1;
-            if started && brace_depth == 0 {
-                end = i + 1;
-                break;
-            }
-        }
+    #[test]
+    fn test_parse_skips_leading_notices() {
+        let output = ">>>> In-memory PM2 is out-of-date, do:\n>>>> $ pm2 update\n[]\n";
+        assert!(parse_pm2_json(output).unwrap().is_empty());
+        assert!(parse_pm2_json("not json").is_err());
     }
 
-    let nested = &parent_content[..end];
-    extract_f32(nested, key)
-}
+    #[test]
+    fn test_parse_skips_pm2_bracket_notices() {
+        let output = "[PM2] Spawning PM2 daemon with pm2_home=/root/.pm2\n\
+                      [PM2] PM2 Successfully daemonized\n\
+                      [PM2][WARN] Current process list is not synchronized with saved list\n\
+                      [{\"pm_id\":0,\"name\":\"api\",\"pid\":4242}]\n";
+        let processes = parse_pm2_json(output).unwrap();
+        assert_eq!(processes.len(), 1);
+        assert_eq!(processes[0].name, "api");
+        assert!(parse_pm2_json("[PM2] Spawning PM2 daemon\n[]").unwrap().is_empty());
+        assert!(parse_pm2_json("[PM2][ERROR] Daemon not launched\n").is_err());
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn test_fixture_cluster_app() {
+        let procs = fixture();
+        assert_eq!(procs.len(), 3);
+
+        let api = &procs[0];
+        assert_eq!(api.pm_id, 0);
+        assert_eq!(api.name, "api");
+        assert_eq!(api.mode, "cluster");
+        assert_eq!(api.status, "online");
+        assert_eq!(api.pid, Some(48213));
+        assert_eq!(api.memory_bytes, Some(71_532_544));
+        assert_eq!(api.cpu, Some(1.5));
+        assert_eq!(api.restarts, 3);
+        assert_eq!(api.unstable_restarts, 1);
+        assert!(api.uptime_ms.is_some());
+        assert_eq!(api.created_at, Some(1_717_400_000_000));
+        assert_eq!(api.script.as_deref(), Some("/srv/api/dist/server.js"));
+        assert_eq!(api.cwd.as_deref(), Some("/srv/api"));
+        assert_eq!(api.node_args, vec!["--max-old-space-size=512", "--enable-source-maps"]);
+        assert!(!api.watch);
+        assert_eq!(api.max_memory_restart, Some(314_572_800));
+        assert_eq!(api.instance_var.as_deref(), Some("NODE_APP_INSTANCE"));
+        assert_eq!(api.out_log.as_deref(), Some("/home/deploy/.pm2/logs/api-out.log"));
+        assert_eq!(api.err_log.as_deref(), Some("/home/deploy/.pm2/logs/api-error.log"));
+
+        let versioning = api.versioning.as_ref().unwrap();
+        assert_eq!(versioning.branch.as_deref(), Some("main"));
+        assert_eq!(
+            versioning.revision.as_deref(),
+            Some("9f2c41be0d7a3c5e8b1f6a2d4c7e9b0a1d3f5c7e")
+        );
+    }
 
     #[test]
-    fn test_parse_empty_array() {
-        let result = parse_pm2_json("[]").unwrap();
-        assert!(result.is_empty());
+    fn test_fixture_ignores_env_lookalikes() {
+        // The worker's env defines `status`, `exec_mode` and `pm_id` keys of
+        // its own; only the real pm2_env fields may be used.
+        let worker = &fixture()[1];
+        assert_eq!(worker.pm_id, 1);
+        assert_eq!(worker.name, "worker");
+        assert_eq!(worker.status, "online");
+        assert_eq!(worker.mode, "fork");
+        assert_eq!(worker.node_args, vec!["--inspect=0"]);
+        assert!(worker.watch);
+        assert!(worker.versioning.is_none());
+        assert_eq!(worker.max_memory_restart, None);
     }
 
     #[test]
-    fn test_extract_string() {
-        let json = r#"{"name":"my-app","version":"1.0"}"#;
-        assert_eq!(extract_string(json, "name"), Some("my-app".to_string()));
-        assert_eq!(extract_string(json, "version"), Some("1.0".to_string()));
+    fn test_fixture_stopped_app() {
+        let cron = &fixture()[2];
+        assert_eq!(cron.pm_id, 2);
+        assert_eq!(cron.status, "stopped");
+        assert_eq!(cron.pid, None);
+        assert_eq!(cron.uptime_ms, None);
+        assert_eq!(cron.memory_bytes, Some(0));
+        assert_eq!(cron.max_memory_restart, Some(1024 * 1024 * 1024));
     }
 
     #[test]
-    fn test_extract_u32() {
-        let json = r#"{"pm_id":5,"pid":1234}"#;
-        assert_eq!(extract_u32(json, "pm_id"), Some(5));
-        assert_eq!(extract_u32(json, "pid"), Some(1234));
+    fn test_parse_memory_size() {
+        assert_eq!(parse_memory_size("300M"), Some(300 * 1024 * 1024));
+        assert_eq!(parse_memory_size("512k"), Some(512 * 1024));
+        assert_eq!(parse_memory_size("2048"), Some(2048));
+        assert_eq!(parse_memory_size("lots"), None);
     }
 }
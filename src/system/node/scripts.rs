@@ -0,0 +1,181 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+
+/// Package manager used to run a project's scripts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PackageManager {
+    Npm,
+    Yarn,
+    Pnpm,
+    Bun,
+}
+
+impl PackageManager {
+    pub fn program(&self) -> &'static str {
+        match self {
+            PackageManager::Npm => "npm",
+            PackageManager::Yarn => "yarn",
+            PackageManager::Pnpm => "pnpm",
+            PackageManager::Bun => "bun",
+        }
+    }
+
+    /// Arguments that run `script`; all four accept `run <script>`.
+    pub fn run_args(&self, script: &str) -> Vec<String> {
+        vec!["run".to_string(), script.to_string()]
+    }
+
+    /// Reads the `packageManager` field of package.json, e.g. "pnpm@8.15.4".
+    fn from_declared(declared: &str) -> Option<Self> {
+        let name = declared.split('@').next().unwrap_or_default();
+        match name.trim() {
+            "npm" => Some(PackageManager::Npm),
+            "yarn" => Some(PackageManager::Yarn),
+            "pnpm" => Some(PackageManager::Pnpm),
+            "bun" => Some(PackageManager::Bun),
+            _ => None,
+        }
+    }
+}
+
+/// Lockfiles in the order they are checked; a workspace root may hold the lockfile
+const LOCKFILES: [(&str, PackageManager); 6] = [
+    ("pnpm-lock.yaml", PackageManager::Pnpm),
+    ("yarn.lock", PackageManager::Yarn),
+    ("bun.lockb", PackageManager::Bun),
+    ("bun.lock", PackageManager::Bun),
+    ("package-lock.json", PackageManager::Npm),
+    ("npm-shrinkwrap.json", PackageManager::Npm),
+];
+
+/// The `scripts` of the package.json nearest to a project directory.
+#[derive(Clone, Debug)]
+pub struct PackageScripts {
+    /// Directory holding package.json; scripts run here
+    pub dir: PathBuf,
+    pub name: Option<String>,
+    pub manager: PackageManager,
+    /// (name, command) pairs, sorted by name
+    pub scripts: Vec<(String, String)>,
+}
+
+/// Finds the package.json at or above `start` and reads its scripts.
+pub fn load_package_scripts(start: &Path) -> Option<PackageScripts> {
+    let manifest = find_package_json(start)?;
+    let dir = manifest.parent()?.to_path_buf();
+    let contents = fs::read_to_string(&manifest).ok()?;
+    let package = parse_package_json(&contents)?;
+    let manager = package
+        .package_manager
+        .as_deref()
+        .and_then(PackageManager::from_declared)
+        .unwrap_or_else(|| detect_package_manager(&dir));
+    Some(PackageScripts {
+        dir,
+        name: package.name,
+        manager,
+        scripts: package.scripts,
+    })
+}
+
+pub(crate) fn find_package_json(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .take(16)
+        .map(|dir| dir.join("package.json"))
+        .find(|path| path.is_file())
+}
+
+/// Picks the package manager from the nearest lockfile, defaulting to npm.
+fn detect_package_manager(dir: &Path) -> PackageManager {
+    dir.ancestors()
+        .take(16)
+        .find_map(lockfile_manager)
+        .unwrap_or(PackageManager::Npm)
+}
+
+/// Package manager of the lockfile in `dir` itself, if there is one.
+fn lockfile_manager(dir: &Path) -> Option<PackageManager> {
+    LOCKFILES
+        .iter()
+        .find(|(lockfile, _)| dir.join(lockfile).is_file())
+        .map(|(_, manager)| *manager)
+}
+
+struct PackageJson {
+    name: Option<String>,
+    package_manager: Option<String>,
+    scripts: Vec<(String, String)>,
+}
+
+fn parse_package_json(contents: &str) -> Option<PackageJson> {
+    let value: Value = serde_json::from_str(contents).ok()?;
+    let string = |key: &str| value.get(key).and_then(Value::as_str).map(str::to_string);
+    let scripts = value
+        .get("scripts")
+        .and_then(Value::as_object)
+        .map(|scripts| {
+            scripts
+                .iter()
+                .filter_map(|(name, command)| Some((name.clone(), command.as_str()?.to_string())))
+                .collect()
+        })
+        .unwrap_or_default();
+    Some(PackageJson {
+        name: string("name"),
+        package_manager: string("packageManager"),
+        scripts,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::TempDir;
+
+    #[test]
+    fn test_parse_package_json_scripts() {
+        let contents = r#"{
+            "name": "@acme/web",
+            "packageManager": "pnpm@8.15.4",
+            "scripts": { "dev": "vite --port 5173", "build": "tsc && vite build", "bogus": 1 },
+            "dependencies": { "vite": "^5.0.0" }
+        }"#;
+        let package = parse_package_json(contents).unwrap();
+        assert_eq!(package.name.as_deref(), Some("@acme/web"));
+        assert_eq!(
+            package.package_manager.as_deref().and_then(PackageManager::from_declared),
+            Some(PackageManager::Pnpm)
+        );
+        assert_eq!(
+            package.scripts,
+            vec![
+                ("build".to_string(), "tsc && vite build".to_string()),
+                ("dev".to_string(), "vite --port 5173".to_string()),
+            ]
+        );
+        assert!(parse_package_json("{}").unwrap().scripts.is_empty());
+    }
+
+    #[test]
+    fn test_detect_package_manager_from_lockfile() {
+        let root = TempDir::new("scripts");
+        let app = root.join("packages").join("app");
+        fs::create_dir_all(&app).unwrap();
+        assert_eq!(lockfile_manager(&app), None);
+
+        // Workspaces keep the lockfile at the repository root
+        fs::write(root.join("yarn.lock"), "").unwrap();
+        assert_eq!(lockfile_manager(&root), Some(PackageManager::Yarn));
+        fs::write(app.join("package.json"), r#"{"scripts":{"start":"node ."}}"#).unwrap();
+        let scripts = load_package_scripts(&app).unwrap();
+        assert_eq!(scripts.manager, PackageManager::Yarn);
+        assert_eq!(scripts.dir, app);
+
+        // pnpm wins over yarn when both lockfiles sit in the same directory
+        fs::write(root.join("pnpm-lock.yaml"), "").unwrap();
+        assert_eq!(lockfile_manager(&root), Some(PackageManager::Pnpm));
+    }
+}
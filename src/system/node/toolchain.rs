@@ -0,0 +1,425 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::SystemTime;
+
+use serde_json::Value;
+
+/// How the Node binary of a process was installed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VersionManager {
+    Nvm,
+    Volta,
+    Fnm,
+    Asdf,
+    Nodenv,
+    N,
+    Homebrew,
+    System,
+    Other,
+}
+
+impl VersionManager {
+    pub fn label(&self) -> &'static str {
+        match self {
+            VersionManager::Nvm => "nvm",
+            VersionManager::Volta => "volta",
+            VersionManager::Fnm => "fnm",
+            VersionManager::Asdf => "asdf",
+            VersionManager::Nodenv => "nodenv",
+            VersionManager::N => "n",
+            VersionManager::Homebrew => "brew",
+            VersionManager::System => "system",
+            VersionManager::Other => "other",
+        }
+    }
+}
+
+/// Path fragments that identify each manager, with the directory that holds
+/// the version-named install directory.
+const MANAGER_PATHS: &[(&str, VersionManager)] = &[
+    ("/.nvm/versions/node/", VersionManager::Nvm),
+    ("/.volta/tools/image/node/", VersionManager::Volta),
+    ("/fnm/node-versions/", VersionManager::Fnm),
+    ("/.asdf/installs/nodejs/", VersionManager::Asdf),
+    ("/.nodenv/versions/", VersionManager::Nodenv),
+    ("/n/versions/node/", VersionManager::N),
+    ("/Cellar/node/", VersionManager::Homebrew),
+];
+
+/// Where a project states which Node version it expects.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VersionRequirement {
+    pub spec: String,
+    /// File (and field) the spec was read from, e.g. ".nvmrc" or "package.json engines.node"
+    pub source: String,
+}
+
+/// Node install details of one process.
+#[derive(Clone, Debug)]
+pub struct NodeToolchain {
+    pub manager: VersionManager,
+    pub required: Option<VersionRequirement>,
+    /// Whether the running version satisfies `required`; None if either is unknown
+    /// or the spec is an alias such as `lts/*`
+    pub satisfied: Option<bool>,
+}
+
+impl NodeToolchain {
+    pub fn is_mismatch(&self) -> bool {
+        self.satisfied == Some(false)
+    }
+
+    /// KEY=VALUE lines for the detail panes.
+    pub fn detail_lines(&self) -> Vec<String> {
+        let required = self.required.as_ref();
+        let version_ok = match self.satisfied {
+            Some(true) => "yes",
+            Some(false) => "no",
+            None => "unknown",
+        };
+        vec![
+            format!("VERSION_MANAGER={}", self.manager.label()),
+            format!("REQUIRED={}", required.map_or("-", |req| req.spec.as_str())),
+            format!("REQUIRED_SOURCE={}", required.map_or("-", |req| req.source.as_str())),
+            format!("VERSION_OK={version_ok}"),
+        ]
+    }
+}
+
+pub fn manager_for_exe(exe: &str) -> VersionManager {
+    if let Some((_, manager)) = MANAGER_PATHS.iter().find(|(fragment, _)| exe.contains(fragment)) {
+        return *manager;
+    }
+    // Versioned Homebrew formulae such as node@18
+    if exe.contains("/Cellar/node@") || exe.contains("/opt/homebrew/") || exe.contains("/linuxbrew/") {
+        return VersionManager::Homebrew;
+    }
+    if ["/usr/bin/", "/usr/local/bin/", "/usr/lib/", "/bin/"]
+        .iter()
+        .any(|prefix| exe.starts_with(prefix))
+    {
+        return VersionManager::System;
+    }
+    VersionManager::Other
+}
+
+/// Version from a manager's install path, e.g. "v20.11.1" from
+/// `~/.nvm/versions/node/v20.11.1/bin/node` or `~/.volta/tools/image/node/20.11.1/bin/node`.
+pub fn version_from_exe(exe: &str) -> Option<String> {
+    let rest = MANAGER_PATHS
+        .iter()
+        .find_map(|(fragment, _)| exe.split_once(fragment).map(|(_, rest)| rest))
+        .or_else(|| exe.split_once("/Cellar/node@").and_then(|(_, rest)| rest.split_once('/')).map(|(_, rest)| rest))?;
+    let dir = rest.split('/').next()?;
+    // fnm and Homebrew suffix revisions, e.g. "20.11.1_1"
+    let version = dir.trim_start_matches('v').split('_').next()?;
+    parse_version(version).map(|_| format!("v{version}"))
+}
+
+/// Files whose modification times decide whether a cached requirement is stale.
+const REQUIREMENT_FILES: &[&str] =
+    &[".nvmrc", ".node-version", ".tool-versions", "package.json", ".git"];
+
+/// Looks for a version requirement at or above the project directory. Within
+/// one directory `.nvmrc` wins over `.node-version`, `.tool-versions`, Volta's
+/// pin and `engines.node`. Cached per project until one of those files is
+/// added, removed or modified.
+pub fn find_requirement(project_dir: &Path) -> Option<VersionRequirement> {
+    type Cache = HashMap<PathBuf, (Vec<Option<SystemTime>>, Option<VersionRequirement>)>;
+    static CACHE: OnceLock<Mutex<Cache>> = OnceLock::new();
+
+    let stamp: Vec<_> = search_dirs(project_dir)
+        .flat_map(|dir| REQUIREMENT_FILES.iter().map(move |name| dir.join(name)))
+        .map(|path| fs::metadata(path).and_then(|meta| meta.modified()).ok())
+        .collect();
+    let mut cache = CACHE
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .unwrap_or_else(|err| err.into_inner());
+    if let Some((cached_stamp, required)) = cache.get(project_dir) {
+        if *cached_stamp == stamp {
+            return required.clone();
+        }
+    }
+    let required = read_requirement(project_dir);
+    cache.insert(project_dir.to_path_buf(), (stamp, required.clone()));
+    required
+}
+
+/// The project directory and its ancestors up to the repository root.
+fn search_dirs(project_dir: &Path) -> impl Iterator<Item = &Path> {
+    let mut past_root = false;
+    project_dir.ancestors().take(8).take_while(move |dir| {
+        let searched = !past_root;
+        past_root = dir.join(".git").exists();
+        searched
+    })
+}
+
+fn read_requirement(project_dir: &Path) -> Option<VersionRequirement> {
+    for dir in search_dirs(project_dir) {
+        let found = read_trimmed(&dir.join(".nvmrc"))
+            .map(|spec| (spec, ".nvmrc".to_string()))
+            .or_else(|| {
+                read_trimmed(&dir.join(".node-version")).map(|spec| (spec, ".node-version".to_string()))
+            })
+            .or_else(|| {
+                let contents = fs::read_to_string(dir.join(".tool-versions")).ok()?;
+                let spec = contents.lines().find_map(|line| line.trim().strip_prefix("nodejs "))?;
+                Some((spec.trim().to_string(), ".tool-versions".to_string()))
+            })
+            .or_else(|| package_json_requirement(dir));
+        if let Some((spec, source)) = found {
+            return Some(VersionRequirement { spec, source });
+        }
+    }
+    None
+}
+
+fn read_trimmed(path: &Path) -> Option<String> {
+    let contents = fs::read_to_string(path).ok()?;
+    let first = contents.lines().map(str::trim).find(|line| !line.is_empty() && !line.starts_with('#'))?;
+    Some(first.to_string())
+}
+
+fn package_json_requirement(dir: &Path) -> Option<(String, String)> {
+    let contents = fs::read_to_string(dir.join("package.json")).ok()?;
+    let package: Value = serde_json::from_str(&contents).ok()?;
+    let field = |path: [&str; 2]| package.get(path[0])?.get(path[1])?.as_str().map(str::to_string);
+    field(["volta", "node"])
+        .map(|spec| (spec, "package.json volta.node".to_string()))
+        .or_else(|| field(["engines", "node"]).map(|spec| (spec, "package.json engines.node".to_string())))
+}
+
+type Version = (u64, u64, u64);
+
+fn parse_version(text: &str) -> Option<Version> {
+    let mut parts = text.trim().trim_start_matches('v').splitn(3, '.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next().map_or(Some(0), |part| part.parse().ok())?;
+    // Drop prerelease and build suffixes
+    let patch = parts.next().map_or(Some(0), |part| {
+        let digits: String = part.chars().take_while(char::is_ascii_digit).collect();
+        digits.parse().ok()
+    })?;
+    Some((major, minor, patch))
+}
+
+/// Major version of an `lts/<codename>` alias.
+fn lts_major(codename: &str) -> Option<u64> {
+    let major = match codename.to_ascii_lowercase().as_str() {
+        "argon" => 4,
+        "boron" => 6,
+        "carbon" => 8,
+        "dubnium" => 10,
+        "erbium" => 12,
+        "fermium" => 14,
+        "gallium" => 16,
+        "hydrogen" => 18,
+        "iron" => 20,
+        "jod" => 22,
+        "krypton" => 24,
+        _ => return None,
+    };
+    Some(major)
+}
+
+/// Checks a running version such as "v20.11.1" against a version file entry
+/// or an `engines` range. None if the spec is not understood.
+pub fn satisfies(version: &str, spec: &str) -> Option<bool> {
+    let version = parse_version(version)?;
+    let spec = spec.trim();
+    match spec {
+        "" | "*" | "node" | "latest" | "current" | "stable" => return Some(true),
+        _ => {}
+    }
+    if let Some(codename) = spec.strip_prefix("lts/") {
+        return lts_major(codename).map(|major| version.0 == major);
+    }
+
+    let mut any_understood = false;
+    for alternative in spec.split("||") {
+        match alternative_matches(version, alternative.trim()) {
+            Some(true) => return Some(true),
+            Some(false) => any_understood = true,
+            None => {}
+        }
+    }
+    any_understood.then_some(false)
+}
+
+/// One `||` branch: a hyphen range or space-separated comparators that must all hold.
+fn alternative_matches(version: Version, alternative: &str) -> Option<bool> {
+    if let Some((low, high)) = alternative.split_once(" - ") {
+        let low = Partial::parse(low)?;
+        let high = Partial::parse(high)?;
+        return Some(version >= low.floor() && high.contains_or_below(version));
+    }
+
+    // Join operators written apart from their version, e.g. ">= 18"
+    let mut comparators: Vec<String> = Vec::new();
+    for token in alternative.split_whitespace() {
+        match comparators.last_mut() {
+            Some(last) if last.chars().all(|ch| "<>=^~".contains(ch)) => last.push_str(token),
+            _ => comparators.push(token.to_string()),
+        }
+    }
+    if comparators.is_empty() {
+        return Some(true);
+    }
+    for comparator in &comparators {
+        if !comparator_matches(version, comparator)? {
+            return Some(false);
+        }
+    }
+    Some(true)
+}
+
+fn comparator_matches(version: Version, comparator: &str) -> Option<bool> {
+    let (op, rest) = [">=", "<=", ">", "<", "=", "^", "~"]
+        .iter()
+        .find_map(|op| comparator.strip_prefix(op).map(|rest| (*op, rest)))
+        .unwrap_or(("", comparator));
+    let partial = Partial::parse(rest)?;
+    let floor = partial.floor();
+    let matched = match op {
+        ">=" => version >= floor,
+        ">" => !partial.contains_or_below(version),
+        "<" => version < floor,
+        "<=" => partial.contains_or_below(version),
+        "^" => version >= floor && version < partial.caret_ceiling(),
+        "~" => version >= floor && version < partial.tilde_ceiling(),
+        _ => partial.contains(version),
+    };
+    Some(matched)
+}
+
+/// A version with optional minor and patch, as in "20", "20.11" or "20.x".
+#[derive(Clone, Copy, Debug)]
+struct Partial {
+    major: u64,
+    minor: Option<u64>,
+    patch: Option<u64>,
+}
+
+impl Partial {
+    fn parse(text: &str) -> Option<Self> {
+        let text = text.trim().trim_start_matches('v');
+        let mut parts = text.split('.');
+        let component = |part: Option<&str>| -> Option<Option<u64>> {
+            match part {
+                None | Some("x" | "X" | "*") => Some(None),
+                Some(part) => {
+                    let digits: String = part.chars().take_while(char::is_ascii_digit).collect();
+                    digits.parse().ok().map(Some)
+                }
+            }
+        };
+        let major = component(parts.next())??;
+        let minor = component(parts.next())?;
+        let patch = if minor.is_some() { component(parts.next())? } else { None };
+        Some(Self { major, minor, patch })
+    }
+
+    fn floor(&self) -> Version {
+        (self.major, self.minor.unwrap_or(0), self.patch.unwrap_or(0))
+    }
+
+    fn contains(&self, version: Version) -> bool {
+        version.0 == self.major
+            && self.minor.is_none_or(|minor| version.1 == minor)
+            && self.patch.is_none_or(|patch| version.2 == patch)
+    }
+
+    /// True if `version` is within the partial or below it, so `<=20` allows 20.x.
+    fn contains_or_below(&self, version: Version) -> bool {
+        self.contains(version) || version < self.floor()
+    }
+
+    fn caret_ceiling(&self) -> Version {
+        match (self.major, self.minor, self.patch) {
+            (0, Some(0), Some(patch)) => (0, 0, patch + 1),
+            (0, Some(minor), _) => (0, minor + 1, 0),
+            (major, _, _) => (major + 1, 0, 0),
+        }
+    }
+
+    fn tilde_ceiling(&self) -> Version {
+        match self.minor {
+            Some(minor) => (self.major, minor + 1, 0),
+            None => (self.major + 1, 0, 0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::TempDir;
+
+    #[test]
+    fn test_manager_and_version_from_exe() {
+        let cases = [
+            ("/home/dev/.nvm/versions/node/v20.11.1/bin/node", VersionManager::Nvm, Some("v20.11.1")),
+            ("/home/dev/.volta/tools/image/node/18.19.0/bin/node", VersionManager::Volta, Some("v18.19.0")),
+            (
+                "/home/dev/.local/share/fnm/node-versions/v21.6.2/installation/bin/node",
+                VersionManager::Fnm,
+                Some("v21.6.2"),
+            ),
+            ("/home/dev/.asdf/installs/nodejs/20.11.1/bin/node", VersionManager::Asdf, Some("v20.11.1")),
+            ("/opt/homebrew/Cellar/node@18/18.19.1_1/bin/node", VersionManager::Homebrew, Some("v18.19.1")),
+            ("/usr/bin/node", VersionManager::System, None),
+            ("/srv/tools/node/bin/node", VersionManager::Other, None),
+        ];
+        for (exe, manager, version) in cases {
+            assert_eq!(manager_for_exe(exe), manager, "{exe}");
+            assert_eq!(version_from_exe(exe).as_deref(), version, "{exe}");
+        }
+    }
+
+    #[test]
+    fn test_satisfies_version_files() {
+        assert_eq!(satisfies("v20.11.1", "20"), Some(true));
+        assert_eq!(satisfies("v20.11.1", "v20.11"), Some(true));
+        assert_eq!(satisfies("v20.11.1", "20.10.0"), Some(false));
+        assert_eq!(satisfies("v18.19.0", "lts/iron"), Some(false));
+        assert_eq!(satisfies("v20.11.1", "lts/iron"), Some(true));
+        assert_eq!(satisfies("v20.11.1", "lts/*"), None);
+        assert_eq!(satisfies("v20.11.1", "node"), Some(true));
+    }
+
+    #[test]
+    fn test_satisfies_engines_ranges() {
+        assert_eq!(satisfies("v20.11.1", ">=18"), Some(true));
+        assert_eq!(satisfies("v16.20.2", ">= 18"), Some(false));
+        assert_eq!(satisfies("v20.11.1", "^20.10.0"), Some(true));
+        assert_eq!(satisfies("v21.0.0", "^20.10.0"), Some(false));
+        assert_eq!(satisfies("v20.12.0", "~20.11"), Some(false));
+        assert_eq!(satisfies("v18.19.0", "18.x || 20.x"), Some(true));
+        assert_eq!(satisfies("v19.9.0", "18.x || 20.x"), Some(false));
+        assert_eq!(satisfies("v20.11.1", ">=16 <20"), Some(false));
+        assert_eq!(satisfies("v20.11.1", "<=20"), Some(true));
+        assert_eq!(satisfies("v20.11.1", ">20"), Some(false));
+        assert_eq!(satisfies("v20.11.1", "18 - 20"), Some(true));
+        assert_eq!(satisfies("v20.11.1", "lts"), None);
+    }
+
+    #[test]
+    fn test_find_requirement_prefers_nvmrc() {
+        let root = TempDir::new("toolchain");
+        let app = root.join("apps").join("web");
+        fs::create_dir_all(&app).unwrap();
+        fs::create_dir_all(root.join(".git")).unwrap();
+        fs::write(app.join("package.json"), r#"{"engines":{"node":">=18"}}"#).unwrap();
+        fs::write(root.join(".nvmrc"), "20\n").unwrap();
+
+        let found = find_requirement(&app).unwrap();
+        assert_eq!(found.source, "package.json engines.node");
+        fs::write(app.join(".nvmrc"), "# pinned\nv20.11.1\n").unwrap();
+        let found = find_requirement(&app).unwrap();
+        assert_eq!((found.spec.as_str(), found.source.as_str()), ("v20.11.1", ".nvmrc"));
+    }
+}
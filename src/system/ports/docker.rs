@@ -1,12 +1,12 @@
 use std::path::Path;
-use std::process::Command;
 
 use sysinfo::Pid;
 
 use super::PortInfo;
+use crate::system::docker;
 
 pub fn load_docker_port_bindings() -> Vec<PortInfo> {
-    let output = Command::new("docker")
+    let output = docker::docker_command()
         .args([
             "ps",
             "--format",
@@ -1,11 +1,15 @@
 mod docker;
 mod proc;
 
+use std::borrow::Cow;
 use std::collections::{HashMap, HashSet};
 
 use sysinfo::{Pid, System};
 
-use crate::util::{contains_lower, Filterable};
+use crate::app::{PortSortBy, SortOrder, ViewSort};
+use crate::util::{FieldValue, Filterable};
+
+pub use proc::{build_inode_pid_map, listening_ports_by_pid, listening_ports_for_pid, socket_descriptions};
 
 pub struct PortInfo {
     pub proto: String,
@@ -24,18 +28,41 @@ pub enum PortRow {
 }
 
 impl Filterable for PortInfo {
-    fn matches_filter(&self, filter_lower: &str) -> bool {
-        contains_lower(&self.proto, filter_lower)
-            || self.port.to_string().contains(filter_lower)
-            || self.pid.to_string().contains(filter_lower)
-            || contains_lower(&self.name, filter_lower)
-            || contains_lower(&self.exe_path, filter_lower)
-            || self.container_id.as_deref().map_or(false, |c| contains_lower(c, filter_lower))
-            || self.group_name.as_deref().map_or(false, |g| contains_lower(g, filter_lower))
-            || self
-                .project_name
-                .as_deref()
-                .map_or(false, |p| contains_lower(p, filter_lower))
+    const FIELDS: &'static [&'static str] =
+        &["proto", "port", "pid", "name", "path", "container", "group", "project"];
+
+    fn search_text(&self) -> Vec<Cow<'_, str>> {
+        let mut text = vec![
+            Cow::Borrowed(self.proto.as_str()),
+            Cow::Owned(self.port.to_string()),
+            Cow::Owned(self.pid.to_string()),
+            Cow::Borrowed(self.name.as_str()),
+            Cow::Borrowed(self.exe_path.as_str()),
+        ];
+        text.extend(
+            [&self.container_id, &self.group_name, &self.project_name]
+                .into_iter()
+                .filter_map(|value| value.as_deref().map(Cow::Borrowed)),
+        );
+        text
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn field(&self, name: &str) -> Option<FieldValue<'_>> {
+        Some(match name {
+            "proto" => FieldValue::Text(Cow::Borrowed(&self.proto)),
+            "port" => FieldValue::Number(f64::from(self.port)),
+            "pid" => FieldValue::Number(f64::from(self.pid.as_u32())),
+            "name" => FieldValue::Text(Cow::Borrowed(&self.name)),
+            "path" => FieldValue::Text(Cow::Borrowed(&self.exe_path)),
+            "container" => FieldValue::Text(Cow::Borrowed(self.container_id.as_deref()?)),
+            "group" => FieldValue::Text(Cow::Borrowed(self.group_name.as_deref()?)),
+            "project" => FieldValue::Text(Cow::Borrowed(self.project_name.as_deref()?)),
+            _ => return None,
+        })
     }
 }
 
@@ -73,6 +100,27 @@ pub fn collect_ports(system: &System) -> Vec<PortInfo> {
     rows
 }
 
+/// Orders the ports before grouping; groups then follow their first port.
+pub fn sort_ports(ports: &mut [PortInfo], sort: ViewSort<PortSortBy>) {
+    ports.sort_by(|a, b| {
+        let ordering = match sort.by {
+            PortSortBy::Port => a.port.cmp(&b.port),
+            PortSortBy::Proto => a.proto.cmp(&b.proto),
+            PortSortBy::Pid => a.pid.cmp(&b.pid),
+            PortSortBy::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+            PortSortBy::Project => a.project_name.cmp(&b.project_name),
+        };
+        let ordering = match sort.order {
+            SortOrder::Asc => ordering,
+            SortOrder::Desc => ordering.reverse(),
+        };
+        ordering
+            .then_with(|| a.port.cmp(&b.port))
+            .then_with(|| a.proto.cmp(&b.proto))
+            .then_with(|| a.pid.cmp(&b.pid))
+    });
+}
+
 pub fn group_ports(ports: &[PortInfo]) -> Vec<PortRow> {
     if ports.is_empty() {
         return Vec::new();
@@ -194,3 +242,76 @@ fn display_group_label(name: &str) -> String {
         after_colon.to_string()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn port(proto: &str, port: u16, pid: u32, name: &str) -> PortInfo {
+        PortInfo {
+            proto: proto.to_string(),
+            port,
+            pid: Pid::from_u32(pid),
+            name: name.to_string(),
+            exe_path: String::new(),
+            container_id: None,
+            group_name: None,
+            project_name: None,
+        }
+    }
+
+    fn sorted(by: PortSortBy, order: SortOrder) -> Vec<(String, u16, u32)> {
+        let mut ports = vec![
+            port("udp", 5353, 900, "avahi"),
+            port("tcp", 8080, 200, "Node"),
+            port("tcp", 3000, 200, "node"),
+            port("udp", 3000, 200, "node"),
+            port("tcp", 3000, 150, "node"),
+        ];
+        sort_ports(&mut ports, ViewSort { by, order });
+        ports
+            .into_iter()
+            .map(|port| (port.proto, port.port, port.pid.as_u32()))
+            .collect()
+    }
+
+    fn entry(proto: &str, port: u16, pid: u32) -> (String, u16, u32) {
+        (proto.to_string(), port, pid)
+    }
+
+    #[test]
+    fn test_sort_ports_breaks_ties_by_port_proto_pid() {
+        // Names compare case-insensitively, so all four node sockets tie
+        assert_eq!(
+            sorted(PortSortBy::Name, SortOrder::Asc),
+            vec![
+                entry("udp", 5353, 900),
+                entry("tcp", 3000, 150),
+                entry("tcp", 3000, 200),
+                entry("udp", 3000, 200),
+                entry("tcp", 8080, 200),
+            ]
+        );
+        // Descending reverses the key only; ties keep ascending order
+        assert_eq!(
+            sorted(PortSortBy::Name, SortOrder::Desc),
+            vec![
+                entry("tcp", 3000, 150),
+                entry("tcp", 3000, 200),
+                entry("udp", 3000, 200),
+                entry("tcp", 8080, 200),
+                entry("udp", 5353, 900),
+            ]
+        );
+        assert_eq!(
+            sorted(PortSortBy::Pid, SortOrder::Asc),
+            vec![
+                entry("tcp", 3000, 150),
+                entry("tcp", 3000, 200),
+                entry("udp", 3000, 200),
+                entry("tcp", 8080, 200),
+                entry("udp", 5353, 900),
+            ]
+        );
+    }
+}
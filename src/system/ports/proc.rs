@@ -1,5 +1,6 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
+use std::net::{Ipv4Addr, Ipv6Addr};
 use std::path::Path;
 use std::sync::{Mutex, OnceLock};
 use std::time::{Duration, Instant};
@@ -111,6 +112,159 @@ fn build_inode_pid_map_uncached() -> HashMap<u64, Pid> {
     map
 }
 
+/// TCP ports a single process is listening on, read fresh (bypassing the
+/// inode cache) from its own fd table and network namespace.
+pub fn listening_ports_for_pid(pid: Pid) -> Vec<u16> {
+    let proc_dir = Path::new("/proc").join(pid.as_u32().to_string());
+    let Ok(fd_entries) = fs::read_dir(proc_dir.join("fd")) else {
+        return Vec::new();
+    };
+    let inodes: HashSet<u64> = fd_entries
+        .flatten()
+        .filter_map(|fd| fs::read_link(fd.path()).ok())
+        .filter_map(|target| parse_socket_inode(&target))
+        .collect();
+    if inodes.is_empty() {
+        return Vec::new();
+    }
+
+    let mut ports = Vec::new();
+    for table in ["net/tcp", "net/tcp6"] {
+        let Ok(contents) = fs::read_to_string(proc_dir.join(table)) else {
+            continue;
+        };
+        for (port, inode) in listening_sockets(&contents) {
+            if inodes.contains(&inode) && !ports.contains(&port) {
+                ports.push(port);
+            }
+        }
+    }
+    ports.sort_unstable();
+    ports
+}
+
+/// TCP ports every process is listening on, matched through the cached
+/// inode-to-PID map.
+pub fn listening_ports_by_pid() -> HashMap<Pid, Vec<u16>> {
+    let inode_map = build_inode_pid_map();
+    let mut by_pid: HashMap<Pid, Vec<u16>> = HashMap::new();
+    for table in ["/proc/net/tcp", "/proc/net/tcp6"] {
+        let Ok(contents) = fs::read_to_string(table) else {
+            continue;
+        };
+        for (port, inode) in listening_sockets(&contents) {
+            let Some(pid) = inode_map.get(&inode) else {
+                continue;
+            };
+            let ports = by_pid.entry(*pid).or_default();
+            if !ports.contains(&port) {
+                ports.push(port);
+            }
+        }
+    }
+    for ports in by_pid.values_mut() {
+        ports.sort_unstable();
+    }
+    by_pid
+}
+
+/// "proto local -> remote STATE" for each socket inode visible in the
+/// network namespace of `pid`, including unix sockets.
+pub fn socket_descriptions(pid: Pid) -> HashMap<u64, String> {
+    let net_dir = Path::new("/proc").join(pid.as_u32().to_string()).join("net");
+    let mut descriptions = HashMap::new();
+    for proto in ["tcp", "tcp6", "udp", "udp6"] {
+        let Ok(contents) = fs::read_to_string(net_dir.join(proto)) else {
+            continue;
+        };
+        descriptions.extend(describe_inet_sockets(&contents, proto));
+    }
+    if let Ok(contents) = fs::read_to_string(net_dir.join("unix")) {
+        descriptions.extend(describe_unix_sockets(&contents));
+    }
+    descriptions
+}
+
+fn describe_inet_sockets<'a>(contents: &'a str, proto: &'a str) -> impl Iterator<Item = (u64, String)> + 'a {
+    contents.lines().skip(1).filter_map(move |line| {
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        if parts.len() < 10 {
+            return None;
+        }
+        let inode: u64 = parts[9].parse().ok().filter(|inode| *inode != 0)?;
+        let local = parse_address(parts[1])?;
+        let description = match (proto.starts_with("tcp"), tcp_state(parts[3])) {
+            (true, "LISTEN") => format!("{proto} {local} LISTEN"),
+            (true, state) => format!("{proto} {local} -> {} {state}", parse_address(parts[2])?),
+            (false, _) => format!("{proto} {local}"),
+        };
+        Some((inode, description))
+    })
+}
+
+fn describe_unix_sockets(contents: &str) -> impl Iterator<Item = (u64, String)> + '_ {
+    contents.lines().skip(1).filter_map(|line| {
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        let inode: u64 = parts.get(6)?.parse().ok()?;
+        let path = parts.get(7).copied().unwrap_or("(unnamed)");
+        Some((inode, format!("unix {path}")))
+    })
+}
+
+/// Formats a hex "address:port" pair from a /proc/net table. Addresses are
+/// written as 32-bit words in host byte order.
+fn parse_address(text: &str) -> Option<String> {
+    let (address, port) = text.split_once(':')?;
+    let port = u16::from_str_radix(port, 16).ok()?;
+    let mut bytes = Vec::with_capacity(16);
+    for chunk in address.as_bytes().chunks(8) {
+        let word = u32::from_str_radix(std::str::from_utf8(chunk).ok()?, 16).ok()?;
+        bytes.extend(word.to_le_bytes());
+    }
+    match bytes.len() {
+        4 => Some(format!("{}:{port}", Ipv4Addr::new(bytes[0], bytes[1], bytes[2], bytes[3]))),
+        16 => {
+            let octets: [u8; 16] = bytes.try_into().ok()?;
+            let ip = Ipv6Addr::from(octets);
+            match ip.to_ipv4_mapped() {
+                Some(v4) => Some(format!("{v4}:{port}")),
+                None => Some(format!("[{ip}]:{port}")),
+            }
+        }
+        _ => None,
+    }
+}
+
+fn tcp_state(code: &str) -> &'static str {
+    match code {
+        "01" => "ESTABLISHED",
+        "02" => "SYN_SENT",
+        "03" => "SYN_RECV",
+        "04" => "FIN_WAIT1",
+        "05" => "FIN_WAIT2",
+        "06" => "TIME_WAIT",
+        "07" => "CLOSE",
+        "08" => "CLOSE_WAIT",
+        "09" => "LAST_ACK",
+        "0A" => "LISTEN",
+        "0B" => "CLOSING",
+        _ => "UNKNOWN",
+    }
+}
+
+/// (port, inode) of each socket in LISTEN state in a /proc/net/tcp table.
+fn listening_sockets(contents: &str) -> impl Iterator<Item = (u16, u64)> + '_ {
+    contents.lines().skip(1).filter_map(|line| {
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        if parts.len() < 10 || parts[3] != "0A" {
+            return None;
+        }
+        let inode: u64 = parts[9].parse().unwrap_or(0);
+        let port = parse_port(parts[1]);
+        (port != 0 && inode != 0).then_some((port, inode))
+    })
+}
+
 fn parse_socket_table(
     path: &str,
     proto: &str,
@@ -188,3 +342,32 @@ fn parse_socket_inode(path: &Path) -> Option<u64> {
     let inner = link.trim_start_matches("socket:[").trim_end_matches(']');
     inner.parse::<u64>().ok()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_describe_sockets() {
+        let tcp = "  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 0100007F:0BB8 00000000:0000 0A 00000000:00000000 00:00000000 00000000  1000        0 51234 1 0 100 0 0 10 0
+   1: 0100007F:D431 0100007F:1538 01 00000000:00000000 00:00000000 00000000  1000        0 51240 1 0 20 4 30 10 -1
+";
+        let sockets: HashMap<u64, String> = describe_inet_sockets(tcp, "tcp").collect();
+        assert_eq!(sockets[&51234], "tcp 127.0.0.1:3000 LISTEN");
+        assert_eq!(sockets[&51240], "tcp 127.0.0.1:54321 -> 127.0.0.1:5432 ESTABLISHED");
+
+        assert_eq!(
+            parse_address("00000000000000000000000001000000:1F90").as_deref(),
+            Some("[::1]:8080")
+        );
+
+        let unix = "Num       RefCount Protocol Flags    Type St Inode Path
+0000000000000000: 00000002 00000000 00010000 0001 01 20915 /run/docker.sock
+0000000000000000: 00000003 00000000 00000000 0001 03 20990
+";
+        let sockets: HashMap<u64, String> = describe_unix_sockets(unix).collect();
+        assert_eq!(sockets[&20915], "unix /run/docker.sock");
+        assert_eq!(sockets[&20990], "unix (unnamed)");
+    }
+}
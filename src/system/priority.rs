@@ -0,0 +1,342 @@
+//! Scheduling priority, I/O priority and CPU affinity of processes.
+//!
+//! Linux keeps all three per thread, so reads use the main thread and
+//! changes are applied to every thread of the process.
+
+use std::io;
+
+use sysinfo::Pid;
+
+use super::stat::{self, StatFields};
+
+/// Lowest and highest nice values
+const NICE_RANGE: (i32, i32) = (-20, 19);
+/// Levels within the realtime and best-effort I/O classes, 0 being the highest
+const IO_LEVELS: u8 = 8;
+
+/// I/O scheduling class, as set by `ionice`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IoClass {
+    /// No class set; the kernel derives best-effort from the nice value
+    None,
+    Realtime,
+    BestEffort,
+    Idle,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IoPriority {
+    pub class: IoClass,
+    /// 0-7 within the realtime and best-effort classes
+    pub level: u8,
+}
+
+impl IoPriority {
+    /// e.g. "be/4", "rt/0", "idle"
+    pub fn label(&self) -> String {
+        match self.class {
+            IoClass::None => "none".to_string(),
+            IoClass::Realtime => format!("rt/{}", self.level),
+            IoClass::BestEffort => format!("be/{}", self.level),
+            IoClass::Idle => "idle".to_string(),
+        }
+    }
+
+    fn parse(text: &str) -> Result<IoPriority, String> {
+        let text = text.trim().to_ascii_lowercase();
+        let (class, level) = match text.split_once('/') {
+            Some((class, level)) => (class, Some(level)),
+            None => (text.as_str(), None),
+        };
+        let class = match class {
+            "none" => IoClass::None,
+            "rt" | "realtime" => IoClass::Realtime,
+            "be" | "best-effort" => IoClass::BestEffort,
+            "idle" => IoClass::Idle,
+            _ => return Err(format!("'{text}' is not one of be/N, rt/N, idle, none")),
+        };
+        let level = match (class, level) {
+            (IoClass::Realtime | IoClass::BestEffort, Some(level)) => level
+                .parse::<u8>()
+                .ok()
+                .filter(|level| *level < IO_LEVELS)
+                .ok_or_else(|| format!("level '{level}' is not 0-7"))?,
+            (IoClass::Realtime | IoClass::BestEffort, None) => 4,
+            (_, Some(_)) => return Err(format!("'{text}' takes no level")),
+            (_, None) => 0,
+        };
+        Ok(IoPriority { class, level })
+    }
+}
+
+/// Current nice value, I/O priority and allowed CPUs of a process.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SchedInfo {
+    pub nice: i32,
+    /// Unknown when the process belongs to another user
+    pub io: Option<IoPriority>,
+    /// Allowed CPUs as a list such as "0-3,6"
+    pub cpus: String,
+}
+
+/// Changes to apply to a process. `None` fields are left untouched.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PriorityUpdate {
+    pub nice: Option<i32>,
+    pub io: Option<IoPriority>,
+    pub cpus: Option<Vec<usize>>,
+}
+
+impl PriorityUpdate {
+    pub fn is_empty(&self) -> bool {
+        self.nice.is_none() && self.io.is_none() && self.cpus.is_none()
+    }
+
+    /// Drops the values `current` already has.
+    pub fn changed_from(mut self, current: &SchedInfo) -> PriorityUpdate {
+        if self.nice == Some(current.nice) {
+            self.nice = None;
+        }
+        if self.io.is_some() && self.io == current.io {
+            self.io = None;
+        }
+        let current_cpus = parse_cpu_list(&current.cpus, usize::MAX).ok();
+        if self.cpus.is_some() && self.cpus == current_cpus {
+            self.cpus = None;
+        }
+        self
+    }
+}
+
+#[cfg(target_os = "linux")]
+pub fn read_sched_info(pid: Pid) -> Option<SchedInfo> {
+    let stat = std::fs::read_to_string(format!("/proc/{}/stat", pid.as_u32())).ok()?;
+    let status = std::fs::read_to_string(format!("/proc/{}/status", pid.as_u32())).ok()?;
+    Some(SchedInfo {
+        nice: parse_nice(&stat)?,
+        io: sys::io_priority(pid.as_u32()),
+        cpus: parse_cpus_allowed(&status)?,
+    })
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn read_sched_info(_pid: Pid) -> Option<SchedInfo> {
+    None
+}
+
+/// Validates form input; empty fields are left untouched.
+/// `max_cpus` bounds the CPU indexes.
+pub fn validate_priority(nice: &str, io: &str, cpus: &str, max_cpus: usize) -> Result<PriorityUpdate, String> {
+    let mut update = PriorityUpdate::default();
+
+    let nice = nice.trim();
+    if !nice.is_empty() {
+        let value: i32 = nice
+            .parse()
+            .map_err(|_| format!("Nice: '{nice}' is not a number"))?;
+        if value < NICE_RANGE.0 || value > NICE_RANGE.1 {
+            return Err(format!("Nice: must be between {} and {}", NICE_RANGE.0, NICE_RANGE.1));
+        }
+        update.nice = Some(value);
+    }
+
+    let io = io.trim();
+    if !io.is_empty() {
+        update.io = Some(IoPriority::parse(io).map_err(|err| format!("I/O class: {err}"))?);
+    }
+
+    let cpus = cpus.trim();
+    if !cpus.is_empty() {
+        update.cpus = Some(parse_cpu_list(cpus, max_cpus).map_err(|err| format!("CPUs: {err}"))?);
+    }
+
+    Ok(update)
+}
+
+/// Applies `update` to every thread of each process in `pids`.
+pub fn apply_priority(pids: &[Pid], update: &PriorityUpdate) -> io::Result<()> {
+    for pid in pids {
+        for tid in thread_ids(*pid) {
+            #[cfg(target_os = "linux")]
+            {
+                if let Some(nice) = update.nice {
+                    sys::set_nice(tid, nice)?;
+                }
+                if let Some(io) = update.io {
+                    sys::set_io_priority(tid, io)?;
+                }
+                if let Some(cpus) = &update.cpus {
+                    sys::set_affinity(tid, cpus)?;
+                }
+            }
+            #[cfg(not(target_os = "linux"))]
+            {
+                let _ = tid;
+                return Err(io::Error::new(
+                    io::ErrorKind::Unsupported,
+                    "priorities can only be changed on Linux",
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Thread IDs of a process; just the PID when its tasks cannot be listed.
+fn thread_ids(pid: Pid) -> Vec<u32> {
+    let tids: Vec<u32> = std::fs::read_dir(format!("/proc/{}/task", pid.as_u32()))
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.ok()?.file_name().to_str()?.parse().ok())
+        .collect();
+    if tids.is_empty() {
+        vec![pid.as_u32()]
+    } else {
+        tids
+    }
+}
+
+fn parse_nice(stat: &str) -> Option<i32> {
+    StatFields::parse(stat)?.parse_field(stat::NICE)
+}
+
+fn parse_cpus_allowed(status: &str) -> Option<String> {
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix("Cpus_allowed_list:"))
+        .map(|list| list.trim().to_string())
+}
+
+/// Parses CPU lists such as "0-3,6" into sorted indexes.
+fn parse_cpu_list(text: &str, max_cpus: usize) -> Result<Vec<usize>, String> {
+    let mut cpus = Vec::new();
+    for part in text.split(',').map(str::trim) {
+        let (start, end) = part.split_once('-').unwrap_or((part, part));
+        let parse = |value: &str| -> Result<usize, String> {
+            value
+                .trim()
+                .parse()
+                .map_err(|_| format!("'{part}' is not a CPU index or range"))
+        };
+        let (start, end) = (parse(start)?, parse(end)?);
+        if start > end {
+            return Err(format!("range '{part}' is reversed"));
+        }
+        if end >= max_cpus {
+            return Err(format!("CPU {end} does not exist (0-{})", max_cpus.saturating_sub(1)));
+        }
+        cpus.extend(start..=end);
+    }
+    cpus.sort_unstable();
+    cpus.dedup();
+    Ok(cpus)
+}
+
+#[cfg(target_os = "linux")]
+mod sys {
+    use std::io;
+
+    use super::{IoClass, IoPriority};
+
+    const IOPRIO_WHO_PROCESS: libc::c_long = 1;
+    const IOPRIO_CLASS_SHIFT: u32 = 13;
+
+    pub(super) fn set_nice(tid: u32, nice: i32) -> io::Result<()> {
+        // SAFETY: plain syscall wrapper without pointers
+        let result = unsafe { libc::setpriority(libc::PRIO_PROCESS, tid, nice) };
+        check(result as libc::c_long)
+    }
+
+    pub(super) fn io_priority(tid: u32) -> Option<IoPriority> {
+        // SAFETY: ioprio_get only takes integers
+        let value = unsafe {
+            libc::syscall(libc::SYS_ioprio_get, IOPRIO_WHO_PROCESS, tid as libc::c_long)
+        };
+        if value < 0 {
+            return None;
+        }
+        let class = match value >> IOPRIO_CLASS_SHIFT {
+            1 => IoClass::Realtime,
+            2 => IoClass::BestEffort,
+            3 => IoClass::Idle,
+            _ => IoClass::None,
+        };
+        let level = (value & ((1 << IOPRIO_CLASS_SHIFT) - 1)) as u8;
+        Some(IoPriority { class, level })
+    }
+
+    pub(super) fn set_io_priority(tid: u32, io: IoPriority) -> io::Result<()> {
+        let class: libc::c_long = match io.class {
+            IoClass::None => 0,
+            IoClass::Realtime => 1,
+            IoClass::BestEffort => 2,
+            IoClass::Idle => 3,
+        };
+        let value = (class << IOPRIO_CLASS_SHIFT) | io.level as libc::c_long;
+        // SAFETY: ioprio_set only takes integers
+        let result = unsafe {
+            libc::syscall(libc::SYS_ioprio_set, IOPRIO_WHO_PROCESS, tid as libc::c_long, value)
+        };
+        check(result)
+    }
+
+    pub(super) fn set_affinity(tid: u32, cpus: &[usize]) -> io::Result<()> {
+        // SAFETY: cpu_set_t is a plain bitmask, valid when zeroed
+        let mut set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+        for cpu in cpus {
+            // SAFETY: CPU_SET bounds-checks the index against the set size
+            unsafe { libc::CPU_SET(*cpu, &mut set) };
+        }
+        // SAFETY: `set` outlives the call and its size is passed along
+        let result = unsafe {
+            libc::sched_setaffinity(tid as libc::pid_t, std::mem::size_of::<libc::cpu_set_t>(), &set)
+        };
+        check(result as libc::c_long)
+    }
+
+    fn check(result: libc::c_long) -> io::Result<()> {
+        if result < 0 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_proc_fields() {
+        let stat = "812 (cargo build) R 700 812 700 34817 812 4194304 9000 0 0 0 500 80 0 0 30 10 12 0";
+        assert_eq!(parse_nice(stat), Some(10));
+        let status = "Name:\tcargo\nCpus_allowed:\tff\nCpus_allowed_list:\t0-7\n";
+        assert_eq!(parse_cpus_allowed(status).as_deref(), Some("0-7"));
+        assert_eq!(parse_cpu_list("0-2,6,1", 8), Ok(vec![0, 1, 2, 6]));
+        assert!(parse_cpu_list("3-1", 8).is_err());
+        assert!(parse_cpu_list("8", 8).is_err());
+    }
+
+    #[test]
+    fn test_validate_priority() {
+        let current = SchedInfo {
+            nice: 0,
+            io: Some(IoPriority { class: IoClass::None, level: 4 }),
+            cpus: "0-3".to_string(),
+        };
+        assert!(validate_priority("0", "", "0-3", 4).unwrap().changed_from(&current).is_empty());
+
+        let update = validate_priority("10", "idle", "0,1", 4).unwrap().changed_from(&current);
+        assert_eq!(update.nice, Some(10));
+        assert_eq!(update.io, Some(IoPriority { class: IoClass::Idle, level: 0 }));
+        assert_eq!(update.cpus, Some(vec![0, 1]));
+        let io = validate_priority("", "be", "", 4).unwrap().io;
+        assert_eq!(io.map(|io| io.label()).as_deref(), Some("be/4"));
+
+        assert!(validate_priority("25", "", "", 4).is_err());
+        assert!(validate_priority("", "be/9", "", 4).is_err());
+        assert!(validate_priority("", "idle/2", "", 4).is_err());
+        assert!(validate_priority("", "", "5", 4).is_err());
+    }
+}
@@ -1,14 +1,19 @@
-use std::collections::HashMap;
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
 use std::fs::File;
 use std::io::{self, BufRead, BufReader};
 use std::sync::{Mutex, OnceLock};
 use std::time::{Duration, Instant};
 
-use sysinfo::{Pid, System, Uid};
+use sysinfo::{Pid, ProcessStatus, Signal, System, Uid};
 
 use crate::app::{SortBy, SortOrder};
 use super::docker;
-use crate::util::cmp_f32;
+use super::net::NetRates;
+use super::priority::SchedInfo;
+use super::stat::{self, StatFields};
+use super::wait;
+use crate::util::{cmp_f32, FieldValue, Filterable, Query};
 
 pub struct ProcInfo {
     pub pid: Pid,
@@ -16,30 +21,131 @@ pub struct ProcInfo {
     pub name_lower: String,
     pub cpu: f32,
     pub memory_bytes: u64,
+    /// Disk throughput since the previous refresh, in bytes per second
+    pub read_rate: u64,
+    pub write_rate: u64,
     pub user: String,
     pub exe_path: String,
+    pub cmdline: String,
     pub parent: Option<Pid>,
     pub container: Option<String>,
     pub is_thread: bool,
+    /// Seconds since the epoch; tells a process apart from a later one reusing its PID
+    pub start_time: u64,
+    /// `memory_bytes` holds the total of a multi-process app family, not just this process
+    pub family_memory: bool,
+    /// Nice value, I/O priority and affinity; read only while their columns are shown
+    pub sched: Option<SchedInfo>,
+    /// Network throughput; sampled only while its columns are shown
+    pub net: Option<NetRates>,
+    /// 'Z' for zombies; 'D' for uninterruptible processes kept by [`retain_stuck`]
+    pub stuck_state: Option<char>,
+}
+
+impl ProcInfo {
+    pub fn is_zombie(&self) -> bool {
+        self.stuck_state == Some('Z')
+    }
+}
+
+impl Filterable for ProcInfo {
+    const FIELDS: &'static [&'static str] = &[
+        "pid", "ppid", "name", "user", "cpu", "mem", "read", "write", "path", "cmd", "container",
+    ];
+
+    fn search_text(&self) -> Vec<Cow<'_, str>> {
+        vec![Cow::Borrowed(self.name.as_str())]
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn field(&self, name: &str) -> Option<FieldValue<'_>> {
+        Some(match name {
+            "pid" => FieldValue::Number(f64::from(self.pid.as_u32())),
+            "ppid" => FieldValue::Number(f64::from(self.parent?.as_u32())),
+            "name" => FieldValue::Text(Cow::Borrowed(&self.name)),
+            "user" => FieldValue::Text(Cow::Borrowed(&self.user)),
+            "cpu" => FieldValue::Number(f64::from(self.cpu)),
+            "mem" => FieldValue::Number(self.memory_bytes as f64),
+            "read" => FieldValue::Number(self.read_rate as f64),
+            "write" => FieldValue::Number(self.write_rate as f64),
+            "path" => FieldValue::Text(Cow::Borrowed(&self.exe_path)),
+            "cmd" => FieldValue::Text(Cow::Borrowed(&self.cmdline)),
+            "container" => FieldValue::Text(Cow::Borrowed(self.container.as_deref()?)),
+            _ => return None,
+        })
+    }
+}
+
+/// Identifies a process across refreshes, for state such as tree folding.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ProcessKey {
+    pub pid: Pid,
+    pub start_time: u64,
+}
+
+impl ProcessKey {
+    pub fn of(process: &sysinfo::Process) -> Self {
+        ProcessKey {
+            pid: process.pid(),
+            start_time: process.start_time(),
+        }
+    }
 }
 
 pub struct TreeRow {
     pub pid: Pid,
     pub prefix: String,
+    pub fold: Fold,
+    /// Totals of the hidden subtree, set on collapsed rows
+    pub subtree: Option<SubtreeTotals>,
+}
+
+/// Whether a tree row has children and if they are shown.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Fold {
+    Leaf,
+    Expanded,
+    Collapsed,
+}
+
+/// CPU and memory of a process and all its descendants.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SubtreeTotals {
+    pub cpu: f32,
+    pub memory_bytes: u64,
+    pub read_rate: u64,
+    pub write_rate: u64,
+    pub net: NetRates,
+    /// Descendants folded into the row, excluding the row itself
+    pub hidden: usize,
 }
 
 /// Static string constant to avoid repeated allocations
 const DASH: &str = "-";
 
+/// Collects process info for the Process view. With `container_scope` set, only
+/// processes running inside that container (matched via cgroup) are returned.
 pub fn collect_processes(
     system: &System,
-    filter: &str,
+    query: &Query,
     container_cache: &HashMap<String, String>,
     user_cache: &HashMap<Uid, String>,
     skip_threads: bool,
+    container_scope: Option<&str>,
+    io_interval: Duration,
 ) -> HashMap<Pid, ProcInfo> {
-    let filter_lower = filter.to_lowercase();
-    let has_filter = !filter_lower.is_empty();
+    // sysinfo reports bytes transferred since the previous refresh
+    let io_secs = io_interval.as_secs_f64();
+    let per_second = |bytes: u64| {
+        if io_secs > 0.0 {
+            (bytes as f64 / io_secs) as u64
+        } else {
+            0
+        }
+    };
 
     // Pre-allocate with estimated capacity
     let mut processes: HashMap<Pid, ProcInfo> =
@@ -50,23 +156,22 @@ pub fn collect_processes(
             continue;
         }
 
-        // Get name as borrowed reference for filter check
-        let name_ref = process.name();
-
-        // Filter early using borrowed reference to avoid allocation for filtered items
-        if has_filter {
-            // Check if lowercase name contains filter - only allocate if we need to
-            let matches = name_ref.to_lowercase().contains(&filter_lower);
-            if !matches {
+        let container_id = docker::container_id_for(*pid);
+        if let Some(scope) = container_scope {
+            let in_scope = container_id
+                .as_deref()
+                .is_some_and(|id| docker::container_ids_match(id, scope));
+            if !in_scope {
                 continue;
             }
         }
 
-        // Only allocate strings after filter passes
-        let name = name_ref.to_string();
+        let name = process.name().to_string();
         let name_lower = name.to_lowercase();
         let is_thread = process.thread_kind().is_some();
-        let container = docker::container_label_for(*pid, container_cache);
+        let container = container_id
+            .as_deref()
+            .map(|id| docker::container_label_for_id(id, container_cache));
         let user = process
             .user_id()
             .and_then(|uid| user_cache.get(uid))
@@ -77,6 +182,7 @@ pub fn collect_processes(
             .exe()
             .map(|path| path.to_string_lossy().into_owned())
             .unwrap_or_else(|| DASH.to_string());
+        let disk = process.disk_usage();
 
         processes.insert(
             *pid,
@@ -86,25 +192,40 @@ pub fn collect_processes(
                 name_lower,
                 cpu: process.cpu_usage(),
                 memory_bytes: process.memory(),
+                read_rate: per_second(disk.read_bytes),
+                write_rate: per_second(disk.written_bytes),
                 user,
                 exe_path,
+                cmdline: process.cmd().join(" "),
                 parent: process.parent(),
                 container,
                 is_thread,
+                start_time: process.start_time(),
+                family_memory: false,
+                sched: None,
+                net: None,
+                stuck_state: (process.status() == ProcessStatus::Zombie).then_some('Z'),
             },
         );
     }
 
     apply_multiprocess_memory_aggregation(&mut processes);
+    // Filter after aggregation so that `mem` predicates see family totals
+    if !query.is_empty() {
+        processes.retain(|_, info| query.matches(info));
+    }
 
     processes
 }
 
+/// Rows of the process list. With `show_children` the list is a tree in
+/// which the children of processes in `collapsed` are folded into their parent.
 pub fn build_tree_rows(
     processes: &HashMap<Pid, ProcInfo>,
     sort_by: SortBy,
     sort_order: SortOrder,
     show_children: bool,
+    collapsed: &HashSet<ProcessKey>,
 ) -> Vec<TreeRow> {
     let mut children: HashMap<Pid, Vec<Pid>> = HashMap::new();
     let mut roots: Vec<Pid> = Vec::new();
@@ -127,6 +248,22 @@ pub fn build_tree_rows(
         }
     }
 
+    // Without the tree, zombies would be hidden along with the other children;
+    // list them under their reaping parent instead
+    let mut zombies: HashMap<Pid, Vec<Pid>> = HashMap::new();
+    if !show_children {
+        let root_set: HashSet<Pid> = roots.iter().copied().collect();
+        for (pid, info) in processes {
+            if !info.is_zombie() || root_set.contains(pid) {
+                continue;
+            }
+            match info.parent.filter(|parent| root_set.contains(parent)) {
+                Some(parent) => zombies.entry(parent).or_default().push(*pid),
+                None => roots.push(*pid),
+            }
+        }
+    }
+
     sort_pid_list(&mut roots, processes, sort_by, sort_order);
     if show_children {
         for list in children.values_mut() {
@@ -136,17 +273,36 @@ pub fn build_tree_rows(
 
     let mut rows = Vec::new();
     if show_children {
+        let tree = Tree {
+            processes,
+            children: &children,
+            collapsed,
+        };
         let mut ancestor_last = Vec::new();
         for (idx, pid) in roots.iter().enumerate() {
             let is_last = idx + 1 == roots.len();
-            push_tree_rows(*pid, is_last, &mut ancestor_last, &children, &mut rows);
+            tree.push_rows(*pid, is_last, &mut ancestor_last, &mut rows);
         }
     } else {
         for pid in roots {
             rows.push(TreeRow {
                 pid,
                 prefix: String::new(),
+                fold: Fold::Leaf,
+                subtree: None,
             });
+            let Some(list) = zombies.get_mut(&pid) else {
+                continue;
+            };
+            list.sort();
+            for (idx, zombie) in list.iter().enumerate() {
+                rows.push(TreeRow {
+                    pid: *zombie,
+                    prefix: build_tree_prefix(&[true], idx + 1 == list.len()),
+                    fold: Fold::Leaf,
+                    subtree: None,
+                });
+            }
         }
     }
 
@@ -180,6 +336,269 @@ pub fn load_process_env(pid: Pid) -> io::Result<Vec<String>> {
     }
 }
 
+/// KEY=VALUE lines describing a process, shown in the detail pane.
+pub fn load_process_details(
+    system: &System,
+    pid: Pid,
+    container_cache: &HashMap<String, String>,
+    user_cache: &HashMap<Uid, String>,
+) -> Option<Vec<String>> {
+    let process = system.process(pid)?;
+    let user = process
+        .user_id()
+        .and_then(|uid| user_cache.get(uid))
+        .cloned()
+        .unwrap_or_else(|| DASH.to_string());
+    let parent = process
+        .parent()
+        .map(|ppid| ppid.to_string())
+        .unwrap_or_else(|| DASH.to_string());
+    let path_or_dash = |path: Option<&std::path::Path>| {
+        path.map(|path| path.to_string_lossy().into_owned())
+            .unwrap_or_else(|| DASH.to_string())
+    };
+    let container = match docker::container_id_for(pid) {
+        Some(id) => {
+            let label = docker::container_label_for_id(&id, container_cache);
+            let short = if id.len() > 12 { &id[..12] } else { &id };
+            format!("{label} ({short})")
+        }
+        None => DASH.to_string(),
+    };
+    let command = process.cmd().join(" ");
+    let wait = wait::read_wait_info(pid);
+    let state = wait
+        .state
+        .map(wait::state_description)
+        .unwrap_or_else(|| process.status().to_string());
+
+    let mut lines = vec![
+        format!("NAME={}", process.name()),
+        format!("PID={pid}"),
+        format!("PPID={parent}"),
+        format!("STATE={state}"),
+        format!("USER={user}"),
+        format!("UPTIME={}", format_duration(process.run_time())),
+        format!("CPU%={:.1}", process.cpu_usage()),
+        format!("MEMORY={:.1} MB", process.memory() as f64 / 1024.0 / 1024.0),
+        format!("VIRTUAL={:.1} MB", process.virtual_memory() as f64 / 1024.0 / 1024.0),
+        format!("EXE={}", path_or_dash(process.exe())),
+        format!("CWD={}", path_or_dash(process.cwd())),
+        format!("COMMAND={}", if command.is_empty() { DASH } else { &command }),
+        format!("CONTAINER={container}"),
+    ];
+    if let Some(tasks) = process.tasks() {
+        lines.insert(5, format!("THREADS={}", tasks.len()));
+    }
+    lines.push(format!("WCHAN={}", wait.wchan.as_deref().unwrap_or(DASH)));
+    lines.push(format!("SYSCALL={}", wait.syscall.as_deref().unwrap_or(DASH)));
+    match wait.stack {
+        Ok(frames) if frames.is_empty() => lines.push(format!("STACK={DASH}")),
+        Ok(frames) => lines.extend(frames.into_iter().map(|frame| format!("STACK={frame}"))),
+        Err(reason) => lines.push(format!("STACK={reason}")),
+    }
+    #[cfg(target_os = "linux")]
+    if let Ok(cgroup) = std::fs::read_to_string(format!("/proc/{}/cgroup", pid.as_u32())) {
+        for entry in cgroup.lines().filter(|line| !line.is_empty()) {
+            lines.push(format!("CGROUP={entry}"));
+        }
+    }
+    Some(lines)
+}
+
+/// Sends `signal` to a process, looking it up fresh so stale PIDs are reported.
+pub fn send_signal(pid: Pid, signal: Signal) -> io::Result<()> {
+    let mut system = System::new();
+    if !system.refresh_process(pid) {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("process {pid} not found"),
+        ));
+    }
+    let process = system
+        .process(pid)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, format!("process {pid} not found")))?;
+    match process.kill_with(signal) {
+        Some(true) => Ok(()),
+        Some(false) => Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            format!("failed to send {signal} to {pid}"),
+        )),
+        None => Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            format!("{signal} is not supported on this platform"),
+        )),
+    }
+}
+
+/// Which processes a kill reaches besides the selected one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KillScope {
+    /// The process and all its descendants
+    Tree,
+    /// Every process in the selected process's group
+    Group,
+    /// Every process in the selected process's session
+    Session,
+}
+
+impl KillScope {
+    pub fn label(&self) -> &'static str {
+        match self {
+            KillScope::Tree => "tree",
+            KillScope::Group => "process group",
+            KillScope::Session => "session",
+        }
+    }
+}
+
+/// A process that a tree, group or session kill will signal.
+pub struct KillTarget {
+    pub key: ProcessKey,
+    pub name: String,
+    pub command: String,
+}
+
+/// Processes reached by killing `pid` with `scope`, children before parents.
+/// Refuses scopes that include Spark itself.
+pub fn kill_targets(system: &System, pid: Pid, scope: KillScope) -> Result<Vec<KillTarget>, String> {
+    if system.process(pid).is_none() {
+        return Err(format!("process {pid} not found"));
+    }
+    let processes = || {
+        system
+            .processes()
+            .values()
+            .filter(|process| process.thread_kind().is_none())
+    };
+
+    let members: HashSet<Pid> = match scope {
+        KillScope::Tree => tree_pids(system, pid).into_iter().collect(),
+        KillScope::Group | KillScope::Session => {
+            let id_of = |ids: (u32, u32)| if scope == KillScope::Group { ids.0 } else { ids.1 };
+            let ids = read_group_ids(pid)
+                .ok_or_else(|| format!("cannot read the {} of {pid}", scope.label()))?;
+            let wanted = id_of(ids);
+            processes()
+                .map(|process| process.pid())
+                .filter(|member| read_group_ids(*member).map(id_of) == Some(wanted))
+                .collect()
+        }
+    };
+    if members.contains(&Pid::from_u32(std::process::id())) {
+        return Err(format!("Spark itself runs in this {}", scope.label()));
+    }
+
+    let depth = |pid: Pid| {
+        let mut depth = 0;
+        let mut current = system.process(pid).and_then(|process| process.parent());
+        while let Some(parent) = current.filter(|parent| members.contains(parent)) {
+            depth += 1;
+            current = system.process(parent).and_then(|process| process.parent());
+        }
+        depth
+    };
+    let mut ordered: Vec<(usize, Pid)> = members.iter().map(|pid| (depth(*pid), *pid)).collect();
+    ordered.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+    Ok(ordered
+        .into_iter()
+        .filter_map(|(_, pid)| {
+            let process = system.process(pid)?;
+            Some(KillTarget {
+                key: ProcessKey::of(process),
+                name: process.name().to_string(),
+                command: process.cmd().join(" "),
+            })
+        })
+        .collect())
+}
+
+/// `pid` and all its descendants, parents before children.
+pub fn tree_pids(system: &System, pid: Pid) -> Vec<Pid> {
+    let mut children: HashMap<Pid, Vec<Pid>> = HashMap::new();
+    for process in system.processes().values() {
+        if process.thread_kind().is_some() {
+            continue;
+        }
+        if let Some(parent) = process.parent() {
+            children.entry(parent).or_default().push(process.pid());
+        }
+    }
+    let mut pids = Vec::new();
+    let mut stack = vec![pid];
+    while let Some(next) = stack.pop() {
+        if !pids.contains(&next) {
+            pids.push(next);
+            stack.extend(children.get(&next).into_iter().flatten().copied());
+        }
+    }
+    pids
+}
+
+/// Sends `signal` to each target that is still the same process, returning how many were signalled.
+pub fn signal_targets(targets: &[ProcessKey], signal: Signal) -> usize {
+    let mut system = System::new();
+    system.refresh_processes();
+    targets
+        .iter()
+        .filter(|key| {
+            system
+                .process(key.pid)
+                .filter(|process| process.start_time() == key.start_time)
+                .and_then(|process| process.kill_with(signal))
+                .unwrap_or(false)
+        })
+        .count()
+}
+
+/// How many targets are still running; zombies count as exited.
+pub fn count_running(targets: &[ProcessKey]) -> usize {
+    let mut system = System::new();
+    system.refresh_processes();
+    targets
+        .iter()
+        .filter(|key| {
+            system.process(key.pid).is_some_and(|process| {
+                process.start_time() == key.start_time
+                    && process.status() != ProcessStatus::Zombie
+            })
+        })
+        .count()
+}
+
+/// Process group and session IDs from `/proc/<pid>/stat`.
+#[cfg(target_os = "linux")]
+fn read_group_ids(pid: Pid) -> Option<(u32, u32)> {
+    let stat = std::fs::read_to_string(format!("/proc/{}/stat", pid.as_u32())).ok()?;
+    parse_group_ids(&stat)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_group_ids(_pid: Pid) -> Option<(u32, u32)> {
+    None
+}
+
+fn parse_group_ids(stat: &str) -> Option<(u32, u32)> {
+    let fields = StatFields::parse(stat)?;
+    Some((fields.parse_field(stat::PGRP)?, fields.parse_field(stat::SESSION)?))
+}
+
+fn format_duration(secs: u64) -> String {
+    let days = secs / 86_400;
+    let hours = (secs % 86_400) / 3_600;
+    let minutes = (secs % 3_600) / 60;
+    let seconds = secs % 60;
+    if days > 0 {
+        format!("{days}d {hours}h {minutes}m")
+    } else if hours > 0 {
+        format!("{hours}h {minutes}m {seconds}s")
+    } else if minutes > 0 {
+        format!("{minutes}m {seconds}s")
+    } else {
+        format!("{seconds}s")
+    }
+}
+
 fn sort_pid_list(
     pids: &mut [Pid],
     processes: &HashMap<Pid, ProcInfo>,
@@ -210,27 +629,106 @@ fn compare_proc(a: &ProcInfo, b: &ProcInfo, sort_by: SortBy) -> std::cmp::Orderi
         SortBy::Cpu => cmp_f32(a.cpu, b.cpu),
         SortBy::Memory => a.memory_bytes.cmp(&b.memory_bytes),
         SortBy::Name => a.name_lower.cmp(&b.name_lower),
+        SortBy::Io => (a.read_rate + a.write_rate).cmp(&(b.read_rate + b.write_rate)),
+        SortBy::Net => net_total(a).cmp(&net_total(b)),
+        SortBy::Pid => a.pid.cmp(&b.pid),
+        SortBy::User => a.user.cmp(&b.user),
+        SortBy::Started => a.start_time.cmp(&b.start_time),
     }
 }
 
-fn push_tree_rows(
-    pid: Pid,
-    is_last: bool,
-    ancestor_last: &mut Vec<bool>,
-    children: &HashMap<Pid, Vec<Pid>>,
-    rows: &mut Vec<TreeRow>,
-) {
-    let prefix = build_tree_prefix(ancestor_last, is_last);
-    rows.push(TreeRow { pid, prefix });
+fn net_total(info: &ProcInfo) -> u64 {
+    info.net.map_or(0, |net| net.total())
+}
+
+struct Tree<'a> {
+    processes: &'a HashMap<Pid, ProcInfo>,
+    children: &'a HashMap<Pid, Vec<Pid>>,
+    collapsed: &'a HashSet<ProcessKey>,
+}
+
+impl Tree<'_> {
+    fn push_rows(&self, pid: Pid, is_last: bool, ancestor_last: &mut Vec<bool>, rows: &mut Vec<TreeRow>) {
+        let prefix = build_tree_prefix(ancestor_last, is_last);
+        let Some(child_list) = self.children.get(&pid).filter(|list| !list.is_empty()) else {
+            rows.push(TreeRow {
+                pid,
+                prefix,
+                fold: Fold::Leaf,
+                subtree: None,
+            });
+            return;
+        };
+        if self.is_collapsed(pid) {
+            rows.push(TreeRow {
+                pid,
+                prefix,
+                fold: Fold::Collapsed,
+                subtree: Some(self.subtree_totals(pid)),
+            });
+            return;
+        }
+        rows.push(TreeRow {
+            pid,
+            prefix,
+            fold: Fold::Expanded,
+            subtree: None,
+        });
 
-    ancestor_last.push(is_last);
-    if let Some(child_list) = children.get(&pid) {
+        ancestor_last.push(is_last);
         for (idx, child_pid) in child_list.iter().enumerate() {
             let child_last = idx + 1 == child_list.len();
-            push_tree_rows(*child_pid, child_last, ancestor_last, children, rows);
+            self.push_rows(*child_pid, child_last, ancestor_last, rows);
+        }
+        ancestor_last.pop();
+    }
+
+    fn is_collapsed(&self, pid: Pid) -> bool {
+        self.processes.get(&pid).is_some_and(|info| {
+            self.collapsed.contains(&ProcessKey {
+                pid,
+                start_time: info.start_time,
+            })
+        })
+    }
+
+    /// Sums the subtree, counting a multi-process app family's memory once
+    /// when its aggregated root is part of the subtree.
+    fn subtree_totals(&self, root: Pid) -> SubtreeTotals {
+        let mut members = Vec::new();
+        let mut stack = vec![root];
+        while let Some(pid) = stack.pop() {
+            if let Some(info) = self.processes.get(&pid) {
+                members.push(info);
+            }
+            stack.extend(self.children.get(&pid).into_iter().flatten().copied());
+        }
+        let aggregated: HashSet<&str> = members
+            .iter()
+            .filter(|info| info.family_memory)
+            .filter_map(|info| get_app_family(&info.name_lower))
+            .collect();
+
+        let mut totals = SubtreeTotals {
+            hidden: members.len().saturating_sub(1),
+            ..SubtreeTotals::default()
+        };
+        for info in members {
+            totals.cpu += info.cpu;
+            totals.read_rate += info.read_rate;
+            totals.write_rate += info.write_rate;
+            if let Some(net) = info.net {
+                totals.net.rx += net.rx;
+                totals.net.tx += net.tx;
+            }
+            let counted_by_root = !info.family_memory
+                && get_app_family(&info.name_lower).is_some_and(|family| aggregated.contains(family));
+            if !counted_by_root {
+                totals.memory_bytes = totals.memory_bytes.saturating_add(info.memory_bytes);
+            }
         }
+        totals
     }
-    ancestor_last.pop();
 }
 
 fn build_tree_prefix(ancestor_last: &[bool], is_last: bool) -> String {
@@ -267,6 +765,36 @@ fn is_skipped_parent(pid: Pid, processes: &HashMap<Pid, ProcInfo>) -> bool {
     )
 }
 
+/// Zombie processes and the number of distinct parents holding them.
+pub fn count_zombies(system: &System) -> (usize, usize) {
+    let mut zombies = 0;
+    let mut parents = HashSet::new();
+    for process in system.processes().values() {
+        if process.thread_kind().is_none() && process.status() == ProcessStatus::Zombie {
+            zombies += 1;
+            parents.extend(process.parent());
+        }
+    }
+    (zombies, parents.len())
+}
+
+/// Keeps only processes in uninterruptible sleep (D) or zombies (Z), plus
+/// their parents, which hold the zombies and may be waiting on the D ones.
+pub fn retain_stuck(processes: &mut HashMap<Pid, ProcInfo>, system: &System) {
+    let mut parents = HashSet::new();
+    for (pid, info) in processes.iter_mut() {
+        info.stuck_state = match system.process(*pid).map(|process| process.status()) {
+            Some(ProcessStatus::UninterruptibleDiskSleep) => Some('D'),
+            Some(ProcessStatus::Zombie) => Some('Z'),
+            _ => None,
+        };
+        if info.stuck_state.is_some() {
+            parents.extend(info.parent);
+        }
+    }
+    processes.retain(|pid, info| info.stuck_state.is_some() || parents.contains(pid));
+}
+
 /// Aggregates memory for multi-process applications (Chrome, Firefox, Electron apps).
 /// Groups all processes by app family and sums PSS for accurate memory reporting.
 fn apply_multiprocess_memory_aggregation(processes: &mut HashMap<Pid, ProcInfo>) {
@@ -312,6 +840,7 @@ fn apply_multiprocess_memory_aggregation(processes: &mut HashMap<Pid, ProcInfo>)
         // Set the aggregated memory on the root process
         if let Some(info) = processes.get_mut(&root_pid) {
             info.memory_bytes = total;
+            info.family_memory = true;
         }
     }
 }
@@ -395,7 +924,7 @@ fn find_family_root(pids: &[Pid], processes: &HashMap<Pid, ProcInfo>, family: &s
     let mut candidates: Vec<Pid> = pids
         .iter()
         .filter(|pid| {
-            processes.get(pid).map_or(false, |info| {
+            processes.get(pid).is_some_and(|info| {
                 let parent_in_family = info
                     .parent
                     .and_then(|parent| processes.get(&parent))
@@ -551,3 +1080,168 @@ fn read_cgroup_memory_if_unified(_pids: &[Pid], _app_name_lower: &str) -> Option
 fn get_cgroup_path(_pid: Pid) -> Option<String> {
     None
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_group_ids() {
+        let stat = "4242 (node (worker) 1) S 4100 4100 3900 34817 4100 4194304 1200 0 0 0 12 3 0 0 20 0 11 0";
+        assert_eq!(parse_group_ids(stat), Some((4100, 3900)));
+        assert_eq!(parse_group_ids("4242 (node"), None);
+    }
+
+    fn proc_info(pid: u32, parent: u32, zombie: bool) -> ProcInfo {
+        ProcInfo {
+            pid: Pid::from_u32(pid),
+            name: format!("p{pid}"),
+            name_lower: format!("p{pid}"),
+            cpu: 0.0,
+            memory_bytes: 0,
+            read_rate: 0,
+            write_rate: 0,
+            user: DASH.to_string(),
+            exe_path: DASH.to_string(),
+            cmdline: String::new(),
+            parent: Some(Pid::from_u32(parent)),
+            container: None,
+            is_thread: false,
+            start_time: 0,
+            family_memory: false,
+            sched: None,
+            net: None,
+            stuck_state: zombie.then_some('Z'),
+        }
+    }
+
+    #[test]
+    fn test_flat_rows_group_zombies_under_parent() {
+        let processes: HashMap<Pid, ProcInfo> = [
+            proc_info(100, 1, false),
+            proc_info(101, 100, false),
+            proc_info(102, 100, true),
+            // Zombie whose parent is itself hidden as a child
+            proc_info(103, 101, true),
+        ]
+        .into_iter()
+        .map(|info| (info.pid, info))
+        .collect();
+        let rows = build_tree_rows(&processes, SortBy::Name, SortOrder::Asc, false, &HashSet::new());
+        let pids: Vec<u32> = rows.iter().map(|row| row.pid.as_u32()).collect();
+        assert_eq!(pids, vec![100, 102, 103]);
+        assert_eq!(rows[1].prefix, "   └─ ");
+        assert_eq!(rows[2].prefix, "");
+    }
+
+    fn proc_map(infos: Vec<ProcInfo>) -> HashMap<Pid, ProcInfo> {
+        infos.into_iter().map(|info| (info.pid, info)).collect()
+    }
+
+    fn key(pid: u32, start_time: u64) -> ProcessKey {
+        ProcessKey {
+            pid: Pid::from_u32(pid),
+            start_time,
+        }
+    }
+
+    fn tree_rows(processes: &HashMap<Pid, ProcInfo>, collapsed: &[ProcessKey]) -> Vec<TreeRow> {
+        let collapsed: HashSet<ProcessKey> = collapsed.iter().copied().collect();
+        build_tree_rows(processes, SortBy::Pid, SortOrder::Asc, true, &collapsed)
+    }
+
+    fn summary(rows: &[TreeRow]) -> Vec<(u32, Fold)> {
+        rows.iter().map(|row| (row.pid.as_u32(), row.fold)).collect()
+    }
+
+    #[test]
+    fn test_tree_rows_fold_collapsed_subtrees() {
+        let processes = proc_map(vec![
+            proc_info(100, 0, false),
+            proc_info(101, 100, false),
+            proc_info(102, 100, false),
+            proc_info(103, 101, false),
+            proc_info(104, 103, false),
+        ]);
+
+        let rows = tree_rows(&processes, &[]);
+        assert_eq!(
+            summary(&rows),
+            vec![
+                (100, Fold::Expanded),
+                (101, Fold::Expanded),
+                (103, Fold::Expanded),
+                (104, Fold::Leaf),
+                (102, Fold::Leaf),
+            ]
+        );
+        assert_eq!(rows[2].prefix, "   │  └─ ");
+        assert!(rows.iter().all(|row| row.subtree.is_none()));
+
+        let rows = tree_rows(&processes, &[key(101, 0)]);
+        assert_eq!(
+            summary(&rows),
+            vec![(100, Fold::Expanded), (101, Fold::Collapsed), (102, Fold::Leaf)]
+        );
+        assert_eq!(rows[1].subtree.map(|totals| totals.hidden), Some(2));
+
+        // Folding a leaf has nothing to hide
+        let rows = tree_rows(&processes, &[key(102, 0)]);
+        assert_eq!(summary(&rows).last(), Some(&(102, Fold::Leaf)));
+    }
+
+    #[test]
+    fn test_tree_fold_ignores_reused_pid() {
+        let mut processes = proc_map(vec![
+            proc_info(100, 0, false),
+            proc_info(101, 100, false),
+            proc_info(102, 101, false),
+        ]);
+        processes.get_mut(&Pid::from_u32(101)).unwrap().start_time = 1_700_000_000;
+
+        // PID 101 was folded when it belonged to an earlier process
+        let rows = tree_rows(&processes, &[key(101, 1_600_000_000)]);
+        assert_eq!(rows[1].fold, Fold::Expanded);
+        assert_eq!(rows.len(), 3);
+
+        let rows = tree_rows(&processes, &[key(101, 1_700_000_000)]);
+        assert_eq!(rows[1].fold, Fold::Collapsed);
+        assert_eq!(rows.len(), 2);
+    }
+
+    #[test]
+    fn test_subtree_totals_count_family_memory_once() {
+        let mut infos = vec![
+            proc_info(10, 1, false),
+            proc_info(11, 10, false),
+            proc_info(12, 10, false),
+            proc_info(13, 11, false),
+        ];
+        for (info, (name, cpu, memory)) in infos.iter_mut().zip([
+            ("chrome", 1.0, 900),
+            ("chrome", 2.0, 300),
+            ("chrome", 3.0, 400),
+            ("ssh-agent", 0.5, 50),
+        ]) {
+            info.name = name.to_string();
+            info.name_lower = name.to_string();
+            info.cpu = cpu;
+            info.memory_bytes = memory;
+        }
+        // The family root already holds the memory of the whole family
+        infos[0].family_memory = true;
+        let processes = proc_map(infos);
+
+        let rows = tree_rows(&processes, &[key(10, 0)]);
+        let totals = rows[0].subtree.unwrap();
+        assert_eq!(totals.memory_bytes, 950);
+        assert_eq!(totals.cpu, 6.5);
+        assert_eq!(totals.hidden, 3);
+
+        // Below the family root, each member's own memory counts
+        let rows = tree_rows(&processes, &[key(11, 0)]);
+        let collapsed = rows.iter().find(|row| row.fold == Fold::Collapsed).unwrap();
+        assert_eq!(collapsed.pid.as_u32(), 11);
+        assert_eq!(collapsed.subtree.map(|totals| totals.memory_bytes), Some(350));
+    }
+}
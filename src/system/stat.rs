@@ -0,0 +1,74 @@
+//! Fields of `/proc/<pid>/stat` and `/proc/<pid>/task/<tid>/stat`.
+
+use std::str::FromStr;
+
+/// Field numbers as listed in proc(5).
+pub(crate) const STATE: usize = 3;
+pub(crate) const PGRP: usize = 5;
+pub(crate) const SESSION: usize = 6;
+pub(crate) const UTIME: usize = 14;
+pub(crate) const STIME: usize = 15;
+pub(crate) const NICE: usize = 19;
+pub(crate) const PROCESSOR: usize = 39;
+
+/// A parsed stat line.
+pub(crate) struct StatFields<'a> {
+    comm: &'a str,
+    /// Fields after the command name, starting at [`STATE`]
+    rest: Vec<&'a str>,
+}
+
+impl<'a> StatFields<'a> {
+    pub fn parse(stat: &'a str) -> Option<Self> {
+        // The command name may contain spaces and parentheses, so it runs from
+        // the first '(' to the last ')'
+        let open = stat.find('(')?;
+        let close = stat.rfind(')')?;
+        Some(StatFields {
+            comm: stat.get(open + 1..close)?,
+            rest: stat[close + 1..].split_whitespace().collect(),
+        })
+    }
+
+    pub fn comm(&self) -> &'a str {
+        self.comm
+    }
+
+    /// Field `number` as numbered in proc(5); the PID and command name are
+    /// not available here.
+    pub fn get(&self, number: usize) -> Option<&'a str> {
+        self.rest.get(number.checked_sub(STATE)?).copied()
+    }
+
+    pub fn parse_field<T: FromStr>(&self, number: usize) -> Option<T> {
+        self.get(number)?.parse().ok()
+    }
+
+    pub fn state(&self) -> Option<char> {
+        self.get(STATE)?.chars().next()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stat_fields() {
+        let stat = "4242 (node (worker) 1) S 4100 4100 3900 34817 4100 4194304 1200 0 0 0 \
+                    12 3 0 0 20 -5 11 0 1 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 17 6 0 0";
+        let fields = StatFields::parse(stat).unwrap();
+        assert_eq!(fields.comm(), "node (worker) 1");
+        assert_eq!(fields.state(), Some('S'));
+        assert_eq!(fields.parse_field::<u32>(PGRP), Some(4100));
+        assert_eq!(fields.parse_field::<u32>(SESSION), Some(3900));
+        assert_eq!(fields.parse_field::<u64>(UTIME), Some(12));
+        assert_eq!(fields.parse_field::<u64>(STIME), Some(3));
+        assert_eq!(fields.parse_field::<i32>(NICE), Some(-5));
+        assert_eq!(fields.parse_field::<u32>(PROCESSOR), Some(6));
+        assert_eq!(fields.get(1), None);
+        assert_eq!(fields.get(60), None);
+
+        assert!(StatFields::parse("4242 (node").is_none());
+    }
+}
@@ -0,0 +1,188 @@
+//! Threads of a process, read from `/proc/<pid>/task`.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::Instant;
+
+use sysinfo::Pid;
+
+use super::stat::{self, StatFields};
+
+/// One thread as of the latest sample.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ThreadInfo {
+    pub tid: u32,
+    /// `comm`, as set by `prctl(PR_SET_NAME)` or `pthread_setname_np`
+    pub name: String,
+    /// Scheduler state letter: R, S, D, Z, T, ...
+    pub state: char,
+    /// Share of one CPU since the previous sample, in percent
+    pub cpu: f32,
+    /// CPU the thread last ran on
+    pub last_cpu: Option<u32>,
+    pub voluntary_switches: u64,
+    pub involuntary_switches: u64,
+}
+
+/// Fields of `/proc/<pid>/task/<tid>/stat` used for [`ThreadInfo`].
+#[derive(Debug, PartialEq, Eq)]
+struct ThreadStat {
+    name: String,
+    state: char,
+    /// User plus system time in clock ticks
+    cpu_ticks: u64,
+    last_cpu: Option<u32>,
+}
+
+/// Keeps per-thread CPU time between samples to compute CPU%.
+pub struct ThreadSampler {
+    pid: Pid,
+    ticks: HashMap<u32, u64>,
+    last: Option<Instant>,
+}
+
+impl ThreadSampler {
+    pub fn new(pid: Pid) -> Self {
+        Self {
+            pid,
+            ticks: HashMap::new(),
+            last: None,
+        }
+    }
+
+    /// Every thread of the process, busiest first. CPU% is zero on the first sample.
+    pub fn sample(&mut self) -> io::Result<Vec<ThreadInfo>> {
+        let task_dir = Path::new("/proc").join(self.pid.as_u32().to_string()).join("task");
+        let elapsed = self.last.map(|last| last.elapsed().as_secs_f64());
+        self.last = Some(Instant::now());
+        let ticks_per_sec = clock_ticks_per_second();
+
+        let mut ticks = HashMap::new();
+        let mut threads = Vec::new();
+        for entry in fs::read_dir(task_dir)? {
+            let entry = entry?;
+            let Some(tid) = entry.file_name().to_str().and_then(|name| name.parse::<u32>().ok()) else {
+                continue;
+            };
+            // Threads may exit between listing and reading them
+            let Some(stat) = fs::read_to_string(entry.path().join("stat"))
+                .ok()
+                .and_then(|stat| parse_thread_stat(&stat))
+            else {
+                continue;
+            };
+            let status = fs::read_to_string(entry.path().join("status")).unwrap_or_default();
+            let (voluntary_switches, involuntary_switches) = parse_context_switches(&status);
+
+            let cpu = match (elapsed, self.ticks.get(&tid)) {
+                (Some(secs), Some(prev)) if secs > 0.0 => {
+                    let used = stat.cpu_ticks.saturating_sub(*prev) as f64 / ticks_per_sec;
+                    (used / secs * 100.0) as f32
+                }
+                _ => 0.0,
+            };
+            ticks.insert(tid, stat.cpu_ticks);
+            threads.push(ThreadInfo {
+                tid,
+                name: stat.name,
+                state: stat.state,
+                cpu,
+                last_cpu: stat.last_cpu,
+                voluntary_switches,
+                involuntary_switches,
+            });
+        }
+        self.ticks = ticks;
+        threads.sort_by(|a, b| b.cpu.total_cmp(&a.cpu).then(a.tid.cmp(&b.tid)));
+        Ok(threads)
+    }
+}
+
+pub fn state_label(state: char) -> &'static str {
+    match state {
+        'R' => "running",
+        'S' => "sleeping",
+        'D' => "disk sleep",
+        'Z' => "zombie",
+        'T' => "stopped",
+        't' => "tracing stop",
+        'X' | 'x' => "dead",
+        'I' => "idle",
+        'P' => "parked",
+        'W' => "waking",
+        _ => "unknown",
+    }
+}
+
+fn parse_thread_stat(stat: &str) -> Option<ThreadStat> {
+    let fields = StatFields::parse(stat)?;
+    let utime: u64 = fields.parse_field(stat::UTIME)?;
+    let stime: u64 = fields.parse_field(stat::STIME)?;
+    Some(ThreadStat {
+        name: fields.comm().to_string(),
+        state: fields.state()?,
+        cpu_ticks: utime + stime,
+        last_cpu: fields.parse_field(stat::PROCESSOR),
+    })
+}
+
+fn parse_context_switches(status: &str) -> (u64, u64) {
+    let field = |name: &str| {
+        status
+            .lines()
+            .find_map(|line| line.strip_prefix(name))
+            .and_then(|value| value.trim().parse().ok())
+            .unwrap_or(0)
+    };
+    (
+        field("voluntary_ctxt_switches:"),
+        field("nonvoluntary_ctxt_switches:"),
+    )
+}
+
+#[cfg(target_os = "linux")]
+fn clock_ticks_per_second() -> f64 {
+    // SAFETY: sysconf only reads a configuration value
+    let ticks = unsafe { libc::sysconf(libc::_SC_CLK_TCK) };
+    if ticks > 0 {
+        ticks as f64
+    } else {
+        100.0
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn clock_ticks_per_second() -> f64 {
+    100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_thread_stat() {
+        let stat = "4711 (C2 Compiler (1)) R 4700 4700 4700 0 -1 4194368 90 0 0 0 \
+                    350 25 0 0 20 0 40 0 1234 0 0 18446744073709551615 0 0 0 0 0 0 0 \
+                    0 0 0 0 0 17 5 0 0 0 0 0";
+        assert_eq!(
+            parse_thread_stat(stat),
+            Some(ThreadStat {
+                name: "C2 Compiler (1)".to_string(),
+                state: 'R',
+                cpu_ticks: 375,
+                last_cpu: Some(5),
+            })
+        );
+        assert_eq!(parse_thread_stat("4711 (java"), None);
+    }
+
+    #[test]
+    fn test_parse_context_switches() {
+        let status = "Name:\tnode\nState:\tS (sleeping)\nvoluntary_ctxt_switches:\t1520\nnonvoluntary_ctxt_switches:\t37\n";
+        assert_eq!(parse_context_switches(status), (1520, 37));
+        assert_eq!(parse_context_switches(""), (0, 0));
+    }
+}
@@ -0,0 +1,199 @@
+//! What a process is waiting on: its scheduler state, wait channel, current
+//! system call and kernel stack. Mostly useful for processes stuck in D state.
+
+use std::fs;
+use std::io;
+
+use sysinfo::Pid;
+
+use super::stat::StatFields;
+use super::threads;
+
+/// Kernel-side view of a process, read from `/proc/<pid>`.
+pub struct WaitInfo {
+    pub state: Option<char>,
+    /// Kernel function the process sleeps in; None while it runs
+    pub wchan: Option<String>,
+    pub syscall: Option<String>,
+    /// Kernel stack frames, or why they could not be read
+    pub stack: Result<Vec<String>, String>,
+}
+
+pub fn read_wait_info(pid: Pid) -> WaitInfo {
+    let read = |name: &str| fs::read_to_string(format!("/proc/{}/{name}", pid.as_u32()));
+    let state = read("stat").ok().and_then(|stat| parse_state(&stat));
+    let wchan = read("wchan")
+        .ok()
+        .map(|wchan| wchan.trim().to_string())
+        .filter(|wchan| !wchan.is_empty() && wchan != "0");
+    let syscall = read("syscall").ok().map(|syscall| describe_syscall(&syscall));
+    let stack = match read("stack") {
+        Ok(stack) => Ok(parse_stack(&stack)),
+        Err(err) if err.kind() == io::ErrorKind::PermissionDenied => {
+            Err("not permitted (needs root)".to_string())
+        }
+        Err(err) => Err(err.to_string()),
+    };
+    WaitInfo {
+        state,
+        wchan,
+        syscall,
+        stack,
+    }
+}
+
+/// e.g. "D (disk sleep)"
+pub fn state_description(state: char) -> String {
+    format!("{state} ({})", threads::state_label(state))
+}
+
+fn parse_state(stat: &str) -> Option<char> {
+    StatFields::parse(stat)?.state()
+}
+
+/// Turns `/proc/<pid>/syscall` into e.g. "futex (202)" or "running".
+fn describe_syscall(syscall: &str) -> String {
+    let mut fields = syscall.split_whitespace();
+    match fields.next() {
+        Some("running") => "running".to_string(),
+        // Blocked in the kernel, but not inside a system call (e.g. a page fault)
+        Some("-1") => "none (blocked outside a system call)".to_string(),
+        Some(number) => match number.parse::<u32>().ok().and_then(syscall_name) {
+            Some(name) => format!("{name} ({number})"),
+            None => format!("#{number}"),
+        },
+        None => "-".to_string(),
+    }
+}
+
+/// Names of the system calls processes usually block in.
+#[cfg(target_arch = "x86_64")]
+fn syscall_name(number: u32) -> Option<&'static str> {
+    Some(match number {
+        0 => "read",
+        1 => "write",
+        2 => "open",
+        3 => "close",
+        7 => "poll",
+        17 => "pread64",
+        18 => "pwrite64",
+        19 => "readv",
+        20 => "writev",
+        23 => "select",
+        34 => "pause",
+        35 => "nanosleep",
+        42 => "connect",
+        43 => "accept",
+        44 => "sendto",
+        45 => "recvfrom",
+        46 => "sendmsg",
+        47 => "recvmsg",
+        61 => "wait4",
+        72 => "fcntl",
+        73 => "flock",
+        74 => "fsync",
+        75 => "fdatasync",
+        76 => "truncate",
+        82 => "rename",
+        83 => "mkdir",
+        87 => "unlink",
+        162 => "sync",
+        165 => "mount",
+        166 => "umount2",
+        202 => "futex",
+        208 => "io_getevents",
+        230 => "clock_nanosleep",
+        232 => "epoll_wait",
+        247 => "waitid",
+        257 => "openat",
+        270 => "pselect6",
+        271 => "ppoll",
+        281 => "epoll_pwait",
+        288 => "accept4",
+        299 => "recvmmsg",
+        426 => "io_uring_enter",
+        _ => return None,
+    })
+}
+
+#[cfg(target_arch = "aarch64")]
+fn syscall_name(number: u32) -> Option<&'static str> {
+    Some(match number {
+        22 => "epoll_pwait",
+        25 => "fcntl",
+        32 => "flock",
+        39 => "umount2",
+        40 => "mount",
+        56 => "openat",
+        57 => "close",
+        63 => "read",
+        64 => "write",
+        65 => "readv",
+        66 => "writev",
+        67 => "pread64",
+        68 => "pwrite64",
+        72 => "pselect6",
+        73 => "ppoll",
+        81 => "sync",
+        82 => "fsync",
+        83 => "fdatasync",
+        95 => "waitid",
+        98 => "futex",
+        101 => "nanosleep",
+        115 => "clock_nanosleep",
+        202 => "accept",
+        203 => "connect",
+        206 => "sendto",
+        207 => "recvfrom",
+        211 => "sendmsg",
+        212 => "recvmsg",
+        242 => "accept4",
+        260 => "wait4",
+        426 => "io_uring_enter",
+        _ => return None,
+    })
+}
+
+#[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+fn syscall_name(_number: u32) -> Option<&'static str> {
+    None
+}
+
+/// Strips the "[<0>] " address prefix from each frame of `/proc/<pid>/stack`.
+fn parse_stack(stack: &str) -> Vec<String> {
+    stack
+        .lines()
+        .map(|line| match line.split_once("] ") {
+            Some((address, frame)) if address.starts_with("[<") => frame,
+            _ => line,
+        })
+        .map(str::trim)
+        .filter(|frame| !frame.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_state_and_stack() {
+        assert_eq!(parse_state("915 (nfs (io)) D 1 915 915 0 -1"), Some('D'));
+        assert_eq!(state_description('Z'), "Z (zombie)");
+        let stack = "[<0>] rpc_wait_bit_killable+0x1e/0xa0\n[<0>] __rpc_execute+0x11c/0x3b0\n";
+        assert_eq!(
+            parse_stack(stack),
+            vec!["rpc_wait_bit_killable+0x1e/0xa0", "__rpc_execute+0x11c/0x3b0"]
+        );
+    }
+
+    #[test]
+    fn test_describe_syscall() {
+        assert_eq!(describe_syscall("running\n"), "running");
+        assert_eq!(describe_syscall("-1 0x7ffc8d1e6b28 0x7f3a2d0e1a3d\n"), "none (blocked outside a system call)");
+        assert!(describe_syscall("99999 0x0 0x0").starts_with('#'));
+        #[cfg(target_arch = "x86_64")]
+        assert_eq!(describe_syscall("202 0x7f1c 0x80 0x2 0x0 0x0 0x0 0x7ffd 0x7f1c"), "futex (202)");
+    }
+}
@@ -0,0 +1,122 @@
+use std::io;
+
+use crossterm::cursor::MoveTo;
+use crossterm::queue;
+use crossterm::style::{Print, ResetColor, SetForegroundColor};
+
+use crate::app::{AppState, ColumnChooser};
+
+use super::table::{fit_left, truncate_str};
+use super::theme::{theme, Style};
+
+const HELP: [&str; 2] = [
+    " Space show/hide | J/K move | +/- width",
+    " 0 auto | r reset | Enter save | Esc cancel",
+];
+
+pub(crate) fn render_column_chooser(
+    stdout: &mut io::Stdout,
+    state: &AppState,
+    chooser: &ColumnChooser,
+    main_x: u16,
+    main_width: usize,
+    height: usize,
+) -> io::Result<()> {
+    let (x, y, width, box_height) = chooser.frame(main_x, main_width, height);
+    let inner = width.saturating_sub(2);
+
+    let title = format!(" Columns: {} ", chooser.view.label());
+    let top = format!(
+        "┌{}{}┐",
+        truncate_str(&title, inner),
+        "─".repeat(inner.saturating_sub(title.chars().count()))
+    );
+    queue!(
+        stdout,
+        MoveTo(x, y),
+        theme().panel,
+        Print(truncate_str(&top, width)),
+        ResetColor
+    )?;
+
+    // Keep the selected column in view when the terminal is short
+    let rows = box_height.saturating_sub(2 + HELP.len());
+    let offset = (chooser.selected + 1).saturating_sub(rows);
+    for row in 0..rows {
+        let row_y = y + 1 + row as u16;
+        queue!(
+            stdout,
+            MoveTo(x, row_y),
+            theme().panel,
+            Print("│")
+        )?;
+
+        let index = offset + row;
+        let entry = chooser
+            .settings
+            .get(index)
+            .and_then(|setting| Some((setting, chooser.view.def(setting.id)?)));
+        match entry {
+            Some((setting, def)) => {
+                let check = if setting.visible { "[x]" } else { "[ ]" };
+                let width_label = match setting.width {
+                    Some(width) => format!("pinned {width}"),
+                    None => "auto".to_string(),
+                };
+                let note = if state.column_available(def.requires) {
+                    width_label
+                } else {
+                    format!("{width_label}, {}", def.requires.hint())
+                };
+                let text = format!(" {check} {:<10} {note}", def.title);
+                let style = if index == chooser.selected {
+                    theme().selected
+                } else if setting.visible {
+                    theme().panel
+                } else {
+                    Style {
+                        fg: theme().dim,
+                        ..theme().panel
+                    }
+                };
+                queue!(
+                    stdout,
+                    style,
+                    Print(fit_left(&text, inner)),
+                    theme().panel
+                )?;
+            }
+            None => queue!(stdout, Print(" ".repeat(inner)))?,
+        }
+
+        queue!(
+            stdout,
+            theme().panel,
+            Print("│"),
+            ResetColor
+        )?;
+    }
+
+    let help_y = y + box_height as u16 - 1 - HELP.len() as u16;
+    for (i, line) in HELP.iter().enumerate() {
+        queue!(
+            stdout,
+            MoveTo(x, help_y + i as u16),
+            theme().panel,
+            Print("│"),
+            SetForegroundColor(theme().dim),
+            Print(fit_left(line, inner)),
+            theme().panel,
+            Print("│")
+        )?;
+    }
+
+    queue!(
+        stdout,
+        MoveTo(x, y + box_height as u16 - 1),
+        theme().panel,
+        Print(format!("└{}┘", "─".repeat(inner))),
+        ResetColor
+    )?;
+    Ok(())
+}
@@ -0,0 +1,261 @@
+use std::collections::HashMap;
+use std::io::{self, Write};
+
+use crossterm::cursor::MoveTo;
+use crossterm::queue;
+use crossterm::style::{Attribute, Print, ResetColor, SetAttribute};
+use crossterm::terminal;
+use sysinfo::Pid;
+
+use crate::app::AppState;
+use crate::system::process::{ProcInfo, TreeRow};
+
+use super::layout::{layout_for_screen, render_sidebar, render_sidebar_gap};
+use super::table::{
+    clear_list_area_at, fit_left, fit_right, format_bottom_border, format_separator,
+    format_top_border, render_help_table_rows_colored_at, render_line_at, render_title_at,
+    truncate_str, HelpSegment,
+};
+use super::theme::theme;
+
+pub fn render_container_top(
+    stdout: &mut io::Stdout,
+    state: &AppState,
+    processes: &HashMap<Pid, ProcInfo>,
+    rows: &[TreeRow],
+) -> io::Result<()> {
+    let (width, height) = terminal::size().unwrap_or((80, 24));
+    let screen_width = width as usize;
+    let height_usize = height as usize;
+    let layout = layout_for_screen(screen_width);
+    if layout.show_sidebar {
+        render_sidebar(stdout, state, &layout, height_usize)?;
+        render_sidebar_gap(stdout, &layout, height_usize)?;
+    }
+    let width_usize = layout.main_width;
+    let main_x = layout.main_x;
+
+    queue!(stdout, MoveTo(main_x, 0))?;
+
+    let mut row = 0u16;
+    render_line_at(
+        stdout,
+        main_x,
+        row,
+        &format!("┌{}┐", "─".repeat(width_usize.saturating_sub(2))),
+        width_usize,
+    )?;
+    row += 1;
+    render_title_at(stdout, main_x, row, width_usize, "CONTAINER PROCESSES")?;
+    row += 2;
+
+    let id = &state.top_container_id;
+    let short_id = if id.len() > 12 { &id[..12] } else { id.as_str() };
+    let context = state.docker_context.as_deref().unwrap_or("default");
+    let info_widths = info_column_widths(width_usize);
+    render_line_at(stdout, main_x, row, &format_top_border(&info_widths), width_usize)?;
+    row += 1;
+    let info_row1 = format_info_row(
+        &info_widths,
+        &format!("Container: {}", state.top_container_name),
+        &format!("ID: {short_id}"),
+    );
+    render_line_at(stdout, main_x, row, &info_row1, width_usize)?;
+    row += 1;
+    render_line_at(stdout, main_x, row, &format_separator(&info_widths), width_usize)?;
+    row += 1;
+    let info_row2 = format_info_row(
+        &info_widths,
+        &format!("Processes: {}", rows.len()),
+        &format!("Context: {context}"),
+    );
+    render_line_at(stdout, main_x, row, &info_row2, width_usize)?;
+    row += 1;
+    render_line_at(stdout, main_x, row, &format_bottom_border(&info_widths), width_usize)?;
+    row += 1;
+
+    let mut max_user_len = 4usize;
+    for row in rows {
+        if let Some(proc_info) = processes.get(&row.pid) {
+            max_user_len = max_user_len.max(proc_info.user.chars().count());
+        }
+    }
+    let widths = top_column_widths(width_usize, max_user_len);
+    render_line_at(stdout, main_x, row, &format_top_border(&widths), width_usize)?;
+    row += 1;
+    render_line_at(stdout, main_x, row, &format_top_header(&widths), width_usize)?;
+    row += 1;
+    render_line_at(stdout, main_x, row, &format_separator(&widths), width_usize)?;
+    row += 1;
+
+    let list_start = row as usize;
+    let footer_lines = 5usize;
+    let max_rows = height_usize.saturating_sub(list_start + footer_lines);
+
+    if max_rows > 0 {
+        if rows.is_empty() {
+            render_line_at(
+                stdout,
+                main_x,
+                list_start as u16,
+                "No host processes found for this container.",
+                width_usize,
+            )?;
+            clear_list_area_at(
+                stdout,
+                main_x,
+                list_start + 1,
+                max_rows.saturating_sub(1),
+                width_usize,
+            )?;
+        } else {
+            // Keep selection centered when possible
+            let total = rows.len();
+            let half = max_rows / 2;
+            let scroll = if state.selected <= half {
+                0
+            } else if state.selected + half >= total {
+                total.saturating_sub(max_rows)
+            } else {
+                state.selected - half
+            };
+            let end = (scroll + max_rows).min(rows.len());
+            let mut rendered = 0usize;
+            for (idx, row) in rows[scroll..end].iter().enumerate() {
+                let line_index = scroll + idx;
+                let y = list_start + idx;
+                let Some(proc_info) = processes.get(&row.pid) else {
+                    continue;
+                };
+                let line = format_top_line(proc_info, &widths, &row.prefix);
+                let is_selected = line_index == state.selected;
+                let is_hovered = state.hover_row == Some(line_index) && !is_selected;
+                if is_selected {
+                    queue!(
+                        stdout,
+                        MoveTo(main_x, y as u16),
+                        SetAttribute(Attribute::Reverse),
+                        Print(fit_left(&line, width_usize)),
+                        SetAttribute(Attribute::Reset)
+                    )?;
+                } else if is_hovered {
+                    queue!(
+                        stdout,
+                        MoveTo(main_x, y as u16),
+                        theme().hover,
+                        Print(fit_left(&line, width_usize)),
+                        ResetColor
+                    )?;
+                } else {
+                    render_line_at(stdout, main_x, y as u16, &line, width_usize)?;
+                }
+                rendered += 1;
+            }
+            clear_list_area_at(
+                stdout,
+                main_x,
+                list_start + rendered,
+                max_rows.saturating_sub(rendered),
+                width_usize,
+            )?;
+        }
+    }
+
+    if height_usize >= footer_lines {
+        let message_line = height_usize.saturating_sub(footer_lines) as u16;
+        let message = state.message.as_deref().unwrap_or("Esc to return");
+        render_line_at(stdout, main_x, message_line, message, width_usize)?;
+
+        let help_rows = vec![vec![
+            HelpSegment::plain("Actions: "),
+            HelpSegment::key("Esc"),
+            HelpSegment::plain(" back | "),
+            HelpSegment::key("Enter"),
+            HelpSegment::plain(" process view | "),
+            HelpSegment::key("i"),
+            HelpSegment::plain(" detail | "),
+            HelpSegment::key("s"),
+            HelpSegment::plain(" signal | "),
+            HelpSegment::key("e"),
+            HelpSegment::plain(" env | "),
+            HelpSegment::key("k"),
+            HelpSegment::plain(" kill"),
+        ]];
+        let help_start = height_usize.saturating_sub(help_rows.len() + 2) as u16;
+        render_help_table_rows_colored_at(stdout, main_x, help_start, width_usize, &help_rows)?;
+    }
+
+    if let Some(ref menu) = state.context_menu {
+        super::menu::render_context_menu(stdout, menu)?;
+    }
+
+    stdout.flush()?;
+    Ok(())
+}
+
+fn info_column_widths(width: usize) -> Vec<usize> {
+    let content_width = width.saturating_sub(3);
+    let left = content_width / 2;
+    vec![left, content_width - left]
+}
+
+fn format_info_row(widths: &[usize], left: &str, right: &str) -> String {
+    format!("│{}│{}│", fit_left(left, widths[0]), fit_left(right, widths[1]))
+}
+
+fn top_column_widths(width: usize, max_user_len: usize) -> Vec<usize> {
+    let pid_width = 7usize;
+    let ppid_width = 7usize;
+    let cpu_width = 6usize;
+    let mem_width = 9usize;
+    let separators = 7usize;
+    let content_width = width.saturating_sub(separators);
+    let fixed = pid_width + ppid_width + cpu_width + mem_width;
+    let available = content_width.saturating_sub(fixed);
+    let min_command = 10usize;
+    let user_width = max_user_len
+        .max(4)
+        .min(available.saturating_sub(min_command).max(4))
+        .min(available);
+    let command_width = available.saturating_sub(user_width);
+    vec![pid_width, ppid_width, user_width, cpu_width, mem_width, command_width]
+}
+
+fn format_top_header(widths: &[usize]) -> String {
+    format!(
+        "│{}│{}│{}│{}│{}│{}│",
+        fit_right("PID", widths[0]),
+        fit_right("PPID", widths[1]),
+        fit_left("USER", widths[2]),
+        fit_right("CPU%", widths[3]),
+        fit_right("MEM(MB)", widths[4]),
+        fit_left("COMMAND", widths[5]),
+    )
+}
+
+fn format_top_line(proc_info: &ProcInfo, widths: &[usize], prefix: &str) -> String {
+    let ppid = proc_info
+        .parent
+        .map(|pid| pid.to_string())
+        .unwrap_or_else(|| "-".to_string());
+    let mem_mb = proc_info.memory_bytes as f64 / 1024.0 / 1024.0;
+    let command = if proc_info.cmdline.is_empty() {
+        proc_info.name.as_str()
+    } else {
+        proc_info.cmdline.as_str()
+    };
+
+    let command_width = widths[5];
+    let command_space = command_width.saturating_sub(prefix.chars().count());
+    let command_text = format!("{prefix}{}", truncate_str(command, command_space));
+
+    format!(
+        "│{}│{}│{}│{}│{}│{}│",
+        fit_right(&proc_info.pid.to_string(), widths[0]),
+        fit_right(&ppid, widths[1]),
+        fit_left(&proc_info.user, widths[2]),
+        fit_right(&format!("{:.1}", proc_info.cpu), widths[3]),
+        fit_right(&format!("{:.1}", mem_mb), widths[4]),
+        fit_left(&command_text, command_width),
+    )
+}
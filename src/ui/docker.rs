@@ -2,21 +2,23 @@ use std::io::{self, Write};
 
 use crossterm::cursor::MoveTo;
 use crossterm::queue;
-use crossterm::style::{
-    Attribute, Color, Print, ResetColor, SetAttribute, SetBackgroundColor, SetForegroundColor,
-};
+use crossterm::style::{Attribute, Color, Print, ResetColor, SetAttribute, SetForegroundColor};
 use crossterm::terminal;
 
-use crate::app::{AppState, ContextMenu, InputMode, SortBy, SortOrder};
+use crate::app::{AppState, Column, ColumnView, ContextMenu, DockerContextPicker, InputMode};
 use crate::system::docker::{ContainerInfo, DockerRow, HealthStatus};
+use crate::system::net::NetRates;
 
 use super::bars::{format_cpu_bar, format_memory_bar, format_swap_bar};
 use super::layout::{layout_for_screen, render_sidebar, render_sidebar_gap};
+use super::process::format_rate;
 use super::table::{
-    clear_list_area_at, fit_left, fit_right, format_separator, format_top_border, is_dim_mode,
+    clear_list_area_at, column_widths, fit_column, fit_left, format_header, format_row,
+    format_separator, format_top_border, is_dim_mode, print_table_bar,
     render_help_table_rows_colored_at, render_line_at, render_search_box_at, render_title_at,
-    set_dim_mode, truncate_str, HelpSegment, print_table_bar,
+    set_dim_mode, truncate_str, HelpSegment,
 };
+use super::theme::theme;
 
 pub fn render_containers(
     stdout: &mut io::Stdout,
@@ -39,15 +41,6 @@ pub fn render_containers(
 
     queue!(stdout, MoveTo(main_x, 0))?;
 
-    let sort_label = match state.sort_by {
-        SortBy::Cpu => "CPU",
-        SortBy::Memory => "MEM",
-        SortBy::Name => "NAME",
-    };
-    let order_label = match state.sort_order {
-        SortOrder::Asc => "asc",
-        SortOrder::Desc => "desc",
-    };
     let mode_label = match state.input_mode {
         InputMode::Normal => "NORMAL",
         InputMode::Filter => "FILTER",
@@ -65,9 +58,12 @@ pub fn render_containers(
     render_title_at(stdout, main_x, row, width_usize, "DOCKER VIEW")?;
     row += 2;
 
+    let context_label = state.docker_context.as_deref().unwrap_or("default");
     let header = format!(
-        "Spark | View: DOCKER | Sort: {} {} | Mode: {}",
-        sort_label, order_label, mode_label
+        "Spark | View: DOCKER | Context: {} | Sort: {} | Mode: {}",
+        context_label,
+        state.docker_sort.label(),
+        mode_label
     );
     render_line_at(stdout, main_x, row, &header, width_usize)?;
     row += 1;
@@ -97,12 +93,12 @@ pub fn render_containers(
         width_usize,
     )?;
     row += 1;
-    let docker_widths = docker_column_widths(width_usize);
+    let columns = state.resolved_columns(ColumnView::Docker, width_usize);
+    let docker_widths = column_widths(&columns);
     let docker_top = format_top_border(&docker_widths);
     render_line_at(stdout, main_x, row, &docker_top, width_usize)?;
     row += 1;
-    let docker_header = format_docker_header(&docker_widths);
-    render_line_at(stdout, main_x, row, &docker_header, width_usize)?;
+    render_line_at(stdout, main_x, row, &format_header(&columns, state.sorted_column(ColumnView::Docker)), width_usize)?;
     row += 1;
     let docker_sep = format_separator(&docker_widths);
     render_line_at(stdout, main_x, row, &docker_sep, width_usize)?;
@@ -153,7 +149,7 @@ pub fn render_containers(
                             stdout,
                             main_x,
                             y as u16,
-                            &docker_widths,
+                            &columns,
                             name,
                             path.as_deref(),
                             *count,
@@ -177,6 +173,7 @@ pub fn render_containers(
                             main_x,
                             y as u16,
                             container,
+                            &columns,
                             width_usize,
                             prefix,
                             spinner,
@@ -276,9 +273,21 @@ pub fn render_containers(
                     HelpSegment::plain(" logs | "),
                     HelpSegment::key("e"),
                     HelpSegment::plain(" env | "),
+                    HelpSegment::key("t"),
+                    HelpSegment::plain(" procs | "),
+                    HelpSegment::key("u"),
+                    HelpSegment::plain(" limits | "),
+                    HelpSegment::key("N"),
+                    HelpSegment::plain(" network | "),
                     HelpSegment::key("k"),
                     HelpSegment::plain(kill_label),
                     HelpSegment::plain(" | "),
+                    HelpSegment::key("C"),
+                    HelpSegment::plain(" context | "),
+                    HelpSegment::key("</>"),
+                    HelpSegment::plain(" sort | "),
+                    HelpSegment::key("O"),
+                    HelpSegment::plain(" columns | "),
                     HelpSegment::key("q"),
                     HelpSegment::plain(" quit | "),
                     HelpSegment::key("arrows"),
@@ -304,22 +313,42 @@ pub fn render_containers(
         render_context_menu(stdout, menu)?;
     }
 
+    if let Some(ref picker) = state.docker_context_picker {
+        render_context_picker(
+            stdout,
+            picker,
+            state.docker_context.as_deref(),
+            main_x,
+            width_usize,
+            height_usize,
+        )?;
+    }
+
+    if let Some(ref form) = state.form {
+        super::form::render_form(stdout, form, main_x, width_usize, height_usize)?;
+    }
+
+    if let Some(ref chooser) = state.column_chooser {
+        super::columns::render_column_chooser(stdout, state, chooser, main_x, width_usize, height_usize)?;
+    }
+
     stdout.flush()?;
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 fn render_container_row_at(
     stdout: &mut io::Stdout,
     x: u16,
     y: u16,
     container: &ContainerInfo,
+    columns: &[Column],
     width: usize,
     prefix: &str,
     spinner: Option<char>,
     selected: bool,
     hovered: bool,
 ) -> io::Result<()> {
-    let widths = docker_column_widths(width);
     let id = if container.id.len() > 12 {
         &container.id[..12]
     } else {
@@ -327,10 +356,6 @@ fn render_container_row_at(
     };
     let mem_gb = container.memory_bytes as f64 / 1024.0 / 1024.0 / 1024.0;
 
-    let id_cell = fit_right(id, widths[0]);
-    let cpu_cell = fit_right(&format!("{:.1}", container.cpu), widths[1]);
-    let mem_cell = fit_right(&format!("{:.2}", mem_gb), widths[2]);
-
     // Build name without health indicator (we'll add it with color)
     let name_base = if let Some(spin_char) = spinner {
         format!("{}{} {}", prefix, spin_char, container.name)
@@ -339,31 +364,34 @@ fn render_container_row_at(
     };
 
     let (health_char, health_color) = match container.health {
-        HealthStatus::Healthy => (Some("*"), Color::Green),
-        HealthStatus::Unhealthy => (Some("!"), Color::Red),
-        HealthStatus::Starting => (Some("~"), Color::Yellow),
+        HealthStatus::Healthy => (Some("*"), theme().ok),
+        HealthStatus::Unhealthy => (Some("!"), theme().error),
+        HealthStatus::Starting => (Some("~"), theme().warn),
         HealthStatus::None => (None, Color::Reset),
     };
 
     // Calculate name cell with space for health indicator
-    let name_with_health = if health_char.is_some() {
-        format!("{} {}", name_base, health_char.unwrap_or(""))
-    } else {
-        name_base.clone()
+    let name_with_health = match health_char {
+        Some(health_char) => format!("{} {}", name_base, health_char),
+        None => name_base.clone(),
     };
-    let name_cell = fit_left(&name_with_health, widths[3]);
 
-    let image_cell = fit_left(&container.image, widths[4]);
-    let port_cell = fit_left(&container.port_public, widths[5]);
-    let int_port_cell = fit_left(&container.port_internal, widths[6]);
-    let status_cell = fit_left(&container.status, widths[7]);
+    let cell = |column: &Column| match column.id {
+        "id" => id.to_string(),
+        "cpu" => format!("{:.1}", container.cpu),
+        "mem" => format!("{:.2}", mem_gb),
+        "name" => name_with_health.clone(),
+        "image" => container.image.to_string(),
+        "port" => container.port_public.to_string(),
+        "int_port" => container.port_internal.to_string(),
+        "status" => container.status.to_string(),
+        "rx" | "tx" => format_net_cell(column.id, container.net),
+        _ => String::new(),
+    };
+    let line = format_row(columns, cell);
 
     // For selected/hovered/stopped, render without special health coloring
     if selected {
-        let line = format!(
-            "│{}│{}│{}│{}│{}│{}│{}│{}│",
-            id_cell, cpu_cell, mem_cell, name_cell, image_cell, port_cell, int_port_cell, status_cell
-        );
         queue!(
             stdout,
             MoveTo(x, y),
@@ -372,73 +400,54 @@ fn render_container_row_at(
             SetAttribute(Attribute::Reset)
         )?;
     } else if hovered {
-        let line = format!(
-            "│{}│{}│{}│{}│{}│{}│{}│{}│",
-            id_cell, cpu_cell, mem_cell, name_cell, image_cell, port_cell, int_port_cell, status_cell
-        );
         queue!(
             stdout,
             MoveTo(x, y),
-            SetBackgroundColor(Color::DarkGrey),
+            theme().hover,
             Print(fit_left(&line, width)),
             ResetColor
         )?;
     } else if !container.running {
-        let line = format!(
-            "│{}│{}│{}│{}│{}│{}│{}│{}│",
-            id_cell, cpu_cell, mem_cell, name_cell, image_cell, port_cell, int_port_cell, status_cell
-        );
         queue!(
             stdout,
             MoveTo(x, y),
-            SetForegroundColor(Color::DarkGrey),
+            SetForegroundColor(theme().dim),
             Print(fit_left(&line, width)),
             ResetColor
         )?;
-    } else if health_char.is_some() {
+    } else if let Some(health_char) = health_char {
         // Render with colored health indicator
-        let name_cell_no_health = fit_left(&format!("{} ", name_base), widths[3] - 1);
-        queue!(
-            stdout,
-            MoveTo(x, y),
-            Print("│"),
-            Print(&id_cell),
-            Print("│"),
-            Print(&cpu_cell),
-            Print("│"),
-            Print(&mem_cell),
-            Print("│"),
-            Print(&name_cell_no_health),
-            SetForegroundColor(health_color),
-            Print(health_char.unwrap_or("")),
-            ResetColor,
-            Print("│"),
-            Print(&image_cell),
-            Print("│"),
-            Print(&port_cell),
-            Print("│"),
-            Print(&int_port_cell),
-            Print("│"),
-            Print(&status_cell),
-            Print("│")
-        )?;
+        queue!(stdout, MoveTo(x, y))?;
+        for column in columns {
+            queue!(stdout, Print("│"))?;
+            if column.id == "name" && column.width > 0 {
+                let name_cell_no_health = fit_left(&format!("{} ", name_base), column.width - 1);
+                queue!(
+                    stdout,
+                    Print(&name_cell_no_health),
+                    SetForegroundColor(health_color),
+                    Print(health_char),
+                    ResetColor
+                )?;
+            } else {
+                queue!(stdout, Print(fit_column(&cell(column), column)))?;
+            }
+        }
+        queue!(stdout, Print("│"))?;
     } else {
         // Normal rendering without health indicator
-        let line = format!(
-            "│{}│{}│{}│{}│{}│{}│{}│{}│",
-            id_cell, cpu_cell, mem_cell, name_cell, image_cell, port_cell, int_port_cell, status_cell
-        );
         render_line_at(stdout, x, y, &line, width)?;
     }
 
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 fn render_group_row_at(
     stdout: &mut io::Stdout,
     x: u16,
     y: u16,
-    widths: &[usize],
+    columns: &[Column],
     name: &str,
     path: Option<&str>,
     count: usize,
@@ -446,88 +455,65 @@ fn render_group_row_at(
     selected: bool,
     hovered: bool,
 ) -> io::Result<()> {
-    // Status dot: ● green if all running, ○ gray if none, ◐ yellow if partial
+    // Status dot: ● all running, ○ none, ◐ some
     let (dot, dot_color) = if running_count == 0 {
-        ("○", Color::DarkGrey)
+        ("○", theme().dim)
     } else if running_count == count {
-        ("●", Color::Green)
+        ("●", theme().ok)
     } else {
-        ("◐", Color::Yellow)
+        ("◐", theme().warn)
     };
     let label = format!("{} {name}", dot);
     let path_label = path.unwrap_or("-");
     let status_label = format!("{}/{} running", running_count, count);
     let all_stopped = running_count == 0;
 
-    let id_cell = fit_right("", widths[0]);
-    let cpu_cell = fit_right("", widths[1]);
-    let mem_cell = fit_right("", widths[2]);
-    let name_cell = fit_left(&label, widths[3]);
-    let image_cell = fit_left(path_label, widths[4]);
-    let port_cell = fit_left("-", widths[5]);
-    let int_port_cell = fit_left("-", widths[6]);
-    let status_cell = fit_left(&status_label, widths[7]);
+    let cell = |column: &Column| match column.id {
+        "name" => label.clone(),
+        "image" => path_label.to_string(),
+        "port" | "int_port" => "-".to_string(),
+        "status" => status_label.clone(),
+        "rx" | "tx" => format_net_cell(column.id, None),
+        _ => String::new(),
+    };
+    let line = format_row(columns, cell);
 
     if selected {
-        let line = format!(
-            "│{}│{}│{}│{}│{}│{}│{}│{}│",
-            id_cell, cpu_cell, mem_cell, name_cell, image_cell, port_cell, int_port_cell, status_cell
-        );
-        let total_width: usize = widths.iter().sum::<usize>() + 9; // 9 separators
         queue!(
             stdout,
             MoveTo(x, y),
-            SetBackgroundColor(Color::Yellow),
-            SetForegroundColor(Color::Black),
-            Print(fit_left(&line, total_width)),
+            theme().highlight,
+            Print(&line),
             ResetColor
         )?;
     } else if hovered {
-        let line = format!(
-            "│{}│{}│{}│{}│{}│{}│{}│{}│",
-            id_cell, cpu_cell, mem_cell, name_cell, image_cell, port_cell, int_port_cell, status_cell
-        );
-        let total_width: usize = widths.iter().sum::<usize>() + 9;
         queue!(
             stdout,
             MoveTo(x, y),
-            SetBackgroundColor(Color::DarkGrey),
-            Print(fit_left(&line, total_width)),
+            theme().hover,
+            Print(&line),
             ResetColor
         )?;
     } else if all_stopped {
         // Grey out entire group if all containers stopped
-        let line = format!(
-            "│{}│{}│{}│{}│{}│{}│{}│{}│",
-            id_cell, cpu_cell, mem_cell, name_cell, image_cell, port_cell, int_port_cell, status_cell
-        );
-        let total_width: usize = widths.iter().sum::<usize>() + 9;
         queue!(
             stdout,
             MoveTo(x, y),
-            SetForegroundColor(Color::DarkGrey),
-            Print(fit_left(&line, total_width)),
+            SetForegroundColor(theme().dim),
+            Print(&line),
             ResetColor
         )?;
     } else {
         queue!(stdout, MoveTo(x, y))?;
-        print_table_bar(stdout)?;
-        print_dim_cell(stdout, &id_cell)?;
-        print_table_bar(stdout)?;
-        print_dim_cell(stdout, &cpu_cell)?;
-        print_table_bar(stdout)?;
-        print_dim_cell(stdout, &mem_cell)?;
-        print_table_bar(stdout)?;
-        // Render name with colored dot
-        render_group_name_cell_with_dot(stdout, name, dot, dot_color, widths[3])?;
-        print_table_bar(stdout)?;
-        print_dim_cell(stdout, &image_cell)?;
-        print_table_bar(stdout)?;
-        print_dim_cell(stdout, &port_cell)?;
-        print_table_bar(stdout)?;
-        print_dim_cell(stdout, &int_port_cell)?;
-        print_table_bar(stdout)?;
-        print_dim_cell(stdout, &status_cell)?;
+        for column in columns {
+            print_table_bar(stdout)?;
+            if column.id == "name" {
+                // Render name with colored dot
+                render_group_name_cell_with_dot(stdout, name, dot, dot_color, column.width)?;
+            } else {
+                print_dim_cell(stdout, &fit_column(&cell(column), column))?;
+            }
+        }
         print_table_bar(stdout)?;
     }
     Ok(())
@@ -545,7 +531,7 @@ fn render_group_name_cell_with_dot(
         let display_len = display.chars().count();
         queue!(
             stdout,
-            SetForegroundColor(Color::DarkGrey),
+            SetForegroundColor(theme().dim),
             Print(&display),
             ResetColor
         )?;
@@ -560,11 +546,11 @@ fn render_group_name_cell_with_dot(
     queue!(stdout, SetForegroundColor(dot_color), Print(dot), ResetColor)?;
     queue!(stdout, Print(" "))?;
 
-    // Print name in yellow
+    // Print name in the group colour
     let name_width = width.saturating_sub(2); // dot + space
     let name_display = truncate_str(name, name_width);
     let name_len = name_display.chars().count();
-    queue!(stdout, SetForegroundColor(Color::Yellow), Print(name_display), ResetColor)?;
+    queue!(stdout, SetForegroundColor(theme().group), Print(name_display), ResetColor)?;
 
     let remaining = name_width.saturating_sub(name_len);
     if remaining > 0 {
@@ -605,7 +591,7 @@ fn render_docker_search_status_at(
         queue!(
             stdout,
             MoveTo(x, y),
-            SetForegroundColor(Color::DarkGrey),
+            SetForegroundColor(theme().dim),
             Print(prefix),
             ResetColor
         )?;
@@ -615,7 +601,7 @@ fn render_docker_search_status_at(
     if is_dim_mode() {
         queue!(
             stdout,
-            SetForegroundColor(Color::DarkGrey),
+            SetForegroundColor(theme().dim),
             Print(term_display),
             Print(suffix_display),
             ResetColor
@@ -624,7 +610,7 @@ fn render_docker_search_status_at(
         if !term_display.is_empty() {
             queue!(
                 stdout,
-                SetForegroundColor(Color::Blue),
+                SetForegroundColor(theme().link),
                 SetAttribute(Attribute::Bold),
                 Print(term_display),
                 SetAttribute(Attribute::Reset),
@@ -637,8 +623,7 @@ fn render_docker_search_status_at(
             queue!(stdout, Print(before))?;
             queue!(
                 stdout,
-                SetBackgroundColor(Color::Yellow),
-                SetForegroundColor(Color::Black),
+                theme().highlight,
                 Print(x_char),
                 ResetColor
             )?;
@@ -659,7 +644,7 @@ fn print_dim_cell(stdout: &mut io::Stdout, text: &str) -> io::Result<()> {
     if is_dim_mode() {
         queue!(
             stdout,
-            SetForegroundColor(Color::DarkGrey),
+            SetForegroundColor(theme().dim),
             Print(text),
             ResetColor
         )?;
@@ -669,116 +654,13 @@ fn print_dim_cell(stdout: &mut io::Stdout, text: &str) -> io::Result<()> {
     Ok(())
 }
 
-fn docker_column_widths(width: usize) -> Vec<usize> {
-    let fixed = 12 + 6 + 9;
-    let separators = 9usize;
-    let content_width = width.saturating_sub(separators);
-    let remaining = content_width.saturating_sub(fixed);
-    let min_name = 10usize;
-    let min_image = 10usize;
-    let min_ports = 8usize;
-    let min_int_ports = 8usize;
-    let min_status = 10usize;
-
-    let (name_width, image_width, ports_width, int_ports_width, status_width) = if remaining
-        >= min_name + min_image + min_ports + min_int_ports + min_status
-    {
-        let mut name_width = remaining * 3 / 12;
-        let mut image_width = remaining * 3 / 12;
-        let mut ports_width = remaining * 2 / 12;
-        let mut int_ports_width = remaining * 2 / 12;
-        let mut status_width =
-            remaining - name_width - image_width - ports_width - int_ports_width;
-        if status_width < min_status {
-            status_width = min_status;
-            let leftover = remaining - status_width;
-            name_width = leftover * 3 / 9;
-            image_width = leftover * 3 / 9;
-            ports_width = leftover * 2 / 9;
-            int_ports_width = leftover - name_width - image_width - ports_width;
-        }
-        if name_width < min_name {
-            name_width = min_name;
-            let leftover = remaining - name_width;
-            image_width = leftover * 3 / 8;
-            ports_width = leftover * 2 / 8;
-            int_ports_width = leftover * 2 / 8;
-            status_width = leftover - image_width - ports_width - int_ports_width;
-        }
-        if image_width < min_image {
-            image_width = min_image;
-            let leftover = remaining - image_width;
-            name_width = leftover * 3 / 8;
-            ports_width = leftover * 2 / 8;
-            int_ports_width = leftover * 2 / 8;
-            status_width = leftover - name_width - ports_width - int_ports_width;
-        }
-        if ports_width < min_ports {
-            ports_width = min_ports;
-            let leftover = remaining - ports_width;
-            name_width = leftover * 3 / 8;
-            image_width = leftover * 3 / 8;
-            int_ports_width = leftover * 2 / 8;
-            status_width = leftover - name_width - image_width - int_ports_width;
-        }
-        if int_ports_width < min_int_ports {
-            int_ports_width = min_int_ports;
-            let leftover = remaining - int_ports_width;
-            name_width = leftover * 3 / 8;
-            image_width = leftover * 3 / 8;
-            ports_width = leftover * 2 / 8;
-            status_width = leftover - name_width - image_width - ports_width;
-        }
-        (
-            name_width,
-            image_width,
-            ports_width,
-            int_ports_width,
-            status_width,
-        )
-    } else {
-        let mut name_width = remaining;
-        let mut image_width = 0usize;
-        let mut ports_width = 0usize;
-        let mut int_ports_width = 0usize;
-        let mut status_width = 0usize;
-        if name_width >= min_name + min_image + min_ports + min_int_ports + min_status {
-            name_width = min_name;
-            image_width = min_image;
-            ports_width = min_ports;
-            int_ports_width = min_int_ports;
-            status_width = remaining - name_width - image_width - ports_width - int_ports_width;
-        }
-        (name_width, image_width, ports_width, int_ports_width, status_width)
-    };
-
-    vec![
-        12,
-        6,
-        9,
-        name_width,
-        image_width,
-        ports_width,
-        int_ports_width,
-        status_width,
-    ]
-}
-
-fn format_docker_header(widths: &[usize]) -> String {
-    let cells = vec![
-        fit_right("CONTAINER", widths[0]),
-        fit_right("CPU%", widths[1]),
-        fit_right("MEM(GB)", widths[2]),
-        fit_left("NAME", widths[3]),
-        fit_left("IMAGE", widths[4]),
-        fit_left("PORT", widths[5]),
-        fit_left("INT PORT", widths[6]),
-        fit_left("STATUS", widths[7]),
-    ];
-    format!(
-        "│{}│{}│{}│{}│{}│{}│{}│{}│",
-        cells[0], cells[1], cells[2], cells[3], cells[4], cells[5], cells[6], cells[7]
-    )
+/// RX/s or TX/s of a container, "-" while not sampled.
+fn format_net_cell(id: &str, net: Option<NetRates>) -> String {
+    match (id, net) {
+        ("rx", Some(net)) => format_rate(net.rx),
+        ("tx", Some(net)) => format_rate(net.tx),
+        _ => "-".to_string(),
+    }
 }
 
 fn render_context_menu(stdout: &mut io::Stdout, menu: &ContextMenu) -> io::Result<()> {
@@ -798,8 +680,7 @@ fn render_context_menu(stdout: &mut io::Stdout, menu: &ContextMenu) -> io::Resul
     queue!(
         stdout,
         MoveTo(x, y),
-        SetBackgroundColor(Color::Black),
-        SetForegroundColor(Color::Grey),
+        theme().panel,
         Print(&top_border),
         ResetColor
     )?;
@@ -816,8 +697,7 @@ fn render_context_menu(stdout: &mut io::Stdout, menu: &ContextMenu) -> io::Resul
         if is_hovered {
             queue!(
                 stdout,
-                SetBackgroundColor(Color::DarkCyan),
-                SetForegroundColor(Color::White),
+                theme().selected,
                 Print("│"),
                 Print(&padded),
                 Print("│"),
@@ -826,8 +706,7 @@ fn render_context_menu(stdout: &mut io::Stdout, menu: &ContextMenu) -> io::Resul
         } else {
             queue!(
                 stdout,
-                SetBackgroundColor(Color::Black),
-                SetForegroundColor(Color::Grey),
+                theme().panel,
                 Print("│"),
                 Print(&padded),
                 Print("│"),
@@ -840,11 +719,77 @@ fn render_context_menu(stdout: &mut io::Stdout, menu: &ContextMenu) -> io::Resul
     queue!(
         stdout,
         MoveTo(x, y + menu_height - 1),
-        SetBackgroundColor(Color::Black),
-        SetForegroundColor(Color::Grey),
+        theme().panel,
         Print(&bottom_border),
         ResetColor
     )?;
 
     Ok(())
 }
+
+fn render_context_picker(
+    stdout: &mut io::Stdout,
+    picker: &DockerContextPicker,
+    active: Option<&str>,
+    main_x: u16,
+    main_width: usize,
+    height: usize,
+) -> io::Result<()> {
+    let (x, y, width, box_height) = picker.frame(main_x, main_width, height);
+    let inner = width.saturating_sub(2);
+    let visible = box_height.saturating_sub(2);
+    let offset = picker.scroll_offset(visible);
+
+    let title = " Docker contexts ";
+    let top = format!(
+        "┌{}{}┐",
+        title,
+        "─".repeat(inner.saturating_sub(title.chars().count()))
+    );
+    queue!(
+        stdout,
+        MoveTo(x, y),
+        theme().panel,
+        Print(truncate_str(&top, width)),
+        ResetColor
+    )?;
+
+    for (row, ctx) in picker.contexts.iter().skip(offset).take(visible).enumerate() {
+        let idx = offset + row;
+        let in_use = match active {
+            Some(name) => ctx.name == name,
+            None => ctx.current,
+        };
+        let marker = if in_use { "●" } else { " " };
+        let name_width = inner.saturating_sub(3) / 2;
+        let label = format!(
+            " {} {}{}",
+            marker,
+            fit_left(&ctx.name, name_width),
+            ctx.endpoint
+        );
+        let style = if idx == picker.selected {
+            theme().selected
+        } else {
+            theme().panel
+        };
+        queue!(
+            stdout,
+            MoveTo(x, y + 1 + row as u16),
+            style,
+            Print("│"),
+            Print(fit_left(&label, inner)),
+            Print("│"),
+            ResetColor
+        )?;
+    }
+
+    queue!(
+        stdout,
+        MoveTo(x, y + box_height as u16 - 1),
+        theme().panel,
+        Print(format!("└{}┘", "─".repeat(inner))),
+        ResetColor
+    )?;
+    Ok(())
+}
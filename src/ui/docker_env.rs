@@ -9,7 +9,7 @@ use crate::app::AppState;
 
 use super::layout::{layout_for_screen, render_sidebar, render_sidebar_gap};
 use super::table::{
-    clear_list_area_at, fit_left, format_separator, format_top_border,
+    clear_list_area_at, fit_left, format_bottom_border, format_separator, format_top_border,
     render_help_table_rows_colored_at, render_line_at, render_title_at, HelpSegment,
 };
 
@@ -53,13 +53,13 @@ pub fn render_envs(stdout: &mut io::Stdout, state: &AppState) -> io::Result<()>
     let info_top = format_top_border(&info_widths);
     render_line_at(stdout, main_x, row, &info_top, width_usize)?;
     row += 1;
-    let info_row1 = format_env_info_row(&info_widths, &compose_text, &path_text);
+    let info_row1 = format_env_info_row(&info_widths, compose_text, path_text);
     render_line_at(stdout, main_x, row, &info_row1, width_usize)?;
     row += 1;
     let info_sep = format_separator(&info_widths);
     render_line_at(stdout, main_x, row, &info_sep, width_usize)?;
     row += 1;
-    let info_row2 = format_env_info_row(&info_widths, &container_text, &ports_text);
+    let info_row2 = format_env_info_row(&info_widths, container_text, ports_text);
     render_line_at(stdout, main_x, row, &info_row2, width_usize)?;
     row += 1;
     let info_bottom = format_bottom_border(&info_widths);
@@ -118,13 +118,25 @@ pub fn render_envs(stdout: &mut io::Stdout, state: &AppState) -> io::Result<()>
 
     if height_usize >= footer_lines {
         let message_line = height_usize.saturating_sub(footer_lines) as u16;
-        render_line_at(stdout, main_x, message_line, "Esc to return", width_usize)?;
+        let message = state.message.as_deref().unwrap_or("Esc to return");
+        render_line_at(stdout, main_x, message_line, message, width_usize)?;
 
-        let help_rows = vec![vec![
+        let mut help_row = vec![
             HelpSegment::plain("Actions: "),
             HelpSegment::key("Esc"),
-            HelpSegment::plain(" back"),
-        ]];
+            HelpSegment::plain(" back | "),
+        ];
+        if state.env_scripts.is_some() {
+            help_row.push(HelpSegment::key("Enter"));
+            help_row.push(HelpSegment::plain(" run script | "));
+        }
+        if state.kill_plan.is_some() {
+            help_row.push(HelpSegment::key("Enter"));
+            help_row.push(HelpSegment::plain(" kill all | "));
+        }
+        help_row.push(HelpSegment::key("y"));
+        help_row.push(HelpSegment::plain(" copy value"));
+        let help_rows = vec![help_row];
         let help_start = height_usize.saturating_sub(help_rows.len() + 2) as u16;
         render_help_table_rows_colored_at(stdout, main_x, help_start, width_usize, &help_rows)?;
     }
@@ -204,9 +216,7 @@ fn env_info_widths(
         }
     }
 
-    if left_len + right_len > content_width {
-        right_len = content_width.saturating_sub(left_len);
-    } else if left_len + right_len < content_width {
+    if left_len + right_len != content_width {
         right_len = content_width.saturating_sub(left_len);
     }
 
@@ -218,17 +228,3 @@ fn format_env_info_row(widths: &[usize], left: &str, right: &str) -> String {
     let right_cell = fit_left(right, widths[1]);
     format!("│{}│{}│", left_cell, right_cell)
 }
-
-fn format_bottom_border(widths: &[usize]) -> String {
-    let mut line = String::new();
-    line.push('└');
-    for (idx, width) in widths.iter().enumerate() {
-        line.push_str(&"─".repeat(*width));
-        if idx + 1 == widths.len() {
-            line.push('┘');
-        } else {
-            line.push('┴');
-        }
-    }
-    line
-}
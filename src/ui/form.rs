@@ -0,0 +1,108 @@
+use std::io;
+
+use crossterm::cursor::MoveTo;
+use crossterm::queue;
+use crossterm::style::{Print, ResetColor, SetForegroundColor};
+
+use crate::app::FormOverlay;
+
+use super::table::{fit_left, truncate_str};
+use super::theme::theme;
+
+const LABEL_WIDTH: usize = 10;
+
+pub(crate) fn render_form(
+    stdout: &mut io::Stdout,
+    form: &FormOverlay,
+    main_x: u16,
+    main_width: usize,
+    height: usize,
+) -> io::Result<()> {
+    let (x, y, width, box_height) = form.frame(main_x, main_width, height);
+    let inner = width.saturating_sub(2);
+
+    let title = format!(" {} ", form.title);
+    let top = format!(
+        "┌{}{}┐",
+        truncate_str(&title, inner),
+        "─".repeat(inner.saturating_sub(title.chars().count()))
+    );
+    queue!(
+        stdout,
+        MoveTo(x, y),
+        theme().panel,
+        Print(truncate_str(&top, width)),
+        ResetColor
+    )?;
+
+    let input_width = inner.saturating_sub(LABEL_WIDTH + 3) / 2;
+    let rows = box_height.saturating_sub(2);
+    for row in 0..rows {
+        let row_y = y + 1 + row as u16;
+        queue!(
+            stdout,
+            MoveTo(x, row_y),
+            theme().panel,
+            Print("│")
+        )?;
+
+        if let Some(field) = form.fields.get(row) {
+            let is_selected = row == form.selected;
+            let cursor = if is_selected { "_" } else { "" };
+            let value = format!("{}{}", field.value, cursor);
+            // Keep the end of long values (and the cursor) visible
+            let shown: String = {
+                let len = value.chars().count();
+                value.chars().skip(len.saturating_sub(input_width)).collect()
+            };
+            let style = if is_selected {
+                theme().selected
+            } else {
+                theme().input
+            };
+            queue!(
+                stdout,
+                Print(fit_left(&format!(" {}", field.label), LABEL_WIDTH + 1)),
+                style,
+                Print(fit_left(&shown, input_width)),
+                theme().panel,
+                SetForegroundColor(theme().dim),
+                Print(fit_left(
+                    &format!(" {}", field.hint),
+                    inner.saturating_sub(LABEL_WIDTH + 1 + input_width)
+                )),
+            )?;
+        } else if row + 1 == rows {
+            match &form.error {
+                Some(error) => queue!(
+                    stdout,
+                    SetForegroundColor(theme().error),
+                    Print(fit_left(&format!(" {error}"), inner))
+                )?,
+                None => queue!(
+                    stdout,
+                    SetForegroundColor(theme().dim),
+                    Print(fit_left(" Enter apply | Tab next | Esc cancel", inner))
+                )?,
+            }
+        } else {
+            queue!(stdout, Print(" ".repeat(inner)))?;
+        }
+
+        queue!(
+            stdout,
+            theme().panel,
+            Print("│"),
+            ResetColor
+        )?;
+    }
+
+    queue!(
+        stdout,
+        MoveTo(x, y + box_height as u16 - 1),
+        theme().panel,
+        Print(format!("└{}┘", "─".repeat(inner))),
+        ResetColor
+    )?;
+    Ok(())
+}
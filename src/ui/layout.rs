@@ -1,11 +1,12 @@
 use std::io;
 
-use crossterm::style::{Attribute, Color, Print, ResetColor, SetAttribute, SetBackgroundColor, SetForegroundColor};
+use crossterm::style::{Attribute, Print, ResetColor, SetAttribute, SetForegroundColor};
 use crossterm::{cursor::MoveTo, queue};
 
 use crate::app::{AppState, Focus, ViewMode};
 
 use super::table::{center_text, fit_left, is_dim_mode, print_table_bar, render_line_at};
+use super::theme::theme;
 
 const SIDEBAR_WIDTH: usize = 20;
 const SIDEBAR_GAP: usize = 1;
@@ -100,7 +101,7 @@ pub(crate) fn render_sidebar(
         row += 1;
     }
 
-    let items = ["Processes", "Ports", "Docker", "Node JS"];
+    let items = ["Processes", "Ports", "Docker", "Apps"];
     let active_view = if state.view_mode == ViewMode::DockerEnv {
         state.env_return_view
     } else {
@@ -109,8 +110,8 @@ pub(crate) fn render_sidebar(
     let active_index = match active_view {
         ViewMode::Process => 0,
         ViewMode::Ports => 1,
-        ViewMode::Docker | ViewMode::DockerEnv => 2,
-        ViewMode::Node => 3,
+        ViewMode::Docker | ViewMode::DockerEnv | ViewMode::ContainerTop => 2,
+        ViewMode::Node | ViewMode::Logs => 3,
     };
     let list_start = row;
     let mut item_idx = 0usize;
@@ -158,6 +159,7 @@ pub(crate) fn render_sidebar_gap(
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 fn render_sidebar_item(
     stdout: &mut io::Stdout,
     x: u16,
@@ -174,7 +176,7 @@ fn render_sidebar_item(
     queue!(stdout, MoveTo(x, y))?;
     print_table_bar(stdout)?;
     if is_dim_mode() {
-        queue!(stdout, SetForegroundColor(Color::DarkGrey), Print(text), ResetColor)?;
+        queue!(stdout, SetForegroundColor(theme().dim), Print(text), ResetColor)?;
     } else if focus_sidebar && is_selected {
         queue!(
             stdout,
@@ -185,12 +187,12 @@ fn render_sidebar_item(
     } else if is_hovered {
         queue!(
             stdout,
-            SetBackgroundColor(Color::DarkGrey),
+            theme().hover,
             Print(text),
             ResetColor
         )?;
     } else if is_active {
-        queue!(stdout, SetForegroundColor(Color::Cyan), Print(text), ResetColor)?;
+        queue!(stdout, SetForegroundColor(theme().accent), Print(text), ResetColor)?;
     } else {
         queue!(stdout, Print(text))?;
     }
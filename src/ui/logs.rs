@@ -0,0 +1,265 @@
+use std::io::{self, Write};
+
+use crossterm::cursor::MoveTo;
+use crossterm::queue;
+use crossterm::style::{Attribute, Color, Print, ResetColor, SetAttribute, SetForegroundColor};
+use crossterm::terminal;
+
+use crate::app::{AppState, LogPane};
+use crate::system::logs::{LogLine, LogSource, LogStream, LogTail};
+
+use super::layout::{layout_for_screen, render_sidebar, render_sidebar_gap};
+use super::table::{
+    clear_list_area_at, fit_left, format_bottom_border, format_separator, format_top_border,
+    render_help_table_rows_colored_at, render_line_at, render_title_at, truncate_str, HelpSegment,
+};
+use super::theme::theme;
+
+pub fn render_logs(stdout: &mut io::Stdout, state: &AppState) -> io::Result<()> {
+    let Some(pane) = state.log_pane.as_ref() else {
+        return Ok(());
+    };
+    let (width, height) = terminal::size().unwrap_or((80, 24));
+    let screen_width = width as usize;
+    let height_usize = height as usize;
+    let layout = layout_for_screen(screen_width);
+    if layout.show_sidebar {
+        render_sidebar(stdout, state, &layout, height_usize)?;
+        render_sidebar_gap(stdout, &layout, height_usize)?;
+    }
+    let width_usize = layout.main_width;
+    let main_x = layout.main_x;
+
+    queue!(stdout, MoveTo(main_x, 0))?;
+
+    let mut row = 0u16;
+    render_line_at(
+        stdout,
+        main_x,
+        row,
+        &format!("┌{}┐", "─".repeat(width_usize.saturating_sub(2))),
+        width_usize,
+    )?;
+    row += 1;
+    render_title_at(stdout, main_x, row, width_usize, &pane.title)?;
+    row += 2;
+
+    let (source_line1, source_line2) = match &pane.source {
+        LogSource::Files(tail) => (
+            format!("Out: {}", path_for(tail, LogStream::Stdout)),
+            format!("Err: {}", path_for(tail, LogStream::Stderr)),
+        ),
+        LogSource::Command(output) => (
+            format!("Cmd: {}", output.command_line()),
+            format!("Dir: {}", output.dir().display()),
+        ),
+    };
+    let info_widths = info_column_widths(width_usize);
+    render_line_at(stdout, main_x, row, &format_top_border(&info_widths), width_usize)?;
+    row += 1;
+    let info_row1 = format_info_row(
+        &info_widths,
+        &format!("App: {}", pane.subject),
+        &source_line1,
+    );
+    render_line_at(stdout, main_x, row, &info_row1, width_usize)?;
+    row += 1;
+    render_line_at(stdout, main_x, row, &format_separator(&info_widths), width_usize)?;
+    row += 1;
+    let info_row2 = format_info_row(
+        &info_widths,
+        &status_text(pane),
+        &source_line2,
+    );
+    render_line_at(stdout, main_x, row, &info_row2, width_usize)?;
+    row += 1;
+    render_line_at(stdout, main_x, row, &format_bottom_border(&info_widths), width_usize)?;
+    row += 1;
+
+    let inner = width_usize.saturating_sub(2);
+    render_line_at(stdout, main_x, row, &format_top_border(&[inner]), width_usize)?;
+    row += 1;
+
+    let list_start = row as usize;
+    let footer_lines = 5usize;
+    // One row below the lines is taken by the bottom border
+    let max_rows = height_usize.saturating_sub(list_start + footer_lines + 1);
+    if max_rows > 0 {
+        let total = pane.lines.len();
+        let end = total.saturating_sub(pane.scroll);
+        let start = end.saturating_sub(max_rows);
+        if pane.lines.is_empty() {
+            render_line_at(
+                stdout,
+                main_x,
+                list_start as u16,
+                &format!("│{}│", fit_left(" No log output yet", inner)),
+                width_usize,
+            )?;
+        }
+        let mut rendered = usize::from(pane.lines.is_empty());
+        for index in start..end {
+            let Some(line) = pane.lines.get(index) else {
+                continue;
+            };
+            let y = (list_start + rendered) as u16;
+            let is_current = pane.current_match == Some(index);
+            render_log_line(stdout, main_x, y, inner, line, &pane.search, is_current)?;
+            rendered += 1;
+        }
+        for offset in rendered..max_rows {
+            render_line_at(
+                stdout,
+                main_x,
+                (list_start + offset) as u16,
+                &format!("│{}│", " ".repeat(inner)),
+                width_usize,
+            )?;
+        }
+        render_line_at(
+            stdout,
+            main_x,
+            (list_start + max_rows) as u16,
+            &format_bottom_border(&[inner]),
+            width_usize,
+        )?;
+    } else {
+        clear_list_area_at(stdout, main_x, list_start, 1, width_usize)?;
+    }
+
+    if height_usize >= footer_lines {
+        let message_line = height_usize.saturating_sub(footer_lines) as u16;
+        let message = state.message.as_deref().unwrap_or("Esc to return");
+        render_line_at(stdout, main_x, message_line, message, width_usize)?;
+
+        let pause_label = if pane.paused { " resume | " } else { " pause | " };
+        let help_rows = vec![vec![
+            HelpSegment::plain("Actions: "),
+            HelpSegment::key("Esc"),
+            HelpSegment::plain(" back | "),
+            HelpSegment::key("space"),
+            HelpSegment::plain(pause_label),
+            HelpSegment::key("/"),
+            HelpSegment::plain(" search | "),
+            HelpSegment::key("n/N"),
+            HelpSegment::plain(" older/newer hit | "),
+            HelpSegment::key("arrows"),
+            HelpSegment::plain(" scroll | "),
+            HelpSegment::key("End"),
+            HelpSegment::plain(" follow"),
+        ]];
+        let help_start = height_usize.saturating_sub(help_rows.len() + 2) as u16;
+        render_help_table_rows_colored_at(stdout, main_x, help_start, width_usize, &help_rows)?;
+    }
+
+    if pane.search_editing {
+        super::search::render_search_bar_at(stdout, main_x, 0, width_usize, &pane.search)?;
+    }
+
+    stdout.flush()?;
+    Ok(())
+}
+
+fn path_for(tail: &LogTail, stream: LogStream) -> String {
+    tail.paths()
+        .find(|(_, s)| *s == stream)
+        .map(|(path, _)| path.display().to_string())
+        .unwrap_or_else(|| "-".to_string())
+}
+
+fn status_text(pane: &LogPane) -> String {
+    let mode = if let LogSource::Command(output) = &pane.source {
+        match output.exit_status() {
+            Some(status) => format!("Finished ({status})"),
+            None if pane.paused => format!("Running (PID {}), paused", output.pid()),
+            None => format!("Running (PID {})", output.pid()),
+        }
+    } else if pane.paused {
+        "Paused".to_string()
+    } else if pane.scroll > 0 {
+        format!("Scrolled back {} lines", pane.scroll)
+    } else {
+        "Following".to_string()
+    };
+    let mut text = format!("{mode} | {} lines", pane.lines.len());
+    if !pane.search.is_empty() && !pane.search_editing {
+        let hits = pane.lines.matches(&pane.search).len();
+        text.push_str(&format!(" | \"{}\": {hits} hits", pane.search));
+    }
+    text
+}
+
+/// Draws one bordered log line; stderr is red, search hits are highlighted and
+/// the current hit is shown reversed.
+fn render_log_line(
+    stdout: &mut io::Stdout,
+    x: u16,
+    y: u16,
+    inner: usize,
+    line: &LogLine,
+    search: &str,
+    is_current: bool,
+) -> io::Result<()> {
+    let text = truncate_str(&line.text, inner);
+    let color = match line.stream {
+        LogStream::Stdout => Color::Reset,
+        LogStream::Stderr => theme().error,
+    };
+
+    queue!(
+        stdout,
+        MoveTo(x, y),
+        SetForegroundColor(theme().dim),
+        Print("│"),
+        ResetColor
+    )?;
+    if is_current {
+        queue!(stdout, SetAttribute(Attribute::Reverse))?;
+    }
+
+    // ASCII lowercasing keeps byte offsets, so hit ranges index into `text`
+    let lower = text.to_ascii_lowercase();
+    let needle = search.to_ascii_lowercase();
+    let mut pos = 0usize;
+    while !needle.is_empty() {
+        let Some(found) = lower[pos..].find(&needle) else {
+            break;
+        };
+        let start = pos + found;
+        let end = start + needle.len();
+        queue!(
+            stdout,
+            SetForegroundColor(color),
+            Print(&text[pos..start]),
+            theme().highlight,
+            Print(&text[start..end]),
+            ResetColor
+        )?;
+        if is_current {
+            queue!(stdout, SetAttribute(Attribute::Reverse))?;
+        }
+        pos = end;
+    }
+    queue!(stdout, SetForegroundColor(color), Print(&text[pos..]))?;
+
+    let padding = inner.saturating_sub(text.chars().count());
+    queue!(
+        stdout,
+        Print(" ".repeat(padding)),
+        SetAttribute(Attribute::Reset),
+        SetForegroundColor(theme().dim),
+        Print("│"),
+        ResetColor
+    )?;
+    Ok(())
+}
+
+fn info_column_widths(width: usize) -> Vec<usize> {
+    let content_width = width.saturating_sub(3);
+    let left = content_width / 2;
+    vec![left, content_width - left]
+}
+
+fn format_info_row(widths: &[usize], left: &str, right: &str) -> String {
+    format!("│{}│{}│", fit_left(left, widths[0]), fit_left(right, widths[1]))
+}
@@ -0,0 +1,59 @@
+use std::io;
+
+use crossterm::cursor::MoveTo;
+use crossterm::queue;
+use crossterm::style::{Print, ResetColor};
+
+use crate::app::ContextMenu;
+
+use super::theme::theme;
+
+pub(crate) fn render_context_menu(stdout: &mut io::Stdout, menu: &ContextMenu) -> io::Result<()> {
+    const MENU_WIDTH: usize = 16;
+    const PADDING: u16 = 1;
+
+    let x = menu.x;
+    let y = menu.y;
+    let menu_height = menu.items.len() as u16 + PADDING * 2;
+
+    let top_border = format!("┌{}┐", "─".repeat(MENU_WIDTH - 2));
+    let bottom_border = format!("└{}┘", "─".repeat(MENU_WIDTH - 2));
+
+    queue!(
+        stdout,
+        MoveTo(x, y),
+        theme().panel,
+        Print(&top_border),
+        ResetColor
+    )?;
+
+    for (idx, action) in menu.items.iter().enumerate() {
+        let row_y = y + PADDING + idx as u16;
+        let label = action.label(menu.is_group);
+        let padded = format!(" {:<width$}", label, width = MENU_WIDTH - 3);
+        let style = if menu.hover == Some(idx) {
+            theme().selected
+        } else {
+            theme().panel
+        };
+        queue!(
+            stdout,
+            MoveTo(x, row_y),
+            style,
+            Print("│"),
+            Print(&padded),
+            Print("│"),
+            ResetColor
+        )?;
+    }
+
+    queue!(
+        stdout,
+        MoveTo(x, y + menu_height - 1),
+        theme().panel,
+        Print(&bottom_border),
+        ResetColor
+    )?;
+
+    Ok(())
+}
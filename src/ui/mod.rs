@@ -1,15 +1,24 @@
 mod bars;
+mod columns;
+mod container_top;
 mod docker;
 mod docker_env;
+mod form;
 mod layout;
+mod logs;
+mod menu;
 mod node;
 mod ports;
 mod process;
 mod search;
 mod table;
+mod theme;
 
+pub use container_top::render_container_top;
 pub use docker::render_containers;
 pub use docker_env::render_envs;
+pub use logs::render_logs;
 pub use node::render_node_processes;
 pub use ports::render_ports;
 pub use process::render_processes;
+pub use theme::init_theme;
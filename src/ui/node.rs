@@ -2,26 +2,27 @@ use std::io::{self, Write};
 
 use crossterm::cursor::MoveTo;
 use crossterm::queue;
-use crossterm::style::{Attribute, Color, Print, ResetColor, SetAttribute, SetBackgroundColor, SetForegroundColor};
+use crossterm::style::{Attribute, Color, Print, ResetColor, SetAttribute, SetForegroundColor};
 use crossterm::terminal;
 
-use crate::app::{AppState, InputMode};
+use crate::app::{AppState, Column, ColumnView, InputMode};
 use crate::system::node::{format_uptime, NodeProcessInfo, NodeRow};
 
 use super::bars::{format_cpu_bar, format_memory_bar, format_swap_bar};
 use super::layout::{layout_for_screen, render_sidebar, render_sidebar_gap};
 use super::table::{
-    clear_list_area_at, fit_left, fit_right, format_separator, format_top_border,
-    is_dim_mode, print_table_bar, render_help_table_rows_colored_at, render_line_at,
-    render_search_box_at, render_title_at, set_dim_mode, truncate_str, HelpSegment,
+    clear_list_area_at, column_widths, fit_column, fit_left, format_header, format_row,
+    format_separator, format_top_border, is_dim_mode, print_table_bar,
+    render_help_table_rows_colored_at, render_line_at, render_search_box_at, render_title_at,
+    set_dim_mode, truncate_str, HelpSegment,
 };
+use super::theme::theme;
 
 pub fn render_node_processes(
     stdout: &mut io::Stdout,
     state: &AppState,
     processes: &[NodeProcessInfo],
     rows: &[NodeRow],
-    pm2_available: bool,
 ) -> io::Result<()> {
     let (width, height) = terminal::size().unwrap_or((80, 24));
     let screen_width = width as usize;
@@ -51,14 +52,19 @@ pub fn render_node_processes(
         width_usize,
     )?;
     row += 1;
-    render_title_at(stdout, main_x, row, width_usize, "NODE.JS VIEW")?;
+    render_title_at(stdout, main_x, row, width_usize, "APPS VIEW")?;
     row += 2;
 
-    let pm2_status = if pm2_available { "PM2: ✓" } else { "PM2: -" };
-    let header = format!(
-        "Spark | View: NODE | {} | Mode: {}",
-        pm2_status, mode_label
+    let pm2_status = if state.pm2_available { "PM2: ✓" } else { "PM2: -" };
+    let mut header = format!(
+        "Spark | View: APPS | {} | Sort: {} | Mode: {}",
+        pm2_status,
+        state.node_sort.label(),
+        mode_label
     );
+    if state.node_wrong_version_only {
+        header.push_str(" | Only: wrong Node version");
+    }
     render_line_at(stdout, main_x, row, &header, width_usize)?;
     row += 1;
     render_search_box_at(stdout, main_x, row, width_usize, &state.node_filter)?;
@@ -88,7 +94,8 @@ pub fn render_node_processes(
     )?;
     row += 1;
 
-    let col_widths = node_column_widths(width_usize, pm2_available);
+    let columns = state.resolved_columns(ColumnView::Node, width_usize);
+    let col_widths = column_widths(&columns);
     let show_main_table = match rows.first() {
         Some(NodeRow::UtilsSpacer)
         | Some(NodeRow::UtilsTitle)
@@ -101,8 +108,7 @@ pub fn render_node_processes(
         let top_border = format_top_border(&col_widths);
         render_line_at(stdout, main_x, row, &top_border, width_usize)?;
         row += 1;
-        let table_header = format_node_header(&col_widths, pm2_available);
-        render_line_at(stdout, main_x, row, &table_header, width_usize)?;
+        render_line_at(stdout, main_x, row, &format_header(&columns, state.sorted_column(ColumnView::Node)), width_usize)?;
         row += 1;
         let sep = format_separator(&col_widths);
         render_line_at(stdout, main_x, row, &sep, width_usize)?;
@@ -119,7 +125,7 @@ pub fn render_node_processes(
                 stdout,
                 main_x,
                 list_start as u16,
-                "No Node.js processes found.",
+                "No app processes found.",
                 width_usize,
             )?;
             clear_list_area_at(
@@ -154,10 +160,9 @@ pub fn render_node_processes(
                             stdout,
                             main_x,
                             y as u16,
-                            &col_widths,
+                            &columns,
                             name,
                             *count,
-                            pm2_available,
                         )?;
                     }
                     NodeRow::Item { index } => {
@@ -174,15 +179,21 @@ pub fn render_node_processes(
                         };
                         let prefix = if is_last_in_group { "└─ " } else { "├─ " };
                         let name = format!("{}{}", prefix, node_display_name(proc));
+                        let pending = match (&proc.pm2, &proc.declared) {
+                            (Some(pm2), _) => state.is_pm2_pending(pm2.pm_id),
+                            (None, Some(declared)) => state.is_pm2_start_pending(&declared.name),
+                            (None, None) => false,
+                        };
+                        let spinner = pending.then(|| state.spinner_char());
                         render_node_line(
                             stdout,
                             main_x,
                             y as u16,
                             width_usize,
                             proc,
-                            &col_widths,
-                            pm2_available,
+                            &columns,
                             &name,
+                            spinner,
                             is_selected,
                             is_hovered,
                         )?;
@@ -198,8 +209,7 @@ pub fn render_node_processes(
                         render_line_at(stdout, main_x, y as u16, &top_border, width_usize)?;
                     }
                     NodeRow::UtilsHeader => {
-                        let table_header = format_node_header(&col_widths, pm2_available);
-                        render_line_at(stdout, main_x, y as u16, &table_header, width_usize)?;
+                        render_line_at(stdout, main_x, y as u16, &format_header(&columns, state.sorted_column(ColumnView::Node)), width_usize)?;
                     }
                     NodeRow::UtilsSeparator => {
                         let sep = format_separator(&col_widths);
@@ -246,6 +256,9 @@ pub fn render_node_processes(
                 HelpSegment::plain(" search | "),
                 clear_key,
                 clear_label,
+                HelpSegment::plain(" | "),
+                HelpSegment::key("w"),
+                HelpSegment::plain(" wrong node version"),
             ],
             vec![
                 HelpSegment::plain("Actions: "),
@@ -255,8 +268,26 @@ pub fn render_node_processes(
                 HelpSegment::plain(" docker | "),
                 HelpSegment::key("e"),
                 HelpSegment::plain(" env | "),
+                HelpSegment::key("enter"),
+                HelpSegment::plain(" pm2/start | "),
+                HelpSegment::key("i"),
+                HelpSegment::plain(" detail | "),
+                HelpSegment::key("I"),
+                HelpSegment::plain(" inspect | "),
+                HelpSegment::key("o"),
+                HelpSegment::plain(" open url | "),
+                HelpSegment::key("s"),
+                HelpSegment::plain(" scripts | "),
+                HelpSegment::key("l"),
+                HelpSegment::plain(" logs | "),
+                HelpSegment::key("+/-"),
+                HelpSegment::plain(" scale | "),
                 HelpSegment::key("k"),
                 HelpSegment::plain(" kill | "),
+                HelpSegment::key("</>"),
+                HelpSegment::plain(" sort | "),
+                HelpSegment::key("O"),
+                HelpSegment::plain(" columns | "),
                 HelpSegment::key("q"),
                 HelpSegment::plain(" quit | "),
                 HelpSegment::key("arrows"),
@@ -274,259 +305,64 @@ pub fn render_node_processes(
         set_dim_mode(false);
     }
 
-    stdout.flush()?;
-    Ok(())
-}
-
-fn node_column_widths(width: usize, pm2_available: bool) -> Vec<usize> {
-    if pm2_available {
-        // PM2 mode: ID | Name | Status | PID | CPU | Memory | Restarts | Uptime | Project | Script
-        let id_width = 3usize;
-        let status_width = 8usize;
-        let pid_width = 7usize;
-        let cpu_width = 6usize;
-        let mem_width = 9usize;
-        let restarts_width = 4usize;
-        let uptime_width = 8usize;
-        let separators = 11usize;
-        let fixed_width = id_width + status_width + pid_width + cpu_width + mem_width + restarts_width + uptime_width;
-        let remaining = width.saturating_sub(separators + fixed_width);
-
-        let min_name = 8usize;
-        let min_project = 8usize;
-        let min_script = 10usize;
-        let mut name_width = (remaining * 2 / 7).max(min_name);
-        let mut project_width = (remaining * 2 / 7).max(min_project);
-        let mut script_width = remaining.saturating_sub(name_width + project_width).max(min_script);
-        if name_width + project_width + script_width > remaining {
-            script_width = remaining.saturating_sub(name_width + project_width);
-        }
-        if script_width < min_script {
-            script_width = min_script;
-            let leftover = remaining.saturating_sub(script_width);
-            name_width = (leftover * 2 / 4).max(min_name);
-            project_width = leftover.saturating_sub(name_width).max(min_project);
-        }
-
-        vec![
-            id_width,
-            name_width,
-            status_width,
-            pid_width,
-            cpu_width,
-            mem_width,
-            restarts_width,
-            uptime_width,
-            project_width,
-            script_width,
-        ]
-    } else {
-        // Non-PM2 mode: PID | Name | CPU | Memory | Version | Uptime | Project | Script
-        let pid_width = 7usize;
-        let cpu_width = 6usize;
-        let mem_width = 9usize;
-        let version_width = 10usize;
-        let uptime_width = 8usize;
-        let separators = 9usize;
-        let fixed_width = pid_width + cpu_width + mem_width + version_width + uptime_width;
-        let remaining = width.saturating_sub(separators + fixed_width);
-
-        let min_name = 8usize;
-        let min_project = 8usize;
-        let min_script = 10usize;
-        let mut name_width = (remaining * 2 / 7).max(min_name);
-        let mut project_width = (remaining * 2 / 7).max(min_project);
-        let mut script_width = remaining.saturating_sub(name_width + project_width).max(min_script);
-        if name_width + project_width + script_width > remaining {
-            script_width = remaining.saturating_sub(name_width + project_width);
-        }
-        if script_width < min_script {
-            script_width = min_script;
-            let leftover = remaining.saturating_sub(script_width);
-            name_width = (leftover * 2 / 4).max(min_name);
-            project_width = leftover.saturating_sub(name_width).max(min_project);
-        }
-
-        vec![
-            pid_width,
-            name_width,
-            cpu_width,
-            mem_width,
-            version_width,
-            uptime_width,
-            project_width,
-            script_width,
-        ]
+    if let Some(ref menu) = state.context_menu {
+        super::menu::render_context_menu(stdout, menu)?;
     }
-}
 
-fn format_node_header(widths: &[usize], pm2_available: bool) -> String {
-    if pm2_available {
-        let cells = vec![
-            fit_right("ID", widths[0]),
-            fit_left("NAME", widths[1]),
-            fit_left("STATUS", widths[2]),
-            fit_right("PID", widths[3]),
-            fit_right("CPU%", widths[4]),
-            fit_right("MEMORY", widths[5]),
-            fit_right("RST", widths[6]),
-            fit_left("UPTIME", widths[7]),
-            fit_left("PROJECT", widths[8]),
-            fit_left("SCRIPT", widths[9]),
-        ];
-        format!(
-            "│{}│{}│{}│{}│{}│{}│{}│{}│{}│{}│",
-            cells[0],
-            cells[1],
-            cells[2],
-            cells[3],
-            cells[4],
-            cells[5],
-            cells[6],
-            cells[7],
-            cells[8],
-            cells[9]
-        )
-    } else {
-        let cells = vec![
-            fit_right("PID", widths[0]),
-            fit_left("NAME", widths[1]),
-            fit_right("CPU%", widths[2]),
-            fit_right("MEMORY", widths[3]),
-            fit_left("VERSION", widths[4]),
-            fit_left("UPTIME", widths[5]),
-            fit_left("PROJECT", widths[6]),
-            fit_left("SCRIPT", widths[7]),
-        ];
-        format!(
-            "│{}│{}│{}│{}│{}│{}│{}│{}│",
-            cells[0], cells[1], cells[2], cells[3], cells[4], cells[5], cells[6], cells[7]
-        )
+    if let Some(ref chooser) = state.column_chooser {
+        super::columns::render_column_chooser(stdout, state, chooser, main_x, width_usize, height_usize)?;
     }
+
+    stdout.flush()?;
+    Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 fn render_node_line(
     stdout: &mut io::Stdout,
     x: u16,
     y: u16,
     width: usize,
     proc: &NodeProcessInfo,
-    widths: &[usize],
-    pm2_available: bool,
+    columns: &[Column],
     display_name: &str,
+    spinner: Option<char>,
     selected: bool,
     hovered: bool,
 ) -> io::Result<()> {
     queue!(stdout, MoveTo(x, y))?;
 
-    let project_display = proc.project_name.as_deref().unwrap_or("-");
-
-    if pm2_available {
-        // PM2 mode
-        let pm2 = proc.pm2.as_ref();
-        let id_cell = pm2.map_or("-".to_string(), |p| p.pm_id.to_string());
-        let status = pm2.map_or("-", |p| p.status.as_str());
-        let restarts = pm2.map_or(0, |p| p.restarts);
-        let pid_str = if proc.pid.as_u32() == 0 {
-            "-".to_string()
-        } else {
-            proc.pid.to_string()
-        };
-
-        let id_cell = fit_right(&id_cell, widths[0]);
-        let name_cell = fit_left(display_name, widths[1]);
-        let status_cell = fit_left(status, widths[2]);
-        let pid_cell = fit_right(&pid_str, widths[3]);
-        let cpu_cell = fit_right(&format!("{:.1}", proc.cpu), widths[4]);
-        let mem_cell = fit_right(&format_memory(proc.memory_bytes), widths[5]);
-        let restarts_cell = fit_right(&restarts.to_string(), widths[6]);
-        let uptime_secs = pm2
-            .and_then(|info| info.pm2_uptime)
-            .or(proc.uptime_secs);
-        let uptime_cell = fit_left(&format_uptime(uptime_secs), widths[7]);
-        let project_cell = fit_left(project_display, widths[8]);
-        let script_cell = fit_left(&proc.script, widths[9]);
-
-        let line = format!(
-            "│{}│{}│{}│{}│{}│{}│{}│{}│{}│{}│",
-            id_cell,
-            name_cell,
-            status_cell,
-            pid_cell,
-            cpu_cell,
-            mem_cell,
-            restarts_cell,
-            uptime_cell,
-            project_cell,
-            script_cell,
-        );
-
-        if selected {
-            queue!(
-                stdout,
-                SetAttribute(Attribute::Reverse),
-                Print(fit_left(&line, width)),
-                SetAttribute(Attribute::Reset)
-            )?;
-            return Ok(());
-        }
-
-        if hovered {
-            queue!(
-                stdout,
-                SetBackgroundColor(Color::DarkGrey),
-                Print(fit_left(&line, width)),
-                ResetColor
-            )?;
-            return Ok(());
-        }
-
-        print_table_bar(stdout)?;
-        print_dim_cell(stdout, &id_cell)?;
-        print_table_bar(stdout)?;
-        print_dim_cell(stdout, &name_cell)?;
-        print_table_bar(stdout)?;
-        render_status_cell(stdout, status, widths[2])?;
-        print_table_bar(stdout)?;
-        print_dim_cell(stdout, &pid_cell)?;
-        print_table_bar(stdout)?;
-        print_dim_cell(stdout, &cpu_cell)?;
-        print_table_bar(stdout)?;
-        print_dim_cell(stdout, &mem_cell)?;
-        print_table_bar(stdout)?;
-        print_dim_cell(stdout, &restarts_cell)?;
-        print_table_bar(stdout)?;
-        print_dim_cell(stdout, &uptime_cell)?;
-        print_table_bar(stdout)?;
-        print_dim_cell(stdout, &project_cell)?;
-        print_table_bar(stdout)?;
-        print_dim_cell(stdout, &script_cell)?;
-        print_table_bar(stdout)?;
-        return Ok(());
-    }
-
-    // Non-PM2 mode
-    let version = proc.node_version.as_deref().unwrap_or("-");
-    let pid_cell = fit_right(&proc.pid.to_string(), widths[0]);
-    let name_cell = fit_left(display_name, widths[1]);
-    let cpu_cell = fit_right(&format!("{:.1}", proc.cpu), widths[2]);
-    let mem_cell = fit_right(&format_memory(proc.memory_bytes), widths[3]);
-    let version_cell = fit_left(version, widths[4]);
-    let uptime_cell = fit_left(&format_uptime(proc.uptime_secs), widths[5]);
-    let project_cell = fit_left(project_display, widths[6]);
-    let script_cell = fit_left(&proc.script, widths[7]);
-
-    let line = format!(
-        "│{}│{}│{}│{}│{}│{}│{}│{}│",
-        pid_cell,
-        name_cell,
-        cpu_cell,
-        mem_cell,
-        version_cell,
-        uptime_cell,
-        project_cell,
-        script_cell,
-    );
+    let pm2 = proc.pm2.as_ref();
+    let status = match (pm2, &proc.declared) {
+        (Some(p), _) => p.status.as_str(),
+        (None, Some(_)) => "declared",
+        (None, None) => "-",
+    };
+    let status_text = match spinner {
+        Some(ch) => format!("{ch} {status}"),
+        None => status.to_string(),
+    };
+    let mismatch = proc.is_version_mismatch();
+
+    let cell = |column: &Column| match column.id {
+        "id" => pm2.map_or("-".to_string(), |p| p.pm_id.to_string()),
+        "pid" if proc.pid.as_u32() == 0 => "-".to_string(),
+        "pid" => proc.pid.to_string(),
+        "name" => display_name.to_string(),
+        "status" => status_text.clone(),
+        "cpu" => format!("{:.1}", proc.cpu),
+        "mem" => format_memory(proc.memory_bytes),
+        "restarts" => pm2.map_or(0, |p| p.restarts).to_string(),
+        "runtime" if mismatch => format!("!{}", proc.runtime_label()),
+        "runtime" => proc.runtime_label(),
+        "uptime" => format_uptime(pm2.and_then(|info| info.pm2_uptime).or(proc.uptime_secs)),
+        "framework" => proc.framework.map_or_else(|| "-".to_string(), |info| info.label()),
+        "url" => proc.url().unwrap_or_else(|| "-".to_string()),
+        "project" => proc.project_name.as_deref().unwrap_or("-").to_string(),
+        "script" => proc.script.clone(),
+        _ => String::new(),
+    };
+    let line = format_row(columns, cell);
 
     if selected {
         queue!(
@@ -541,33 +377,60 @@ fn render_node_line(
     if hovered {
         queue!(
             stdout,
-            SetBackgroundColor(Color::DarkGrey),
+            theme().hover,
             Print(fit_left(&line, width)),
             ResetColor
         )?;
         return Ok(());
     }
 
-    print_table_bar(stdout)?;
-    print_dim_cell(stdout, &pid_cell)?;
-    print_table_bar(stdout)?;
-    print_dim_cell(stdout, &name_cell)?;
-    print_table_bar(stdout)?;
-    print_dim_cell(stdout, &cpu_cell)?;
-    print_table_bar(stdout)?;
-    print_dim_cell(stdout, &mem_cell)?;
-    print_table_bar(stdout)?;
-    print_dim_cell(stdout, &version_cell)?;
-    print_table_bar(stdout)?;
-    print_dim_cell(stdout, &uptime_cell)?;
-    print_table_bar(stdout)?;
-    print_dim_cell(stdout, &project_cell)?;
-    print_table_bar(stdout)?;
-    print_dim_cell(stdout, &script_cell)?;
+    if proc.declared.is_some() {
+        return print_declared_line(stdout, &line, width);
+    }
+
+    // Without a runtime column, flag a wrong Node version on the name
+    let has_runtime = columns.iter().any(|column| column.id == "runtime");
+    for column in columns {
+        print_table_bar(stdout)?;
+        let text = fit_column(&cell(column), column);
+        let warn = match column.id {
+            "name" => mismatch && !has_runtime,
+            "runtime" => mismatch,
+            _ => false,
+        };
+        if warn && !is_dim_mode() {
+            queue!(stdout, SetForegroundColor(theme().warn), Print(&text), ResetColor)?;
+        } else if column.id == "status" {
+            render_status_cell(stdout, status, &status_text, column.width)?;
+        } else if column.id == "url" {
+            print_url_cell(stdout, &text)?;
+        } else {
+            print_dim_cell(stdout, &text)?;
+        }
+    }
     print_table_bar(stdout)?;
     Ok(())
 }
 
+fn print_url_cell(stdout: &mut io::Stdout, cell: &str) -> io::Result<()> {
+    if is_dim_mode() || cell.trim() == "-" {
+        return print_dim_cell(stdout, cell);
+    }
+    queue!(stdout, SetForegroundColor(theme().accent), Print(cell), ResetColor)?;
+    Ok(())
+}
+
+/// Greys out an app that is only declared in an ecosystem file.
+fn print_declared_line(stdout: &mut io::Stdout, line: &str, width: usize) -> io::Result<()> {
+    queue!(
+        stdout,
+        SetForegroundColor(theme().dim),
+        Print(fit_left(line, width)),
+        ResetColor
+    )?;
+    Ok(())
+}
+
 fn node_display_name(proc: &NodeProcessInfo) -> String {
     let base_name = proc
         .pm2
@@ -581,17 +444,21 @@ fn node_display_name(proc: &NodeProcessInfo) -> String {
     }
 }
 
-
-fn render_status_cell(stdout: &mut io::Stdout, status: &str, width: usize) -> io::Result<()> {
-    let cell = fit_left(status, width);
+fn render_status_cell(
+    stdout: &mut io::Stdout,
+    status: &str,
+    text: &str,
+    width: usize,
+) -> io::Result<()> {
+    let cell = fit_left(text, width);
     if is_dim_mode() {
         return print_dim_cell(stdout, &cell);
     }
 
     let color = match status {
-        "online" => Color::Green,
-        "stopping" | "launching" => Color::Yellow,
-        "stopped" | "errored" => Color::Red,
+        "online" => theme().ok,
+        "stopping" | "launching" => theme().warn,
+        "stopped" | "errored" => theme().error,
         _ => Color::Reset,
     };
 
@@ -612,75 +479,21 @@ fn render_node_group_row_at(
     stdout: &mut io::Stdout,
     x: u16,
     y: u16,
-    widths: &[usize],
+    columns: &[Column],
     name: &str,
     count: usize,
-    pm2_available: bool,
 ) -> io::Result<()> {
-    let count_label = format!("{count} procs");
-    let label = truncate_str(name, widths[1]);
-
     queue!(stdout, MoveTo(x, y))?;
-    if pm2_available {
-        let id_cell = fit_right("", widths[0]);
-        let status_cell = fit_left("", widths[2]);
-        let pid_cell = fit_right("", widths[3]);
-        let cpu_cell = fit_right("", widths[4]);
-        let mem_cell = fit_right("", widths[5]);
-        let restarts_cell = fit_right("", widths[6]);
-        let uptime_cell = fit_left("", widths[7]);
-        let project_cell = fit_left("-", widths[8]);
-        let script_cell = fit_left(&count_label, widths[9]);
-
-        print_table_bar(stdout)?;
-        print_dim_cell(stdout, &id_cell)?;
-        print_table_bar(stdout)?;
-        render_group_name_cell(stdout, &label, widths[1])?;
-        print_table_bar(stdout)?;
-        print_dim_cell(stdout, &status_cell)?;
-        print_table_bar(stdout)?;
-        print_dim_cell(stdout, &pid_cell)?;
-        print_table_bar(stdout)?;
-        print_dim_cell(stdout, &cpu_cell)?;
-        print_table_bar(stdout)?;
-        print_dim_cell(stdout, &mem_cell)?;
-        print_table_bar(stdout)?;
-        print_dim_cell(stdout, &restarts_cell)?;
-        print_table_bar(stdout)?;
-        print_dim_cell(stdout, &uptime_cell)?;
-        print_table_bar(stdout)?;
-        print_dim_cell(stdout, &project_cell)?;
-        print_table_bar(stdout)?;
-        print_dim_cell(stdout, &script_cell)?;
-        print_table_bar(stdout)?;
-    } else {
-        let pid_cell = fit_right("", widths[0]);
-        let cpu_cell = fit_right("", widths[2]);
-        let mem_cell = fit_right("", widths[3]);
-        let version_cell = fit_left("", widths[4]);
-        let uptime_cell = fit_left("", widths[5]);
-        let project_cell = fit_left("-", widths[6]);
-        let script_cell = fit_left(&count_label, widths[7]);
-
-        print_table_bar(stdout)?;
-        print_dim_cell(stdout, &pid_cell)?;
-        print_table_bar(stdout)?;
-        render_group_name_cell(stdout, &label, widths[1])?;
-        print_table_bar(stdout)?;
-        print_dim_cell(stdout, &cpu_cell)?;
-        print_table_bar(stdout)?;
-        print_dim_cell(stdout, &mem_cell)?;
-        print_table_bar(stdout)?;
-        print_dim_cell(stdout, &version_cell)?;
-        print_table_bar(stdout)?;
-        print_dim_cell(stdout, &uptime_cell)?;
-        print_table_bar(stdout)?;
-        print_dim_cell(stdout, &project_cell)?;
-        print_table_bar(stdout)?;
-        print_dim_cell(stdout, &script_cell)?;
+    for column in columns {
         print_table_bar(stdout)?;
+        match column.id {
+            "name" => render_group_name_cell(stdout, &truncate_str(name, column.width), column.width)?,
+            "project" => print_dim_cell(stdout, &fit_column("-", column))?,
+            "script" => print_dim_cell(stdout, &fit_column(&format!("{count} procs"), column))?,
+            _ => print_dim_cell(stdout, &fit_column("", column))?,
+        }
     }
-
+    print_table_bar(stdout)?;
     Ok(())
 }
 
@@ -690,14 +503,14 @@ fn render_group_name_cell(stdout: &mut io::Stdout, label: &str, width: usize) ->
     if is_dim_mode() {
         queue!(
             stdout,
-            SetForegroundColor(Color::DarkGrey),
+            SetForegroundColor(theme().dim),
             Print(&display),
             ResetColor
         )?;
     } else {
         queue!(
             stdout,
-            SetForegroundColor(Color::Yellow),
+            SetForegroundColor(theme().group),
             Print(&display),
             ResetColor
         )?;
@@ -713,7 +526,7 @@ fn print_dim_cell(stdout: &mut io::Stdout, text: &str) -> io::Result<()> {
     if is_dim_mode() {
         queue!(
             stdout,
-            SetForegroundColor(Color::DarkGrey),
+            SetForegroundColor(theme().dim),
             Print(text),
             ResetColor
         )?;
@@ -2,19 +2,21 @@ use std::io::{self, Write};
 
 use crossterm::cursor::MoveTo;
 use crossterm::queue;
-use crossterm::style::{Attribute, Color, Print, ResetColor, SetAttribute, SetBackgroundColor, SetForegroundColor};
+use crossterm::style::{Attribute, Print, ResetColor, SetAttribute, SetForegroundColor};
 use crossterm::terminal;
 
-use crate::app::{AppState, ContextMenu, InputMode};
+use crate::app::{AppState, Column, ColumnView, ContextMenu, InputMode};
 use crate::system::ports::{PortInfo, PortRow};
 
 use super::bars::{format_cpu_bar, format_memory_bar, format_swap_bar};
 use super::layout::{layout_for_screen, render_sidebar, render_sidebar_gap};
 use super::table::{
-    clear_list_area_at, fit_left, fit_right, format_separator, format_top_border,
-    is_dim_mode, print_table_bar, render_help_table_rows_colored_at, render_line_at,
-    render_search_box_at, render_title_at, set_dim_mode, truncate_str, HelpSegment,
+    clear_list_area_at, column_widths, fit_column, fit_left, format_header, format_row,
+    format_separator, format_top_border, is_dim_mode, print_table_bar,
+    render_help_table_rows_colored_at, render_line_at, render_search_box_at, render_title_at,
+    set_dim_mode, truncate_str, HelpSegment,
 };
+use super::theme::theme;
 
 pub fn render_ports(
     stdout: &mut io::Stdout,
@@ -53,7 +55,11 @@ pub fn render_ports(
     render_title_at(stdout, main_x, row, width_usize, "PORTS VIEW")?;
     row += 2;
 
-    let header = format!("Spark | View: PORTS | Mode: {}", mode_label);
+    let header = format!(
+        "Spark | View: PORTS | Sort: {} | Mode: {}",
+        state.ports_sort.label(),
+        mode_label
+    );
     render_line_at(stdout, main_x, row, &header, width_usize)?;
     row += 1;
     render_search_box_at(stdout, main_x, row, width_usize, &state.ports_filter)?;
@@ -83,12 +89,12 @@ pub fn render_ports(
     )?;
     row += 1;
 
-    let port_widths = ports_column_widths(width_usize);
+    let columns = state.resolved_columns(ColumnView::Ports, width_usize);
+    let port_widths = column_widths(&columns);
     let port_top = format_top_border(&port_widths);
     render_line_at(stdout, main_x, row, &port_top, width_usize)?;
     row += 1;
-    let port_header = format_ports_header(&port_widths);
-    render_line_at(stdout, main_x, row, &port_header, width_usize)?;
+    render_line_at(stdout, main_x, row, &format_header(&columns, state.sorted_column(ColumnView::Ports)), width_usize)?;
     row += 1;
     let port_sep = format_separator(&port_widths);
     render_line_at(stdout, main_x, row, &port_sep, width_usize)?;
@@ -132,7 +138,7 @@ pub fn render_ports(
                             stdout,
                             main_x,
                             y as u16,
-                            &port_widths,
+                            &columns,
                             width_usize,
                             name,
                             *count,
@@ -149,7 +155,7 @@ pub fn render_ports(
                         };
                         let prefix = if is_last_in_group { "└─ " } else { "├─ " };
                         let name = format!("{prefix}{}", port.name);
-                        let line = format_ports_line(port, &port_widths, &name);
+                        let line = format_ports_line(port, &columns, &name);
                         if selected {
                             queue!(
                                 stdout,
@@ -162,7 +168,7 @@ pub fn render_ports(
                             queue!(
                                 stdout,
                                 MoveTo(main_x, y as u16),
-                                SetBackgroundColor(Color::DarkGrey),
+                                theme().hover,
                                 Print(fit_left(&line, width_usize)),
                                 ResetColor
                             )?;
@@ -222,6 +228,10 @@ pub fn render_ports(
                 HelpSegment::plain(" env | "),
                 HelpSegment::key("k"),
                 HelpSegment::plain(" kill | "),
+                HelpSegment::key("</>"),
+                HelpSegment::plain(" sort | "),
+                HelpSegment::key("O"),
+                HelpSegment::plain(" columns | "),
                 HelpSegment::key("q"),
                 HelpSegment::plain(" quit | "),
                 HelpSegment::key("arrows"),
@@ -246,6 +256,10 @@ pub fn render_ports(
         render_context_menu(stdout, menu)?;
     }
 
+    if let Some(ref chooser) = state.column_chooser {
+        super::columns::render_column_chooser(stdout, state, chooser, main_x, width_usize, height_usize)?;
+    }
+
     stdout.flush()?;
     Ok(())
 }
@@ -267,8 +281,7 @@ fn render_context_menu(stdout: &mut io::Stdout, menu: &ContextMenu) -> io::Resul
     queue!(
         stdout,
         MoveTo(x, y),
-        SetBackgroundColor(Color::Black),
-        SetForegroundColor(Color::Grey),
+        theme().panel,
         Print(&top_border),
         ResetColor
     )?;
@@ -285,8 +298,7 @@ fn render_context_menu(stdout: &mut io::Stdout, menu: &ContextMenu) -> io::Resul
         if is_hovered {
             queue!(
                 stdout,
-                SetBackgroundColor(Color::DarkCyan),
-                SetForegroundColor(Color::White),
+                theme().selected,
                 Print("│"),
                 Print(&padded),
                 Print("│"),
@@ -295,8 +307,7 @@ fn render_context_menu(stdout: &mut io::Stdout, menu: &ContextMenu) -> io::Resul
         } else {
             queue!(
                 stdout,
-                SetBackgroundColor(Color::Black),
-                SetForegroundColor(Color::Grey),
+                theme().panel,
                 Print("│"),
                 Print(&padded),
                 Print("│"),
@@ -309,8 +320,7 @@ fn render_context_menu(stdout: &mut io::Stdout, menu: &ContextMenu) -> io::Resul
     queue!(
         stdout,
         MoveTo(x, y + menu_height - 1),
-        SetBackgroundColor(Color::Black),
-        SetForegroundColor(Color::Grey),
+        theme().panel,
         Print(&bottom_border),
         ResetColor
     )?;
@@ -318,123 +328,42 @@ fn render_context_menu(stdout: &mut io::Stdout, menu: &ContextMenu) -> io::Resul
     Ok(())
 }
 
-fn ports_column_widths(width: usize) -> Vec<usize> {
-    let proto_width = 5usize;
-    let port_width = 6usize;
-    let pid_width = 7usize;
-    let separators = 7usize;
-    let content_width = width.saturating_sub(separators);
-    let remaining = content_width.saturating_sub(proto_width + port_width + pid_width);
-    let min_name = 10usize;
-    let min_project = 8usize;
-    let min_path = 10usize;
-
-    let (name_width, project_width, path_width) = if remaining >= min_name + min_project + min_path
-    {
-        let mut name_width = remaining * 2 / 5;
-        let mut project_width = remaining / 5;
-        let mut path_width = remaining - name_width - project_width;
-        if name_width < min_name {
-            name_width = min_name;
-            path_width = remaining - name_width - project_width;
-        }
-        if project_width < min_project {
-            project_width = min_project;
-            path_width = remaining - name_width - project_width;
-        }
-        if path_width < min_path {
-            path_width = min_path;
-            let leftover = remaining.saturating_sub(path_width);
-            name_width = (leftover * 2 / 3).max(min_name);
-            project_width = leftover.saturating_sub(name_width).max(min_project);
-        }
-        (name_width, project_width, path_width)
-    } else if remaining >= min_name + min_path {
-        (remaining - min_path, 0, min_path)
-    } else {
-        (remaining, 0, 0)
-    };
-
-    vec![
-        proto_width,
-        port_width,
-        pid_width,
-        name_width,
-        project_width,
-        path_width,
-    ]
-}
-
-fn format_ports_header(widths: &[usize]) -> String {
-    let cells = vec![
-        fit_left("PROTO", widths[0]),
-        fit_right("PORT", widths[1]),
-        fit_right("PID", widths[2]),
-        fit_left("NAME", widths[3]),
-        fit_left("PROJECT", widths[4]),
-        fit_left("PATH", widths[5]),
-    ];
-    format!(
-        "│{}│{}│{}│{}│{}│{}│",
-        cells[0], cells[1], cells[2], cells[3], cells[4], cells[5]
-    )
-}
-
-fn format_ports_line(port: &PortInfo, widths: &[usize], name: &str) -> String {
-    let proto_cell = fit_left(&port.proto, widths[0]);
-    let port_cell = fit_right(&port.port.to_string(), widths[1]);
-    let pid_cell = if port.pid == sysinfo::Pid::from_u32(0) {
-        fit_right("-", widths[2])
-    } else {
-        fit_right(&port.pid.to_string(), widths[2])
-    };
-    let name_cell = fit_left(name, widths[3]);
-    let project_cell = fit_left(port.project_name.as_deref().unwrap_or("-"), widths[4]);
-    let path_cell = fit_left(&port.exe_path, widths[5]);
-
-    format!(
-        "│{}│{}│{}│{}│{}│{}│",
-        proto_cell, port_cell, pid_cell, name_cell, project_cell, path_cell
-    )
+fn format_ports_line(port: &PortInfo, columns: &[Column], name: &str) -> String {
+    format_row(columns, |column| match column.id {
+        "proto" => port.proto.clone(),
+        "port" => port.port.to_string(),
+        "pid" if port.pid == sysinfo::Pid::from_u32(0) => "-".to_string(),
+        "pid" => port.pid.to_string(),
+        "name" => name.to_string(),
+        "project" => port.project_name.as_deref().unwrap_or("-").to_string(),
+        "path" => port.exe_path.clone(),
+        _ => String::new(),
+    })
 }
 
-fn format_ports_group_line(name: &str, count: usize, widths: &[usize]) -> String {
-    let count_label = format!("{count} ports");
-    let proto_cell = fit_left("", widths[0]);
-    let port_cell = fit_right("", widths[1]);
-    let pid_cell = fit_right("", widths[2]);
-    let name_cell = fit_left(name, widths[3]);
-    let project_cell = fit_left("-", widths[4]);
-    let path_cell = fit_left(&count_label, widths[5]);
-
-    format!(
-        "│{}│{}│{}│{}│{}│{}│",
-        proto_cell, port_cell, pid_cell, name_cell, project_cell, path_cell
-    )
+fn ports_group_cell(column: &Column, name: &str, count: usize) -> String {
+    match column.id {
+        "name" => name.to_string(),
+        "project" => "-".to_string(),
+        "path" => format!("{count} ports"),
+        _ => String::new(),
+    }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn render_port_group_row_at(
     stdout: &mut io::Stdout,
     x: u16,
     y: u16,
-    widths: &[usize],
+    columns: &[Column],
     table_width: usize,
     name: &str,
     count: usize,
     selected: bool,
     hovered: bool,
 ) -> io::Result<()> {
-    let label = name;
-    let count_label = format!("{count} ports");
-
-    let proto_cell = fit_left("", widths[0]);
-    let port_cell = fit_right("", widths[1]);
-    let pid_cell = fit_right("", widths[2]);
-    let project_cell = fit_left("-", widths[4]);
-    let path_cell = fit_left(&count_label, widths[5]);
-
     if selected && !is_dim_mode() {
-        let line = format_ports_group_line(label, count, widths);
+        let line = format_row(columns, |column| ports_group_cell(column, name, count));
         queue!(
             stdout,
             MoveTo(x, y),
@@ -446,11 +375,11 @@ fn render_port_group_row_at(
     }
 
     if hovered && !is_dim_mode() {
-        let line = format_ports_group_line(label, count, widths);
+        let line = format_row(columns, |column| ports_group_cell(column, name, count));
         queue!(
             stdout,
             MoveTo(x, y),
-            SetBackgroundColor(Color::DarkGrey),
+            theme().hover,
             Print(fit_left(&line, table_width)),
             ResetColor
         )?;
@@ -458,18 +387,14 @@ fn render_port_group_row_at(
     }
 
     queue!(stdout, MoveTo(x, y))?;
-    print_table_bar(stdout)?;
-    print_dim_cell(stdout, &proto_cell)?;
-    print_table_bar(stdout)?;
-    print_dim_cell(stdout, &port_cell)?;
-    print_table_bar(stdout)?;
-    print_dim_cell(stdout, &pid_cell)?;
-    print_table_bar(stdout)?;
-    render_group_name_cell(stdout, label, widths[3])?;
-    print_table_bar(stdout)?;
-    print_dim_cell(stdout, &project_cell)?;
-    print_table_bar(stdout)?;
-    print_dim_cell(stdout, &path_cell)?;
+    for column in columns {
+        print_table_bar(stdout)?;
+        if column.id == "name" {
+            render_group_name_cell(stdout, name, column.width)?;
+        } else {
+            print_dim_cell(stdout, &fit_column(&ports_group_cell(column, name, count), column))?;
+        }
+    }
     print_table_bar(stdout)?;
     Ok(())
 }
@@ -480,14 +405,14 @@ fn render_group_name_cell(stdout: &mut io::Stdout, label: &str, width: usize) ->
     if is_dim_mode() {
         queue!(
             stdout,
-            SetForegroundColor(Color::DarkGrey),
+            SetForegroundColor(theme().dim),
             Print(&display),
             ResetColor
         )?;
     } else {
         queue!(
             stdout,
-            SetForegroundColor(Color::Yellow),
+            SetForegroundColor(theme().group),
             Print(&display),
             ResetColor
         )?;
@@ -504,7 +429,7 @@ fn print_dim_cell(stdout: &mut io::Stdout, text: &str) -> io::Result<()> {
     if is_dim_mode() {
         queue!(
             stdout,
-            SetForegroundColor(Color::DarkGrey),
+            SetForegroundColor(theme().dim),
             Print(text),
             ResetColor
         )?;
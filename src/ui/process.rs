@@ -2,19 +2,21 @@ use std::io::{self, Write};
 
 use crossterm::cursor::MoveTo;
 use crossterm::queue;
-use crossterm::style::{Attribute, Color, Print, ResetColor, SetAttribute, SetBackgroundColor, SetForegroundColor};
+use crossterm::style::{Attribute, Print, ResetColor, SetAttribute, SetForegroundColor};
 use crossterm::terminal;
 
-use crate::app::{AppState, ContextMenu, InputMode, SortBy, SortOrder};
-use crate::system::process::{ProcInfo, TreeRow};
+use crate::app::{AppState, Column, ColumnView, ContextMenu, InputMode};
+use crate::system::process::{Fold, ProcInfo, TreeRow};
+use crate::util::{format_bytes, format_start_time};
 
 use super::bars::{format_cpu_bar, format_memory_bar, format_swap_bar};
 use super::layout::{layout_for_screen, render_sidebar, render_sidebar_gap};
 use super::table::{
-    clear_list_area_at, fit_left, fit_right, format_separator, format_top_border,
-    render_help_table_rows_colored_at, render_line_at, render_search_box_at, render_title_at,
-    set_dim_mode, HelpSegment,
+    clear_list_area_at, column_widths, fit_left, format_header, format_row, format_separator,
+    format_top_border, render_help_table_rows_colored_at, render_line_at, render_search_box_at,
+    render_title_at, set_dim_mode, HelpSegment,
 };
+use super::theme::theme;
 
 pub fn render_processes(
     stdout: &mut io::Stdout,
@@ -37,15 +39,6 @@ pub fn render_processes(
 
     queue!(stdout, MoveTo(main_x, 0))?;
 
-    let sort_label = match state.sort_by {
-        SortBy::Cpu => "CPU",
-        SortBy::Memory => "MEM",
-        SortBy::Name => "NAME",
-    };
-    let order_label = match state.sort_order {
-        SortOrder::Asc => "asc",
-        SortOrder::Desc => "desc",
-    };
     let mode_label = match state.input_mode {
         InputMode::Normal => "NORMAL",
         InputMode::Filter => "FILTER",
@@ -64,10 +57,18 @@ pub fn render_processes(
     render_title_at(stdout, main_x, row, width_usize, "PROCESS VIEW")?;
     row += 2;
 
-    let header = format!(
-        "Spark | View: PROC | Sort: {} {} | Zoom: {} | Mode: {}",
-        sort_label, order_label, zoom_label, mode_label
+    let mut header = format!(
+        "Spark | View: PROC | Sort: {} | Zoom: {} | Mode: {}",
+        state.process_sort.label(),
+        zoom_label,
+        mode_label
     );
+    if let Some(scope) = &state.process_scope {
+        header.push_str(&format!(" | Scope: {}", scope.container_name));
+    }
+    if state.process_stuck_only {
+        header.push_str(" | Only: D/Z + parents");
+    }
     render_line_at(stdout, main_x, row, &header, width_usize)?;
     row += 1;
     render_search_box_at(stdout, main_x, row, width_usize, &state.process_filter)?;
@@ -76,7 +77,11 @@ pub fn render_processes(
         stdout,
         main_x,
         row,
-        &format_cpu_bar("CPU ", state.cpu_usage, width_usize),
+        &format!(
+            "{}{}",
+            format_cpu_bar("CPU ", state.cpu_usage, width_usize),
+            format_zombie_summary(state.zombie_count, state.zombie_parents)
+        ),
         width_usize,
     )?;
     row += 1;
@@ -96,18 +101,12 @@ pub fn render_processes(
         width_usize,
     )?;
     row += 1;
-    let mut max_user_len = 4usize;
-    for row in rows {
-        if let Some(proc_info) = processes.get(&row.pid) {
-            max_user_len = max_user_len.max(proc_info.user.chars().count());
-        }
-    }
-    let proc_widths = process_column_widths(width_usize, max_user_len);
+    let columns = state.resolved_columns(ColumnView::Process, width_usize);
+    let proc_widths = column_widths(&columns);
     let proc_top = format_top_border(&proc_widths);
     render_line_at(stdout, main_x, row, &proc_top, width_usize)?;
     row += 1;
-    let proc_header = format_process_header(&proc_widths);
-    render_line_at(stdout, main_x, row, &proc_header, width_usize)?;
+    render_line_at(stdout, main_x, row, &format_header(&columns, state.sorted_column(ColumnView::Process)), width_usize)?;
     row += 1;
     let proc_sep = format_separator(&proc_widths);
     render_line_at(stdout, main_x, row, &proc_sep, width_usize)?;
@@ -152,7 +151,7 @@ pub fn render_processes(
                 let Some(proc_info) = processes.get(&row.pid) else {
                     continue;
                 };
-                let line = format_process_line(proc_info, &proc_widths, &row.prefix);
+                let line = format_process_line(proc_info, &columns, row);
                 let is_selected = line_index == state.selected && !dim;
                 let is_hovered = state.hover_row == Some(line_index) && !is_selected && !dim;
                 if is_selected {
@@ -167,7 +166,15 @@ pub fn render_processes(
                     queue!(
                         stdout,
                         MoveTo(main_x, y as u16),
-                        SetBackgroundColor(Color::DarkGrey),
+                        theme().hover,
+                        Print(fit_left(&line, width_usize)),
+                        ResetColor
+                    )?;
+                } else if proc_info.is_zombie() && !dim {
+                    queue!(
+                        stdout,
+                        MoveTo(main_x, y as u16),
+                        SetForegroundColor(theme().dim),
                         Print(fit_left(&line, width_usize)),
                         ResetColor
                     )?;
@@ -204,6 +211,10 @@ pub fn render_processes(
         let clear_label = if state.input_mode == InputMode::Normal && !state.process_filter.is_empty()
         {
             clear_label_active
+        } else if state.process_scope.is_some() {
+            HelpSegment::plain(" clear scope")
+        } else if state.process_stuck_only {
+            HelpSegment::plain(" clear D/Z filter")
         } else {
             clear_label_default
         };
@@ -237,14 +248,44 @@ pub fn render_processes(
                     HelpSegment::plain("Actions: "),
                     HelpSegment::key("z"),
                     HelpSegment::plain(" zoom | "),
+                    HelpSegment::key("space"),
+                    HelpSegment::plain(" fold | "),
+                    HelpSegment::key("+/-"),
+                    HelpSegment::plain(" expand/collapse all | "),
                     HelpSegment::key("d"),
                     HelpSegment::plain(" docker | "),
                     HelpSegment::key("p"),
                     HelpSegment::plain(" ports | "),
                     HelpSegment::key("e"),
                     HelpSegment::plain(" env | "),
+                    HelpSegment::key("i"),
+                    HelpSegment::plain(" detail | "),
+                    HelpSegment::key("f"),
+                    HelpSegment::plain(" open files | "),
+                    HelpSegment::key("T"),
+                    HelpSegment::plain(" threads | "),
+                    HelpSegment::key("S"),
+                    HelpSegment::plain(" D/Z only | "),
+                    HelpSegment::key("Z"),
+                    HelpSegment::plain(" reap zombies | "),
+                    HelpSegment::key("F"),
+                    HelpSegment::plain(" who has file open | "),
+                    HelpSegment::key("s"),
+                    HelpSegment::plain(" signal | "),
                     HelpSegment::key("k"),
                     HelpSegment::plain(" kill | "),
+                    HelpSegment::key("K"),
+                    HelpSegment::plain(" kill tree/group | "),
+                    HelpSegment::key("R"),
+                    HelpSegment::plain(" priority | "),
+                    HelpSegment::key("P"),
+                    HelpSegment::plain(" priority columns | "),
+                    HelpSegment::key("N"),
+                    HelpSegment::plain(" network columns | "),
+                    HelpSegment::key("</>"),
+                    HelpSegment::plain(" sort | "),
+                    HelpSegment::key("O"),
+                    HelpSegment::plain(" columns | "),
                     HelpSegment::key("q"),
                     HelpSegment::plain(" quit | "),
                     HelpSegment::key("arrows"),
@@ -270,6 +311,14 @@ pub fn render_processes(
         render_context_menu(stdout, menu)?;
     }
 
+    if let Some(ref form) = state.form {
+        super::form::render_form(stdout, form, main_x, width_usize, height_usize)?;
+    }
+
+    if let Some(ref chooser) = state.column_chooser {
+        super::columns::render_column_chooser(stdout, state, chooser, main_x, width_usize, height_usize)?;
+    }
+
     stdout.flush()?;
     Ok(())
 }
@@ -291,8 +340,7 @@ fn render_context_menu(stdout: &mut io::Stdout, menu: &ContextMenu) -> io::Resul
     queue!(
         stdout,
         MoveTo(x, y),
-        SetBackgroundColor(Color::Black),
-        SetForegroundColor(Color::Grey),
+        theme().panel,
         Print(&top_border),
         ResetColor
     )?;
@@ -309,8 +357,7 @@ fn render_context_menu(stdout: &mut io::Stdout, menu: &ContextMenu) -> io::Resul
         if is_hovered {
             queue!(
                 stdout,
-                SetBackgroundColor(Color::DarkCyan),
-                SetForegroundColor(Color::White),
+                theme().selected,
                 Print("│"),
                 Print(&padded),
                 Print("│"),
@@ -319,8 +366,7 @@ fn render_context_menu(stdout: &mut io::Stdout, menu: &ContextMenu) -> io::Resul
         } else {
             queue!(
                 stdout,
-                SetBackgroundColor(Color::Black),
-                SetForegroundColor(Color::Grey),
+                theme().panel,
                 Print("│"),
                 Print(&padded),
                 Print("│"),
@@ -333,8 +379,7 @@ fn render_context_menu(stdout: &mut io::Stdout, menu: &ContextMenu) -> io::Resul
     queue!(
         stdout,
         MoveTo(x, y + menu_height - 1),
-        SetBackgroundColor(Color::Black),
-        SetForegroundColor(Color::Grey),
+        theme().panel,
         Print(&bottom_border),
         ResetColor
     )?;
@@ -342,107 +387,83 @@ fn render_context_menu(stdout: &mut io::Stdout, menu: &ContextMenu) -> io::Resul
     Ok(())
 }
 
-fn format_process_line(proc_info: &ProcInfo, widths: &[usize], prefix: &str) -> String {
-    let pid_str = proc_info.pid.to_string();
-    let cpu = proc_info.cpu;
-    let mem_gb = proc_info.memory_bytes as f64 / 1024.0 / 1024.0 / 1024.0;
-
-    let user_width = widths[3];
-    let name_width = widths[4];
-    let path_width = widths[5];
+fn format_process_line(proc_info: &ProcInfo, columns: &[Column], row: &TreeRow) -> String {
+    // Collapsed rows stand for their whole subtree
+    let (cpu, memory_bytes, read_rate, write_rate) = match row.subtree {
+        Some(totals) => (totals.cpu, totals.memory_bytes, totals.read_rate, totals.write_rate),
+        None => (proc_info.cpu, proc_info.memory_bytes, proc_info.read_rate, proc_info.write_rate),
+    };
+    let net = match row.subtree {
+        Some(totals) => proc_info.net.map(|_| totals.net),
+        None => proc_info.net,
+    };
+    let sched = proc_info.sched.as_ref();
+    let dash = || "-".to_string();
+
+    format_row(columns, |column| match column.id {
+        "pid" => proc_info.pid.to_string(),
+        "cpu" => format!("{:.1}", cpu),
+        "mem" => format!("{:.2}", memory_bytes as f64 / 1024.0 / 1024.0 / 1024.0),
+        "read" => format_rate(read_rate),
+        "write" => format_rate(write_rate),
+        "rx" => net.map_or_else(dash, |net| format_rate(net.rx)),
+        "tx" => net.map_or_else(dash, |net| format_rate(net.tx)),
+        "nice" => sched.map_or_else(dash, |sched| sched.nice.to_string()),
+        "ionice" => sched.and_then(|sched| sched.io).map_or_else(dash, |io| io.label()),
+        "cpus" => sched.map_or_else(dash, |sched| sched.cpus.clone()),
+        "started" => format_start_time(proc_info.start_time),
+        "user" => proc_info.user.clone(),
+        "name" => format_name_cell(proc_info, row, column.width),
+        "cmd" if proc_info.cmdline.is_empty() => dash(),
+        "cmd" => proc_info.cmdline.clone(),
+        "path" => proc_info.exe_path.clone(),
+        _ => String::new(),
+    })
+}
 
-    let mut name_text = proc_info.name.clone();
+/// Tree prefix, fold marker and name, with the hidden subtree size, D/Z state
+/// and container appended.
+fn format_name_cell(proc_info: &ProcInfo, row: &TreeRow, name_width: usize) -> String {
+    let mut name_text = match row.fold {
+        Fold::Leaf => proc_info.name.clone(),
+        Fold::Expanded => format!("▾ {}", proc_info.name),
+        Fold::Collapsed => format!("▸ {}", proc_info.name),
+    };
+    if let Some(totals) = row.subtree {
+        name_text.push_str(&format!(" (+{})", totals.hidden));
+    }
+    if let Some(state) = proc_info.stuck_state {
+        name_text.push_str(&format!(" [{state}]"));
+    }
     if let Some(container) = proc_info.container.as_deref() {
         name_text.push_str(" @");
         name_text.push_str(container);
     }
+    let prefix = row.prefix.as_str();
 
     let prefix_len = prefix.chars().count();
     let name_space = name_width.saturating_sub(prefix_len);
     let name_body = super::table::truncate_str(&name_text, name_space);
-    let name = if name_space == 0 {
+    if name_space == 0 {
         prefix.to_string()
     } else {
         format!("{prefix}{name_body}")
-    };
-
-    let pid_cell = fit_right(&pid_str, widths[0]);
-    let cpu_cell = fit_right(&format!("{:.1}", cpu), widths[1]);
-    let mem_cell = fit_right(&format!("{:.2}", mem_gb), widths[2]);
-    let user_cell = fit_left(&proc_info.user, user_width);
-    let name_cell = fit_left(&name, name_width);
-    let path_cell = fit_left(&proc_info.exe_path, path_width);
-
-    format!(
-        "│{}│{}│{}│{}│{}│{}│",
-        pid_cell, cpu_cell, mem_cell, user_cell, name_cell, path_cell
-    )
+    }
 }
 
-fn process_column_widths(width: usize, max_user_len: usize) -> Vec<usize> {
-    let pid_width = 7usize;
-    let cpu_width = 6usize;
-    let mem_width = 9usize;
-    let mut user_width = max_user_len.max(4);
-    let separators = 7usize;
-    let content_width = width.saturating_sub(separators);
-    let fixed = pid_width + cpu_width + mem_width;
-    let available = content_width.saturating_sub(fixed);
-    if available == 0 {
-        return vec![pid_width, cpu_width, mem_width, 0, 0, 0];
-    }
-    if user_width > available {
-        user_width = available;
+fn format_zombie_summary(zombies: usize, parents: usize) -> String {
+    match (zombies, parents) {
+        (0, _) => String::new(),
+        (zombies, 1) => format!(" | Zombies: {zombies} (1 parent)"),
+        (zombies, parents) => format!(" | Zombies: {zombies} ({parents} parents)"),
     }
-    let mut remaining = available.saturating_sub(user_width);
-    let min_name = 10usize;
-    let min_path = 10usize;
-    let min_user = 4usize;
-
-    if remaining < min_name + min_path {
-        let deficit = (min_name + min_path).saturating_sub(remaining);
-        let shrink = deficit.min(user_width.saturating_sub(min_user));
-        user_width = user_width.saturating_sub(shrink);
-        remaining = available.saturating_sub(user_width);
-    }
-
-    let (name_width, path_width) = if remaining >= min_name + min_path {
-        let mut name_width = remaining * 2 / 3;
-        let mut path_width = remaining - name_width;
-        if name_width < min_name {
-            name_width = min_name;
-            path_width = remaining - name_width;
-        }
-        if path_width < min_path {
-            path_width = min_path;
-            name_width = remaining - path_width;
-        }
-        (name_width, path_width)
-    } else {
-        (remaining, 0)
-    };
-
-    vec![
-        pid_width,
-        cpu_width,
-        mem_width,
-        user_width,
-        name_width,
-        path_width,
-    ]
 }
 
-fn format_process_header(widths: &[usize]) -> String {
-    let cells = vec![
-        fit_right("PID", widths[0]),
-        fit_right("CPU%", widths[1]),
-        fit_right("MEM(GB)", widths[2]),
-        fit_left("USER", widths[3]),
-        fit_left("NAME@CTR", widths[4]),
-        fit_left("PATH", widths[5]),
-    ];
-    format!(
-        "│{}│{}│{}│{}│{}│{}│",
-        cells[0], cells[1], cells[2], cells[3], cells[4], cells[5]
-    )
+/// Idle processes show "0" rather than "0B" to keep busy ones easy to spot.
+pub(super) fn format_rate(bytes_per_sec: u64) -> String {
+    if bytes_per_sec == 0 {
+        "0".to_string()
+    } else {
+        format_bytes(bytes_per_sec)
+    }
 }
@@ -2,11 +2,10 @@ use std::io;
 
 use crossterm::cursor::MoveTo;
 use crossterm::queue;
-use crossterm::style::{
-    Attribute, Color, Print, ResetColor, SetAttribute, SetBackgroundColor, SetForegroundColor,
-};
+use crossterm::style::{Attribute, Print, ResetColor, SetAttribute};
 
 use super::table::fit_left;
+use super::theme::theme;
 
 pub(crate) fn render_search_bar_at(
     stdout: &mut io::Stdout,
@@ -17,7 +16,7 @@ pub(crate) fn render_search_bar_at(
 ) -> io::Result<()> {
     let suffix = " | Enter/Esc exit";
     let label = if term.is_empty() {
-        " Search:".to_string()
+        " Search: (e.g. cpu>20 user:root !name:chrome /regex/ ~fuzzy)".to_string()
     } else {
         format!(" Search: {term}")
     };
@@ -26,8 +25,7 @@ pub(crate) fn render_search_bar_at(
     queue!(
         stdout,
         MoveTo(x, y),
-        SetBackgroundColor(Color::Yellow),
-        SetForegroundColor(Color::Black),
+        theme().highlight,
         SetAttribute(Attribute::Bold),
         Print(line),
         SetAttribute(Attribute::Reset),
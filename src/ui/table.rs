@@ -3,7 +3,11 @@ use std::sync::atomic::{AtomicBool, Ordering};
 
 use crossterm::cursor::MoveTo;
 use crossterm::queue;
-use crossterm::style::{Color, Print, ResetColor, SetBackgroundColor, SetForegroundColor};
+use crossterm::style::{Color, Print, ResetColor, SetForegroundColor};
+
+use crate::app::{Align, Column, SortOrder};
+
+use super::theme::{theme, Style};
 
 static DIM_MODE: AtomicBool = AtomicBool::new(false);
 
@@ -18,7 +22,7 @@ pub(crate) fn is_dim_mode() -> bool {
 pub(crate) struct HelpSegment {
     pub(crate) text: String,
     pub(crate) color: Option<Color>,
-    pub(crate) background: Option<Color>,
+    pub(crate) style: Option<Style>,
 }
 
 impl HelpSegment {
@@ -26,23 +30,23 @@ impl HelpSegment {
         Self {
             text: text.to_string(),
             color: None,
-            background: None,
+            style: None,
         }
     }
 
     pub(crate) fn key(text: &str) -> Self {
         Self {
             text: text.to_string(),
-            color: Some(Color::Cyan),
-            background: None,
+            color: Some(theme().accent),
+            style: None,
         }
     }
 
     pub(crate) fn highlight(text: &str) -> Self {
         Self {
             text: text.to_string(),
-            color: Some(Color::Black),
-            background: Some(Color::Yellow),
+            color: None,
+            style: Some(theme().highlight),
         }
     }
 }
@@ -148,7 +152,7 @@ pub(crate) fn render_search_box_at(
     if is_dim_mode() {
         queue!(
             stdout,
-            SetForegroundColor(Color::DarkGrey),
+            SetForegroundColor(theme().dim),
             Print(prefix_display),
             ResetColor
         )?;
@@ -159,7 +163,7 @@ pub(crate) fn render_search_box_at(
         if is_dim_mode() {
             queue!(
                 stdout,
-                SetForegroundColor(Color::DarkGrey),
+                SetForegroundColor(theme().dim),
                 Print(term_display),
                 ResetColor
             )?;
@@ -169,8 +173,7 @@ pub(crate) fn render_search_box_at(
     } else {
         queue!(
             stdout,
-            SetBackgroundColor(Color::Yellow),
-            SetForegroundColor(Color::Black),
+            theme().highlight,
             Print(term_display),
             ResetColor
         )?;
@@ -210,13 +213,13 @@ pub(crate) fn render_help_table_rows_colored_at(
             if dim {
                 queue!(
                     stdout,
-                    SetForegroundColor(Color::DarkGrey),
+                    SetForegroundColor(theme().dim),
                     Print(text),
                     ResetColor
                 )?;
             } else {
-                if let Some(bg) = segment.background {
-                    queue!(stdout, SetBackgroundColor(bg))?;
+                if let Some(style) = segment.style {
+                    queue!(stdout, style)?;
                 }
                 if let Some(color) = segment.color {
                     queue!(stdout, SetForegroundColor(color))?;
@@ -274,6 +277,35 @@ pub(crate) fn fit_right(text: &str, width: usize) -> String {
     }
 }
 
+/// Fits `text` to the column's width, aligned the way the column is.
+pub(crate) fn fit_column(text: &str, column: &Column) -> String {
+    match column.align {
+        Align::Left => fit_left(text, column.width),
+        Align::Right => fit_right(text, column.width),
+    }
+}
+
+pub(crate) fn column_widths(columns: &[Column]) -> Vec<usize> {
+    columns.iter().map(|column| column.width).collect()
+}
+
+/// A table line with one cell per column, e.g. "│1234│node│".
+pub(crate) fn format_row(columns: &[Column], mut cell: impl FnMut(&Column) -> String) -> String {
+    let cells: Vec<String> = columns
+        .iter()
+        .map(|column| fit_column(&cell(column), column))
+        .collect();
+    format!("│{}│", cells.join("│"))
+}
+
+/// Header row, with an arrow after the title of the sorted column.
+pub(crate) fn format_header(columns: &[Column], sorted: Option<(&str, SortOrder)>) -> String {
+    format_row(columns, |column| match sorted {
+        Some((id, order)) if id == column.id => format!("{}{}", column.title, order.arrow()),
+        _ => column.title.to_string(),
+    })
+}
+
 pub(crate) fn truncate_str(input: &str, max_len: usize) -> String {
     if max_len == 0 {
         return String::new();
@@ -333,10 +365,24 @@ pub(crate) fn format_top_border(widths: &[usize]) -> String {
     line
 }
 
+pub(crate) fn format_bottom_border(widths: &[usize]) -> String {
+    let mut line = String::new();
+    line.push('└');
+    for (idx, width) in widths.iter().enumerate() {
+        line.push_str(&"─".repeat(*width));
+        if idx + 1 == widths.len() {
+            line.push('┘');
+        } else {
+            line.push('┴');
+        }
+    }
+    line
+}
+
 pub(crate) fn print_table_bar(stdout: &mut io::Stdout) -> io::Result<()> {
     queue!(
         stdout,
-        SetForegroundColor(Color::DarkGrey),
+        SetForegroundColor(theme().border),
         Print("│"),
         ResetColor
     )?;
@@ -361,12 +407,12 @@ fn flush_table_segment(
     is_table: bool,
 ) -> io::Result<()> {
     if is_table {
-        queue!(stdout, SetForegroundColor(Color::DarkGrey), Print(text), ResetColor)?;
+        queue!(stdout, SetForegroundColor(theme().border), Print(text), ResetColor)?;
     } else {
         if is_dim_mode() {
             queue!(
                 stdout,
-                SetForegroundColor(Color::DarkGrey),
+                SetForegroundColor(theme().dim),
                 Print(text),
                 ResetColor
             )?;
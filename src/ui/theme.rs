@@ -0,0 +1,520 @@
+//! Colours of the UI. A theme is picked with `--theme` or the "theme" section
+//! of the config file, and is degraded to what the terminal can show: no
+//! colour with `NO_COLOR` or `TERM=dumb`, the 16 basic colours unless `TERM`
+//! or `COLORTERM` advertise more.
+
+use std::env;
+use std::fmt;
+use std::sync::OnceLock;
+
+use crossterm::style::{Attribute, Color, SetAttribute, SetBackgroundColor, SetForegroundColor};
+use crossterm::Command;
+use serde_json::{Map, Value};
+
+use crate::config;
+
+/// Foreground, background and reverse video, queued as one command.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct Style {
+    pub fg: Color,
+    pub bg: Color,
+    /// Swaps foreground and background; how monochrome themes stand out
+    pub reverse: bool,
+}
+
+impl Style {
+    const fn new(fg: Color, bg: Color) -> Self {
+        Self { fg, bg, reverse: false }
+    }
+
+    const fn reversed() -> Self {
+        Self {
+            fg: Color::Reset,
+            bg: Color::Reset,
+            reverse: true,
+        }
+    }
+}
+
+impl Command for Style {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        // Every part is written so a style fully replaces the one before it
+        let reverse = if self.reverse { Attribute::Reverse } else { Attribute::NoReverse };
+        SetAttribute(reverse).write_ansi(f)?;
+        SetForegroundColor(self.fg).write_ansi(f)?;
+        SetBackgroundColor(self.bg).write_ansi(f)
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> std::io::Result<()> {
+        SetForegroundColor(self.fg).execute_winapi()?;
+        SetBackgroundColor(self.bg).execute_winapi()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct Theme {
+    /// Table borders and bars
+    pub border: Color,
+    /// Secondary text, and the whole list while the search bar is open
+    pub dim: Color,
+    /// Help keys, the active view and URLs
+    pub accent: Color,
+    /// Group names
+    pub group: Color,
+    pub ok: Color,
+    pub warn: Color,
+    pub error: Color,
+    /// Published container ports
+    pub link: Color,
+    /// Row under the mouse
+    pub hover: Style,
+    /// Search bar, search terms and matches
+    pub highlight: Style,
+    /// Menus, forms and pickers drawn over the list
+    pub panel: Style,
+    /// Selected entry of a panel
+    pub selected: Style,
+    /// Text fields of a form
+    pub input: Style,
+}
+
+const BUILT_IN: [&str; 4] = ["dark", "light", "high-contrast", "mono"];
+
+impl Theme {
+    pub(crate) const fn dark() -> Self {
+        Self {
+            border: Color::DarkGrey,
+            dim: Color::DarkGrey,
+            accent: Color::Cyan,
+            group: Color::Yellow,
+            ok: Color::Green,
+            warn: Color::Yellow,
+            error: Color::Red,
+            link: Color::Blue,
+            hover: Style::new(Color::Reset, Color::DarkGrey),
+            highlight: Style::new(Color::Black, Color::Yellow),
+            panel: Style::new(Color::Grey, Color::Black),
+            selected: Style::new(Color::White, Color::DarkCyan),
+            input: Style::new(Color::White, Color::DarkGrey),
+        }
+    }
+
+    /// Darker colours that stay readable on a white background
+    pub(crate) const fn light() -> Self {
+        Self {
+            border: Color::DarkGrey,
+            dim: Color::DarkGrey,
+            accent: Color::DarkBlue,
+            group: Color::DarkMagenta,
+            ok: Color::DarkGreen,
+            warn: Color::DarkYellow,
+            error: Color::DarkRed,
+            link: Color::DarkBlue,
+            hover: Style::new(Color::Black, Color::Grey),
+            highlight: Style::new(Color::Black, Color::Yellow),
+            panel: Style::new(Color::Black, Color::White),
+            selected: Style::new(Color::White, Color::DarkBlue),
+            input: Style::new(Color::Black, Color::Grey),
+        }
+    }
+
+    /// Bright colours only, with nothing drawn in grey on grey
+    pub(crate) const fn high_contrast() -> Self {
+        Self {
+            border: Color::White,
+            dim: Color::Grey,
+            accent: Color::Cyan,
+            group: Color::Yellow,
+            ok: Color::Green,
+            warn: Color::Yellow,
+            error: Color::Red,
+            link: Color::Cyan,
+            hover: Style::new(Color::Black, Color::White),
+            highlight: Style::new(Color::Black, Color::Yellow),
+            panel: Style::new(Color::White, Color::Black),
+            selected: Style::new(Color::Black, Color::Cyan),
+            input: Style::new(Color::Black, Color::White),
+        }
+    }
+
+    /// No colour at all; highlights use reverse video
+    pub(crate) const fn mono() -> Self {
+        let plain = Style::new(Color::Reset, Color::Reset);
+        Self {
+            border: Color::Reset,
+            dim: Color::Reset,
+            accent: Color::Reset,
+            group: Color::Reset,
+            ok: Color::Reset,
+            warn: Color::Reset,
+            error: Color::Reset,
+            link: Color::Reset,
+            hover: plain,
+            highlight: Style::reversed(),
+            panel: plain,
+            selected: Style::reversed(),
+            input: plain,
+        }
+    }
+
+    fn built_in(name: &str) -> Option<Self> {
+        match name {
+            "dark" => Some(Self::dark()),
+            "light" => Some(Self::light()),
+            "high-contrast" => Some(Self::high_contrast()),
+            "mono" => Some(Self::mono()),
+            _ => None,
+        }
+    }
+
+    /// Theme `name`, either built in or defined under "themes" in `section`.
+    fn named(name: &str, section: Option<&Value>) -> Result<Self, String> {
+        let custom = section
+            .and_then(|section| section.get("themes"))
+            .and_then(|themes| themes.get(name));
+        match (custom, Self::built_in(name)) {
+            (Some(custom), _) => Self::from_json(name, custom),
+            (None, Some(theme)) => Ok(theme),
+            (None, None) => Err(format!(
+                "unknown theme '{name}' (built in: {})",
+                BUILT_IN.join(", ")
+            )),
+        }
+    }
+
+    /// A user theme: a built-in "base" with some colours replaced.
+    fn from_json(name: &str, value: &Value) -> Result<Self, String> {
+        let fields = value
+            .as_object()
+            .ok_or_else(|| format!("theme '{name}' must be an object"))?;
+        let base = match fields.get("base") {
+            Some(Value::String(base)) => Self::built_in(base)
+                .ok_or_else(|| format!("theme '{name}': unknown base theme '{base}'"))?,
+            Some(_) => return Err(format!("theme '{name}': base must be a theme name")),
+            None => Self::dark(),
+        };
+
+        let mut theme = base;
+        for (key, value) in fields {
+            let color = |slot: &mut Color| -> Result<(), String> {
+                *slot = parse_color(value).map_err(|err| format!("theme '{name}', {key}: {err}"))?;
+                Ok(())
+            };
+            match key.as_str() {
+                "base" => {}
+                "border" => color(&mut theme.border)?,
+                "dim" => color(&mut theme.dim)?,
+                "accent" => color(&mut theme.accent)?,
+                "group" => color(&mut theme.group)?,
+                "ok" => color(&mut theme.ok)?,
+                "warn" => color(&mut theme.warn)?,
+                "error" => color(&mut theme.error)?,
+                "link" => color(&mut theme.link)?,
+                "hover" | "highlight" | "panel" | "selected" | "input" => {
+                    let slot = match key.as_str() {
+                        "hover" => &mut theme.hover,
+                        "highlight" => &mut theme.highlight,
+                        "panel" => &mut theme.panel,
+                        "selected" => &mut theme.selected,
+                        _ => &mut theme.input,
+                    };
+                    *slot = parse_style(value, *slot)
+                        .map_err(|err| format!("theme '{name}', {key}: {err}"))?;
+                }
+                _ => return Err(format!("theme '{name}': unknown key '{key}'")),
+            }
+        }
+        Ok(theme)
+    }
+
+    /// The theme with every colour reduced to what `support` can show.
+    fn degrade(self, support: ColorSupport) -> Self {
+        if support == ColorSupport::None {
+            return Self::mono();
+        }
+        let color = |color| degrade_color(color, support);
+        let style = |style: Style| Style {
+            fg: color(style.fg),
+            bg: color(style.bg),
+            reverse: style.reverse,
+        };
+        Self {
+            border: color(self.border),
+            dim: color(self.dim),
+            accent: color(self.accent),
+            group: color(self.group),
+            ok: color(self.ok),
+            warn: color(self.warn),
+            error: color(self.error),
+            link: color(self.link),
+            hover: style(self.hover),
+            highlight: style(self.highlight),
+            panel: style(self.panel),
+            selected: style(self.selected),
+            input: style(self.input),
+        }
+    }
+}
+
+static THEME: OnceLock<Theme> = OnceLock::new();
+
+pub(crate) fn theme() -> &'static Theme {
+    THEME.get_or_init(Theme::dark)
+}
+
+/// Picks the theme named on the command line, or else in the config file,
+/// and fits it to the terminal. Must run before the first frame is drawn.
+pub fn init_theme(name: Option<&str>) -> Result<(), String> {
+    let section = config::read_section("theme");
+    let configured = match &section {
+        Some(Value::String(name)) => Some(name.as_str()),
+        Some(Value::Object(fields)) => configured_name(fields)?,
+        Some(_) => return Err("config: theme must be a name or an object".to_string()),
+        None => None,
+    };
+    let theme = Theme::named(name.or(configured).unwrap_or("dark"), section.as_ref())?;
+    let _ = THEME.set(theme.degrade(ColorSupport::detect()));
+    Ok(())
+}
+
+fn configured_name(fields: &Map<String, Value>) -> Result<Option<&str>, String> {
+    match fields.get("name") {
+        Some(Value::String(name)) => Ok(Some(name)),
+        Some(_) => Err("config: theme name must be a string".to_string()),
+        None => Ok(None),
+    }
+}
+
+/// Parses "#rrggbb", a colour name such as "dark_grey", or a 256-colour index.
+fn parse_color(value: &Value) -> Result<Color, String> {
+    match value {
+        Value::Number(number) => number
+            .as_u64()
+            .and_then(|index| u8::try_from(index).ok())
+            .map(Color::AnsiValue)
+            .ok_or_else(|| format!("colour index {number} is not 0-255")),
+        Value::String(text) => {
+            if let Some(hex) = text.strip_prefix('#') {
+                let channel = |range: std::ops::Range<usize>| {
+                    hex.get(range).and_then(|digits| u8::from_str_radix(digits, 16).ok())
+                };
+                return match (hex.len(), channel(0..2), channel(2..4), channel(4..6)) {
+                    (6, Some(r), Some(g), Some(b)) => Ok(Color::Rgb { r, g, b }),
+                    _ => Err(format!("'{text}' is not a #rrggbb colour")),
+                };
+            }
+            let name = text.to_lowercase().replace('-', "_").replace("gray", "grey");
+            let name = name.replace("darkgrey", "dark_grey");
+            match name.as_str() {
+                "default" | "reset" => Ok(Color::Reset),
+                _ => Color::try_from(name.as_str()).map_err(|()| format!("unknown colour '{text}'")),
+            }
+        }
+        _ => Err("colour must be a name, #rrggbb or 0-255".to_string()),
+    }
+}
+
+/// Parses {"fg": .., "bg": .., "reverse": ..}; missing parts keep `base`'s.
+fn parse_style(value: &Value, base: Style) -> Result<Style, String> {
+    let fields = value
+        .as_object()
+        .ok_or_else(|| "style must be an object with fg, bg or reverse".to_string())?;
+    let mut style = base;
+    for (key, value) in fields {
+        match key.as_str() {
+            "fg" => style.fg = parse_color(value)?,
+            "bg" => style.bg = parse_color(value)?,
+            "reverse" => {
+                style.reverse = value
+                    .as_bool()
+                    .ok_or_else(|| "reverse must be true or false".to_string())?
+            }
+            _ => return Err(format!("unknown style key '{key}'")),
+        }
+    }
+    Ok(style)
+}
+
+/// Colours the terminal can show.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ColorSupport {
+    None,
+    /// The 16 named colours
+    Basic,
+    Ansi256,
+    TrueColor,
+}
+
+impl ColorSupport {
+    fn detect() -> Self {
+        Self::from_env(|name| env::var(name).ok())
+    }
+
+    fn from_env(var: impl Fn(&str) -> Option<String>) -> Self {
+        // https://no-color.org: any non-empty value turns colour off
+        if var("NO_COLOR").is_some_and(|value| !value.is_empty()) {
+            return ColorSupport::None;
+        }
+        let term = var("TERM").unwrap_or_default();
+        if term == "dumb" {
+            return ColorSupport::None;
+        }
+        let colorterm = var("COLORTERM").unwrap_or_default().to_lowercase();
+        if colorterm == "truecolor" || colorterm == "24bit" {
+            ColorSupport::TrueColor
+        } else if term.contains("256color") {
+            ColorSupport::Ansi256
+        } else {
+            ColorSupport::Basic
+        }
+    }
+}
+
+/// xterm's default values of the 16 named colours
+const BASIC: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::DarkRed, (205, 0, 0)),
+    (Color::DarkGreen, (0, 205, 0)),
+    (Color::DarkYellow, (205, 205, 0)),
+    (Color::DarkBlue, (0, 0, 238)),
+    (Color::DarkMagenta, (205, 0, 205)),
+    (Color::DarkCyan, (0, 205, 205)),
+    (Color::Grey, (229, 229, 229)),
+    (Color::DarkGrey, (127, 127, 127)),
+    (Color::Red, (255, 0, 0)),
+    (Color::Green, (0, 255, 0)),
+    (Color::Yellow, (255, 255, 0)),
+    (Color::Blue, (92, 92, 255)),
+    (Color::Magenta, (255, 0, 255)),
+    (Color::Cyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+/// Channel values of the 6x6x6 colour cube in the 256-colour palette
+const CUBE: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+fn degrade_color(color: Color, support: ColorSupport) -> Color {
+    match (support, color) {
+        (ColorSupport::None, _) => Color::Reset,
+        (ColorSupport::Ansi256, Color::Rgb { r, g, b }) => Color::AnsiValue(ansi256(r, g, b)),
+        (ColorSupport::Basic, Color::Rgb { r, g, b }) => nearest_basic((r, g, b)),
+        (ColorSupport::Basic, Color::AnsiValue(index)) => nearest_basic(ansi_rgb(index)),
+        _ => color,
+    }
+}
+
+fn ansi256(r: u8, g: u8, b: u8) -> u8 {
+    let nearest = |value: u8| {
+        (0..CUBE.len())
+            .min_by_key(|&idx| CUBE[idx].abs_diff(value))
+            .unwrap_or(0) as u8
+    };
+    if r == g && g == b {
+        // The 24-step grey ramp runs from 8 to 238
+        return match r {
+            0..=3 => 16,
+            248..=255 => 231,
+            _ => 232 + (r.saturating_sub(3) / 10).min(23),
+        };
+    }
+    16 + 36 * nearest(r) + 6 * nearest(g) + nearest(b)
+}
+
+fn ansi_rgb(index: u8) -> (u8, u8, u8) {
+    match index {
+        0..=15 => BASIC[index as usize].1,
+        16..=231 => {
+            let cube = index - 16;
+            (
+                CUBE[(cube / 36) as usize],
+                CUBE[(cube / 6 % 6) as usize],
+                CUBE[(cube % 6) as usize],
+            )
+        }
+        _ => {
+            let level = 8 + 10 * (index - 232);
+            (level, level, level)
+        }
+    }
+}
+
+fn nearest_basic((r, g, b): (u8, u8, u8)) -> Color {
+    let distance = |(br, bg, bb): (u8, u8, u8)| {
+        let d = |a: u8, b: u8| (a as i32 - b as i32).pow(2);
+        d(r, br) + d(g, bg) + d(b, bb)
+    };
+    BASIC
+        .iter()
+        .min_by_key(|(_, rgb)| distance(*rgb))
+        .map_or(Color::Reset, |(color, _)| *color)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_color() {
+        assert_eq!(parse_color(&json!("dark-gray")), Ok(Color::DarkGrey));
+        assert_eq!(parse_color(&json!("DarkGrey")), Ok(Color::DarkGrey));
+        assert_eq!(parse_color(&json!("default")), Ok(Color::Reset));
+        assert_eq!(
+            parse_color(&json!("#268bd2")),
+            Ok(Color::Rgb { r: 0x26, g: 0x8b, b: 0xd2 })
+        );
+        assert_eq!(parse_color(&json!(208)), Ok(Color::AnsiValue(208)));
+        assert!(parse_color(&json!("#12345")).is_err());
+        assert!(parse_color(&json!(300)).is_err());
+        assert!(parse_color(&json!("chartreuse")).is_err());
+    }
+
+    #[test]
+    fn test_custom_theme() {
+        let section = json!({
+            "name": "ocean",
+            "themes": {
+                "ocean": {
+                    "base": "light",
+                    "accent": "#005f87",
+                    "selected": { "bg": "dark_cyan" }
+                },
+                "broken": { "accent": "nope" }
+            }
+        });
+        let theme = Theme::named("ocean", Some(&section)).unwrap();
+        assert_eq!(theme.accent, Color::Rgb { r: 0, g: 0x5f, b: 0x87 });
+        assert_eq!(theme.selected, Style::new(Color::White, Color::DarkCyan));
+        assert_eq!(theme.error, Theme::light().error);
+
+        assert!(Theme::named("broken", Some(&section)).is_err());
+        assert!(Theme::named("missing", Some(&section)).is_err());
+        assert_eq!(Theme::named("high-contrast", None), Ok(Theme::high_contrast()));
+    }
+
+    #[test]
+    fn test_color_support() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            ColorSupport::from_env(move |name| {
+                vars.iter().find(|(key, _)| *key == name).map(|(_, value)| value.to_string())
+            })
+        };
+        assert_eq!(env(&[("NO_COLOR", "1"), ("COLORTERM", "truecolor")]), ColorSupport::None);
+        assert_eq!(env(&[("NO_COLOR", ""), ("TERM", "xterm")]), ColorSupport::Basic);
+        assert_eq!(env(&[("TERM", "dumb")]), ColorSupport::None);
+        assert_eq!(env(&[("TERM", "xterm-256color")]), ColorSupport::Ansi256);
+        assert_eq!(env(&[("TERM", "xterm"), ("COLORTERM", "24bit")]), ColorSupport::TrueColor);
+    }
+
+    #[test]
+    fn test_degrade() {
+        let teal = Color::Rgb { r: 0, g: 0x87, b: 0x87 };
+        assert_eq!(degrade_color(teal, ColorSupport::TrueColor), teal);
+        assert_eq!(degrade_color(teal, ColorSupport::Ansi256), Color::AnsiValue(30));
+        assert_eq!(degrade_color(teal, ColorSupport::Basic), Color::DarkCyan);
+        assert_eq!(degrade_color(Color::AnsiValue(196), ColorSupport::Basic), Color::Red);
+        assert_eq!(degrade_color(Color::Yellow, ColorSupport::Basic), Color::Yellow);
+        assert_eq!(Theme::dark().degrade(ColorSupport::None), Theme::mono());
+    }
+}
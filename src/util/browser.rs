@@ -0,0 +1,32 @@
+//! Opening URLs in the user's browser.
+
+use std::io;
+use std::process::{Command, Stdio};
+
+/// Opens `url` with the desktop's URL handler and returns the program used.
+pub fn open_url(url: &str) -> io::Result<&'static str> {
+    let candidates: &[&'static str] = if cfg!(target_os = "macos") {
+        &["open"]
+    } else {
+        // wslview hands the URL to the Windows browser under WSL
+        &["xdg-open", "wslview", "sensible-browser"]
+    };
+    let mut last_err = io::Error::new(io::ErrorKind::NotFound, "no URL opener found");
+    for program in candidates {
+        let spawned = Command::new(program)
+            .arg(url)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn();
+        match spawned {
+            Ok(mut child) => {
+                // Reap the opener in the background; browsers detach on their own
+                std::thread::spawn(move || child.wait());
+                return Ok(program);
+            }
+            Err(err) => last_err = err,
+        }
+    }
+    Err(last_err)
+}
@@ -1,22 +1,289 @@
-/// Unified filtering utilities for consistent filtering across views.
-/// This module reduces code duplication and provides optimized filtering.
+//! Unified filtering utilities for consistent filtering across views.
+//!
+//! Every view's search box takes the same query language. Whitespace separates
+//! terms, and an item must match all of them:
+//!
+//! - `chrome` matches items whose search text contains "chrome"
+//! - `~chrm` matches names containing the letters in order (fuzzy)
+//! - `/^node.*dev$/` matches the search text against a regex
+//! - `user:root`, `status:unhealthy` match text fields by substring
+//! - `cpu>20`, `mem>=500M`, `port:3000-3999` compare numeric fields
+//! - `!name:chrome` negates any term
+//!
+//! Words whose prefix is not a field, such as `nginx:latest` or
+//! `localhost:3000`, are plain text terms.
+//!
+//! Each [`Filterable`] type defines which fields it offers.
 
-/// Trait for types that can be filtered by a search string.
+use std::borrow::Cow;
+
+use regex::{Regex, RegexBuilder};
+
+/// Trait for types that can be filtered by a search query.
 /// Implementors define which fields should be searched.
 pub trait Filterable {
-    /// Returns true if this item matches the given lowercase filter string.
-    fn matches_filter(&self, filter_lower: &str) -> bool;
+    /// Fields usable in predicates such as `cpu>20` or `user:root`
+    const FIELDS: &'static [&'static str];
+
+    /// Text matched by bare words and regexes.
+    fn search_text(&self) -> Vec<Cow<'_, str>>;
+
+    /// Name matched by fuzzy `~` terms.
+    fn name(&self) -> &str;
+
+    /// Value of one of [`Self::FIELDS`].
+    fn field(&self, name: &str) -> Option<FieldValue<'_>>;
 }
 
-/// Apply a filter to a collection, removing items that don't match.
-/// The filter string is converted to lowercase once and reused.
-pub fn apply_filter<T: Filterable>(items: &mut Vec<T>, filter: &str) {
-    if filter.is_empty() {
-        return;
+/// Value of a field, deciding how predicates compare against it.
+pub enum FieldValue<'a> {
+    Text(Cow<'a, str>),
+    Number(f64),
+    /// Matches when any of the numbers does, e.g. all ports of a container
+    Numbers(Vec<f64>),
+}
+
+/// A parsed filter; the empty query matches everything.
+#[derive(Debug, Default)]
+pub struct Query {
+    terms: Vec<Term>,
+}
+
+#[derive(Debug)]
+struct Term {
+    negated: bool,
+    kind: TermKind,
+}
+
+#[derive(Debug)]
+enum TermKind {
+    Text(String),
+    Fuzzy(String),
+    Regex(Regex),
+    Field { name: String, predicate: Predicate },
+}
+
+#[derive(Debug)]
+enum Predicate {
+    /// `field:value`: substring for text, equality or `a-b` range for numbers
+    Matches { text: String, range: Option<(f64, f64)> },
+    Regex(Regex),
+    Compare(CompareOp, f64),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum CompareOp {
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Eq,
+}
+
+impl CompareOp {
+    fn holds(self, value: f64, bound: f64) -> bool {
+        match self {
+            CompareOp::Lt => value < bound,
+            CompareOp::Le => value <= bound,
+            CompareOp::Gt => value > bound,
+            CompareOp::Ge => value >= bound,
+            CompareOp::Eq => value == bound,
+        }
+    }
+}
+
+impl Query {
+    /// Parses `input`, checking field names against `T::FIELDS`.
+    pub fn parse<T: Filterable>(input: &str) -> Result<Self, String> {
+        let mut terms = Vec::new();
+        for token in tokenize(input) {
+            let (negated, body) = match token.strip_prefix('!') {
+                Some(body) => (true, body),
+                None => (false, token.as_str()),
+            };
+            if body.is_empty() {
+                continue;
+            }
+            let Some(kind) = parse_term::<T>(body)? else {
+                // A predicate still being typed, such as "cpu>"
+                continue;
+            };
+            terms.push(Term { negated, kind });
+        }
+        Ok(Self { terms })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    pub fn matches<T: Filterable>(&self, item: &T) -> bool {
+        self.terms
+            .iter()
+            .all(|term| term.kind.matches(item) != term.negated)
+    }
+}
+
+impl TermKind {
+    fn matches<T: Filterable>(&self, item: &T) -> bool {
+        match self {
+            TermKind::Text(text) => item.search_text().iter().any(|field| contains_lower(field, text)),
+            TermKind::Fuzzy(text) => fuzzy_match(&item.name().to_lowercase(), text),
+            TermKind::Regex(regex) => item.search_text().iter().any(|field| regex.is_match(field)),
+            TermKind::Field { name, predicate } => {
+                item.field(name).is_some_and(|value| predicate.matches(&value))
+            }
+        }
+    }
+}
+
+impl Predicate {
+    fn matches(&self, value: &FieldValue) -> bool {
+        match value {
+            FieldValue::Text(text) => match self {
+                Predicate::Matches { text: wanted, .. } => contains_lower(text, wanted),
+                Predicate::Regex(regex) => regex.is_match(text),
+                Predicate::Compare(op, bound) => parse_number(text).is_some_and(|value| op.holds(value, *bound)),
+            },
+            FieldValue::Number(number) => self.matches_number(*number),
+            FieldValue::Numbers(numbers) => numbers.iter().any(|number| self.matches_number(*number)),
+        }
+    }
+
+    fn matches_number(&self, number: f64) -> bool {
+        match self {
+            Predicate::Matches { range: Some((low, high)), .. } => (*low..=*high).contains(&number),
+            Predicate::Matches { range: None, .. } => false,
+            Predicate::Regex(regex) => regex.is_match(&number.to_string()),
+            Predicate::Compare(op, bound) => op.holds(number, *bound),
+        }
+    }
+}
+
+/// Splits on whitespace outside double quotes, dropping the quotes.
+fn tokenize(input: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    for ch in input.chars() {
+        match ch {
+            '"' => quoted = !quoted,
+            ch if ch.is_whitespace() && !quoted => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+            }
+            ch => current.push(ch),
+        }
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    tokens
+}
+
+fn parse_term<T: Filterable>(body: &str) -> Result<Option<TermKind>, String> {
+    if let Some(pattern) = regex_literal(body) {
+        return Ok(Some(TermKind::Regex(build_regex(pattern)?)));
+    }
+    if let Some(text) = body.strip_prefix('~') {
+        return Ok(Some(TermKind::Fuzzy(text.to_lowercase())));
     }
+    // Only known fields make a predicate, so "nginx:latest" stays a search word
+    let predicate = split_predicate(body)
+        .filter(|(name, _, _)| T::FIELDS.contains(&name.to_lowercase().as_str()));
+    let Some((name, op, value)) = predicate else {
+        return Ok(Some(TermKind::Text(body.to_lowercase())));
+    };
+    let name = name.to_lowercase();
+    if value.is_empty() {
+        return Ok(None);
+    }
+    let predicate = match op {
+        ":" => match regex_literal(value) {
+            Some(pattern) => Predicate::Regex(build_regex(pattern)?),
+            None => Predicate::Matches {
+                text: value.to_lowercase(),
+                range: parse_range(value),
+            },
+        },
+        _ => {
+            let bound = parse_number(value).ok_or_else(|| format!("'{value}' is not a number"))?;
+            let op = match op {
+                "<" => CompareOp::Lt,
+                "<=" => CompareOp::Le,
+                ">" => CompareOp::Gt,
+                ">=" => CompareOp::Ge,
+                _ => CompareOp::Eq,
+            };
+            Predicate::Compare(op, bound)
+        }
+    };
+    Ok(Some(TermKind::Field { name, predicate }))
+}
+
+/// Splits `cpu>=20` into ("cpu", ">=", "20"). Field names are alphanumeric.
+fn split_predicate(body: &str) -> Option<(&str, &str, &str)> {
+    let end = body.find(|ch: char| !ch.is_ascii_alphanumeric() && ch != '_')?;
+    if end == 0 {
+        return None;
+    }
+    let rest = &body[end..];
+    let op = [">=", "<=", ">", "<", "=", ":"]
+        .into_iter()
+        .find(|op| rest.starts_with(op))?;
+    Some((&body[..end], op, &rest[op.len()..]))
+}
 
-    let filter_lower = filter.to_lowercase();
-    items.retain(|item| item.matches_filter(&filter_lower));
+fn regex_literal(text: &str) -> Option<&str> {
+    let pattern = text.strip_prefix('/')?.strip_suffix('/')?;
+    (!pattern.is_empty()).then_some(pattern)
+}
+
+fn build_regex(pattern: &str) -> Result<Regex, String> {
+    RegexBuilder::new(pattern)
+        .case_insensitive(true)
+        .build()
+        .map_err(|err| format!("Invalid regex /{pattern}/: {err}"))
+}
+
+/// A number with an optional size suffix: "20", "1.5", "500M", "2GB", "20%".
+fn parse_number(text: &str) -> Option<f64> {
+    let text = text.trim().trim_end_matches('%');
+    let upper = text.to_ascii_uppercase();
+    let digits = upper.trim_end_matches('B');
+    let (digits, multiplier) = match digits.chars().last()? {
+        'K' => (&digits[..digits.len() - 1], 1024f64),
+        'M' => (&digits[..digits.len() - 1], 1024f64.powi(2)),
+        'G' => (&digits[..digits.len() - 1], 1024f64.powi(3)),
+        'T' => (&digits[..digits.len() - 1], 1024f64.powi(4)),
+        _ => (digits, 1.0),
+    };
+    digits.parse::<f64>().ok().map(|value| value * multiplier)
+}
+
+/// "3000" or "3000-3999" as an inclusive range.
+fn parse_range(text: &str) -> Option<(f64, f64)> {
+    match text.split_once('-') {
+        Some((low, high)) => Some((parse_number(low)?, parse_number(high)?)),
+        None => parse_number(text).map(|value| (value, value)),
+    }
+}
+
+/// True if the letters of `needle_lower` appear in `haystack_lower` in order.
+pub fn fuzzy_match(haystack_lower: &str, needle_lower: &str) -> bool {
+    let mut haystack = haystack_lower.chars();
+    needle_lower
+        .chars()
+        .all(|wanted| haystack.any(|ch| ch == wanted))
+}
+
+/// Apply a parsed filter to a collection, removing items that don't match.
+pub fn apply_filter<T: Filterable>(items: &mut Vec<T>, query: &Query) {
+    if query.is_empty() {
+        return;
+    }
+    items.retain(|item| query.matches(item));
 }
 
 /// Check if a string contains the filter (case-insensitive).
@@ -34,3 +301,105 @@ pub fn contains_lower(haystack: &str, needle_lower: &str) -> bool {
         haystack.to_lowercase().contains(needle_lower)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Item {
+        name: &'static str,
+        user: &'static str,
+        cpu: f64,
+        mem: f64,
+        ports: Vec<f64>,
+    }
+
+    impl Filterable for Item {
+        const FIELDS: &'static [&'static str] = &["name", "user", "cpu", "mem", "port"];
+
+        fn search_text(&self) -> Vec<Cow<'_, str>> {
+            vec![Cow::Borrowed(self.name), Cow::Borrowed(self.user)]
+        }
+
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn field(&self, name: &str) -> Option<FieldValue<'_>> {
+            Some(match name {
+                "name" => FieldValue::Text(Cow::Borrowed(self.name)),
+                "user" => FieldValue::Text(Cow::Borrowed(self.user)),
+                "cpu" => FieldValue::Number(self.cpu),
+                "mem" => FieldValue::Number(self.mem),
+                "port" => FieldValue::Numbers(self.ports.clone()),
+                _ => return None,
+            })
+        }
+    }
+
+    fn item(name: &'static str, user: &'static str, cpu: f64, mem_mb: f64, ports: &[f64]) -> Item {
+        Item {
+            name,
+            user,
+            cpu,
+            mem: mem_mb * 1024.0 * 1024.0,
+            ports: ports.to_vec(),
+        }
+    }
+
+    fn matching(query: &str) -> Vec<&'static str> {
+        let mut items = vec![
+            item("chrome", "alice", 35.0, 900.0, &[]),
+            item("node", "root", 4.0, 120.0, &[3000, 9229].map(f64::from)),
+            item("postgres", "postgres", 22.0, 600.0, &[5432.0]),
+        ];
+        apply_filter(&mut items, &Query::parse::<Item>(query).unwrap());
+        items.iter().map(|item| item.name).collect()
+    }
+
+    #[test]
+    fn test_field_predicates() {
+        assert_eq!(matching("cpu>20"), vec!["chrome", "postgres"]);
+        assert_eq!(matching("cpu>20 mem<700M"), vec!["postgres"]);
+        assert_eq!(matching("user:root"), vec!["node"]);
+        assert_eq!(matching("port:3000-3999"), vec!["node"]);
+        assert_eq!(matching("port:5432"), vec!["postgres"]);
+        assert_eq!(matching("mem>=0.5GB"), vec!["chrome", "postgres"]);
+    }
+
+    #[test]
+    fn test_negation_regex_and_fuzzy() {
+        assert_eq!(matching("!name:chrome"), vec!["node", "postgres"]);
+        assert_eq!(matching("/^(node|chrome)$/"), vec!["chrome", "node"]);
+        assert_eq!(matching("name:/GRES$/"), vec!["postgres"]);
+        assert_eq!(matching("~pgrs"), vec!["postgres"]);
+        assert_eq!(matching("o !~chr"), vec!["node", "postgres"]);
+        // Incomplete predicates are ignored while typing
+        assert_eq!(matching("cpu>"), vec!["chrome", "node", "postgres"]);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(Query::parse::<Item>("/ab(/").is_err());
+        assert!(Query::parse::<Item>("cpu>lots").is_err());
+        assert_eq!(parse_number("500M"), Some(500.0 * 1024.0 * 1024.0));
+        assert_eq!(parse_number("20%"), Some(20.0));
+        assert_eq!(parse_number("B"), None);
+    }
+
+    #[test]
+    fn test_unknown_fields_are_text() {
+        let query = Query::parse::<Item>("nginx:latest").unwrap();
+        assert!(query.matches(&item("nginx:latest", "root", 0.0, 0.0, &[])));
+        assert!(!query.matches(&item("nginx:alpine", "root", 0.0, 0.0, &[])));
+
+        let query = Query::parse::<Item>("http://localhost:3000").unwrap();
+        assert!(query.matches(&item("http://localhost:3000/health", "root", 0.0, 0.0, &[])));
+        assert!(Query::parse::<Item>("colour>red").is_ok());
+        assert_eq!(matching("postgres:15"), Vec::<&str>::new());
+
+        // Known fields still need valid values
+        assert!(Query::parse::<Item>("cpu>lots").is_err());
+        assert_eq!(matching("user:root"), vec!["node"]);
+    }
+}
@@ -1,9 +1,93 @@
+pub mod browser;
+pub mod clipboard;
 pub mod filter;
 
 use std::cmp::Ordering;
 
-pub use filter::{apply_filter, contains_lower, Filterable};
+pub use filter::{apply_filter, FieldValue, Filterable, Query};
 
 pub fn cmp_f32(a: f32, b: f32) -> Ordering {
     a.partial_cmp(&b).unwrap_or(Ordering::Equal)
 }
+
+/// Compact byte count such as "512B", "12.5K" or "3.4G", for narrow columns.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["K", "M", "G", "T"];
+    if bytes < 1024 {
+        return format!("{bytes}B");
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{value:.1}{}", UNITS[unit])
+}
+
+/// Local time of a Unix timestamp: "14:05" for today, "Mar 02" for earlier days.
+pub fn format_start_time(epoch_secs: u64) -> String {
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs());
+    let (Some(then), Some(today)) = (local_time(epoch_secs), local_time(now)) else {
+        return "-".to_string();
+    };
+    if (then.tm_year, then.tm_yday) == (today.tm_year, today.tm_yday) {
+        format!("{:02}:{:02}", then.tm_hour, then.tm_min)
+    } else {
+        let month = MONTHS.get(then.tm_mon as usize).copied().unwrap_or("???");
+        format!("{month} {:02}", then.tm_mday)
+    }
+}
+
+/// A scratch directory for tests, removed with everything in it on drop.
+#[cfg(test)]
+pub(crate) struct TempDir(std::path::PathBuf);
+
+#[cfg(test)]
+impl TempDir {
+    pub fn new(label: &str) -> Self {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        static NEXT: AtomicUsize = AtomicUsize::new(0);
+        let id = NEXT.fetch_add(1, Ordering::Relaxed);
+        let path = std::env::temp_dir()
+            .join(format!("spark-{label}-test-{}-{id}", std::process::id()));
+        std::fs::create_dir_all(&path).unwrap();
+        TempDir(path)
+    }
+}
+
+#[cfg(test)]
+impl std::ops::Deref for TempDir {
+    type Target = std::path::Path;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+#[cfg(unix)]
+fn local_time(epoch_secs: u64) -> Option<libc::tm> {
+    let time = libc::time_t::try_from(epoch_secs).ok()?;
+    // SAFETY: localtime_r only writes the tm it is given
+    let mut tm: libc::tm = unsafe { std::mem::zeroed() };
+    let result = unsafe { libc::localtime_r(&time, &mut tm) };
+    (!result.is_null()).then_some(tm)
+}
+
+#[cfg(not(unix))]
+fn local_time(_epoch_secs: u64) -> Option<libc::tm> {
+    None
+}